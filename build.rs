@@ -0,0 +1,40 @@
+use std::{env, fs, path::Path, process::Command};
+
+// Embed build information for 'geoq version'
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    // On a branch, HEAD only names the branch's ref, which is what changes on commit
+    if let Ok(head) = fs::read_to_string(".git/HEAD") {
+        println!("cargo:rerun-if-changed=.git/HEAD");
+        if let Some(reference) = head.trim().strip_prefix("ref: ") {
+            println!("cargo:rerun-if-changed=.git/{}", reference);
+        }
+        if Path::new(".git/packed-refs").exists() {
+            println!("cargo:rerun-if-changed=.git/packed-refs");
+        }
+    }
+
+    let target = env::var("TARGET").unwrap_or_default();
+    println!("cargo:rustc-env=GEOQ_BUILD_TARGET={}", target);
+
+    let commit = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok());
+    if let Some(commit) = commit {
+        println!("cargo:rustc-env=GEOQ_GIT_COMMIT={}", commit.trim());
+    }
+
+    // Cargo sets CARGO_FEATURE_<NAME> for each enabled feature
+    let mut features: Vec<String> = env::vars()
+        .filter_map(|(key, _)| {
+            key.strip_prefix("CARGO_FEATURE_")
+                .map(|name| name.to_lowercase().replace('_', "-"))
+        })
+        .filter(|name| name != "default")
+        .collect();
+    features.sort();
+    println!("cargo:rustc-env=GEOQ_FEATURES={}", features.join(","));
+}
@@ -0,0 +1,214 @@
+use crate::geoq::distance::GEODESIC;
+use geo_types::{Coordinate, Geometry, LineString, Polygon};
+use geographiclib_rs::{InverseGeodesic, PolygonArea, Winding};
+
+fn ring_area(ring: &LineString<f64>) -> f64 {
+    let mut pa = PolygonArea::new(&GEODESIC, Winding::CounterClockwise);
+    let coords = &ring.0;
+    // PolygonArea closes the ring itself
+    let n = if coords.len() > 1 && coords.first() == coords.last() {
+        coords.len() - 1
+    } else {
+        coords.len()
+    };
+    if n < 3 {
+        return 0.0;
+    }
+    for c in &coords[..n] {
+        pa.add_point(c.y, c.x);
+    }
+    // Signed so that clockwise rings don't give the area of the rest of the globe
+    pa.compute(true).1.abs()
+}
+
+fn polygon_area(poly: &Polygon<f64>) -> f64 {
+    ring_area(poly.exterior()) - poly.interiors().iter().map(ring_area).sum::<f64>()
+}
+
+// Geodesic area in square meters, on the global --ellipsoid. Points and lines have no area.
+pub fn area(geom: &Geometry<f64>) -> f64 {
+    match geom {
+        Geometry::Polygon(g) => polygon_area(g),
+        Geometry::MultiPolygon(g) => g.0.iter().map(polygon_area).sum(),
+        Geometry::GeometryCollection(gc) => gc.0.iter().map(area).sum(),
+        _ => 0.0,
+    }
+}
+
+// Planar (shoelace) area of a ring: positive when counter-clockwise.
+// The ring may be open or closed, as the closing edge is included either way.
+pub fn signed_area(coords: &[Coordinate<f64>]) -> f64 {
+    let n = coords.len();
+    (0..n)
+        .map(|i| {
+            let a = coords[i];
+            let b = coords[(i + 1) % n];
+            a.x * b.y - b.x * a.y
+        })
+        .sum::<f64>()
+        / 2.0
+}
+
+pub fn ring_signed_area(ring: &LineString<f64>) -> f64 {
+    signed_area(&ring.0)
+}
+
+// Planar (shoelace) area of polygon exterior rings, in square degrees:
+// positive for counter-clockwise rings and negative for clockwise ones.
+// Holes are ignored. Points and lines have no area.
+pub fn signed_planar_area(geom: &Geometry<f64>) -> f64 {
+    match geom {
+        Geometry::Polygon(g) => ring_signed_area(g.exterior()),
+        Geometry::MultiPolygon(g) => g.0.iter().map(|p| ring_signed_area(p.exterior())).sum(),
+        Geometry::GeometryCollection(gc) => gc.0.iter().map(signed_planar_area).sum(),
+        _ => 0.0,
+    }
+}
+
+fn planar_polygon_area(poly: &Polygon<f64>) -> f64 {
+    ring_signed_area(poly.exterior()).abs()
+        - poly.interiors().iter().map(|ring| ring_signed_area(ring).abs()).sum::<f64>()
+}
+
+// Planar (shoelace) area in the square units of the coordinates, for
+// geometries already projected into a planar CRS. Holes are subtracted.
+pub fn planar_area(geom: &Geometry<f64>) -> f64 {
+    match geom {
+        Geometry::Polygon(g) => planar_polygon_area(g),
+        Geometry::MultiPolygon(g) => g.0.iter().map(planar_polygon_area).sum(),
+        Geometry::GeometryCollection(gc) => gc.0.iter().map(planar_area).sum(),
+        _ => 0.0,
+    }
+}
+
+fn planar_line_length(line: &LineString<f64>) -> f64 {
+    line.0.windows(2).map(|pair| (pair[1].x - pair[0].x).hypot(pair[1].y - pair[0].y)).sum()
+}
+
+// Planar (Euclidean) length in the units of the coordinates, for
+// geometries already projected into a planar CRS
+pub fn planar_length(geom: &Geometry<f64>) -> f64 {
+    match geom {
+        Geometry::Line(g) => planar_line_length(&LineString(vec![g.start, g.end])),
+        Geometry::LineString(g) => planar_line_length(g),
+        Geometry::MultiLineString(g) => g.0.iter().map(planar_line_length).sum(),
+        Geometry::GeometryCollection(gc) => gc.0.iter().map(planar_length).sum(),
+        _ => 0.0,
+    }
+}
+
+fn segment_length(a: &Coordinate<f64>, b: &Coordinate<f64>) -> f64 {
+    GEODESIC.inverse(a.y, a.x, b.y, b.x)
+}
+
+fn line_length(line: &LineString<f64>) -> f64 {
+    line.0.windows(2).map(|pair| segment_length(&pair[0], &pair[1])).sum()
+}
+
+// Geodesic length in meters, on the global --ellipsoid. Points and polygons have no length.
+pub fn length(geom: &Geometry<f64>) -> f64 {
+    match geom {
+        Geometry::Line(g) => segment_length(&g.start, &g.end),
+        Geometry::LineString(g) => line_length(g),
+        Geometry::MultiLineString(g) => g.0.iter().map(line_length).sum(),
+        Geometry::GeometryCollection(gc) => gc.0.iter().map(length).sum(),
+        _ => 0.0,
+    }
+}
+
+// Length of a segment between GeoJSON positions, combining its geodesic
+// length with the change in Z (taken as meters of elevation). None if
+// either position has no Z.
+fn segment_length_3d(a: &[f64], b: &[f64]) -> Option<f64> {
+    let (z1, z2) = (a.get(2)?, b.get(2)?);
+    let horizontal: f64 = GEODESIC.inverse(a[1], a[0], b[1], b[0]);
+    Some(horizontal.hypot(z2 - z1))
+}
+
+fn line_length_3d(line: &[Vec<f64>]) -> Option<f64> {
+    line.windows(2).map(|pair| segment_length_3d(&pair[0], &pair[1])).sum()
+}
+
+// Like length, but including the elevation change along each segment using
+// the Z coordinates of a GeoJSON geometry. None if any coordinate of a line
+// has no Z. Points and polygons have no length.
+pub fn length_3d(value: &geojson::Value) -> Option<f64> {
+    match value {
+        geojson::Value::LineString(line) => line_length_3d(line),
+        geojson::Value::MultiLineString(lines) => lines.iter().map(|l| line_length_3d(l)).sum(),
+        geojson::Value::GeometryCollection(gc) => gc.iter().map(|g| length_3d(&g.value)).sum(),
+        _ => Some(0.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::area::{area, length, length_3d, planar_area, planar_length, signed_planar_area};
+    use geo_types::{Geometry, LineString, Polygon};
+
+    #[test]
+    fn test_area_of_polygon_with_hole() {
+        let exterior: LineString<f64> =
+            vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0), (0.0, 0.0)].into();
+        let square = Polygon::new(exterior.clone(), vec![]);
+        let clockwise = Polygon::new(LineString(exterior.0.iter().rev().cloned().collect()), vec![]);
+        let with_hole = Polygon::new(exterior.clone(), vec![exterior]);
+        // 1 degree square at the equator is ~12,308 square km
+        let a = area(&Geometry::Polygon(square));
+        assert!((a - 12_308_778_361.0).abs() < 1.0, "area was {}", a);
+        assert_eq!(0.0, area(&Geometry::Polygon(with_hole)));
+        assert_eq!(a, area(&Geometry::Polygon(clockwise)));
+    }
+
+    #[test]
+    fn test_3d_length_of_line() {
+        let flat = geojson::Value::LineString(vec![vec![0.0, 0.0, 5.0], vec![0.0, 0.001, 5.0]]);
+        let horizontal = length(&Geometry::LineString(vec![(0.0, 0.0), (0.0, 0.001)].into()));
+        assert_eq!(Some(horizontal), length_3d(&flat));
+
+        // 100m of climb over the same horizontal distance
+        let climb = geojson::Value::LineString(vec![vec![0.0, 0.0, 0.0], vec![0.0, 0.001, 100.0]]);
+        let l = length_3d(&climb).unwrap();
+        assert!((l - (horizontal * horizontal + 100.0 * 100.0).sqrt()).abs() < 1e-9, "length was {}", l);
+
+        let partial = geojson::Value::LineString(vec![vec![0.0, 0.0, 0.0], vec![0.0, 0.001]]);
+        assert_eq!(None, length_3d(&partial));
+        assert_eq!(Some(0.0), length_3d(&geojson::Value::Point(vec![1.0, 2.0])));
+    }
+
+    #[test]
+    fn test_planar_length() {
+        let line: LineString<f64> = vec![(0.0, 0.0), (3.0, 4.0), (3.0, 10.0)].into();
+        assert_eq!(11.0, planar_length(&Geometry::LineString(line)));
+        assert_eq!(0.0, planar_length(&Geometry::Point((1.0, 2.0).into())));
+    }
+
+    #[test]
+    fn test_length_of_line() {
+        let line: LineString<f64> = vec![(0.0, 0.0), (1.0, 0.0), (2.0, 0.0)].into();
+        let l = length(&Geometry::LineString(line));
+        assert!((l - 222_638.98).abs() < 0.01, "length was {}", l);
+    }
+
+    #[test]
+    fn test_signed_planar_area() {
+        let ccw: LineString<f64> =
+            vec![(0.0, 0.0), (2.0, 0.0), (2.0, 1.0), (0.0, 1.0), (0.0, 0.0)].into();
+        let cw = LineString(ccw.0.iter().rev().cloned().collect());
+        let hole: LineString<f64> =
+            vec![(0.5, 0.2), (0.5, 0.8), (1.5, 0.8), (1.5, 0.2), (0.5, 0.2)].into();
+        assert_eq!(2.0, signed_planar_area(&Geometry::Polygon(Polygon::new(ccw, vec![hole]))));
+        assert_eq!(-2.0, signed_planar_area(&Geometry::Polygon(Polygon::new(cw, vec![]))));
+    }
+
+    #[test]
+    fn test_planar_area() {
+        let exterior: LineString<f64> =
+            vec![(0.0, 0.0), (0.0, 4.0), (4.0, 4.0), (4.0, 0.0), (0.0, 0.0)].into();
+        let hole: LineString<f64> =
+            vec![(1.0, 1.0), (2.0, 1.0), (2.0, 2.0), (1.0, 2.0), (1.0, 1.0)].into();
+        let poly = Geometry::Polygon(Polygon::new(exterior, vec![hole]));
+        assert_eq!(15.0, planar_area(&poly));
+        assert_eq!(0.0, planar_area(&Geometry::Point((1.0, 1.0).into())));
+    }
+}
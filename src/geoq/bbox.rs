@@ -1,3 +1,4 @@
+use crate::geoq::coord_count;
 use std::fmt::Debug;
 use geo_types::*;
 use std::cmp::Ordering;
@@ -61,13 +62,93 @@ pub fn bbox(geom: &Geometry<f64>) -> geo::Rect<f64> {
         Geometry::MultiLineString(ref g) => g.bounding_rect().or_zero(),
         Geometry::MultiPolygon(ref g) => g.bounding_rect().or_zero(),
         Geometry::GeometryCollection(ref gc) => {
-            let rects: Vec<geo::Rect<f64>> = gc.0.iter().map(|geom| bbox(geom)).collect();
+            // Empty members have no extent, so they don't contribute
+            let rects: Vec<geo::Rect<f64>> = gc
+                .0
+                .iter()
+                .filter(|geom| !coord_count::is_empty(geom))
+                .map(bbox)
+                .collect();
 
-            rects.iter().fold(zero_rect(), |a, b| merge(&a, b))
+            match rects.split_first() {
+                Some((first, rest)) => rest.iter().fold(*first, |a, b| merge(&a, b)),
+                None => zero_rect(),
+            }
         }
     }
 }
 
+// Smallest bbox containing all of the given bboxes, allowing it to wrap
+// across the antimeridian. Wrapping bboxes are given with min.x > max.x.
+// Each of the given bboxes is assumed not to wrap.
+pub fn merge_antimeridian(rects: &[geo::Rect<f64>]) -> Option<geo::Rect<f64>> {
+    let mut spans: Vec<(f64, f64)> = rects.iter().map(|r| (r.min.x, r.max.x)).collect();
+    spans.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+
+    let mut merged: Vec<(f64, f64)> = Vec::new();
+    for (start, end) in spans {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = max(last.1, end),
+            _ => merged.push((start, end)),
+        }
+    }
+
+    let first = *merged.first()?;
+    let last = *merged.last()?;
+    // The widest gap between longitude spans is left out of the bbox.
+    // Start with the gap wrapping around the antimeridian, which gives
+    // an ordinary (non-wrapping) bbox.
+    let mut gap = first.0 + 360.0 - last.1;
+    let mut lons = (first.0, last.1);
+    for pair in merged.windows(2) {
+        let between = pair[1].0 - pair[0].1;
+        if between > gap {
+            gap = between;
+            lons = (pair[1].0, pair[0].1);
+        }
+    }
+
+    let min_y = rects.iter().map(|r| r.min.y).fold(f64::INFINITY, min);
+    let max_y = rects.iter().map(|r| r.max.y).fold(f64::NEG_INFINITY, max);
+    Some(geo::Rect {
+        min: geo::Coordinate { x: lons.0, y: min_y },
+        max: geo::Coordinate { x: lons.1, y: max_y },
+    })
+}
+
+// Polygon for a bbox, split into a MultiPolygon at the
+// antimeridian if it wraps (see merge_antimeridian)
+pub fn antimeridian_polygon(rect: &geo::Rect<f64>) -> Geometry<f64> {
+    if rect.min.x <= rect.max.x {
+        return Geometry::Polygon(rect.to_polygon());
+    }
+    let east = geo::Rect {
+        min: rect.min,
+        max: geo::Coordinate { x: 180.0, y: rect.max.y },
+    };
+    let west = geo::Rect {
+        min: geo::Coordinate { x: -180.0, y: rect.min.y },
+        max: rect.max,
+    };
+    Geometry::MultiPolygon(MultiPolygon(vec![east.to_polygon(), west.to_polygon()]))
+}
+
+// Parse a bounding box given as "minlon,minlat,maxlon,maxlat"
+pub fn parse(raw: &str) -> Option<geo::Rect<f64>> {
+    let nums = raw
+        .split(',')
+        .map(|n| n.trim().parse::<f64>())
+        .collect::<Result<Vec<f64>, _>>()
+        .ok()?;
+    match nums[..] {
+        [min_x, min_y, max_x, max_y] if min_x <= max_x && min_y <= max_y => Some(Rect {
+            min: Coordinate { x: min_x, y: min_y },
+            max: Coordinate { x: max_x, y: max_y },
+        }),
+        _ => None,
+    }
+}
+
 pub trait BBoxToPoly {
     fn to_polygon(&self) -> geo_types::Polygon<f64>;
 }
@@ -87,3 +168,37 @@ impl BBoxToPoly for geo::Rect<f64> {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::bbox::{merge_antimeridian, parse};
+    use geo_types::{Coordinate, Rect};
+
+    fn rect(min_x: f64, min_y: f64, max_x: f64, max_y: f64) -> Rect<f64> {
+        Rect {
+            min: Coordinate { x: min_x, y: min_y },
+            max: Coordinate { x: max_x, y: max_y },
+        }
+    }
+
+    #[test]
+    fn test_merging_across_antimeridian() {
+        let rects = vec![rect(170.0, -10.0, 175.0, 0.0), rect(-178.0, 0.0, -170.0, 5.0)];
+        assert_eq!(Some(rect(170.0, -10.0, -170.0, 5.0)), merge_antimeridian(&rects));
+    }
+
+    #[test]
+    fn test_merging_without_wrapping() {
+        let rects = vec![rect(-10.0, 0.0, 0.0, 1.0), rect(5.0, 2.0, 10.0, 3.0)];
+        assert_eq!(Some(rect(-10.0, 0.0, 10.0, 3.0)), merge_antimeridian(&rects));
+        assert_eq!(None, merge_antimeridian(&[]));
+    }
+
+    #[test]
+    fn test_parsing_bbox() {
+        assert_eq!(Some(rect(-1.0, 2.0, 3.5, 4.0)), parse("-1, 2,3.5,4"));
+        assert_eq!(None, parse("1,2,3"));
+        assert_eq!(None, parse("3,2,1,4"));
+        assert_eq!(None, parse("a,b,c,d"));
+    }
+}
@@ -0,0 +1,177 @@
+use crate::geoq::{distance::GEODESIC, node, valid};
+use geo::algorithm::contains::Contains;
+use geo_types::{Coordinate, Geometry, GeometryCollection, LineString, MultiPolygon, Polygon};
+use geographiclib_rs::{DirectGeodesic, InverseGeodesic};
+
+type Coord = Coordinate<f64>;
+
+// Vertices in each circle around a point
+const CIRCLE_SEGMENTS: usize = 32;
+
+// Point the given geodesic distance (in meters) from c, along the azimuth
+fn offset(c: Coord, azimuth: f64, distance: f64) -> Coord {
+    let (lat, lon): (f64, f64) = GEODESIC.direct(c.y, c.x, azimuth, distance);
+    Coordinate { x: lon, y: lat }
+}
+
+fn circle(center: Coord, distance: f64) -> Polygon<f64> {
+    let mut ring: Vec<Coord> = (0..CIRCLE_SEGMENTS)
+        .map(|i| offset(center, 360.0 * i as f64 / CIRCLE_SEGMENTS as f64, distance))
+        .collect();
+    ring.push(ring[0]);
+    Polygon::new(LineString(ring), vec![])
+}
+
+// The points within the distance of a segment, other than those around its ends
+fn segment_band(a: Coord, b: Coord, distance: f64) -> Option<Polygon<f64>> {
+    if a == b {
+        return None;
+    }
+    let (azi1, azi2, _): (f64, f64, f64) = GEODESIC.inverse(a.y, a.x, b.y, b.x);
+    let ring = vec![
+        offset(a, azi1 - 90.0, distance),
+        offset(b, azi2 - 90.0, distance),
+        offset(b, azi2 + 90.0, distance),
+        offset(a, azi1 + 90.0, distance),
+        offset(a, azi1 - 90.0, distance),
+    ];
+    Some(Polygon::new(LineString(ring), vec![]))
+}
+
+fn line_pieces(line: &LineString<f64>, distance: f64, pieces: &mut Vec<Polygon<f64>>) {
+    pieces.extend(line.0.iter().map(|c| circle(*c, distance)));
+    pieces.extend(
+        line.0
+            .windows(2)
+            .filter_map(|pair| segment_band(pair[0], pair[1], distance)),
+    );
+}
+
+// Polygons which together cover the points within the distance of the geometry
+fn pieces(geom: &Geometry<f64>, distance: f64, pieces: &mut Vec<Polygon<f64>>) {
+    match *geom {
+        Geometry::Point(ref p) => pieces.push(circle(p.0, distance)),
+        Geometry::Line(ref l) => line_pieces(&LineString(vec![l.start, l.end]), distance, pieces),
+        Geometry::LineString(ref ls) => line_pieces(ls, distance, pieces),
+        Geometry::Polygon(ref poly) => {
+            pieces.push(poly.clone());
+            line_pieces(poly.exterior(), distance, pieces);
+            for ring in poly.interiors() {
+                line_pieces(ring, distance, pieces);
+            }
+        }
+        Geometry::MultiPoint(ref mp) => pieces.extend(mp.0.iter().map(|p| circle(p.0, distance))),
+        Geometry::MultiLineString(ref mls) => {
+            for ls in &mls.0 {
+                line_pieces(ls, distance, pieces);
+            }
+        }
+        Geometry::MultiPolygon(ref mp) => {
+            for poly in &mp.0 {
+                self::pieces(&Geometry::Polygon(poly.clone()), distance, pieces);
+            }
+        }
+        Geometry::GeometryCollection(ref gc) => {
+            for g in &gc.0 {
+                self::pieces(g, distance, pieces);
+            }
+        }
+    }
+}
+
+// The area within the given geodesic distance (in meters) of a geometry,
+// as a Polygon or MultiPolygon. Curves are approximated with 32 segments
+// per circle. Gives the geometry itself for a distance of 0 or less.
+pub fn buffer(geom: &Geometry<f64>, distance: f64) -> Geometry<f64> {
+    if distance <= 0.0 {
+        return geom.clone();
+    }
+    let mut parts = Vec::new();
+    pieces(geom, distance, &mut parts);
+    if parts.len() == 1 {
+        return Geometry::Polygon(parts.remove(0));
+    }
+
+    // Split the pieces' rings where they cross, and merge
+    // the faces they form which fall inside of any piece
+    let rings: Vec<LineString<f64>> = parts
+        .iter()
+        .flat_map(|poly| std::iter::once(poly.exterior()).chain(poly.interiors()))
+        .cloned()
+        .collect();
+    let lines: Vec<LineString<f64>> = node::node(&rings).into_iter().flatten().collect();
+    let inside = |p: &geo_types::Point<f64>| parts.iter().any(|poly| poly.contains(p));
+    let faces = valid::faces_where(lines, inside);
+    let mut polys = valid::dissolve_faces(&faces, inside);
+    match polys.len() {
+        0 => Geometry::GeometryCollection(GeometryCollection(vec![])),
+        1 => Geometry::Polygon(polys.remove(0)),
+        _ => Geometry::MultiPolygon(MultiPolygon(polys)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::buffer::buffer;
+    use crate::geoq::distance::{geometry_distance, Method};
+    use geo::algorithm::contains::Contains;
+    use geo_types::{Geometry, Point};
+
+    fn point(x: f64, y: f64) -> Geometry<f64> {
+        Geometry::Point(Point::new(x, y))
+    }
+
+    fn within(buffered: &Geometry<f64>, x: f64, y: f64) -> bool {
+        match *buffered {
+            Geometry::Polygon(ref p) => p.contains(&Point::new(x, y)),
+            Geometry::MultiPolygon(ref mp) => mp.0.iter().any(|p| p.contains(&Point::new(x, y))),
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn test_buffering_points() {
+        let buffered = buffer(&point(0.0, 0.0), 1000.0);
+        match buffered {
+            Geometry::Polygon(ref p) => assert_eq!(33, p.exterior().0.len()),
+            ref other => panic!("Expected a Polygon, got {:?}", other),
+        }
+        // 0.008 degrees is about 890m at the equator
+        assert!(within(&buffered, 0.008, 0.0));
+        assert!(!within(&buffered, 0.01, 0.0));
+        assert_eq!(point(1.0, 1.0), buffer(&point(1.0, 1.0), 0.0));
+    }
+
+    #[test]
+    fn test_buffering_lines() {
+        let line = Geometry::LineString(vec![(0.0, 0.0), (0.1, 0.0), (0.1, 0.1)].into());
+        let buffered = buffer(&line, 1000.0);
+        match buffered {
+            Geometry::Polygon(ref p) => assert!(p.interiors().is_empty()),
+            ref other => panic!("Expected a Polygon, got {:?}", other),
+        }
+        for &(x, y, exp) in &[(0.05, 0.008, true), (0.05, -0.008, true), (0.05, 0.01, false), (0.108, 0.05, true)] {
+            let distance = geometry_distance(&point(x, y), &line, Method::Karney).unwrap();
+            assert_eq!(exp, distance < 1000.0);
+            assert_eq!(exp, within(&buffered, x, y));
+        }
+    }
+
+    #[test]
+    fn test_buffering_polygons_with_holes() {
+        let donut = Geometry::Polygon(geo_types::Polygon::new(
+            vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0), (0.0, 0.0)].into(),
+            vec![vec![(0.2, 0.2), (0.2, 0.8), (0.8, 0.8), (0.8, 0.2), (0.2, 0.2)].into()],
+        ));
+        let buffered = buffer(&donut, 1000.0);
+        match buffered {
+            Geometry::Polygon(ref p) => assert_eq!(1, p.interiors().len()),
+            ref other => panic!("Expected a Polygon, got {:?}", other),
+        }
+        assert!(within(&buffered, 0.5, 0.1));
+        assert!(within(&buffered, 0.5, 0.205));
+        assert!(!within(&buffered, 0.5, 0.5));
+        assert!(within(&buffered, 1.005, 0.5));
+        assert!(!within(&buffered, 1.02, 0.5));
+    }
+}
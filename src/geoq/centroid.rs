@@ -1,17 +1,323 @@
+use crate::geoq::{area, bbox, distance::GEODESIC};
+use geo::algorithm::area::Area;
 use geo::algorithm::centroid::Centroid;
+use geo::algorithm::euclidean_length::EuclideanLength;
 use geo_types::*;
+use geographiclib_rs::{DirectGeodesic, InverseGeodesic};
 
-// TODO Handle additional Multi* geometry types
+// Mean of the component centroids, weighted by the length of each LineString
+fn multi_line_string_centroid(mls: &MultiLineString<f64>) -> Option<Point<f64>> {
+    let mut total_length = 0.0;
+    let mut sum_x = 0.0;
+    let mut sum_y = 0.0;
+    for line in &mls.0 {
+        if let Some(c) = line.centroid() {
+            let length = line.euclidean_length();
+            total_length += length;
+            sum_x += length * c.x();
+            sum_y += length * c.y();
+        }
+    }
+    if total_length == 0.0 {
+        // All components are degenerate, so fall back to their vertices
+        MultiPoint(mls.0.iter().flat_map(|l| l.points_iter()).collect()).centroid()
+    } else {
+        Some(Point::new(sum_x / total_length, sum_y / total_length))
+    }
+}
+
+// All of a geometry's vertices
+pub fn vertices(geom: &Geometry<f64>) -> Vec<Coordinate<f64>> {
+    match *geom {
+        Geometry::Point(ref g) => vec![g.0],
+        Geometry::Line(ref g) => vec![g.start, g.end],
+        Geometry::LineString(ref g) => g.0.clone(),
+        // The closing vertex of each ring is only counted once
+        Geometry::Polygon(ref g) => std::iter::once(g.exterior())
+            .chain(g.interiors().iter())
+            .flat_map(|ring| {
+                let closed = ring.0.len() > 1 && ring.0.first() == ring.0.last();
+                ring.0[..ring.0.len() - closed as usize].to_vec()
+            })
+            .collect(),
+        Geometry::MultiPoint(ref g) => g.0.iter().map(|p| p.0).collect(),
+        Geometry::MultiLineString(ref g) => g.0.iter().flat_map(|l| l.0.clone()).collect(),
+        Geometry::MultiPolygon(ref g) => g
+            .0
+            .iter()
+            .flat_map(|p| vertices(&Geometry::Polygon(p.clone())))
+            .collect(),
+        Geometry::GeometryCollection(ref gc) => gc.0.iter().flat_map(vertices).collect(),
+    }
+}
+
+// Arithmetic mean of all of the geometry's vertices
+fn vertex_mean(geom: &Geometry<f64>) -> Option<Point<f64>> {
+    let coords = vertices(geom);
+    if coords.is_empty() {
+        return None;
+    }
+    let n = coords.len() as f64;
+    let (sum_x, sum_y) = coords.iter().fold((0.0, 0.0), |(x, y), c| (x + c.x, y + c.y));
+    Some(Point::new(sum_x / n, sum_y / n))
+}
+
+// TODO Handle GeometryCollections
+// MultiPolygon centroids are weighted by the area of each component
+// (as in PostGIS ST_Centroid), and MultiLineStrings by length.
+// Degenerate geometries (like Polygons with no area, or LineStrings with no length)
+// give the mean of their vertices rather than NaN.
 pub fn centroid(geom: &Geometry<f64>) -> Option<Point<f64>> {
+    if let Geometry::GeometryCollection(_) = *geom {
+        return None;
+    }
+    weighted_centroid(geom)
+        .filter(|p| p.x().is_finite() && p.y().is_finite())
+        .or_else(|| vertex_mean(geom))
+}
+
+fn weighted_centroid(geom: &Geometry<f64>) -> Option<Point<f64>> {
     match *geom {
         Geometry::Point(ref g) => Some(g.centroid()),
         Geometry::Line(ref g) => Some(g.centroid()),
         Geometry::LineString(ref g) => g.centroid(),
         Geometry::Polygon(ref g) => g.centroid(),
-        // Geometry::MultiPoint(ref g) => g.centroid(),
-        // Geometry::MultiLineString(ref g) => g.centroid(),
+        Geometry::MultiPoint(ref g) => g.centroid(),
+        Geometry::MultiLineString(ref g) => multi_line_string_centroid(g),
         Geometry::MultiPolygon(ref g) => g.centroid(),
         // Geometry::GeometryCollection(ref gc) => g.centroid()
         _ => None,
     }
 }
+
+// Point at the given geodesic distance (in meters) along a sequence of lines
+fn point_along(lines: &[&LineString<f64>], mut remaining: f64) -> Option<Point<f64>> {
+    for line in lines {
+        for pair in line.0.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            let segment: f64 = GEODESIC.inverse(a.y, a.x, b.y, b.x);
+            if remaining <= segment {
+                let (azimuth, _, _): (f64, f64, f64) = GEODESIC.inverse(a.y, a.x, b.y, b.x);
+                let (lat, lon): (f64, f64) = GEODESIC.direct(a.y, a.x, azimuth, remaining);
+                return Some(Point::new(lon, lat));
+            }
+            remaining -= segment;
+        }
+    }
+    // Rounding can leave the point just past the end
+    lines.iter().rev().find_map(|line| line.0.last()).map(|c| Point(*c))
+}
+
+// A point guaranteed to fall inside the Polygon: the middle of the widest
+// span where a horizontal line through the middle of its bbox crosses it
+fn point_on_surface(poly: &Polygon<f64>) -> Option<Point<f64>> {
+    let rect = bbox::bbox(&Geometry::Polygon(poly.clone()));
+    let y = (rect.min.y + rect.max.y) / 2.0;
+    let mut xs: Vec<f64> = std::iter::once(poly.exterior())
+        .chain(poly.interiors().iter())
+        .flat_map(|ring| ring.0.windows(2))
+        .filter(|pair| (pair[0].y > y) != (pair[1].y > y))
+        .map(|pair| {
+            let (a, b) = (pair[0], pair[1]);
+            a.x + (y - a.y) / (b.y - a.y) * (b.x - a.x)
+        })
+        .collect();
+    xs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    xs.chunks(2)
+        .filter(|span| span.len() == 2)
+        .max_by(|a, b| (a[1] - a[0]).partial_cmp(&(b[1] - b[0])).unwrap())
+        .map(|span| Point::new((span[0] + span[1]) / 2.0, y))
+        // Flat polygons have no crossings
+        .or_else(|| poly.centroid())
+}
+
+// A point on the geometry which is more intuitive for labeling than the centroid:
+// the point halfway along the geodesic length of (Multi)LineStrings, and a point
+// inside (the largest component of) (Multi)Polygons. Other geometries give their centroid.
+pub fn midpoint(geom: &Geometry<f64>) -> Option<Point<f64>> {
+    match *geom {
+        Geometry::Line(ref g) => midpoint(&Geometry::LineString(LineString(vec![g.start, g.end]))),
+        Geometry::LineString(ref g) => point_along(&[g], area::length(geom) / 2.0),
+        Geometry::MultiLineString(ref g) => {
+            let lines: Vec<&LineString<f64>> = g.0.iter().collect();
+            point_along(&lines, area::length(geom) / 2.0)
+        }
+        Geometry::Polygon(ref g) => point_on_surface(g),
+        Geometry::MultiPolygon(ref g) => g
+            .0
+            .iter()
+            .max_by(|a, b| a.area().abs().partial_cmp(&b.area().abs()).unwrap())
+            .and_then(point_on_surface),
+        _ => centroid(geom),
+    }
+}
+
+// How to weight each input's centroid when combining them with --all
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Weight {
+    // Polygons by area, lines by length, and points equally
+    ByType,
+    // Geodesic area, so only (Multi)Polygons contribute
+    Area,
+    // Geodesic length, so only (Multi)LineStrings contribute
+    Length,
+    // Every input counts the same
+    Equal,
+}
+
+impl Weight {
+    pub fn from_name(name: &str) -> Option<Weight> {
+        match name {
+            "type" => Some(Weight::ByType),
+            "area" => Some(Weight::Area),
+            "length" => Some(Weight::Length),
+            "equal" => Some(Weight::Equal),
+            _ => None,
+        }
+    }
+}
+
+// 0 for points, 1 for lines, and 2 for polygons.
+// GeometryCollections take the highest dimension of their members.
+fn dimension(geom: &Geometry<f64>) -> usize {
+    match *geom {
+        Geometry::Point(_) | Geometry::MultiPoint(_) => 0,
+        Geometry::Line(_) | Geometry::LineString(_) | Geometry::MultiLineString(_) => 1,
+        Geometry::Polygon(_) | Geometry::MultiPolygon(_) => 2,
+        Geometry::GeometryCollection(ref gc) => gc.0.iter().map(dimension).max().unwrap_or(0),
+    }
+}
+
+pub fn weight(geom: &Geometry<f64>, weight: Weight) -> f64 {
+    match weight {
+        Weight::ByType => match dimension(geom) {
+            2 => area::area(geom),
+            1 => area::length(geom),
+            _ => 1.0,
+        },
+        Weight::Area => area::area(geom),
+        Weight::Length => area::length(geom),
+        Weight::Equal => 1.0,
+    }
+}
+
+// Weighted mean of the given points. If the weights sum to 0
+// (like weighting only Points by area) they're all weighted equally.
+pub fn combine(points: &[(Point<f64>, f64)]) -> Option<Point<f64>> {
+    if points.is_empty() {
+        return None;
+    }
+    let total: f64 = points.iter().map(|(_, w)| w).sum();
+    let (sum_x, sum_y, total) = if total > 0.0 {
+        points
+            .iter()
+            .fold((0.0, 0.0, total), |(x, y, t), (p, w)| (x + w * p.x(), y + w * p.y(), t))
+    } else {
+        points
+            .iter()
+            .fold((0.0, 0.0, points.len() as f64), |(x, y, t), (p, _)| (x + p.x(), y + p.y(), t))
+    };
+    Some(Point::new(sum_x / total, sum_y / total))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::centroid::{centroid, combine, midpoint, weight, Weight};
+    use geo_types::{Geometry, LineString, MultiLineString, MultiPolygon, Point, Polygon};
+
+    fn square(x: f64, y: f64, size: f64) -> Polygon<f64> {
+        Polygon::new(
+            vec![(x, y), (x + size, y), (x + size, y + size), (x, y + size), (x, y)].into(),
+            vec![],
+        )
+    }
+
+    #[test]
+    fn test_area_weighted_multipolygon() {
+        // PostGIS: ST_Centroid('MULTIPOLYGON(((0 0,1 0,1 1,0 1,0 0)),((2 0,4 0,4 2,2 2,2 0)))')
+        // => POINT(2.5 0.9)
+        let mp = MultiPolygon(vec![square(0.0, 0.0, 1.0), square(2.0, 0.0, 2.0)]);
+        assert_eq!(
+            Some(Point::new(2.5, 0.9)),
+            centroid(&Geometry::MultiPolygon(mp))
+        );
+    }
+
+    #[test]
+    fn test_length_weighted_multilinestring() {
+        // PostGIS: ST_Centroid('MULTILINESTRING((0 0,2 0),(0 1,0 5))')
+        // => POINT(0.333333333333333 2)
+        let mls = MultiLineString(vec![
+            LineString::from(vec![(0.0, 0.0), (2.0, 0.0)]),
+            LineString::from(vec![(0.0, 1.0), (0.0, 5.0)]),
+        ]);
+        assert_eq!(
+            Some(Point::new(1.0 / 3.0, 2.0)),
+            centroid(&Geometry::MultiLineString(mls))
+        );
+    }
+
+    #[test]
+    fn test_midpoint_of_linestring() {
+        // Just past the corner (since a degree of longitude is longer than
+        // a degree of latitude here), rather than the centroid at (0.25, 0.75)
+        let line = LineString::from(vec![(0.0, 0.0), (0.0, 1.0), (1.0, 1.0)]);
+        let mid = midpoint(&Geometry::LineString(line)).unwrap();
+        assert!(mid.x() > 0.0 && mid.x() < 0.01, "{:?}", mid);
+        assert!((mid.y() - 1.0).abs() < 1e-4, "{:?}", mid);
+    }
+
+    #[test]
+    fn test_midpoint_of_concave_polygon() {
+        // U-shaped polygon whose centroid falls in the gap
+        let u = Polygon::new(
+            vec![(0.0, 0.0), (3.0, 0.0), (3.0, 3.0), (2.0, 3.0), (2.0, 1.0), (1.0, 1.0), (1.0, 3.0), (0.0, 3.0), (0.0, 0.0)].into(),
+            vec![],
+        );
+        assert_eq!(Some(Point::new(2.5, 1.5)), midpoint(&Geometry::Polygon(u)));
+    }
+
+    #[test]
+    fn test_centroid_of_zero_area_triangle() {
+        let flat = Polygon::new(vec![(0.0, 0.0), (1.0, 1.0), (2.0, 2.0), (0.0, 0.0)].into(), vec![]);
+        // Mean of the distinct vertices
+        assert_eq!(Some(Point::new(1.0, 1.0)), centroid(&Geometry::Polygon(flat)));
+        let mp = MultiPolygon(vec![
+            Polygon::new(vec![(0.0, 0.0), (2.0, 0.0), (0.0, 0.0)].into(), vec![]),
+            Polygon::new(vec![(0.0, 2.0), (2.0, 2.0), (0.0, 2.0)].into(), vec![]),
+        ]);
+        assert_eq!(Some(Point::new(1.0, 1.0)), centroid(&Geometry::MultiPolygon(mp)));
+    }
+
+    #[test]
+    fn test_centroid_of_single_vertex() {
+        let line = LineString::from(vec![(1.0, 2.0)]);
+        assert_eq!(Some(Point::new(1.0, 2.0)), centroid(&Geometry::LineString(line)));
+        let repeated = LineString::from(vec![(1.0, 2.0), (1.0, 2.0)]);
+        assert_eq!(Some(Point::new(1.0, 2.0)), centroid(&Geometry::LineString(repeated)));
+        let poly = Polygon::new(vec![(1.0, 2.0), (1.0, 2.0), (1.0, 2.0)].into(), vec![]);
+        assert_eq!(Some(Point::new(1.0, 2.0)), centroid(&Geometry::Polygon(poly)));
+    }
+
+    #[test]
+    fn test_combining_weighted_points() {
+        let points = vec![(Point::new(0.0, 0.0), 3.0), (Point::new(4.0, 0.0), 1.0)];
+        assert_eq!(Some(Point::new(1.0, 0.0)), combine(&points));
+        let unweighted = vec![(Point::new(0.0, 0.0), 0.0), (Point::new(4.0, 0.0), 0.0)];
+        assert_eq!(Some(Point::new(2.0, 0.0)), combine(&unweighted));
+        assert_eq!(None, combine(&[]));
+    }
+
+    #[test]
+    fn test_weighting_by_type() {
+        let point = Geometry::Point(Point::new(1.0, 1.0));
+        let line = Geometry::LineString(LineString::from(vec![(0.0, 0.0), (1.0, 0.0)]));
+        let poly = Geometry::Polygon(square(0.0, 0.0, 1.0));
+        assert_eq!(1.0, weight(&point, Weight::ByType));
+        assert!((weight(&line, Weight::ByType) - 111_319.49).abs() < 0.01);
+        assert!((weight(&poly, Weight::ByType) - 12_308_778_361.0).abs() < 1.0);
+        assert_eq!(0.0, weight(&point, Weight::Area));
+        assert_eq!(0.0, weight(&poly, Weight::Length));
+        assert_eq!(1.0, weight(&poly, Weight::Equal));
+    }
+}
@@ -0,0 +1,311 @@
+use crate::geoq::{area::signed_area, bbox::BBoxToPoly};
+use geo_types::{
+    Coordinate, Geometry, GeometryCollection, LineString, MultiLineString, MultiPoint,
+    MultiPolygon, Polygon, Rect,
+};
+
+type Coord = Coordinate<f64>;
+
+fn open_ring(ring: &LineString<f64>) -> Vec<Coord> {
+    let mut coords = ring.0.clone();
+    if coords.len() > 1 && coords.first() == coords.last() {
+        coords.pop();
+    }
+    coords
+}
+
+// Repeated vertices are dropped, so rings touching the clipping
+// boundary don't get zero-length edges
+fn close_ring(mut coords: Vec<Coord>) -> Option<LineString<f64>> {
+    coords.dedup();
+    while coords.len() > 1 && coords.first() == coords.last() {
+        coords.pop();
+    }
+    if coords.len() < 3 {
+        return None;
+    }
+    coords.push(coords[0]);
+    Some(LineString(coords))
+}
+
+fn cross(a: Coord, b: Coord, p: Coord) -> f64 {
+    (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x)
+}
+
+fn crossing(a: Coord, b: Coord, side_a: f64, side_b: f64) -> Coord {
+    let t = side_a / (side_a - side_b);
+    Coordinate {
+        x: a.x + t * (b.x - a.x),
+        y: a.y + t * (b.y - a.y),
+    }
+}
+
+// Sutherland-Hodgman clipping of an open ring against a single half-plane.
+// `side` must be an affine function of the coordinate which is >= 0
+// for coordinates on the kept side of the plane.
+pub fn clip_to_half_plane<F>(ring: &[Coord], side: F) -> Vec<Coord>
+where
+    F: Fn(Coord) -> f64,
+{
+    let mut output = Vec::with_capacity(ring.len() + 1);
+    if ring.is_empty() {
+        return output;
+    }
+    let mut prev = ring[ring.len() - 1];
+    let mut prev_side = side(prev);
+    for &curr in ring {
+        let curr_side = side(curr);
+        // Vertices lying on the plane are their own crossing point
+        if curr_side >= 0.0 {
+            if prev_side < 0.0 && curr_side > 0.0 {
+                output.push(crossing(prev, curr, prev_side, curr_side));
+            }
+            output.push(curr);
+        } else if prev_side > 0.0 {
+            output.push(crossing(prev, curr, prev_side, curr_side));
+        }
+        prev = curr;
+        prev_side = curr_side;
+    }
+    output
+}
+
+// Clip a (closed) ring to a convex (closed) clipping ring.
+// The subject ring may be concave, in which case pieces of the result
+// which would be disjoint are joined by degenerate edges along the
+// clipping boundary.
+// Returns None if nothing of the subject falls within the clip ring.
+pub fn clip_ring(subject: &LineString<f64>, clipper: &LineString<f64>) -> Option<LineString<f64>> {
+    let clip_coords = open_ring(clipper);
+    let orientation = if signed_area(&clip_coords) < 0.0 { -1.0 } else { 1.0 };
+    let n = clip_coords.len();
+    let mut output = open_ring(subject);
+    for i in 0..n {
+        let a = clip_coords[i];
+        let b = clip_coords[(i + 1) % n];
+        output = clip_to_half_plane(&output, |c| orientation * cross(a, b, c));
+        if output.is_empty() {
+            return None;
+        }
+    }
+    close_ring(output)
+}
+
+// Clip an open ring to a half-plane and re-close it.
+pub fn clip_ring_to_half_plane<F>(ring: &LineString<f64>, side: F) -> Option<LineString<f64>>
+where
+    F: Fn(Coord) -> f64,
+{
+    close_ring(clip_to_half_plane(&open_ring(ring), side))
+}
+
+// Liang-Barsky clipping of the segment from a to b to a rectangle.
+// Returns None if no part of the segment falls within the rectangle.
+fn clip_segment(a: Coord, b: Coord, rect: &Rect<f64>) -> Option<(Coord, Coord)> {
+    let dx = b.x - a.x;
+    let dy = b.y - a.y;
+    let mut t0 = 0.0;
+    let mut t1 = 1.0;
+    let edges = [
+        (-dx, a.x - rect.min.x),
+        (dx, rect.max.x - a.x),
+        (-dy, a.y - rect.min.y),
+        (dy, rect.max.y - a.y),
+    ];
+    for &(p, q) in &edges {
+        if p == 0.0 {
+            // Parallel to this edge, so either entirely inside or outside of it
+            if q < 0.0 {
+                return None;
+            }
+            continue;
+        }
+        let r = q / p;
+        if p < 0.0 {
+            if r > t1 {
+                return None;
+            }
+            t0 = f64::max(t0, r);
+        } else {
+            if r < t0 {
+                return None;
+            }
+            t1 = f64::min(t1, r);
+        }
+    }
+    let at = |t: f64| Coordinate { x: a.x + t * dx, y: a.y + t * dy };
+    let start = if t0 == 0.0 { a } else { at(t0) };
+    let end = if t1 == 1.0 { b } else { at(t1) };
+    Some((start, end))
+}
+
+// Pieces of a line which fall within a rectangle
+pub fn clip_line_to_rect(line: &LineString<f64>, rect: &Rect<f64>) -> Vec<LineString<f64>> {
+    let mut pieces = Vec::new();
+    let mut current: Vec<Coord> = Vec::new();
+    for pair in line.0.windows(2) {
+        match clip_segment(pair[0], pair[1], rect) {
+            Some((start, end)) if start != end => {
+                if current.last() != Some(&start) {
+                    if current.len() > 1 {
+                        pieces.push(LineString(current));
+                    }
+                    current = vec![start];
+                }
+                current.push(end);
+            }
+            _ => {}
+        }
+    }
+    if current.len() > 1 {
+        pieces.push(LineString(current));
+    }
+    pieces
+}
+
+fn clip_polygon_to_rect(poly: &Polygon<f64>, rect: &Rect<f64>) -> Option<Polygon<f64>> {
+    let clipper = rect.to_polygon();
+    let clip = |ring: &LineString<f64>| {
+        clip_ring(ring, clipper.exterior())
+            .filter(|clipped| signed_area(&open_ring(clipped)) != 0.0)
+    };
+    clip(poly.exterior()).map(|exterior| {
+        let interiors = poly.interiors().iter().filter_map(clip).collect();
+        Polygon::new(exterior, interiors)
+    })
+}
+
+// Clip a geometry to a rectangle, with Sutherland-Hodgman clipping for
+// polygons and Liang-Barsky clipping for lines.
+// Returns None if nothing of the geometry falls within the rectangle.
+pub fn clip_to_rect(geom: &Geometry<f64>, rect: &Rect<f64>) -> Option<Geometry<f64>> {
+    let inside = |c: &Coord| {
+        rect.min.x <= c.x && c.x <= rect.max.x && rect.min.y <= c.y && c.y <= rect.max.y
+    };
+    match *geom {
+        Geometry::Point(ref p) => inside(&p.0).then_some(Geometry::Point(*p)),
+        Geometry::Line(ref l) => {
+            clip_to_rect(&Geometry::LineString(LineString(vec![l.start, l.end])), rect)
+        }
+        Geometry::LineString(ref ls) => {
+            let mut pieces = clip_line_to_rect(ls, rect);
+            match pieces.len() {
+                0 => None,
+                1 => Some(Geometry::LineString(pieces.remove(0))),
+                _ => Some(Geometry::MultiLineString(MultiLineString(pieces))),
+            }
+        }
+        Geometry::Polygon(ref poly) => clip_polygon_to_rect(poly, rect).map(Geometry::Polygon),
+        Geometry::MultiPoint(ref mp) => {
+            let points: Vec<_> = mp.0.iter().filter(|p| inside(&p.0)).cloned().collect();
+            if points.is_empty() {
+                None
+            } else {
+                Some(Geometry::MultiPoint(MultiPoint(points)))
+            }
+        }
+        Geometry::MultiLineString(ref mls) => {
+            let pieces: Vec<_> = mls.0.iter().flat_map(|ls| clip_line_to_rect(ls, rect)).collect();
+            if pieces.is_empty() {
+                None
+            } else {
+                Some(Geometry::MultiLineString(MultiLineString(pieces)))
+            }
+        }
+        Geometry::MultiPolygon(ref mp) => {
+            let polys: Vec<_> = mp.0.iter().filter_map(|p| clip_polygon_to_rect(p, rect)).collect();
+            if polys.is_empty() {
+                None
+            } else {
+                Some(Geometry::MultiPolygon(MultiPolygon(polys)))
+            }
+        }
+        Geometry::GeometryCollection(ref gc) => {
+            let geoms: Vec<_> = gc.0.iter().filter_map(|g| clip_to_rect(g, rect)).collect();
+            if geoms.is_empty() {
+                None
+            } else {
+                Some(Geometry::GeometryCollection(GeometryCollection(geoms)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::clip::{clip_line_to_rect, clip_ring, clip_to_rect};
+    use geo_types::{Coordinate, Geometry, LineString, Polygon, Rect};
+
+    fn window() -> Rect<f64> {
+        Rect {
+            min: Coordinate { x: 0.0, y: 0.0 },
+            max: Coordinate { x: 2.0, y: 2.0 },
+        }
+    }
+
+    #[test]
+    fn test_clipping_overlapping_squares() {
+        let subject: LineString<f64> =
+            vec![(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0), (0.0, 0.0)].into();
+        let clipper: LineString<f64> =
+            vec![(1.0, 1.0), (3.0, 1.0), (3.0, 3.0), (1.0, 3.0), (1.0, 1.0)].into();
+        let exp: LineString<f64> =
+            vec![(1.0, 1.0), (2.0, 1.0), (2.0, 2.0), (1.0, 2.0), (1.0, 1.0)].into();
+        assert_eq!(Some(exp), clip_ring(&subject, &clipper));
+    }
+
+    #[test]
+    fn test_clipping_with_clockwise_clipper() {
+        let subject: LineString<f64> =
+            vec![(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0), (0.0, 0.0)].into();
+        let clipper: LineString<f64> =
+            vec![(1.0, 1.0), (1.0, 3.0), (3.0, 3.0), (3.0, 1.0), (1.0, 1.0)].into();
+        let clipped = clip_ring(&subject, &clipper).unwrap();
+        assert_eq!(5, clipped.0.len());
+    }
+
+    #[test]
+    fn test_clipping_disjoint_rings() {
+        let subject: LineString<f64> =
+            vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 0.0)].into();
+        let clipper: LineString<f64> =
+            vec![(5.0, 5.0), (6.0, 5.0), (6.0, 6.0), (5.0, 5.0)].into();
+        assert_eq!(None, clip_ring(&subject, &clipper));
+    }
+
+    #[test]
+    fn test_clipping_line_to_rect() {
+        // Leaves the window and comes back in
+        let line: LineString<f64> =
+            vec![(-1.0, 1.0), (1.0, 1.0), (1.0, 3.0), (1.5, 3.0), (1.5, 1.0)].into();
+        let exp: Vec<LineString<f64>> = vec![
+            vec![(0.0, 1.0), (1.0, 1.0), (1.0, 2.0)].into(),
+            vec![(1.5, 2.0), (1.5, 1.0)].into(),
+        ];
+        assert_eq!(exp, clip_line_to_rect(&line, &window()));
+
+        let outside: LineString<f64> = vec![(3.0, 0.0), (3.0, 2.0)].into();
+        assert!(clip_line_to_rect(&outside, &window()).is_empty());
+    }
+
+    #[test]
+    fn test_clipping_geometries_to_rect() {
+        let poly = Geometry::Polygon(Polygon::new(
+            vec![(1.0, 1.0), (3.0, 1.0), (3.0, 3.0), (1.0, 3.0), (1.0, 1.0)].into(),
+            vec![],
+        ));
+        let exp = Geometry::Polygon(Polygon::new(
+            vec![(1.0, 2.0), (1.0, 1.0), (2.0, 1.0), (2.0, 2.0), (1.0, 2.0)].into(),
+            vec![],
+        ));
+        assert_eq!(Some(exp), clip_to_rect(&poly, &window()));
+
+        // Only touching the window along its edge
+        let touching = Geometry::Polygon(Polygon::new(
+            vec![(2.0, 0.0), (3.0, 0.0), (3.0, 1.0), (2.0, 1.0), (2.0, 0.0)].into(),
+            vec![],
+        ));
+        assert_eq!(None, clip_to_rect(&touching, &window()));
+        assert_eq!(None, clip_to_rect(&Geometry::Point((5.0, 5.0).into()), &window()));
+    }
+}
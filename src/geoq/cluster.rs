@@ -0,0 +1,76 @@
+use crate::geoq::distance::geodesic_distance;
+use geo_types::Point;
+
+pub const NOISE: i64 = -1;
+const UNVISITED: i64 = -2;
+
+fn neighbors(points: &[Point<f64>], idx: usize, eps: f64) -> Vec<usize> {
+    (0..points.len())
+        .filter(|&other| geodesic_distance(&points[idx], &points[other]) <= eps)
+        .collect()
+}
+
+// DBSCAN clustering over geodesic distances (in meters).
+// Returns a cluster label for each input point, in input order.
+// Clusters are numbered from 0; noise points are labeled NOISE (-1).
+pub fn dbscan(points: &[Point<f64>], eps: f64, min_points: usize) -> Vec<i64> {
+    let mut labels = vec![UNVISITED; points.len()];
+    let mut cluster = 0;
+
+    for idx in 0..points.len() {
+        if labels[idx] != UNVISITED {
+            continue;
+        }
+
+        let seeds = neighbors(points, idx, eps);
+        if seeds.len() < min_points {
+            labels[idx] = NOISE;
+            continue;
+        }
+
+        labels[idx] = cluster;
+        let mut queue = seeds;
+        while let Some(other) = queue.pop() {
+            if labels[other] == NOISE {
+                labels[other] = cluster;
+            }
+            if labels[other] != UNVISITED {
+                continue;
+            }
+            labels[other] = cluster;
+            let other_neighbors = neighbors(points, other, eps);
+            if other_neighbors.len() >= min_points {
+                queue.extend(other_neighbors);
+            }
+        }
+        cluster += 1;
+    }
+    labels
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::cluster::{dbscan, NOISE};
+    use geo_types::Point;
+
+    #[test]
+    fn test_dbscan() {
+        let points = vec![
+            Point::new(-118.0, 34.0),
+            Point::new(-118.0001, 34.0001),
+            Point::new(-118.0002, 34.0),
+            Point::new(-74.0, 40.7),
+            Point::new(-74.0001, 40.7001),
+            Point::new(-74.0002, 40.7),
+            Point::new(0.0, 0.0),
+        ];
+        let labels = dbscan(&points, 100.0, 3);
+        assert_eq!(vec![0, 0, 0, 1, 1, 1, NOISE], labels);
+    }
+
+    #[test]
+    fn test_dbscan_all_noise() {
+        let points = vec![Point::new(-118.0, 34.0), Point::new(-74.0, 40.7)];
+        assert_eq!(vec![NOISE, NOISE], dbscan(&points, 100.0, 2));
+    }
+}
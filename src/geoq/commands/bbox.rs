@@ -1,15 +1,98 @@
-use crate::geoq::{error::Error, par, bbox::BBoxToPoly, reader, bbox};
+use crate::geoq::{error::Error, output, par, bbox::BBoxToPoly, reader, bbox, conversion, coord_count};
 use clap::ArgMatches;
-use geo_types::Rect;
+use geo_types::{Geometry, Rect};
+
+pub fn read_rects(matches: &ArgMatches) -> Result<Vec<Rect<f64>>, Error> {
+    let mut rects = Vec::new();
+    reader::for_input_entity(matches.value_of("input"), |e| {
+        if !coord_count::is_empty(&e.geom()) {
+            rects.push(e.bbox());
+        }
+        Ok(())
+    })?;
+    Ok(rects)
+}
+
+fn antimeridian(matches: &ArgMatches) -> Result<(), Error> {
+    let rects = read_rects(matches)?;
+    match bbox::merge_antimeridian(&rects) {
+        None => Err(Error::NoInputGiven),
+        Some(bbox) => {
+            let geom = bbox::antimeridian_polygon(&bbox);
+            output::check_finite(&geom, None)?;
+            let gj = geojson::Geometry::new(conversion::geojson_value(&geom));
+            println!("{}", output::geojson(gj));
+            Ok(())
+        }
+    }
+}
+
+// Inputs with huge or infinite coordinates can give bboxes which can't be written as GeoJSON
+fn check_finite(bbox: &Rect<f64>, input: Option<&str>) -> Result<(), Error> {
+    output::check_finite(&Geometry::Polygon(bbox.to_polygon()), input)
+}
+
+fn gj_bbox(bbox: &Rect<f64>) -> Vec<f64> {
+    vec![bbox.min.x, bbox.min.y, bbox.max.x, bbox.max.y]
+}
+
+// With --all --embed, give 1 FeatureCollection of the inputs, each with
+// its own embedded bbox, plus the bbox of all of them on the collection.
+// With --antimeridian a wrapping collection bbox has west > east, per RFC 7946.
+fn embedded_collection(matches: &ArgMatches) -> Result<(), Error> {
+    let mut features = Vec::new();
+    let mut rects = Vec::new();
+    reader::for_input_entity(matches.value_of("input"), |e| {
+        if coord_count::is_empty(&e.geom()) {
+            return Ok(());
+        }
+        let bbox = e.bbox();
+        check_finite(&bbox, Some(&e.input()))?;
+        let mut feat = e.geojson_feature();
+        feat.bbox = Some(gj_bbox(&bbox));
+        features.push(feat);
+        rects.push(bbox);
+        Ok(())
+    })?;
+
+    let bbox = if matches.is_present("antimeridian") {
+        bbox::merge_antimeridian(&rects)
+    } else {
+        rects.iter().fold(None, |acc, r| match acc {
+            Some(curr) => Some(bbox::merge(&curr, r)),
+            None => Some(*r),
+        })
+    };
+    match bbox {
+        None => Err(Error::NoInputGiven),
+        Some(bbox) => {
+            check_finite(&bbox, None)?;
+            let fc = geojson::FeatureCollection {
+                bbox: Some(gj_bbox(&bbox)),
+                features,
+                foreign_members: None,
+            };
+            println!("{}", output::geojson(fc));
+            Ok(())
+        }
+    }
+}
 
 pub fn run(matches: &ArgMatches) -> Result<(), Error> {
     let embed = matches.is_present("embed");
     let all = matches.is_present("all");
 
-    if all {
+    if all && embed {
+        embedded_collection(matches)
+    } else if matches.is_present("antimeridian") {
+        antimeridian(matches)
+    } else if all {
         let mut bbox: Option<Rect<f64>> = None;
 
-        reader::for_entity(|e| {
+        reader::for_input_entity(matches.value_of("input"), |e| {
+            if coord_count::is_empty(&e.geom()) {
+                return Ok(());
+            }
             match bbox {
                 Some(curr) => {
                     bbox = Some(bbox::merge(&curr, &e.bbox()));
@@ -26,25 +109,30 @@ pub fn run(matches: &ArgMatches) -> Result<(), Error> {
                 Err(Error::NoInputGiven)
             }
             Some(bbox) => {
+                check_finite(&bbox, None)?;
                 let poly = bbox.to_polygon();
                 let gj = geojson::Geometry::new(geojson::Value::from(&poly));
-                println!("{}", serde_json::to_string(&gj).unwrap());
+                println!("{}", output::geojson(gj));
                 Ok(())
             }
         }
     } else {
-        par::for_stdin_entity(move |e| {
+        par::for_input_entity(matches.value_of("input"), move |e| {
+            // Empty geometries have no bbox
+            if coord_count::is_empty(&e.geom()) {
+                return Ok(vec![]);
+            }
             let bbox: Rect<f64> = e.bbox();
+            check_finite(&bbox, Some(&e.input()))?;
 
             if embed {
                 let mut feat = e.geojson_feature();
-                let gj_bbox = vec![bbox.min.x, bbox.min.y, bbox.max.x, bbox.max.y];
-                feat.bbox = Some(gj_bbox);
-                Ok(vec![format!("{}", serde_json::to_string(&feat).unwrap())])
+                feat.bbox = Some(gj_bbox(&bbox));
+                Ok(vec![output::geojson(feat)])
             } else {
                 let poly = bbox.to_polygon();
                 let gj = geojson::Geometry::new(geojson::Value::from(&poly));
-                Ok(vec![format!("{}", serde_json::to_string(&gj).unwrap())])
+                Ok(vec![output::geojson(gj)])
             }
         })
     }
@@ -0,0 +1,141 @@
+use crate::geoq::{
+    conversion, error::Error, geohash as gh, geohash::MAX_GEOHASH_LENGTH, grid::Grid, output, reader,
+    units,
+};
+use clap::ArgMatches;
+use geo_types::{Geometry, Point, Polygon};
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+
+enum Binning {
+    Geohash(usize),
+    Grid(Grid),
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum Cell {
+    Geohash(String),
+    Grid(i64, i64),
+}
+
+// Running totals for a cell: the number of points, and for each
+// aggregated property, the sum and number of its numeric values
+struct Stats {
+    count: usize,
+    totals: Vec<(f64, usize)>,
+}
+
+impl Binning {
+    fn cell(&self, p: &Point<f64>) -> Result<Cell, Error> {
+        match self {
+            Binning::Geohash(level) => geohash::encode(p.0, *level)
+                .map(Cell::Geohash)
+                .map_err(|_| Error::InvalidGeohashPoint),
+            Binning::Grid(grid) => {
+                let (row, col) = grid.cell(p);
+                Ok(Cell::Grid(row, col))
+            }
+        }
+    }
+
+    fn polygon(&self, cell: &Cell) -> Option<Polygon<f64>> {
+        match (self, cell) {
+            (_, Cell::Geohash(hash)) => gh::bbox(hash),
+            (Binning::Grid(grid), Cell::Grid(row, col)) => Some(grid.cell_polygon((*row, *col))),
+            _ => None,
+        }
+    }
+}
+
+fn read_binning(matches: &ArgMatches) -> Result<Binning, Error> {
+    match (matches.value_of("geohash"), matches.value_of("grid")) {
+        (Some(level), None) => match level.parse::<usize>() {
+            Ok(l) if (1..=MAX_GEOHASH_LENGTH).contains(&l) => Ok(Binning::Geohash(l)),
+            _ => {
+                eprintln!(
+                    "Invalid --geohash level: {:?} - must be an integer between 1 and {}",
+                    level, MAX_GEOHASH_LENGTH
+                );
+                Err(Error::InvalidNumberFormat)
+            }
+        },
+        (None, Some(size)) => {
+            let meters = units::parse_distance(size)?;
+            if !(meters.is_finite() && meters > 0.0) {
+                eprintln!("Invalid --grid: {:?} - must be a distance greater than 0", size);
+                return Err(Error::InvalidNumberFormat);
+            }
+            Ok(Binning::Grid(Grid::new(meters)))
+        }
+        _ => {
+            eprintln!("Must provide a cell size with either --geohash LEVEL or --grid DISTANCE.");
+            Err(Error::MissingArgument)
+        }
+    }
+}
+
+fn values(matches: &ArgMatches, name: &str) -> Vec<String> {
+    matches
+        .values_of(name)
+        .map(|vs| vs.map(|v| v.to_string()).collect())
+        .unwrap_or_default()
+}
+
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let binning = read_binning(matches)?;
+    let sums = values(matches, "sum");
+    let means = values(matches, "mean");
+    let aggregated: Vec<String> = sums.iter().chain(means.iter()).cloned().collect();
+
+    let mut cells: BTreeMap<Cell, Stats> = BTreeMap::new();
+    reader::for_input_entity(matches.value_of("input"), |e| match e.geom() {
+        Geometry::Point(p) => {
+            let stats = cells.entry(binning.cell(&p)?).or_insert_with(|| Stats {
+                count: 0,
+                totals: vec![(0.0, 0); aggregated.len()],
+            });
+            stats.count += 1;
+            let props = e.geojson_properties();
+            for (name, total) in aggregated.iter().zip(stats.totals.iter_mut()) {
+                if let Some(value) = props.get(name).and_then(Value::as_f64) {
+                    total.0 += value;
+                    total.1 += 1;
+                }
+            }
+            Ok(())
+        }
+        _ => {
+            eprintln!("Binning requires Point inputs. Got: {}", e.raw());
+            Err(Error::PointRequired)
+        }
+    })?;
+
+    for (cell, stats) in cells {
+        let mut props = Map::new();
+        if let Cell::Geohash(ref hash) = cell {
+            props.insert("geohash".to_string(), json!(hash));
+        }
+        props.insert("count".to_string(), json!(stats.count));
+        let (sum_totals, mean_totals) = stats.totals.split_at(sums.len());
+        for (name, (sum, _)) in sums.iter().zip(sum_totals) {
+            props.insert(format!("{}_sum", name), json!(sum));
+        }
+        for (name, (sum, n)) in means.iter().zip(mean_totals) {
+            let mean = if *n == 0 { Value::Null } else { json!(sum / *n as f64) };
+            props.insert(format!("{}_mean", name), mean);
+        }
+        if let Some(poly) = binning.polygon(&cell) {
+            let feature = geojson::Feature {
+                bbox: None,
+                geometry: Some(geojson::Geometry::new(conversion::geojson_value(
+                    &Geometry::Polygon(poly),
+                ))),
+                id: None,
+                properties: Some(props),
+                foreign_members: None,
+            };
+            println!("{}", output::geojson(feature));
+        }
+    }
+    Ok(())
+}
@@ -1,22 +1,99 @@
-use crate::geoq::{self, error::Error, reader};
+use crate::geoq::{self, conversion, entity::Entity, error::Error, multi, output, reader};
+use clap::ArgMatches;
 use geo_types::{Geometry, Point};
 use geojson;
-use serde_json;
 
-fn gj_point(point: Point<f64>) -> String {
+fn format_point(point: Point<f64>, raw: bool) -> String {
+    if raw {
+        return format!("{},{}", point.x(), point.y());
+    }
     let geom = Geometry::Point(point);
-    let gj = geojson::Geometry::new(geojson::Value::from(&geom));
-    serde_json::to_string(&gj).unwrap()
+    let gj = geojson::Geometry::new(conversion::geojson_value(&geom));
+    output::geojson(gj)
 }
 
-pub fn run() -> Result<(), Error> {
-    reader::for_entity(|e| {
+// A Feature for the centroid of a component, with the input's
+// id and properties, and the index of the component
+fn component_feature(e: &Entity, point: Point<f64>, index: usize, raw: bool) -> String {
+    if raw {
+        return format!("{}\t{}", format_point(point, true), index);
+    }
+    let mut props = e.geojson_properties();
+    props.insert("component".to_string(), serde_json::json!(index));
+    let feature = geojson::Feature {
+        bbox: None,
+        geometry: Some(geojson::Geometry::new(conversion::geojson_value(&Geometry::Point(point)))),
+        id: e.geojson_id(),
+        properties: Some(props),
+        foreign_members: None,
+    };
+    output::geojson(feature)
+}
+
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let midpoint = matches.is_present("midpoint");
+    let verbose = matches.is_present("verbose");
+    let raw_output = matches.is_present("raw");
+    let multi = matches.is_present("multi");
+    let weight = if matches.is_present("all") {
+        matches
+            .value_of("weight")
+            .and_then(geoq::centroid::Weight::from_name)
+            .or(Some(geoq::centroid::Weight::ByType))
+    } else {
+        None
+    };
+    // Representative point and weight of each input, with --all
+    let mut points: Vec<(Point<f64>, f64)> = Vec::new();
+    reader::for_input_entity(matches.value_of("input"), |e| {
         let raw = e.raw();
+        if e.has_null_geometry() {
+            if verbose {
+                eprintln!("Skipping feature with null geometry: {}", raw);
+            }
+            return Ok(());
+        }
         let g = e.geom();
-        match geoq::centroid::centroid(&g) {
-            Some(point) => println!("{}", gj_point(point)),
-            None => eprintln!("Could not calculate centroid for geom: {}", raw),
+        let center = |g: &Geometry<f64>| {
+            if midpoint {
+                geoq::centroid::midpoint(g)
+            } else {
+                geoq::centroid::centroid(g)
+            }
+        };
+        if multi {
+            for (index, component) in multi::components(g).iter().enumerate() {
+                match center(component) {
+                    Some(point) => {
+                        output::check_finite(&Geometry::Point(point), Some(&e.input()))?;
+                        println!("{}", component_feature(&e, point, index, raw_output))
+                    }
+                    None => eprintln!("Could not calculate centroid for component {} of geom: {}", index, raw),
+                }
+            }
+            return Ok(());
+        }
+        let point = center(&g);
+        if let Some(point) = point {
+            output::check_finite(&Geometry::Point(point), Some(&e.input()))?;
+        }
+        match (point, weight) {
+            (Some(point), Some(weight)) => {
+                points.push((point, geoq::centroid::weight(&g, weight)))
+            }
+            (Some(point), None) => println!("{}", format_point(point, raw_output)),
+            (None, _) => eprintln!("Could not calculate centroid for geom: {}", raw),
         }
         Ok(())
-    })
+    })?;
+    if weight.is_some() {
+        match geoq::centroid::combine(&points) {
+            Some(point) => {
+                output::check_finite(&Geometry::Point(point), None)?;
+                println!("{}", format_point(point, raw_output))
+            }
+            None => return Err(Error::NoInputGiven),
+        }
+    }
+    Ok(())
 }
@@ -0,0 +1,16 @@
+use crate::geoq::{bbox, clip, error::Error, output, par};
+use clap::ArgMatches;
+
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let arg = matches.value_of("bbox").ok_or(Error::MissingArgument)?;
+    let rect = bbox::parse(arg).ok_or_else(|| {
+        eprintln!("Invalid bbox: {:?} - must be minlon,minlat,maxlon,maxlat", arg);
+        Error::InvalidNumberFormat
+    })?;
+    par::for_input_entity(matches.value_of("input"), move |e| {
+        match clip::clip_to_rect(&e.geom(), &rect) {
+            Some(clipped) => Ok(vec![output::like_input(&e, &clipped)]),
+            None => Ok(vec![]),
+        }
+    })
+}
@@ -0,0 +1,96 @@
+use crate::geoq::{cluster, entity::Entity, error::Error, output, reader, units};
+use clap::ArgMatches;
+use geo::algorithm::convexhull::ConvexHull;
+use geo_types::{Geometry, MultiPoint, Point};
+use serde_json::json;
+
+fn read_min_points(matches: &ArgMatches) -> Result<usize, Error> {
+    match matches.value_of("min-points") {
+        Some(arg) => match arg.parse::<usize>() {
+            Ok(min_points) if min_points > 0 => Ok(min_points),
+            _ => {
+                eprintln!(
+                    "Invalid --min-points: {:?} - must be a positive integer",
+                    arg
+                );
+                Err(Error::InvalidNumberFormat)
+            }
+        },
+        None => Err(Error::MissingArgument),
+    }
+}
+
+fn read_points() -> Result<(Vec<Entity>, Vec<Point<f64>>), Error> {
+    let mut entities = Vec::new();
+    let mut points = Vec::new();
+    reader::for_entity(|e| match e.geom() {
+        Geometry::Point(p) => {
+            points.push(p);
+            entities.push(e);
+            Ok(())
+        }
+        _ => {
+            eprintln!("Clustering requires Point inputs. Got: {}", e.raw());
+            Err(Error::PointRequired)
+        }
+    })?;
+    Ok((entities, points))
+}
+
+fn print_labeled_points(entities: Vec<Entity>, labels: Vec<i64>) {
+    for (e, label) in entities.into_iter().zip(labels) {
+        let mut feature = e.geojson_feature();
+        if let Some(props) = feature.properties.as_mut() {
+            props.insert("cluster".to_string(), json!(label));
+        }
+        println!("{}", output::geojson(feature));
+    }
+}
+
+fn print_hulls(points: Vec<Point<f64>>, labels: Vec<i64>) {
+    let num_clusters = labels.iter().max().map(|max| max + 1).unwrap_or(0);
+    for cluster_id in 0..num_clusters {
+        let members: Vec<Point<f64>> = points
+            .iter()
+            .zip(labels.iter())
+            .filter(|(_, &label)| label == cluster_id)
+            .map(|(p, _)| *p)
+            .collect();
+        let count = members.len();
+        // Fewer than 3 points have no hull with an area
+        if count < 3 {
+            continue;
+        }
+        let hull = Geometry::Polygon(MultiPoint(members).convex_hull());
+        let mut props = serde_json::Map::new();
+        props.insert("cluster".to_string(), json!(cluster_id));
+        props.insert("count".to_string(), json!(count));
+        let feature = geojson::Feature {
+            bbox: None,
+            geometry: Some(geojson::Geometry::new(geojson::Value::from(&hull))),
+            id: None,
+            properties: Some(props),
+            foreign_members: None,
+        };
+        println!("{}", output::geojson(feature));
+    }
+}
+
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let eps = match matches.value_of("eps") {
+        Some(arg) => units::parse_distance(arg)?,
+        None => return Err(Error::MissingArgument),
+    };
+    let min_points = read_min_points(matches)?;
+    let hulls = matches.is_present("hulls");
+
+    let (entities, points) = read_points()?;
+    let labels = cluster::dbscan(&points, eps, min_points);
+
+    if hulls {
+        print_hulls(points, labels);
+    } else {
+        print_labeled_points(entities, labels);
+    }
+    Ok(())
+}
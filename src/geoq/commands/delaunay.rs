@@ -0,0 +1,47 @@
+use crate::geoq::{delaunay, error::Error, output, reader};
+use clap::ArgMatches;
+use geo_types::{Geometry, LineString, Point};
+use serde_json::json;
+
+fn print_feature(geom: &Geometry<f64>, props: serde_json::Map<String, serde_json::Value>) {
+    let feature = geojson::Feature {
+        bbox: None,
+        geometry: Some(geojson::Geometry::new(geojson::Value::from(geom))),
+        id: None,
+        properties: Some(props),
+        foreign_members: None,
+    };
+    println!("{}", output::geojson(feature));
+}
+
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let edges = matches.is_present("edges");
+
+    let mut points: Vec<Point<f64>> = Vec::new();
+    reader::for_entity(|e| match e.geom() {
+        Geometry::Point(p) => {
+            points.push(p);
+            Ok(())
+        }
+        _ => {
+            eprintln!("Delaunay triangulation requires Point inputs. Got: {}", e.raw());
+            Err(Error::PointRequired)
+        }
+    })?;
+
+    let triangles = delaunay::triangulate(&points);
+    if edges {
+        for (a, b) in delaunay::edges(&triangles) {
+            let ls = Geometry::LineString(LineString(vec![a, b]));
+            print_feature(&ls, serde_json::Map::new());
+        }
+    } else {
+        for t in triangles {
+            let mut props = serde_json::Map::new();
+            props.insert("area".to_string(), json!(t.area()));
+            props.insert("circumradius".to_string(), json!(t.circumradius()));
+            print_feature(&Geometry::Polygon(t.to_polygon()), props);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use crate::geoq::{conversion, densify, error::Error, output, par, units};
+use clap::ArgMatches;
+
+fn read_count(arg: &str) -> Result<usize, Error> {
+    match arg.parse::<usize>() {
+        Ok(count) if count >= 2 => Ok(count),
+        _ => {
+            eprintln!("Invalid --to-count: {:?} - must be an integer of at least 2", arg);
+            Err(Error::InvalidNumberFormat)
+        }
+    }
+}
+
+fn read_max_segment(arg: &str) -> Result<f64, Error> {
+    let max_segment = units::parse_distance(arg)?;
+    if max_segment.is_finite() && max_segment > 0.0 {
+        Ok(max_segment)
+    } else {
+        eprintln!("Invalid --max-segment: {:?} - must be a distance greater than 0", arg);
+        Err(Error::InvalidNumberFormat)
+    }
+}
+
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let max_segment = match matches.value_of("max-segment") {
+        Some(arg) => Some(read_max_segment(arg)?),
+        None => None,
+    };
+    let count = match matches.value_of("to-count") {
+        Some(arg) => Some(read_count(arg)?),
+        None => None,
+    };
+    if max_segment.is_none() && count.is_none() {
+        return Err(Error::MissingArgument);
+    }
+
+    par::for_input_entity(matches.value_of("input"), move |e| {
+        let props = e.geojson_properties();
+        let densified = match (count, max_segment) {
+            (Some(count), _) => densify::redistribute(e.geom(), count),
+            (None, Some(max_segment)) => densify::densify(e.geom(), max_segment),
+            (None, None) => e.geom(),
+        };
+        let feature = geojson::Feature {
+            bbox: None,
+            geometry: Some(geojson::Geometry::new(conversion::geojson_value(&densified))),
+            id: e.geojson_id(),
+            properties: Some(props),
+            foreign_members: None,
+        };
+        Ok(vec![output::geojson(feature)])
+    })
+}
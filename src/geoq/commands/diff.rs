@@ -0,0 +1,70 @@
+use crate::geoq::{entity::Entity, error::Error, hash, output, reader::Reader};
+use clap::ArgMatches;
+use serde_json::json;
+use std::{
+    collections::{HashMap, HashSet},
+    fs::File,
+    io::BufReader,
+};
+
+fn read_keyed(path: &str, key: &str) -> Result<Vec<(String, Entity)>, Error> {
+    let f = File::open(path)?;
+    let mut f = BufReader::new(f);
+    let reader = Reader::new(&mut f);
+    let mut keyed = Vec::new();
+    for e in reader {
+        let e = e?;
+        match e.key(key) {
+            Some(k) => keyed.push((k, e)),
+            None => eprintln!("Skipping feature in {} with no {:?} key: {}", path, key, e.raw()),
+        }
+    }
+    Ok(keyed)
+}
+
+fn content_hash(e: &Entity) -> String {
+    hash::hash(&e.geom(), Some(&e.geojson_properties()))
+}
+
+fn print_diff(e: &Entity, status: &str) {
+    let mut feature = e.geojson_feature();
+    if let Some(props) = feature.properties.as_mut() {
+        props.insert("_diff".to_string(), json!(status));
+    }
+    println!("{}", output::geojson(feature));
+}
+
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let (a_path, b_path) = match (matches.value_of("a"), matches.value_of("b")) {
+        (Some(a), Some(b)) => (a, b),
+        _ => return Err(Error::MissingArgument),
+    };
+    let key = matches.value_of("key").unwrap_or("id");
+    let include_unchanged = matches.is_present("unchanged");
+
+    let before = read_keyed(a_path, key)?;
+    let after = read_keyed(b_path, key)?;
+    let before_hashes: HashMap<&str, String> = before
+        .iter()
+        .map(|(k, e)| (k.as_str(), content_hash(e)))
+        .collect();
+    let after_keys: HashSet<&str> = after.iter().map(|(k, _)| k.as_str()).collect();
+
+    for (k, e) in &after {
+        match before_hashes.get(k.as_str()) {
+            None => print_diff(e, "added"),
+            Some(h) if *h != content_hash(e) => print_diff(e, "modified"),
+            Some(_) => {
+                if include_unchanged {
+                    print_diff(e, "unchanged")
+                }
+            }
+        }
+    }
+    for (k, e) in &before {
+        if !after_keys.contains(k.as_str()) {
+            print_diff(e, "removed");
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use crate::geoq::{bbox, commands, coord_count, error::Error, par, reader};
+use clap::ArgMatches;
+use geo_types::Rect;
+
+fn format_extent(bbox: &Rect<f64>) -> String {
+    format!("{},{},{},{}", bbox.min.x, bbox.min.y, bbox.max.x, bbox.max.y)
+}
+
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    if matches.is_present("antimeridian") {
+        let rects = commands::bbox::read_rects(matches)?;
+        match bbox::merge_antimeridian(&rects) {
+            None => Err(Error::NoInputGiven),
+            Some(extent) => {
+                println!("{}", format_extent(&extent));
+                Ok(())
+            }
+        }
+    } else if matches.is_present("all") {
+        let mut extent: Option<Rect<f64>> = None;
+        reader::for_input_entity(matches.value_of("input"), |e| {
+            if coord_count::is_empty(&e.geom()) {
+                return Ok(());
+            }
+            extent = Some(match extent {
+                Some(curr) => bbox::merge(&curr, &e.bbox()),
+                None => e.bbox(),
+            });
+            Ok(())
+        })?;
+
+        match extent {
+            None => Err(Error::NoInputGiven),
+            Some(extent) => {
+                println!("{}", format_extent(&extent));
+                Ok(())
+            }
+        }
+    } else {
+        par::for_input_entity(matches.value_of("input"), |e| {
+            if coord_count::is_empty(&e.geom()) {
+                Ok(vec![])
+            } else {
+                Ok(vec![format_extent(&e.bbox())])
+            }
+        })
+    }
+}
@@ -1,12 +1,159 @@
-use crate::geoq::{self, error::Error, par, reader::Reader};
+use crate::geoq::{
+    self, bbox, buffer, conversion, coord_count,
+    distance::{self, Method},
+    entity::Entity,
+    error::Error,
+    par,
+    reader::{self, Reader},
+    units, valid,
+};
 use clap::ArgMatches;
-use geo_types::{Geometry, Polygon};
-use std::{fs::File, io::BufReader};
+use geo_types::{Geometry, Polygon, Rect};
+use std::{
+    cmp::Ordering::Equal,
+    collections::BinaryHeap,
+    fs::File,
+    io::{self, BufReader},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
 
-fn read_query_geoms(matches: &ArgMatches) -> Result<Vec<Geometry<f64>>, Error> {
-    let f = matches.value_of("query-file");
-    let q = matches.value_of("query");
-    match (f, q) {
+// Options shared by all filter subcommands
+struct FilterOptions {
+    negate: bool,
+    count_only: bool,
+    verbose: bool,
+    // Repair invalid polygons before checking spatial predicates (off by default)
+    repair: bool,
+    // File to read the entities to filter from, instead of STDIN
+    data_file: Option<String>,
+    // Distance in meters to buffer each query by before checking predicates
+    query_buffer: Option<f64>,
+}
+
+type Test = Box<dyn Fn(&Geometry<f64>) -> bool + Send + Sync>;
+
+// A test for each input geometry
+struct Predicate {
+    test: Test,
+    // Whether to test repaired geometries (see FilterOptions)
+    repair: bool,
+}
+
+// A predicate to build: the filter subcommand, or one given with --and
+struct Clause<'a> {
+    name: &'a str,
+    query_file: Option<&'a str>,
+    // The query, or for 'type', the geometry types
+    arg: Option<&'a str>,
+    tolerance: Option<&'a str>,
+}
+
+const PREDICATES: [&str; 7] = [
+    "intersects",
+    "contains",
+    "covered-by",
+    "covers",
+    "equals",
+    "type",
+    "within-bbox-of",
+];
+
+// Output the input entities whose geometry matches all of the predicates
+// (or doesn't, with --negate), or with --count-only, just the number of them
+fn filter_input(opts: FilterOptions, predicates: Vec<Predicate>) -> Result<(), Error> {
+    let total = Arc::new(AtomicUsize::new(0));
+    let passing = Arc::new(AtomicUsize::new(0));
+    let repaired = Arc::new(AtomicUsize::new(0));
+    let (worker_total, worker_passing, worker_repaired) =
+        (total.clone(), passing.clone(), repaired.clone());
+    let repair = opts.repair && predicates.iter().any(|p| p.repair);
+    let (negate, count_only) = (opts.negate, opts.count_only);
+    let handler = move |entity: Entity| {
+        worker_total.fetch_add(1, Ordering::Relaxed);
+        let geom = entity.geom();
+        let repaired_geom = if repair && !valid::is_valid(&geom) {
+            worker_repaired.fetch_add(1, Ordering::Relaxed);
+            Some(valid::make_valid(&geom))
+        } else {
+            None
+        };
+        let matches = predicates.iter().all(|p| match repaired_geom {
+            Some(ref repaired_geom) if p.repair => (p.test)(repaired_geom),
+            _ => (p.test)(&geom),
+        });
+        if matches ^ negate {
+            worker_passing.fetch_add(1, Ordering::Relaxed);
+            if !count_only {
+                return Ok(vec![entity.raw()]);
+            }
+        }
+        Ok(vec![])
+    };
+    match opts.data_file {
+        Some(ref path) => {
+            let mut data = BufReader::new(File::open(path)?);
+            par::for_entity_par(&mut data, reader::input_limit(), handler)?
+        }
+        None => par::for_stdin_entity(handler)?,
+    }
+    let repaired = repaired.load(Ordering::Relaxed);
+    if opts.verbose && repaired > 0 {
+        eprintln!("Repaired {} invalid features", repaired);
+    }
+    if opts.count_only {
+        let passing = passing.load(Ordering::Relaxed);
+        if opts.verbose {
+            println!("passing: {}\ttotal: {}", passing, total.load(Ordering::Relaxed));
+        } else {
+            println!("{}", passing);
+        }
+    }
+    Ok(())
+}
+
+fn read_query_geoms(
+    clause: &Clause,
+    repair: bool,
+    opts: &FilterOptions,
+) -> Result<Vec<Geometry<f64>>, Error> {
+    let geoms = read_query_input(clause, opts)?;
+    let geoms: Vec<Geometry<f64>> = if !(repair && opts.repair) {
+        geoms
+    } else {
+        geoms
+            .into_iter()
+            .map(|geom| {
+                if valid::is_valid(&geom) {
+                    geom
+                } else {
+                    if opts.verbose {
+                        eprintln!("Repaired invalid query feature");
+                    }
+                    valid::make_valid(&geom)
+                }
+            })
+            .collect()
+    };
+    Ok(match opts.query_buffer {
+        Some(distance) => geoms.iter().map(|geom| buffer::buffer(geom, distance)).collect(),
+        None => geoms,
+    })
+}
+
+fn read_query_input(clause: &Clause, opts: &FilterOptions) -> Result<Vec<Geometry<f64>>, Error> {
+    match (clause.query_file, clause.arg) {
+        // With the data coming from a file, queries can come from STDIN
+        (None, None) if opts.data_file.is_some() => {
+            let stdin = io::stdin();
+            let mut stdin_reader = stdin.lock();
+            let reader = Reader::new(&mut stdin_reader);
+            let entities = reader.into_iter().collect::<Result<Vec<_>, _>>()?;
+            Ok(entities.into_iter().map(|e| e.geom()).collect())
+        }
         (Some(path), None) => {
             let f = File::open(path)?;
             let mut f = BufReader::new(f);
@@ -22,54 +169,273 @@ fn read_query_geoms(matches: &ArgMatches) -> Result<Vec<Geometry<f64>>, Error> {
             Ok(entities.into_iter().map(|e| e.geom()).collect())
         }
         _ => {
-            eprintln!("Must provide Query Features as either --file or positional argument.");
+            eprintln!("Must provide Query Features as either --query-file or positional argument (or via STDIN, with --data-file).");
             Err(Error::MissingArgument)
         }
     }
 }
 
-fn intersects(matches: &ArgMatches, negate: bool) -> Result<(), Error> {
-    let query_geoms = read_query_geoms(matches)?;
-    par::for_stdin_entity(move |entity| {
-        let output = entity.raw();
-        let geom = entity.geom();
-        let is_match: bool = query_geoms
+fn predicate<F>(repair: bool, test: F) -> Predicate
+where
+    F: 'static + Send + Sync + Fn(&Geometry<f64>) -> bool,
+{
+    Predicate {
+        test: Box::new(test),
+        repair,
+    }
+}
+
+fn intersects(clause: &Clause, opts: &FilterOptions) -> Result<Predicate, Error> {
+    let query_geoms = read_query_geoms(clause, true, opts)?;
+    Ok(predicate(true, move |geom| {
+        query_geoms
             .iter()
-            .any(|ref query_geom| geoq::intersection::intersects(query_geom, &geom));
-        if is_match ^ negate {
-            Ok(vec![output])
-        } else {
-            Ok(vec![])
-        }
-    })
+            .any(|query_geom| geoq::intersection::intersects(query_geom, geom))
+    }))
 }
 
-fn contains(matches: &ArgMatches, negate: bool) -> Result<(), Error> {
-    let query_geoms = read_query_geoms(matches)?;
-    let query_polygons: Vec<Polygon<f64>> = query_geoms
-        .into_iter()
-        .flat_map(|geom| match geom {
-            Geometry::Polygon(poly) => vec![poly],
-            Geometry::MultiPolygon(mp) => mp.0,
-            _ => vec![],
-        })
-        .collect();
+fn polygons(geom: Geometry<f64>) -> Vec<Polygon<f64>> {
+    match geom {
+        Geometry::Polygon(poly) => vec![poly],
+        Geometry::MultiPolygon(mp) => mp.0,
+        _ => vec![],
+    }
+}
+
+fn contains(clause: &Clause, opts: &FilterOptions) -> Result<Predicate, Error> {
+    let query_geoms = read_query_geoms(clause, true, opts)?;
+    let query_polygons: Vec<Polygon<f64>> = query_geoms.into_iter().flat_map(polygons).collect();
 
     if query_polygons.is_empty() {
         Err(Error::PolygonRequired)
     } else {
-        par::for_stdin_entity(move |entity| {
-            let output = entity.raw();
-            let geom = entity.geom();
-            let is_match = query_polygons
+        Ok(predicate(true, move |geom| {
+            query_polygons
                 .iter()
-                .any(|ref query_poly| geoq::contains::contains(query_poly, &geom));
-            if is_match ^ negate {
-                Ok(vec![output])
-            } else {
-                Ok(vec![])
-            }
+                .any(|query_poly| geoq::contains::contains(query_poly, geom))
+        }))
+    }
+}
+
+fn covered_by(clause: &Clause, opts: &FilterOptions) -> Result<Predicate, Error> {
+    let query_geoms = read_query_geoms(clause, true, opts)?;
+    let query_polygons: Vec<Polygon<f64>> = query_geoms.into_iter().flat_map(polygons).collect();
+
+    if query_polygons.is_empty() {
+        Err(Error::PolygonRequired)
+    } else {
+        Ok(predicate(true, move |geom| {
+            query_polygons
+                .iter()
+                .any(|query_poly| geoq::covers::covers(query_poly, geom))
+        }))
+    }
+}
+
+fn covers(clause: &Clause, opts: &FilterOptions) -> Result<Predicate, Error> {
+    let query_geoms = read_query_geoms(clause, true, opts)?;
+    Ok(predicate(true, move |geom| {
+        // Only (Multi)Polygon inputs can cover anything
+        let input_polygons = polygons(geom.clone());
+        !input_polygons.is_empty()
+            && query_geoms.iter().any(|query_geom| {
+                input_polygons
+                    .iter()
+                    .any(|poly| geoq::covers::covers(poly, query_geom))
+            })
+    }))
+}
+
+// Geometries are compared exactly as given, without repairing
+fn equals(clause: &Clause, opts: &FilterOptions) -> Result<Predicate, Error> {
+    let tolerance = match clause.tolerance {
+        Some(arg) => f64::from_str(arg).map_err(|_| {
+            eprintln!(
+                "Invalid --tolerance: {:?} - must be a floating point number, e.g. 0.00001",
+                arg
+            );
+            Error::InvalidNumberFormat
+        })?,
+        None => 0.0,
+    };
+    let query_geoms = read_query_geoms(clause, false, opts)?;
+    Ok(predicate(false, move |geom| {
+        query_geoms
+            .iter()
+            .any(|query_geom| geoq::equals::equals(query_geom, geom, tolerance))
+    }))
+}
+
+// Repairing doesn't change a polygon's bbox
+fn within_bbox_of(clause: &Clause, opts: &FilterOptions) -> Result<Predicate, Error> {
+    let query_rects: Vec<Rect<f64>> = read_query_geoms(clause, false, opts)?
+        .iter()
+        .filter(|geom| !coord_count::is_empty(geom))
+        .map(bbox::bbox)
+        .collect();
+    Ok(predicate(false, move |geom| {
+        if coord_count::is_empty(geom) {
+            return false;
+        }
+        let b = bbox::bbox(geom);
+        query_rects.iter().any(|q| {
+            q.min.x <= b.min.x && b.max.x <= q.max.x && q.min.y <= b.min.y && b.max.y <= q.max.y
+        })
+    }))
+}
+
+const GEOMETRY_TYPES: [&str; 7] = [
+    "Point",
+    "LineString",
+    "Polygon",
+    "MultiPoint",
+    "MultiLineString",
+    "MultiPolygon",
+    "GeometryCollection",
+];
+
+// Repairing could turn a Polygon into a MultiPolygon
+fn geometry_type(clause: &Clause) -> Result<Predicate, Error> {
+    let arg = clause.arg.ok_or(Error::MissingArgument)?;
+    let types = arg
+        .split(',')
+        .map(|name| {
+            GEOMETRY_TYPES
+                .iter()
+                .find(|t| t.eq_ignore_ascii_case(name.trim()))
+                .cloned()
+                .ok_or_else(|| {
+                    eprintln!(
+                        "Invalid geometry type: {:?} - must be one of {}",
+                        name,
+                        GEOMETRY_TYPES.join(", ")
+                    );
+                    Error::UnknownGeometryType
+                })
         })
+        .collect::<Result<Vec<&str>, Error>>()?;
+    Ok(predicate(false, move |geom| {
+        types.contains(&conversion::geometry_type(geom))
+    }))
+}
+
+// An input ranked by its distance to the nearest query,
+// with earlier inputs ranking first at the same distance
+struct Ranked {
+    distance: f64,
+    index: usize,
+    raw: String,
+}
+
+impl PartialEq for Ranked {
+    fn eq(&self, other: &Ranked) -> bool {
+        self.cmp(other) == Equal
+    }
+}
+
+impl Eq for Ranked {}
+
+impl PartialOrd for Ranked {
+    fn partial_cmp(&self, other: &Ranked) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ranked {
+    fn cmp(&self, other: &Ranked) -> std::cmp::Ordering {
+        self.distance
+            .partial_cmp(&other.distance)
+            .unwrap_or(Equal)
+            .then_with(|| self.index.cmp(&other.index))
+    }
+}
+
+fn read_k(arg: Option<&str>) -> Result<usize, Error> {
+    let arg = arg.ok_or(Error::MissingArgument)?;
+    arg.parse::<usize>().map_err(|_| {
+        eprintln!("Invalid K: {:?} - must be a positive integer", arg);
+        Error::InvalidNumberFormat
+    })
+}
+
+// Output the K inputs nearest to any of the queries, in the order they were read,
+// or with --negate, all of the others. Unless negated, only the K nearest so far
+// are held in memory. Inputs with no distance to the queries (like empty
+// geometries) are never among the nearest.
+fn nearest(k: usize, clause: &Clause, opts: FilterOptions) -> Result<(), Error> {
+    let query_geoms = read_query_geoms(clause, false, &opts)?;
+    let mut ranked: BinaryHeap<Ranked> = BinaryHeap::new();
+    let mut unranked: Vec<(usize, String)> = Vec::new();
+    let mut total = 0;
+    let mut handler = |e: Entity| {
+        let geom = e.geom();
+        let distance = query_geoms
+            .iter()
+            .filter_map(|q| distance::geometry_distance(q, &geom, Method::Karney))
+            .fold(None, |min: Option<f64>, d| Some(min.map_or(d, |m| m.min(d))));
+        match distance {
+            Some(distance) => {
+                ranked.push(Ranked { distance, index: total, raw: e.raw() });
+                if ranked.len() > k {
+                    let farthest = ranked.pop().unwrap();
+                    if opts.negate {
+                        unranked.push((farthest.index, farthest.raw));
+                    }
+                }
+            }
+            None if opts.negate => unranked.push((total, e.raw())),
+            None => (),
+        }
+        total += 1;
+        Ok(())
+    };
+    match opts.data_file {
+        Some(ref path) => {
+            let mut data = BufReader::new(File::open(path)?);
+            for e in Reader::new(&mut data).with_limit(reader::input_limit()) {
+                handler(e?)?;
+            }
+        }
+        None => reader::for_entity(handler)?,
+    }
+
+    let mut passing: Vec<(usize, String)> = if opts.negate {
+        unranked
+    } else {
+        ranked.into_iter().map(|r| (r.index, r.raw)).collect()
+    };
+    passing.sort_by_key(|(index, _)| *index);
+    if opts.count_only {
+        if opts.verbose {
+            println!("passing: {}\ttotal: {}", passing.len(), total);
+        } else {
+            println!("{}", passing.len());
+        }
+    } else {
+        for (_, raw) in passing {
+            println!("{}", raw);
+        }
+    }
+    Ok(())
+}
+
+fn build_predicate(clause: &Clause, opts: &FilterOptions) -> Result<Predicate, Error> {
+    match clause.name {
+        "intersects" => intersects(clause, opts),
+        "contains" => contains(clause, opts),
+        "covered-by" => covered_by(clause, opts),
+        "covers" => covers(clause, opts),
+        "equals" => equals(clause, opts),
+        "type" => geometry_type(clause),
+        "within-bbox-of" => within_bbox_of(clause, opts),
+        name => {
+            eprintln!(
+                "Invalid --and predicate: {:?} - must be one of {}",
+                name,
+                PREDICATES.join(", ")
+            );
+            Err(Error::UnknownCommand)
+        }
     }
 }
 
@@ -78,15 +444,73 @@ pub fn run(matches: &ArgMatches) -> Result<(), Error> {
     // geoq filter --negate intersects
     // OR
     // geoq filter intersects --negate
-    let negate = matches
-        .args
-        .get("negate")
-        .or(matches.subcommand().1.and_then(|m| (*m).args.get("negate")))
-        .is_some();
+    // The same goes for --count-only, --verbose, and --repair
+    let flag = |name: &str| {
+        matches
+            .args
+            .get(name)
+            .or(matches.subcommand().1.and_then(|m| m.args.get(name)))
+            .is_some()
+    };
+    let data_file = matches
+        .value_of("data-file")
+        .or(matches.subcommand().1.and_then(|m| m.value_of("data-file")))
+        .map(String::from);
+    let query_buffer = match matches
+        .value_of("query-buffer")
+        .or(matches.subcommand().1.and_then(|m| m.value_of("query-buffer")))
+    {
+        Some(arg) => Some(units::parse_distance(arg)?),
+        None => None,
+    };
+    let opts = FilterOptions {
+        negate: flag("negate"),
+        count_only: flag("count-only"),
+        verbose: flag("verbose"),
+        repair: flag("repair"),
+        data_file,
+        query_buffer,
+    };
+
+    if let ("nearest", Some(m)) = matches.subcommand() {
+        if m.is_present("and") || matches.is_present("and") {
+            eprintln!("'nearest' ranks inputs against each other rather than checking each one, so it can't be combined with --and.");
+            return Err(Error::ConflictingArguments);
+        }
+        let clause = Clause {
+            name: "nearest",
+            query_file: m.value_of("query-file"),
+            arg: m.value_of("query"),
+            tolerance: None,
+        };
+        return nearest(read_k(m.value_of("k"))?, &clause, opts);
+    }
 
-    match matches.subcommand() {
-        ("intersects", Some(m)) => intersects(m, negate),
-        ("contains", Some(m)) => contains(m, negate),
-        _ => Err(Error::UnknownCommand),
+    let (name, m) = match matches.subcommand() {
+        (name, Some(m)) if PREDICATES.contains(&name) => (name, m),
+        _ => return Err(Error::UnknownCommand),
+    };
+    let clause = Clause {
+        name,
+        query_file: m.value_of("query-file"),
+        arg: m.value_of("query").or_else(|| m.value_of("types")),
+        tolerance: m.value_of("tolerance"),
+    };
+    let mut predicates = vec![build_predicate(&clause, &opts)?];
+    // Each --and gives another predicate and its query, which must also match
+    let and_values: Vec<&str> = m
+        .values_of("and")
+        .or_else(|| matches.values_of("and"))
+        .map(|v| v.collect())
+        .unwrap_or_default();
+    for pair in and_values.chunks(2) {
+        let clause = Clause {
+            name: pair[0],
+            query_file: None,
+            arg: pair.get(1).cloned(),
+            tolerance: None,
+        };
+        predicates.push(build_predicate(&clause, &opts)?);
     }
+    filter_input(opts, predicates)
 }
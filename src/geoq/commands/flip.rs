@@ -0,0 +1,16 @@
+use crate::geoq::{self, error::Error, output, par};
+use clap::ArgMatches;
+
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let detect = matches.is_present("detect");
+    par::for_stdin_entity(move |e| {
+        let flipped = geoq::flip::flip(&e.geom());
+        if detect && !geoq::flip::in_lonlat_range(&flipped) {
+            eprintln!(
+                "Warning: flipped coordinates are out of Lon/Lat range, so this input may already have been in Lon/Lat order: {}",
+                e.raw()
+            );
+        }
+        Ok(vec![output::like_input(&e, &flipped)])
+    })
+}
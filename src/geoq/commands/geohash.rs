@@ -1,5 +1,14 @@
-use crate::geoq::{self, entity::Entity, error::Error, par};
+use crate::geoq::{
+    self,
+    bbox::{self, BBoxToPoly},
+    entity::Entity,
+    error::Error,
+    geohash::MAX_GEOHASH_LENGTH,
+    output, par,
+};
 use clap::ArgMatches;
+use geo_types::Geometry;
+use std::collections::HashSet;
 use std::io::{self, prelude::*};
 
 fn read_level(matches: &ArgMatches) -> Result<usize, Error> {
@@ -19,9 +28,17 @@ fn read_level(matches: &ArgMatches) -> Result<usize, Error> {
 
 fn point(matches: &ArgMatches) -> Result<(), Error> {
     let level = read_level(matches)?;
+    let with_bbox = matches.is_present("bbox");
 
     par::for_stdin_entity(move |e| match e.geom() {
         geo_types::Geometry::Point(p) => match geohash::encode(p.0, level) {
+            Ok(gh) if with_bbox => {
+                let cell = geohash::decode_bbox(&gh).map_err(|_| Error::InvalidGeohashPoint)?;
+                Ok(vec![format!(
+                    "{}\t{},{},{},{}",
+                    gh, cell.min.x, cell.min.y, cell.max.x, cell.max.y
+                )])
+            }
             Ok(gh) => Ok(vec![gh]),
             _ => Err(Error::InvalidGeohashPoint),
         },
@@ -29,25 +46,150 @@ fn point(matches: &ArgMatches) -> Result<(), Error> {
     })
 }
 
+fn cover(
+    g: &Geometry<f64>,
+    level: usize,
+    compact: bool,
+    dissolve: bool,
+    hollow: bool,
+    row_major: bool,
+) -> Vec<String> {
+    let mut ghs = geoq::geohash::covering(g, level);
+    if compact {
+        ghs = geoq::geohash::compact(ghs);
+    }
+    if hollow {
+        ghs = geoq::geohash::hollow(&ghs);
+    }
+    if row_major {
+        geoq::geohash::sort_row_major(&mut ghs);
+    }
+    if dissolve {
+        geoq::geohash::dissolve(&ghs)
+            .map(|outline| {
+                let gj = geojson::Geometry::new(geojson::Value::from(&outline));
+                output::geojson(gj)
+            })
+            .into_iter()
+            .collect()
+    } else {
+        ghs
+    }
+}
+
+// Tag each Geohash with its level, followed by the distinct
+// ancestors of the Geohashes at the parent level
+fn with_parents(ghs: Vec<String>, parent_level: usize, parents_only: bool) -> Vec<String> {
+    // Parents in the order they're first seen
+    let mut seen: HashSet<&str> = HashSet::new();
+    let mut parents: Vec<String> = Vec::new();
+    for gh in &ghs {
+        let parent = &gh[..parent_level];
+        if seen.insert(parent) {
+            parents.push(parent.to_string());
+        }
+    }
+    let cells = if parents_only { vec![] } else { ghs };
+    cells
+        .into_iter()
+        .chain(parents)
+        .map(|gh| format!("{}\t{}", gh, gh.len()))
+        .collect()
+}
+
+fn read_parent_level(matches: &ArgMatches, level: usize) -> Result<Option<usize>, Error> {
+    match matches.value_of("parents") {
+        None => Ok(None),
+        Some(arg) => match arg.parse::<usize>() {
+            Ok(parent_level) if parent_level >= 1 && parent_level < level => Ok(Some(parent_level)),
+            _ => {
+                eprintln!(
+                    "Invalid --parents: {:?} - must be a level between 1 and {}",
+                    arg,
+                    level - 1
+                );
+                Err(Error::InvalidNumberFormat)
+            }
+        },
+    }
+}
+
 fn covering(matches: &ArgMatches) -> Result<(), Error> {
     let level = read_level(matches)?;
+    if !(1..=MAX_GEOHASH_LENGTH).contains(&level) {
+        eprintln!(
+            "Invalid level: {} - must be between 1 and {}",
+            level, MAX_GEOHASH_LENGTH
+        );
+        return Err(Error::InvalidNumberFormat);
+    }
+    let parent_level = read_parent_level(matches, level)?;
+    let parents_only = matches.is_present("parents-only");
+    let hollow = matches.is_present("hollow");
+    let row_major = matches.value_of("order") == Some("rowmajor");
+    let cover = move |g: &Geometry<f64>, compact: bool, dissolve: bool| {
+        let ghs = cover(g, level, compact, dissolve, hollow, row_major);
+        match parent_level {
+            Some(parent_level) => with_parents(ghs, parent_level, parents_only),
+            None => ghs,
+        }
+    };
     let include_original = matches.is_present("original");
-    par::for_stdin_entity(move |e| {
-        if include_original {
-            let mut results = vec![e.raw()];
-            let g = e.geom();
-            results.extend(geoq::geohash::covering(&g, level));
-            Ok(results)
+    let dissolve = matches.is_present("dissolve");
+    let compact = matches.is_present("compact");
+    let estimate = matches.is_present("estimate");
+    if let Some(arg) = matches.value_of("bbox") {
+        let rect = bbox::parse(arg).ok_or_else(|| {
+            eprintln!("Invalid --bbox: {:?} - must be minlon,minlat,maxlon,maxlat", arg);
+            Error::InvalidNumberFormat
+        })?;
+        let g = Geometry::Polygon(rect.to_polygon());
+        if estimate {
+            println!("{}", geoq::geohash::estimate_covering(&g, level));
         } else {
-            let g = e.geom();
-            Ok(geoq::geohash::covering(&g, level))
+            for line in cover(&g, compact, dissolve) {
+                println!("{}", line);
+            }
         }
+        return Ok(());
+    }
+    if estimate {
+        return par::for_stdin_entity(move |e| {
+            Ok(vec![geoq::geohash::estimate_covering(&e.geom(), level).to_string()])
+        });
+    }
+    par::for_stdin_entity(move |e| {
+        let mut results = if include_original { vec![e.raw()] } else { vec![] };
+        results.extend(cover(&e.geom(), compact, dissolve));
+        Ok(results)
     })
 }
 
-fn children() -> Result<(), Error> {
-    par::for_stdin_entity(|e| match e {
-        Entity::Geohash(ref raw) => Ok(geoq::geohash::children(raw)),
+// Deepest --level for gh children, giving 32^4 (about 1 million) Geohashes per input
+const MAX_CHILDREN_LEVEL: usize = 4;
+
+fn children(matches: &ArgMatches) -> Result<(), Error> {
+    let level = match matches.value_of("level").map(|l| l.parse::<usize>()) {
+        None => 1,
+        Some(Ok(level)) if (1..=MAX_CHILDREN_LEVEL).contains(&level) => level,
+        Some(_) => {
+            eprintln!(
+                "Invalid --level: {:?} - must be between 1 and {}, as each level gives 32 times as many Geohashes",
+                matches.value_of("level").unwrap(),
+                MAX_CHILDREN_LEVEL
+            );
+            return Err(Error::InvalidNumberFormat);
+        }
+    };
+    par::for_stdin_entity(move |e| match e {
+        Entity::Geohash(ref raw) if raw.len() + level > MAX_GEOHASH_LENGTH => {
+            eprintln!(
+                "Geohash {} has no descendants {} levels deeper - Geohashes have at most {} characters",
+                raw, level, MAX_GEOHASH_LENGTH
+            );
+            Err(Error::InvalidGeohashPoint)
+        }
+        Entity::Geohash(ref raw) => Ok(geoq::geohash::descendants(raw, level)),
         _ => Err(Error::NotImplemented),
     })
 }
@@ -84,7 +226,7 @@ fn encode_long() -> Result<(), Error> {
 pub fn run(matches: &ArgMatches) -> Result<(), Error> {
     match matches.subcommand() {
         ("point", Some(m)) => point(m),
-        ("children", Some(_)) => children(),
+        ("children", Some(m)) => children(m),
         ("neighbors", Some(m)) => neighbors(m),
         ("covering", Some(m)) => covering(m),
         ("roots", Some(_)) => roots(),
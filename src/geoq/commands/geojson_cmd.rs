@@ -1,49 +1,202 @@
-use crate::geoq::{error::Error, par, reader::Reader};
+use crate::geoq::{
+    bbox, conversion, crs, error::Error, output, par,
+    reader::{self, Reader},
+    rfc7946::{self, Severity},
+};
 use clap::ArgMatches;
-use geojson::GeoJson;
-use std::io;
+use geo_types::Geometry;
+use std::io::{self, prelude::*};
 
-fn geom() -> Result<(), Error> {
+// Members of (nested) GeometryCollections, in order
+fn flatten(geom: Geometry<f64>) -> Vec<Geometry<f64>> {
+    match geom {
+        Geometry::GeometryCollection(gc) => gc.0.into_iter().flat_map(flatten).collect(),
+        _ => vec![geom],
+    }
+}
+
+fn geom(matches: &ArgMatches) -> Result<(), Error> {
+    if matches.is_present("flatten") {
+        return par::for_stdin_entity(|e| {
+            Ok(flatten(e.geom())
+                .iter()
+                .map(|g| output::geojson(geojson::Geometry::new(conversion::geojson_value(g))))
+                .collect())
+        });
+    }
     par::for_stdin_entity(|e| {
         let gj_geom = e.geojson_geometry();
-        Ok(vec![serde_json::to_string(&gj_geom).unwrap()])
+        Ok(vec![output::geojson(gj_geom)])
     })
 }
 
 fn feature() -> Result<(), Error> {
     par::for_stdin_entity(|e| {
         let f = e.geojson_feature();
-        Ok(vec![serde_json::to_string(&f).unwrap()])
+        Ok(vec![output::geojson(f)])
     })
 }
 
-fn feature_collection() -> Result<(), Error> {
-    let mut features: Vec<geojson::Feature> = Vec::new();
+// JSON for a value nested `depth` levels deep in a pretty-printed document
+fn nested_json(value: &serde_json::Value, depth: usize, pretty: bool) -> String {
+    if pretty {
+        let indent = format!("\n{}", "  ".repeat(depth));
+        serde_json::to_string_pretty(value).unwrap().replace('\n', &indent)
+    } else {
+        serde_json::to_string(value).unwrap()
+    }
+}
+
+// Write the FeatureCollection as the inputs are read, so only 1 Feature
+// is held in memory at a time. The bbox and any foreign members of the
+// input FeatureCollections aren't known until every Feature has been read,
+// so they're written after the Features.
+fn feature_collection(matches: &ArgMatches) -> Result<(), Error> {
+    let include_bbox = matches.is_present("bbox");
+    let crs_member = match matches.value_of("write-crs") {
+        None => None,
+        Some(code) => match crs::legacy_crs_member(code) {
+            Some(member) => Some(member),
+            None => {
+                eprintln!("Invalid --write-crs: {:?} - must be an EPSG code, e.g. EPSG:4326", code);
+                return Err(Error::InvalidCrs);
+            }
+        },
+    };
+    let pretty = output::pretty();
+    let (indent, sep) = if pretty { ("\n  ", ": ") } else { ("", ":") };
+    let stdout = io::stdout();
+    let mut out = io::BufWriter::new(stdout.lock());
+
+    write!(out, "{{{}\"type\"{}\"FeatureCollection\",", indent, sep)?;
+    if let Some(ref member) = crs_member {
+        write!(out, "{}\"crs\"{}{},", indent, sep, nested_json(member, 1, pretty))?;
+    }
+    write!(out, "{}\"features\"{}[", indent, sep)?;
 
+    let mut count = 0;
+    let mut fc_bbox: Option<geo_types::Rect<f64>> = None;
     let stdin = io::stdin();
     let mut stdin_reader = stdin.lock();
-    let reader = Reader::new(&mut stdin_reader);
-    for e_res in reader {
-        match e_res {
-            Err(e) => return Err(e),
-            Ok(e) => features.push(e.geojson_feature()),
+    let mut reader = Reader::new(&mut stdin_reader).with_limit(reader::input_limit());
+    for e_res in reader.by_ref() {
+        let e = match e_res {
+            Ok(e) => e,
+            Err(err) => {
+                // Close the collection so the Features written so far remain valid JSON
+                if pretty && count > 0 {
+                    write!(out, "{}", indent)?;
+                }
+                if pretty {
+                    writeln!(out, "]\n}}")?;
+                } else {
+                    writeln!(out, "]}}")?;
+                }
+                return Err(err);
+            }
+        };
+        if include_bbox {
+            let e_bbox = e.bbox();
+            fc_bbox = Some(match fc_bbox {
+                Some(curr) => bbox::merge(&curr, &e_bbox),
+                None => e_bbox,
+            });
+        }
+        if count > 0 {
+            write!(out, ",")?;
+        }
+        if pretty {
+            write!(out, "\n    ")?;
         }
+        write!(out, "{}", nested_json(&serde_json::to_value(e.geojson_feature()).unwrap(), 2, pretty))?;
+        count += 1;
     }
 
-    let fc = geojson::FeatureCollection {
-        bbox: None,
-        features: features,
-        foreign_members: None,
-    };
-    println!("{}", GeoJson::from(fc).to_string());
+    if pretty && count > 0 {
+        write!(out, "{}", indent)?;
+    }
+    write!(out, "]")?;
+    // Foreign members of input FeatureCollections are kept, after the Features
+    for (key, value) in reader.collection_members() {
+        if key == "crs" && crs_member.is_some() {
+            continue;
+        }
+        write!(out, ",{}{}{}{}", indent, serde_json::to_string(key).unwrap(), sep, nested_json(value, 1, pretty))?;
+    }
+    if let Some(b) = fc_bbox {
+        let b = serde_json::json!([b.min.x, b.min.y, b.max.x, b.max.y]);
+        write!(out, ",{}\"bbox\"{}{}", indent, sep, nested_json(&b, 1, pretty))?;
+    }
+    if pretty {
+        writeln!(out, "\n}}")?;
+    } else {
+        writeln!(out, "}}")?;
+    }
+    Ok(())
+}
+
+// Check each line of input against RFC 7946, reporting violations to STDERR
+// and passing the inputs (with --fix, corrected) through to STDOUT
+fn validate(matches: &ArgMatches) -> Result<(), Error> {
+    let fix = matches.is_present("fix");
+    let mut failed = 0;
+    let stdin = io::stdin();
+    for (i, line) in stdin.lock().lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let mut value: serde_json::Value = match serde_json::from_str(&line) {
+            Ok(value) => value,
+            Err(_) => {
+                eprintln!("Input {}: error: not valid JSON", i + 1);
+                println!("{}", line);
+                failed += 1;
+                continue;
+            }
+        };
+        let violations = rfc7946::validate(&mut value, fix);
+        for v in &violations {
+            eprintln!("Input {}: {}", i + 1, v);
+        }
+        if violations.iter().any(|v| v.severity == Severity::Error && !v.fixed) {
+            failed += 1;
+        }
+        if violations.iter().any(|v| v.fixed) {
+            if output::pretty() {
+                println!("{}", serde_json::to_string_pretty(&value).unwrap());
+            } else {
+                println!("{}", serde_json::to_string(&value).unwrap());
+            }
+        } else {
+            println!("{}", line);
+        }
+    }
+    if failed > 0 {
+        eprintln!("{} inputs failed RFC 7946 validation", failed);
+        return Err(Error::InvalidGeoJSON);
+    }
     Ok(())
 }
 
 pub fn run(gj: &ArgMatches) -> Result<(), Error> {
+    // --ndjson may be given either before or after the subcommand
+    let ndjson = gj.is_present("ndjson")
+        || gj.subcommand().1.is_some_and(|m| m.is_present("ndjson"));
+    if ndjson && output::pretty() {
+        eprintln!("--ndjson requires each Feature on a single line, so it can't be combined with --pretty.");
+        return Err(Error::ConflictingArguments);
+    }
+    if ndjson && gj.subcommand_name() == Some("fc") {
+        eprintln!("--ndjson outputs 1 Feature per line, so it can't be combined with 'gj fc', which outputs a single FeatureCollection. Use 'gj f --ndjson' instead.");
+        return Err(Error::ConflictingArguments);
+    }
+
     match gj.subcommand() {
-        ("geom", Some(_)) => geom(),
+        ("geom", Some(m)) => geom(m),
         ("f", Some(_)) => feature(),
-        ("fc", Some(_)) => feature_collection(),
+        ("fc", Some(m)) => feature_collection(m),
+        ("validate", Some(m)) => validate(m),
         _ => Err(Error::UnknownCommand),
     }
 }
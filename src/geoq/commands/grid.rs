@@ -0,0 +1,103 @@
+use crate::geoq::{
+    bbox::{self, BBoxToPoly},
+    conversion, coord_count,
+    error::Error,
+    geohash as gh,
+    geohash::MAX_GEOHASH_LENGTH,
+    grid::Grid,
+    output, reader, units,
+};
+use clap::ArgMatches;
+use geo_types::{Geometry, Polygon, Rect};
+use serde_json::{json, Map};
+
+enum Cells {
+    Geohash(usize),
+    Grid(Grid),
+}
+
+fn read_cells(matches: &ArgMatches) -> Result<Cells, Error> {
+    match (matches.value_of("geohash"), matches.value_of("size")) {
+        (Some(level), None) => match level.parse::<usize>() {
+            Ok(l) if (1..=MAX_GEOHASH_LENGTH).contains(&l) => Ok(Cells::Geohash(l)),
+            _ => {
+                eprintln!(
+                    "Invalid --geohash level: {:?} - must be an integer between 1 and {}",
+                    level, MAX_GEOHASH_LENGTH
+                );
+                Err(Error::InvalidNumberFormat)
+            }
+        },
+        (None, Some(size)) => {
+            let meters = units::parse_distance(size)?;
+            if !(meters.is_finite() && meters > 0.0) {
+                eprintln!("Invalid --size: {:?} - must be a distance greater than 0", size);
+                return Err(Error::InvalidNumberFormat);
+            }
+            Ok(Cells::Grid(Grid::new(meters)))
+        }
+        _ => {
+            eprintln!("Must provide a cell size with either --geohash LEVEL or --size DISTANCE.");
+            Err(Error::MissingArgument)
+        }
+    }
+}
+
+fn cell_feature(
+    poly: Polygon<f64>,
+    id: Option<geojson::feature::Id>,
+    props: Map<String, serde_json::Value>,
+) -> geojson::Feature {
+    geojson::Feature {
+        bbox: None,
+        geometry: Some(geojson::Geometry::new(conversion::geojson_value(
+            &Geometry::Polygon(poly),
+        ))),
+        id,
+        properties: Some(props),
+        foreign_members: None,
+    }
+}
+
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let cells = read_cells(matches)?;
+
+    let mut extent: Option<Rect<f64>> = None;
+    reader::for_input_entity(matches.value_of("input"), |e| {
+        if !coord_count::is_empty(&e.geom()) {
+            extent = Some(match extent {
+                Some(curr) => bbox::merge(&curr, &e.bbox()),
+                None => e.bbox(),
+            });
+        }
+        Ok(())
+    })?;
+    let extent = match extent {
+        Some(extent) => extent,
+        None => return Ok(()),
+    };
+
+    match cells {
+        Cells::Geohash(level) => {
+            let covering = gh::covering(&Geometry::Polygon(extent.to_polygon()), level);
+            for hash in covering {
+                if let Some(poly) = gh::bbox(&hash) {
+                    let mut props = Map::new();
+                    props.insert("geohash".to_string(), json!(hash));
+                    let id = geojson::feature::Id::String(hash);
+                    println!("{}", output::geojson(cell_feature(poly, Some(id), props)));
+                }
+            }
+        }
+        Cells::Grid(grid) => {
+            for (row, col) in grid.cells_covering(&extent) {
+                let mut props = Map::new();
+                props.insert("row".to_string(), json!(row));
+                props.insert("col".to_string(), json!(col));
+                let feature = cell_feature(grid.cell_polygon((row, col)), None, props);
+                println!("{}", output::geojson(feature));
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,15 @@
+use crate::geoq::{self, error::Error, par};
+use clap::ArgMatches;
+
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let with_properties = matches.is_present("with-properties");
+    par::for_stdin_entity(move |e| {
+        let props = if with_properties {
+            Some(e.geojson_properties())
+        } else {
+            None
+        };
+        let hash = geoq::hash::hash(&e.geom(), props.as_ref());
+        Ok(vec![format!("{}\t{}", hash, e.raw())])
+    })
+}
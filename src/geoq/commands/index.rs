@@ -0,0 +1,41 @@
+use crate::geoq::{centroid, error::Error, output, par};
+use clap::ArgMatches;
+use serde_json::{json, Value};
+
+fn read_levels(matches: &ArgMatches) -> Result<Vec<usize>, Error> {
+    matches
+        .values_of("geohash")
+        .map(|levels| levels.collect::<Vec<&str>>())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|level| match level.parse::<usize>() {
+            Ok(level) if (1..=12).contains(&level) => Ok(level),
+            _ => {
+                eprintln!("Invalid --geohash level: {:?} - must be between 1 and 12", level);
+                Err(Error::InvalidNumberFormat)
+            }
+        })
+        .collect()
+}
+
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let levels = read_levels(matches)?;
+    par::for_input_entity(matches.value_of("input"), move |e| {
+        let mut feature = e.geojson_feature();
+        // Features with a null geometry have no centroid, so their cells are null
+        let point = if e.has_null_geometry() {
+            None
+        } else {
+            centroid::centroid(&e.geom())
+        };
+        let props = feature.properties.get_or_insert_with(Default::default);
+        for &level in &levels {
+            let cell = match point {
+                Some(p) => json!(geohash::encode(p.0, level).map_err(|_| Error::InvalidGeohashPoint)?),
+                None => Value::Null,
+            };
+            props.insert(format!("geohash_{}", level), cell);
+        }
+        Ok(vec![output::geojson(feature)])
+    })
+}
@@ -0,0 +1,70 @@
+use crate::geoq::{area, conversion, coord_count, entity::Entity, error::Error, output, par, units};
+use clap::ArgMatches;
+use serde_json::{json, Map, Value};
+
+struct Info {
+    geom_type: &'static str,
+    vertices: usize,
+    bbox: Option<[f64; 4]>,
+    area: f64,
+    length: f64,
+    properties: usize,
+}
+
+fn info(e: &Entity, meters_per_unit: f64) -> Info {
+    let geom = e.geom();
+    let bbox = if coord_count::is_empty(&geom) {
+        None
+    } else {
+        let b = e.bbox();
+        Some([b.min.x, b.min.y, b.max.x, b.max.y])
+    };
+    Info {
+        geom_type: conversion::geometry_type(&geom),
+        vertices: coord_count::coord_count(&geom),
+        bbox,
+        area: area::area(&geom) / (meters_per_unit * meters_per_unit),
+        length: area::length(&geom) / meters_per_unit,
+        properties: e.geojson_properties().len(),
+    }
+}
+
+fn json_info(e: &Entity, i: &Info, unit: &str) -> Map<String, Value> {
+    let mut m = Map::new();
+    m.insert("type".to_string(), json!(i.geom_type));
+    m.insert("vertices".to_string(), json!(i.vertices));
+    m.insert("bbox".to_string(), json!(i.bbox));
+    m.insert("area".to_string(), json!(i.area));
+    m.insert("length".to_string(), json!(i.length));
+    m.insert("unit".to_string(), json!(unit));
+    m.insert("properties".to_string(), json!(i.properties));
+    if let Some(id) = e.id() {
+        m.insert("id".to_string(), id);
+    }
+    m
+}
+
+fn text_info(i: &Info, unit: &str) -> String {
+    let bbox = match i.bbox {
+        Some(b) => format!("{},{},{},{}", b[0], b[1], b[2], b[3]),
+        None => "empty".to_string(),
+    };
+    format!(
+        "{}\tvertices: {}\tbbox: {}\tarea: {} {}^2\tlength: {} {}\tproperties: {}",
+        i.geom_type, i.vertices, bbox, i.area, unit, i.length, unit, i.properties
+    )
+}
+
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let as_json = matches.is_present("json");
+    let unit = units::default_distance_unit();
+    let meters_per_unit = units::meters_per(unit).ok_or(Error::InvalidNumberFormat)?;
+    par::for_input_entity(matches.value_of("input"), move |e| {
+        let i = info(&e, meters_per_unit);
+        if as_json {
+            Ok(vec![output::json(json_info(&e, &i, unit))])
+        } else {
+            Ok(vec![text_info(&i, unit)])
+        }
+    })
+}
@@ -0,0 +1,60 @@
+use crate::geoq::{error::Error, output, reader};
+use clap::ArgMatches;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+type Row = Map<String, Value>;
+
+fn read_csv(path: &str, on: &str) -> Result<HashMap<String, Row>, Error> {
+    let mut reader = csv::Reader::from_path(path).map_err(|e| {
+        eprintln!("Unable to read --csv file {}: {}", path, e);
+        Error::InvalidCsv
+    })?;
+    let headers = reader.headers()?.clone();
+    let key_index = match headers.iter().position(|h| h == on) {
+        Some(i) => i,
+        None => {
+            eprintln!("--on column {:?} not found in CSV header: {:?}", on, headers);
+            return Err(Error::InvalidCsv);
+        }
+    };
+
+    let mut rows = HashMap::new();
+    for record in reader.records() {
+        let record = record?;
+        let mut row = Map::new();
+        for (i, (header, value)) in headers.iter().zip(record.iter()).enumerate() {
+            if i != key_index {
+                row.insert(header.to_string(), Value::String(value.to_string()));
+            }
+        }
+        if let Some(key) = record.get(key_index) {
+            rows.insert(key.to_string(), row);
+        }
+    }
+    Ok(rows)
+}
+
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let (path, on) = match (matches.value_of("csv"), matches.value_of("on")) {
+        (Some(path), Some(on)) => (path, on),
+        _ => return Err(Error::MissingArgument),
+    };
+    let inner = matches.is_present("inner");
+    let rows = read_csv(path, on)?;
+
+    reader::for_entity(|e| {
+        let row = e.key(on).and_then(|key| rows.get(&key));
+        if row.is_none() && inner {
+            return Ok(());
+        }
+        let mut feature = e.geojson_feature();
+        if let (Some(row), Some(props)) = (row, feature.properties.as_mut()) {
+            for (k, v) in row {
+                props.insert(k.clone(), v.clone());
+            }
+        }
+        println!("{}", output::geojson(feature));
+        Ok(())
+    })
+}
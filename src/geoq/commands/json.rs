@@ -1,9 +1,9 @@
-use crate::geoq::error::Error;
+use crate::geoq::{conversion, error::Error, output};
 use geo_types::{Geometry, Point};
 use geojson::GeoJson;
 use clap::ArgMatches;
 use serde_json;
-use serde_json::{json, Map, Value};
+use serde_json::{Map, Value};
 use std::{convert::TryInto};
 use std::io::{self, BufRead};
 
@@ -56,28 +56,50 @@ fn latlon_point(v: &Map<String, Value>) -> Option<(Geom, Vec<String>)> {
     lat_lon.map(|((lat_key, lat), (lon_key, lon))| (Geometry::Point(Point::new(lon, lat)), vec![lat_key.to_string(), lon_key.to_string()]))
 }
 
+fn parse_wkt(raw: &str) -> Option<Geom> {
+    wkt::Wkt::from_str(raw).ok().and_then(|wkt| {
+        if wkt.items.is_empty() {
+            None
+        } else {
+            // TODO what to do with multiple wkt geoms
+            wkt::conversion::try_into_geometry(&wkt.items[0]).ok()
+        }
+    })
+}
+
+fn parse_geojson_geom(raw: &str) -> Option<Geom> {
+    match raw.parse().ok()? {
+        GeoJson::Geometry(gj_geom) => TryInto::<Geom>::try_into(gj_geom.value).ok(),
+        _ => None
+    }
+}
+
 fn wkt_geom(v: &Map<String, Value>) -> Option<(Geom, Vec<String>)> {
     let str_opt_with_key = find_string(v, &vec!["geometry", "wkt"]);
-    str_opt_with_key.and_then(|(k, v)| wkt::Wkt::from_str(&v).ok().map(|wkt| (k, wkt)))
-        .and_then(|(k,wkt)| {
-            if wkt.items.is_empty() {
-                None
-            } else {
-                // TODO what to do with multiple wkt geoms
-                wkt::conversion::try_into_geometry(&wkt.items[0]).ok().map(|geom| (geom, vec![k.to_string()]))
-            }
-        })
+    str_opt_with_key.and_then(|(k, v)| parse_wkt(&v).map(|geom| (geom, vec![k.to_string()])))
 }
 
 fn geojson_str_geom(v: &Map<String, Value>) -> Option<(Geom, Vec<String>)> {
     let str_opt_with_key = find_string(v, &vec!["geometry", "geojson"]);
-    str_opt_with_key.and_then(|(k, v)| v.parse().ok().map(|gj| (k, gj)))
-        .and_then(|(k,gj)| {
-            match gj {
-                GeoJson::Geometry(gj_geom) => TryInto::<Geom>::try_into(gj_geom.value).ok().map(|geom|(geom, vec![k.to_string()])),
-                _ => None
-            }
-        })
+    str_opt_with_key.and_then(|(k, v)| parse_geojson_geom(&v).map(|geom| (geom, vec![k.to_string()])))
+}
+
+// Any other string field holding a GeoJSON geometry or WKT, as some APIs
+// give stringified geometries under their own keys. Keys are checked in
+// sorted order, so the first matching key is used if there are several.
+fn any_str_geom(v: &Map<String, Value>) -> Option<(Geom, Vec<String>)> {
+    v.iter().find_map(|(k, val)| match val {
+        Value::String(ref raw) => {
+            let raw = raw.trim();
+            let geom = if raw.starts_with('{') {
+                parse_geojson_geom(raw)
+            } else {
+                parse_wkt(raw)
+            };
+            geom.map(|geom| (geom, vec![k.to_string()]))
+        }
+        _ => None,
+    })
 }
 
 fn geojson_geom(v: &Map<String, Value>) -> Option<(Geom, Vec<String>)> {
@@ -89,7 +111,7 @@ fn geojson_geom(v: &Map<String, Value>) -> Option<(Geom, Vec<String>)> {
 }
 
 pub fn find_geometry(v: &Map<String, Value>) -> Option<(Geom, Vec<String>)> {
-    latlon_point(v).or_else(|| wkt_geom(v)).or_else(|| geojson_str_geom(v)).or_else(|| geojson_geom(v))
+    latlon_point(v).or_else(|| wkt_geom(v)).or_else(|| geojson_str_geom(v)).or_else(|| geojson_geom(v)).or_else(|| any_str_geom(v))
     // latlon_point(v).or().or().or()
     // latlon_point
     // Point
@@ -100,6 +122,7 @@ pub fn find_geometry(v: &Map<String, Value>) -> Option<(Geom, Vec<String>)> {
     // - wkt
     // - geometry: geojson string
     // - geometry: geojson geometry
+    // - any key: geojson or wkt string
     // None
 }
 
@@ -147,14 +170,15 @@ fn munge() -> Result<(), Error> {
                         for k in geomified_keys {
                             o.remove(&k);
                         }
-                        let gj_geom = geojson::Geometry::new(geojson::Value::from(&geom));
-                        let geojson = json!({
-                            "type": "Feature",
-                            "properties": Value::Object(o),
-                            "geometry": gj_geom
-                        });
-                        let json_str = serde_json::to_string(&geojson)?;
-                        println!("{}", json_str)
+                        let gj_geom = geojson::Geometry::new(conversion::geojson_value(&geom));
+                        let feature = geojson::Feature {
+                            bbox: None,
+                            geometry: Some(gj_geom),
+                            id: None,
+                            properties: Some(o),
+                            foreign_members: None,
+                        };
+                        println!("{}", output::geojson(feature))
                     },
                     _ => {
                         eprintln!("Couldn't guess GeoJSON Feature from JSON");
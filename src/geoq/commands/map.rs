@@ -1,13 +1,19 @@
-use crate::geoq::{browser_open, error::Error, reader::Reader};
+use crate::geoq::{browser_open, conversion, error::Error, reader::{self, Reader}, simplify};
+use clap::ArgMatches;
 use geojson::GeoJson;
 use percent_encoding::{utf8_percent_encode, DEFAULT_ENCODE_SET};
 use std::{
+    collections::hash_map::RandomState,
     fs::File,
+    hash::{BuildHasher, Hasher},
     io::{self, prelude::*},
+    str::FromStr,
     time::{SystemTime, UNIX_EPOCH},
 };
 
 const GEOJSON_IO_URL_LIMIT: usize = 27000;
+// geojson.io gets very slow to load maps larger than this (in bytes)
+const GEOJSON_IO_SIZE_WARNING: usize = 5_000_000;
 static GEOJSON_IO_HTML_P1: &'static [u8] = include_bytes!("../../../resources/geojsonio_p1.html");
 static GEOJSON_IO_HTML_P2: &'static [u8] = include_bytes!("../../../resources/geojsonio_p2.html");
 
@@ -19,42 +25,125 @@ fn timestamp() -> u64 {
         .as_secs()
 }
 
-pub fn run() -> Result<(), Error> {
+// Randomly chosen index below n. RandomState is seeded randomly for
+// each process, so hashing a counter gives a different sample each run.
+fn random_index(state: &RandomState, counter: usize, n: usize) -> usize {
+    let mut hasher = state.build_hasher();
+    hasher.write_usize(counter);
+    (hasher.finish() % n as u64) as usize
+}
+
+// Reservoir sample of n features, kept in their original order
+fn sample(features: Vec<geojson::Feature>, n: usize) -> Vec<geojson::Feature> {
+    let state = RandomState::new();
+    let mut reservoir: Vec<(usize, geojson::Feature)> = Vec::with_capacity(n);
+    for (i, f) in features.into_iter().enumerate() {
+        if reservoir.len() < n {
+            reservoir.push((i, f));
+        } else {
+            let j = random_index(&state, i, i + 1);
+            if j < n {
+                reservoir[j] = (i, f);
+            }
+        }
+    }
+    reservoir.sort_by_key(|(i, _)| *i);
+    reservoir.into_iter().map(|(_, f)| f).collect()
+}
+
+fn read_sample_size(matches: &ArgMatches) -> Result<Option<usize>, Error> {
+    match matches.value_of("sample") {
+        None => Ok(None),
+        Some(arg) => arg.parse::<usize>().map(Some).map_err(|_| {
+            eprintln!("Invalid --sample: {:?} - must be a positive integer", arg);
+            Error::InvalidNumberFormat
+        }),
+    }
+}
+
+fn read_epsilon(matches: &ArgMatches) -> Result<Option<f64>, Error> {
+    match matches.value_of("simplify-for-display") {
+        None => Ok(None),
+        Some(arg) => f64::from_str(arg).map(Some).map_err(|_| {
+            eprintln!(
+                "Invalid --simplify-for-display: {:?} - must be a floating point number, e.g. 0.001",
+                arg
+            );
+            Error::InvalidNumberFormat
+        }),
+    }
+}
+
+// Opens the map in a browser, or with --url, prints where it is instead
+fn show(location: String, print_only: bool) {
+    if print_only {
+        println!("{}", location);
+    } else {
+        browser_open::open(location);
+    }
+}
+
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let print_only = matches.is_present("url");
+    let sample_size = read_sample_size(matches)?;
+    let epsilon = read_epsilon(matches)?;
+
     let stdin = io::stdin();
     let mut stdin_reader = stdin.lock();
-    let reader = Reader::new(&mut stdin_reader);
+    let reader = Reader::new(&mut stdin_reader).with_limit(reader::input_limit());
 
     let mut features: Vec<geojson::Feature> = Vec::new();
 
     for e_res in reader {
         match e_res {
-            Ok(entity) => features.push(entity.geojson_feature()),
+            Ok(entity) => {
+                let mut feature = entity.geojson_feature();
+                if let Some(eps) = epsilon {
+                    let simplified = simplify::simplify(entity.geom(), eps);
+                    feature.geometry = Some(geojson::Geometry::new(conversion::geojson_value(&simplified)));
+                }
+                features.push(feature)
+            }
             Err(e) => return Err(e),
         }
     }
 
+    if let Some(n) = sample_size {
+        features = sample(features, n);
+    }
+
     let fc = geojson::FeatureCollection {
         bbox: None,
         features: features,
         foreign_members: None,
     };
     let fc_json = GeoJson::from(fc).to_string();
+    if fc_json.len() > GEOJSON_IO_SIZE_WARNING && sample_size.is_none() && epsilon.is_none() {
+        eprintln!(
+            "Warning: {} bytes of GeoJSON may be too large for geojson.io to display. Try --simplify-for-display EPSILON or --sample N.",
+            fc_json.len()
+        );
+    }
 
     if fc_json.len() < GEOJSON_IO_URL_LIMIT {
         let encoded = utf8_percent_encode(&fc_json, DEFAULT_ENCODE_SET);
         let url = format!("http://geojson.io#data=data:application/json,{}", encoded);
-        browser_open::open(url);
+        show(url, print_only);
 
         Ok(())
     } else {
         let tmpfile = format!("/tmp/geoq_map_{}.html", timestamp());
         let mut file = File::create(tmpfile.clone())?;
-        eprintln!("Opening geojson.io map file: {}", tmpfile);
+        if print_only {
+            eprintln!("Features are too large to embed in a geojson.io URL, so they were written to a map file instead.");
+        } else {
+            eprintln!("Opening geojson.io map file: {}", tmpfile);
+        }
 
         file.write_all(GEOJSON_IO_HTML_P1)?;
         file.write_all(fc_json.as_bytes())?;
         file.write_all(GEOJSON_IO_HTML_P2)?;
-        browser_open::open(tmpfile);
+        show(tmpfile, print_only);
 
         Ok(())
     }
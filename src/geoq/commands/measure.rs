@@ -1,9 +1,112 @@
-use crate::geoq::{distance, entity, error::Error, input, par, coord_count};
+use crate::geoq::{area, distance, entity::{self, Entity}, error::Error, input, multi, output, par, coord_count, units};
 use clap::ArgMatches;
-use geo_types::Geometry;
-use serde_json::json;
+use geo_types::{Geometry, LineString, Point};
+use serde_json::{json, Map, Value};
+
+fn json_measurement(e: &Entity, value: Value, unit: &str) -> Map<String, Value> {
+    let mut m = Map::new();
+    m.insert("value".to_string(), value);
+    m.insert("unit".to_string(), json!(unit));
+    if let Some(id) = e.id() {
+        m.insert("id".to_string(), id);
+    }
+    m
+}
+
+// A measurement for text output, followed by its unit with --labeled
+fn format_value(value: f64, unit: &str, labeled: bool) -> String {
+    if labeled {
+        format!("{} {}", value, unit)
+    } else {
+        value.to_string()
+    }
+}
+
+// A line from the query to the input between their closest points
+fn closest_points_feature(
+    e: &Entity,
+    points: (Point<f64>, Point<f64>),
+    dist: f64,
+    unit: &str,
+) -> geojson::Feature {
+    let line = LineString(vec![points.0 .0, points.1 .0]);
+    let mut props = Map::new();
+    props.insert("distance".to_string(), json!(dist));
+    props.insert("unit".to_string(), json!(unit));
+    geojson::Feature {
+        bbox: None,
+        geometry: Some(geojson::Geometry::new(geojson::Value::from(&line))),
+        id: e.geojson_id(),
+        properties: Some(props),
+        foreign_members: None,
+    }
+}
+
+// Running total of the distance at each vertex of a line, starting from 0
+fn chainage(line: &LineString<f64>, method: distance::Method) -> Vec<f64> {
+    let mut total = 0.0;
+    let mut output = Vec::with_capacity(line.0.len());
+    for (i, c) in line.0.iter().enumerate() {
+        if i > 0 {
+            let (a, b) = (Point(line.0[i - 1]), Point(*c));
+            // Vincenty can fail to converge, but Karney always does
+            total += distance::point_distance(&a, &b, method)
+                .or_else(|| distance::point_distance(&a, &b, distance::Method::Karney))
+                .unwrap();
+        }
+        output.push(total);
+    }
+    output
+}
+
+fn cumulative(
+    matches: &ArgMatches,
+    method: distance::Method,
+    unit: String,
+    meters_per_unit: f64,
+) -> Result<(), Error> {
+    let labeled = matches.is_present("labeled");
+    // There's no QUERY, so an input argument may take its place
+    let input = matches.value_of("input").or_else(|| matches.value_of("query"));
+    par::for_input_entity(input, move |entity| {
+        let lines = match entity.geom() {
+            Geometry::Line(l) => vec![LineString(vec![l.start, l.end])],
+            Geometry::LineString(ls) => vec![ls],
+            Geometry::MultiLineString(mls) => mls.0,
+            _ => {
+                eprintln!("Cumulative distances require LineString inputs. Got: {}", entity.raw());
+                return Ok(vec![]);
+            }
+        };
+        Ok(lines
+            .iter()
+            .flat_map(|line| chainage(line, method))
+            .map(|d| format_value(d / meters_per_unit, &unit, labeled))
+            .collect())
+    })
+}
 
 fn distance(matches: &ArgMatches) -> Result<(), Error> {
+    let with_bearing = matches.is_present("with-bearing");
+    let as_json = matches.is_present("json");
+    let with_closest_points = matches.is_present("closest-points");
+    let labeled = matches.is_present("labeled");
+    let unit = matches
+        .value_of("unit")
+        .unwrap_or_else(|| units::default_distance_unit())
+        .to_string();
+    let meters_per_unit = units::meters_per(&unit).ok_or(Error::InvalidNumberFormat)?;
+    let method = if matches.is_present("planar") {
+        distance::Method::Planar
+    } else {
+        matches
+            .value_of("method")
+            .and_then(distance::Method::from_name)
+            .unwrap_or(distance::Method::Karney)
+    };
+    if matches.is_present("cumulative") {
+        return cumulative(matches, method, unit, meters_per_unit);
+    }
     match matches.value_of("query") {
         Some(q) => {
             let query_input = input::read_line(q.to_string())?;
@@ -13,42 +116,98 @@ fn distance(matches: &ArgMatches) -> Result<(), Error> {
             } else if query_entities.len() > 1 {
                 Err(Error::TooManyFeatures)
             } else {
-                match query_entities.remove(0).geom() {
-                    Geometry::Point(query_point) => par::for_stdin_entity(move |entity| {
-                        let output = entity.raw();
-                        let geom = entity.geom();
-
-                        let dist = distance::distance(&query_point, &geom);
+                let query = query_entities.remove(0).geom();
+                if with_bearing && !matches!(query, Geometry::Point(_)) {
+                    eprintln!(
+                        "<query> argument for measuring distance with --with-bearing must be a Point. Got: {}",
+                        q
+                    );
+                    return Err(Error::PointRequired);
+                }
+                let query_raw = q.to_string();
+                par::for_input_entity(matches.value_of("input"), move |entity| {
+                    let output = entity.raw();
+                    let geom = entity.geom();
+                    if coord_count::is_empty(&geom) {
+                        return Ok(vec![]);
+                    }
 
-                        match dist {
-                            Some(d) => Ok(vec![format!("{}\t{}", d, output)]),
-                            None => {
-                                eprintln!("Couldn't calculate distance between <query>: {:?} and <input>: {}", query_point, output);
+                    let dist = distance::geometry_distance(&query, &geom, method).map(|d| d / meters_per_unit);
+                    if with_closest_points {
+                        return match (dist, distance::closest_points(&query, &geom)) {
+                            (Some(d), Some(points)) => {
+                                let feature = closest_points_feature(&entity, points, d, &unit);
+                                Ok(vec![output::geojson(feature)])
+                            }
+                            _ => {
+                                eprintln!("Couldn't calculate distance between <query>: {} and <input>: {}", query_raw, output);
                                 Err(Error::DistanceFailed)
                             }
+                        };
+                    }
+                    // Outer None means the bearing couldn't be calculated,
+                    // inner None means it wasn't requested
+                    let bearing = match query {
+                        Geometry::Point(ref query_point) if with_bearing => {
+                            distance::bearing(query_point, &geom).map(Some)
+                        }
+                        _ => Some(None),
+                    };
+
+                    match (dist, bearing) {
+                        (Some(d), Some(b)) => {
+                            if as_json {
+                                let mut m = json_measurement(&entity, json!(d), &unit);
+                                if let Some(b) = b {
+                                    m.insert("bearing".to_string(), json!(b));
+                                }
+                                Ok(vec![output::json(m)])
+                            } else {
+                                let d = format_value(d, &unit, labeled);
+                                match b {
+                                    Some(b) => Ok(vec![format!("{}\t{}\t{}", d, b, output)]),
+                                    None => Ok(vec![format!("{}\t{}", d, output)]),
+                                }
+                            }
+                        }
+                        _ => {
+                            eprintln!("Couldn't calculate distance between <query>: {} and <input>: {}", query_raw, output);
+                            Err(Error::DistanceFailed)
                         }
-                    }),
-                    _ => {
-                        eprintln!(
-                            "<query> argument for measuing distance must be a Point. Got: {}",
-                            q
-                        );
-                        Err(Error::PointRequired)
                     }
-                }
+                })
             }
         }
         _ => Err(Error::MissingArgument),
     }
 }
 
+// One line per ring: polygon index (within the input), ring type, and count
+fn ring_lines(geom: &Geometry<f64>) -> Vec<String> {
+    let mut lines = Vec::new();
+    for (index, rings) in coord_count::ring_coord_counts(geom).iter().enumerate() {
+        for (ring_index, count) in rings.iter().enumerate() {
+            let ring_type = if ring_index == 0 { "exterior" } else { "interior" };
+            lines.push(format!("{}\t{}\t{}", index, ring_type, count));
+        }
+    }
+    lines
+}
+
 fn coords(matches: &ArgMatches) -> Result<(), Error> {
+    if matches.is_present("per-ring") {
+        return par::for_input_entity(matches.value_of("input"), |e| Ok(ring_lines(&e.geom())));
+    }
     let gj = matches.is_present("geojson");
-    par::for_stdin_entity(move |e| {
+    let as_json = matches.is_present("json");
+    par::for_input_entity(matches.value_of("input"), move |e| {
         let dupe = e.clone();
         let geom = e.geom();
         let count = coord_count::coord_count(&geom);
-        if gj {
+        if as_json {
+            let m = json_measurement(&e, json!(count), "coords");
+            Ok(vec![output::json(m)])
+        } else if gj {
             let mut feature = dupe.geojson_feature();
             match feature.properties.as_mut() {
                 Some(props) => {
@@ -56,16 +215,116 @@ fn coords(matches: &ArgMatches) -> Result<(), Error> {
                 }
                 None => ()
             }
-            Ok(vec![serde_json::to_string(&feature).unwrap()])
+            Ok(vec![output::geojson(feature)])
         } else {
             Ok(vec![format!("{}", count)])
         }
     })
 }
 
+fn area(matches: &ArgMatches) -> Result<(), Error> {
+    let signed = matches.is_present("signed");
+    let planar = matches.is_present("planar");
+    let labeled = matches.is_present("labeled");
+    let per_component = matches.is_present("per-component");
+    let as_json = matches.is_present("json");
+    let verbose = matches.is_present("verbose");
+    let (unit, meters_per_unit) = if signed {
+        ("deg^2".to_string(), 1.0)
+    } else {
+        let unit = matches
+            .value_of("unit")
+            .unwrap_or_else(|| units::default_distance_unit())
+            .to_string();
+        let meters_per_unit = units::meters_per(&unit).ok_or(Error::InvalidNumberFormat)?;
+        (format!("{}^2", unit), meters_per_unit)
+    };
+    par::for_input_entity(matches.value_of("input"), move |e| {
+        if e.has_null_geometry() {
+            if verbose {
+                eprintln!("Skipping feature with null geometry: {}", e.raw());
+            }
+            return Ok(vec![]);
+        }
+        let measure = |geom: &Geometry<f64>| {
+            if signed {
+                area::signed_planar_area(geom)
+            } else if planar {
+                area::planar_area(geom) / (meters_per_unit * meters_per_unit)
+            } else {
+                area::area(geom) / (meters_per_unit * meters_per_unit)
+            }
+        };
+        let geom = e.geom();
+        if per_component {
+            return Ok(multi::components(geom)
+                .iter()
+                .enumerate()
+                .map(|(index, component)| {
+                    let value = measure(component);
+                    if as_json {
+                        let mut m = json_measurement(&e, json!(value), &unit);
+                        m.insert("component".to_string(), json!(index));
+                        output::json(m)
+                    } else {
+                        format!("{}\t{}\t{}", format_value(value, &unit, labeled), index, e.raw())
+                    }
+                })
+                .collect());
+        }
+        let value = measure(&geom);
+        if as_json {
+            let m = json_measurement(&e, json!(value), &unit);
+            Ok(vec![output::json(m)])
+        } else {
+            Ok(vec![format!("{}\t{}", format_value(value, &unit, labeled), e.raw())])
+        }
+    })
+}
+
+fn length(matches: &ArgMatches) -> Result<(), Error> {
+    let three_d = matches.is_present("3d");
+    let planar = matches.is_present("planar");
+    let labeled = matches.is_present("labeled");
+    let as_json = matches.is_present("json");
+    let unit = matches
+        .value_of("unit")
+        .unwrap_or_else(|| units::default_distance_unit())
+        .to_string();
+    let meters_per_unit = units::meters_per(&unit).ok_or(Error::InvalidNumberFormat)?;
+    par::for_input_entity(matches.value_of("input"), move |e| {
+        if e.has_null_geometry() {
+            return Ok(vec![]);
+        }
+        let with_z = if three_d {
+            e.input_geojson_value().and_then(area::length_3d)
+        } else {
+            None
+        };
+        if three_d && with_z.is_none() {
+            eprintln!("No Z coordinates for --3d, giving the 2D length of: {}", e.raw());
+        }
+        let value = with_z.unwrap_or_else(|| {
+            if planar {
+                area::planar_length(&e.geom())
+            } else {
+                area::length(&e.geom())
+            }
+        }) / meters_per_unit;
+        if as_json {
+            let m = json_measurement(&e, json!(value), &unit);
+            Ok(vec![output::json(m)])
+        } else {
+            Ok(vec![format!("{}\t{}", format_value(value, &unit, labeled), e.raw())])
+        }
+    })
+}
+
 pub fn run(matches: &ArgMatches) -> Result<(), Error> {
     match matches.subcommand() {
         ("distance", Some(m)) => distance(m),
+        ("area", Some(m)) => area(m),
+        ("length", Some(m)) => length(m),
         ("coord-count", Some(m)) => coords(m),
         _ => Err(Error::UnknownCommand),
     }
@@ -0,0 +1,104 @@
+use crate::geoq::{self, error::Error, output, reader};
+use clap::ArgMatches;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+// How to resolve a property given different values by features with the same geometry
+#[derive(Clone, Copy)]
+enum Conflict {
+    First,
+    Last,
+    Array,
+}
+
+impl Conflict {
+    fn from_name(name: &str) -> Option<Conflict> {
+        match name {
+            "first" => Some(Conflict::First),
+            "last" => Some(Conflict::Last),
+            "array" => Some(Conflict::Array),
+            _ => None,
+        }
+    }
+}
+
+// A feature with the distinct values seen for each of its properties,
+// and the last value seen for each, which may repeat an earlier one
+struct Merged {
+    feature: geojson::Feature,
+    values: Vec<(String, Vec<Value>)>,
+    last: Map<String, Value>,
+}
+
+impl Merged {
+    fn add(&mut self, props: Map<String, Value>) {
+        for (key, value) in props {
+            self.last.insert(key.clone(), value.clone());
+            match self.values.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, values)) => {
+                    if !values.contains(&value) {
+                        values.push(value);
+                    }
+                }
+                None => self.values.push((key, vec![value])),
+            }
+        }
+    }
+
+    fn into_feature(self, conflict: Conflict) -> geojson::Feature {
+        let mut last = self.last;
+        let props: Map<String, Value> = self
+            .values
+            .into_iter()
+            .map(|(key, mut values)| {
+                let value = if values.len() == 1 {
+                    values.remove(0)
+                } else {
+                    match conflict {
+                        Conflict::First => values.remove(0),
+                        Conflict::Last => last.remove(&key).unwrap(),
+                        Conflict::Array => Value::Array(values),
+                    }
+                };
+                (key, value)
+            })
+            .collect();
+        let mut feature = self.feature;
+        feature.properties = Some(props);
+        feature
+    }
+}
+
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let conflict = matches
+        .value_of("conflict")
+        .and_then(Conflict::from_name)
+        .unwrap_or(Conflict::First);
+
+    // Features in the order their geometries were first seen
+    let mut merged: Vec<Merged> = Vec::new();
+    let mut by_hash: HashMap<String, usize> = HashMap::new();
+    reader::for_input_entity(matches.value_of("input"), |e| {
+        let hash = geoq::hash::hash(&e.geom(), None);
+        let props = e.geojson_properties();
+        match by_hash.get(&hash) {
+            Some(&index) => merged[index].add(props),
+            None => {
+                by_hash.insert(hash, merged.len());
+                let mut m = Merged {
+                    feature: e.geojson_feature(),
+                    values: Vec::new(),
+                    last: Map::new(),
+                };
+                m.add(props);
+                merged.push(m);
+            }
+        }
+        Ok(())
+    })?;
+
+    for m in merged {
+        println!("{}", output::geojson(m.into_feature(conflict)));
+    }
+    Ok(())
+}
@@ -1,13 +1,43 @@
+pub mod bin;
 pub mod centroid;
+pub mod clip;
+pub mod cluster;
+pub mod delaunay;
+pub mod densify;
+pub mod diff;
+pub mod extent;
 pub mod filter;
+pub mod flip;
 pub mod geohash;
 pub mod geojson_cmd;
+pub mod grid;
+pub mod hash;
+pub mod index;
+pub mod info;
+pub mod join;
 pub mod json;
 pub mod map;
 pub mod measure;
+pub mod merge_properties;
+pub mod node;
+pub mod normalize_lon;
+pub mod pip;
+pub mod polygonize;
 pub mod read;
+pub mod repair_winding;
+pub mod resample;
 pub mod simplify;
+pub mod sjoin;
+pub mod smooth;
 pub mod snip;
+pub mod sort;
+pub mod split_by;
+pub mod stats;
+pub mod to_multi;
+pub mod to_single;
+pub mod validate;
+pub mod version;
+pub mod voronoi;
 pub mod whereami;
 pub mod wkt;
 pub mod bbox;
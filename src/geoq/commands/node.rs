@@ -0,0 +1,35 @@
+use crate::geoq::{entity::Entity, error::Error, node, output, reader};
+use clap::ArgMatches;
+use geo_types::{Geometry, LineString};
+
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let mut entities: Vec<Entity> = Vec::new();
+    let mut lines: Vec<LineString<f64>> = Vec::new();
+    // Index of the entity each line came from
+    let mut owners: Vec<usize> = Vec::new();
+    reader::for_input_entity(matches.value_of("input"), |e| {
+        let parts = match e.geom() {
+            Geometry::Line(l) => vec![LineString(vec![l.start, l.end])],
+            Geometry::LineString(ls) => vec![ls],
+            Geometry::MultiLineString(mls) => mls.0,
+            _ => {
+                eprintln!("Noding requires LineString inputs. Got: {}", e.raw());
+                return Err(Error::LineStringRequired);
+            }
+        };
+        owners.extend(parts.iter().map(|_| entities.len()));
+        lines.extend(parts);
+        entities.push(e);
+        Ok(())
+    })?;
+
+    for (owner, pieces) in owners.into_iter().zip(node::node(&lines)) {
+        for piece in pieces {
+            println!(
+                "{}",
+                output::like_input(&entities[owner], &Geometry::LineString(piece))
+            );
+        }
+    }
+    Ok(())
+}
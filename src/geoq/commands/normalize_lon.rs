@@ -0,0 +1,18 @@
+use crate::geoq::{error::Error, longitude, output, par};
+use clap::ArgMatches;
+
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let to_0_360 = matches.is_present("to-0-360");
+    let split = matches.is_present("split");
+    par::for_stdin_entity(move |e| {
+        let geom = e.geom();
+        let normalized = if to_0_360 {
+            longitude::normalize_0_360(&geom)
+        } else if split {
+            longitude::split(&geom)
+        } else {
+            longitude::normalize(&geom)
+        };
+        Ok(vec![output::like_input(&e, &normalized)])
+    })
+}
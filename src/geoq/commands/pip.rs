@@ -0,0 +1,60 @@
+use crate::geoq::{
+    entity::Entity, error::Error, output, par, polygon_index::PolygonIndex, reader::Reader,
+};
+use clap::ArgMatches;
+use geo_types::Geometry;
+use serde_json::Value;
+use std::{fs::File, io::BufReader};
+
+fn read_polygons(path: &str) -> Result<Vec<Entity>, Error> {
+    let f = File::open(path)?;
+    let mut f = BufReader::new(f);
+    let reader = Reader::new(&mut f);
+    reader.into_iter().collect::<Result<Vec<_>, _>>()
+}
+
+fn build_index(polygons: &[Entity]) -> Result<PolygonIndex, Error> {
+    let mut members = Vec::new();
+    for (id, e) in polygons.iter().enumerate() {
+        match e.geom() {
+            Geometry::Polygon(poly) => members.push((id, poly)),
+            Geometry::MultiPolygon(mp) => members.extend(mp.0.into_iter().map(|poly| (id, poly))),
+            _ => {
+                eprintln!("--polygons must contain only POLYGON or MULTIPOLYGON features. Got: {}", e.raw());
+                return Err(Error::PolygonRequired);
+            }
+        }
+    }
+    Ok(PolygonIndex::new(members))
+}
+
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let (path, field) = match (matches.value_of("polygons"), matches.value_of("field")) {
+        (Some(path), Some(field)) => (path, field.to_string()),
+        _ => return Err(Error::MissingArgument),
+    };
+    let polygons = read_polygons(path)?;
+    let index = build_index(&polygons)?;
+    let tags: Vec<Value> = polygons
+        .iter()
+        .map(|e| e.geojson_properties().remove(&field).unwrap_or(Value::Null))
+        .collect();
+
+    par::for_stdin_entity(move |entity| match entity.geom() {
+        Geometry::Point(p) => {
+            let tag = index
+                .containing(&p)
+                .map(|id| tags[id].clone())
+                .unwrap_or(Value::Null);
+            let mut feature = entity.geojson_feature();
+            if let Some(props) = feature.properties.as_mut() {
+                props.insert(field.clone(), tag);
+            }
+            Ok(vec![output::geojson(feature)])
+        }
+        _ => {
+            eprintln!("pip requires Point inputs. Got: {}", entity.raw());
+            Err(Error::PointRequired)
+        }
+    })
+}
@@ -0,0 +1,49 @@
+use crate::geoq::{conversion, error::Error, output, polygonize, reader};
+use clap::ArgMatches;
+use geo_types::{Geometry, LineString};
+use serde_json::{json, Map};
+
+fn print_feature(geom: Geometry<f64>, props: Map<String, serde_json::Value>) {
+    let feature = geojson::Feature {
+        bbox: None,
+        geometry: Some(geojson::Geometry::new(conversion::geojson_value(&geom))),
+        id: None,
+        properties: Some(props),
+        foreign_members: None,
+    };
+    println!("{}", output::geojson(feature));
+}
+
+fn print_edges(edges: Vec<LineString<f64>>, edge_type: &str) {
+    for edge in edges {
+        let mut props = Map::new();
+        props.insert("edge".to_string(), json!(edge_type));
+        print_feature(Geometry::LineString(edge), props);
+    }
+}
+
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let mut lines: Vec<LineString<f64>> = Vec::new();
+    reader::for_input_entity(matches.value_of("input"), |e| {
+        match e.geom() {
+            Geometry::Line(l) => lines.push(LineString(vec![l.start, l.end])),
+            Geometry::LineString(ls) => lines.push(ls),
+            Geometry::MultiLineString(mls) => lines.extend(mls.0),
+            _ => {
+                eprintln!("Polygonizing requires LineString inputs. Got: {}", e.raw());
+                return Err(Error::LineStringRequired);
+            }
+        }
+        Ok(())
+    })?;
+
+    let result = polygonize::polygonize(lines);
+    for poly in result.polygons {
+        print_feature(Geometry::Polygon(poly), Map::new());
+    }
+    if matches.is_present("report-edges") {
+        print_edges(result.dangles, "dangle");
+        print_edges(result.cut_edges, "cut");
+    }
+    Ok(())
+}
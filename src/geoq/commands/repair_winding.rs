@@ -0,0 +1,22 @@
+use crate::geoq::{error::Error, output, par, winding};
+use clap::ArgMatches;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let fixed = Arc::new(AtomicUsize::new(0));
+    let worker_fixed = fixed.clone();
+    par::for_input_entity(matches.value_of("input"), move |e| {
+        match winding::rewind(&e.geom()) {
+            Some(geom) => {
+                worker_fixed.fetch_add(1, Ordering::Relaxed);
+                Ok(vec![output::like_input(&e, &geom)])
+            }
+            None => Ok(vec![e.raw()]),
+        }
+    })?;
+    eprintln!("Repaired winding of {} features", fixed.load(Ordering::Relaxed));
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use crate::geoq::{conversion, error::Error, output, par, resample, units};
+use clap::ArgMatches;
+
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let min_distance = match matches.value_of("min-distance") {
+        Some(arg) => units::parse_distance(arg)?,
+        None => return Err(Error::MissingArgument),
+    };
+
+    par::for_stdin_entity(move |e| {
+        let props = e.geojson_properties();
+        let resampled = resample::resample(e.geom(), min_distance);
+        let feature = geojson::Feature {
+            bbox: None,
+            geometry: Some(geojson::Geometry::new(conversion::geojson_value(&resampled))),
+            id: e.geojson_id(),
+            properties: Some(props),
+            foreign_members: None,
+        };
+        Ok(vec![output::geojson(feature)])
+    })
+}
@@ -1,24 +1,65 @@
-use crate::geoq::{error::Error, par, simplify, coord_count};
+use crate::geoq::{conversion, entity::Entity, error::Error, output, par, reader, simplify, coord_count};
 use clap::ArgMatches;
+use geo_types::Geometry;
 use std::str::FromStr;
 
 const MAX_ITERS: i32 = 20;
 
-fn simplify(epsilon: f64, coords_target: Option<usize>) -> Result<(), Error> {
+// Epsilon to use for each type of geometry, with per-type
+// overrides taking precedence over the default
+#[derive(Clone, Copy)]
+struct Epsilons {
+    default: f64,
+    line: Option<f64>,
+    polygon: Option<f64>,
+}
+
+impl Epsilons {
+    fn for_geom(&self, geom: &Geometry<f64>) -> f64 {
+        let specific = match *geom {
+            Geometry::LineString(_) | Geometry::MultiLineString(_) => self.line,
+            Geometry::Polygon(_) | Geometry::MultiPolygon(_) => self.polygon,
+            _ => None,
+        };
+        specific.unwrap_or(self.default)
+    }
+}
+
+fn parse_epsilon(name: &str, arg: &str) -> Result<f64, Error> {
+    f64::from_str(arg).map_err(|_| {
+        eprintln!(
+            "Invalid {}: {:?} - must be floating point number, e.g. 0.001.",
+            name, arg
+        );
+        Error::InvalidNumberFormat
+    })
+}
+
+fn feature(e: &Entity, geom: &Geometry<f64>) -> geojson::Feature {
+    geojson::Feature {
+        bbox: None,
+        geometry: Some(geojson::Geometry::new(conversion::geojson_value(geom))),
+        id: e.geojson_id(),
+        properties: Some(e.geojson_properties()),
+        foreign_members: None,
+    }
+}
+
+fn simplify(epsilons: Epsilons, coords_target: Option<usize>, min: simplify::MinPoints) -> Result<(), Error> {
     par::for_stdin_entity(move |e| {
-        let props = e.geojson_properties();
         let geom = e.geom();
-        let simplified = match coords_target {
-            None => simplify::simplify(geom, epsilon),
+        let epsilon = epsilons.for_geom(&geom);
+        let (simplified, floored) = match coords_target {
+            None => simplify::simplify_with_min_points(geom, epsilon, min),
             Some(target) => {
                 if coord_count::coord_count(&geom) <= target {
-                    geom
+                    (geom, 0)
                 } else {
                     let mut eps = epsilon;
-                    let mut simp = geom;
+                    let mut simp = (geom, 0);
                     let mut iters = 0;
-                    while coord_count::coord_count(&simp) > target  && iters < MAX_ITERS {
-                        simp = simplify::simplify(simp, eps);
+                    while coord_count::coord_count(&simp.0) > target  && iters < MAX_ITERS {
+                        simp = simplify::simplify_with_min_points(simp.0, eps, min);
                         eps = eps * 2.0;
                         iters += 1;
                     }
@@ -26,19 +67,41 @@ fn simplify(epsilon: f64, coords_target: Option<usize>) -> Result<(), Error> {
                 }
             }
         };
+        if floored > 0 {
+            eprintln!(
+                "Kept {} rings or lines at --min-points instead of simplifying them further: {}",
+                floored, e.raw()
+            );
+        }
 
-        let gj_geom = geojson::Geometry::new(geojson::Value::from(&simplified));
-        let feature = geojson::Feature {
-            bbox: None,
-            geometry: Some(gj_geom),
-            id: None,
-            properties: Some(props),
-            foreign_members: None,
-        };
-        Ok(vec![serde_json::to_string(&feature).unwrap()])
+        Ok(vec![output::geojson(feature(&e, &simplified))])
     })
 }
 
+// Read every input before simplifying, so that the
+// polygons among them can share their simplified borders
+fn simplify_shared_topology(epsilons: Epsilons) -> Result<(), Error> {
+    let mut entities = Vec::new();
+    reader::for_entity(|e| {
+        entities.push(e);
+        Ok(())
+    })?;
+    let polygon_epsilon = epsilons.polygon.unwrap_or(epsilons.default);
+    let geoms: Vec<Geometry<f64>> = entities.iter().map(|e| e.geom()).collect();
+    let simplified = simplify::simplify_shared_topology(geoms, polygon_epsilon);
+    for (e, geom) in entities.iter().zip(simplified) {
+        let geom = match geom {
+            Geometry::Polygon(_) | Geometry::MultiPolygon(_) => geom,
+            other => {
+                let epsilon = epsilons.for_geom(&other);
+                simplify::simplify_with_min_points(other, epsilon, simplify::MinPoints::new(None)).0
+            }
+        };
+        println!("{}", output::geojson(feature(e, &geom)));
+    }
+    Ok(())
+}
+
 pub fn run(matches: &ArgMatches) -> Result<(), Error> {
     let eps = match matches.value_of("epsilon") {
         Some(arg) => match f64::from_str(arg) {
@@ -70,8 +133,32 @@ pub fn run(matches: &ArgMatches) -> Result<(), Error> {
         _ => None
     };
 
+    let line = match matches.value_of("epsilon-line") {
+        Some(arg) => Some(parse_epsilon("--epsilon-line", arg)?),
+        None => None,
+    };
+    let polygon = match matches.value_of("epsilon-polygon") {
+        Some(arg) => Some(parse_epsilon("--epsilon-polygon", arg)?),
+        None => None,
+    };
+
+    let min_points = match matches.value_of("min-points") {
+        Some(arg) => match arg.parse::<usize>() {
+            Ok(min) => Some(min),
+            Err(_) => {
+                eprintln!("Invalid --min-points: {:?} - must be a positive integer", arg);
+                return Err(Error::InvalidNumberFormat);
+            }
+        },
+        None => None,
+    };
+
+    if matches.is_present("shared-topology") {
+        return eps.and_then(|eps| simplify_shared_topology(Epsilons { default: eps, line, polygon }));
+    }
+
     eps.and_then(|eps| {
-        simplify(eps, target)
+        simplify(Epsilons { default: eps, line, polygon }, target, simplify::MinPoints::new(min_points))
     })
 
 }
@@ -0,0 +1,83 @@
+use crate::geoq::{self, entity::Entity, error::Error, output, par, reader::Reader};
+use clap::ArgMatches;
+use geo_types::Geometry;
+use std::{fs::File, io::BufReader};
+
+#[derive(Clone, Copy)]
+enum Predicate {
+    Intersects,
+    Contains,
+}
+
+fn read_query_entities(path: &str) -> Result<Vec<(Entity, Geometry<f64>)>, Error> {
+    let f = File::open(path)?;
+    let mut f = BufReader::new(f);
+    let reader = Reader::new(&mut f);
+    let entities = reader.into_iter().collect::<Result<Vec<_>, _>>()?;
+    Ok(entities
+        .into_iter()
+        .map(|e| {
+            let geom = e.geom();
+            (e, geom)
+        })
+        .collect())
+}
+
+// Whether the input geometry relates to the query geometry.
+// For Contains, the query must be a (Multi)Polygon containing the input.
+fn relates(predicate: Predicate, query: &Geometry<f64>, input: &Geometry<f64>) -> bool {
+    match predicate {
+        Predicate::Intersects => geoq::intersection::intersects(query, input),
+        Predicate::Contains => match query {
+            Geometry::Polygon(poly) => geoq::contains::contains(poly, input),
+            Geometry::MultiPolygon(mp) => mp
+                .0
+                .iter()
+                .any(|poly| geoq::contains::contains(poly, input)),
+            _ => false,
+        },
+    }
+}
+
+fn joined(input: &Entity, query: &Entity, prefix: &str) -> String {
+    let mut feature = input.geojson_feature();
+    if let Some(props) = feature.properties.as_mut() {
+        for (k, v) in query.geojson_properties() {
+            props.insert(format!("{}{}", prefix, k), v);
+        }
+    }
+    output::geojson(feature)
+}
+
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let path = match matches.value_of("query-file") {
+        Some(path) => path,
+        None => return Err(Error::MissingArgument),
+    };
+    let predicate = match matches.value_of("predicate") {
+        Some("contains") => Predicate::Contains,
+        _ => Predicate::Intersects,
+    };
+    let first_only = matches.value_of("mode") == Some("first");
+    let prefix = matches.value_of("prefix").unwrap_or("").to_string();
+
+    let queries = read_query_entities(path)?;
+    par::for_stdin_entity(move |entity| {
+        let geom = entity.geom();
+        let mut matched = queries
+            .iter()
+            .filter(|(_, query_geom)| relates(predicate, query_geom, &geom));
+        let output = if first_only {
+            matched
+                .next()
+                .map(|(query, _)| joined(&entity, query, &prefix))
+                .into_iter()
+                .collect()
+        } else {
+            matched
+                .map(|(query, _)| joined(&entity, query, &prefix))
+                .collect()
+        };
+        Ok(output)
+    })
+}
@@ -0,0 +1,45 @@
+use crate::geoq::{
+    conversion,
+    error::Error,
+    output, par,
+    smooth::{self, Method},
+};
+use clap::ArgMatches;
+
+fn read_iterations(matches: &ArgMatches) -> Result<usize, Error> {
+    match matches.value_of("iterations") {
+        Some(arg) => match arg.parse::<usize>() {
+            Ok(iterations) if iterations <= smooth::MAX_ITERATIONS => Ok(iterations),
+            _ => {
+                eprintln!(
+                    "Invalid --iterations: {:?} - must be an integer up to {}, as each iteration doubles the number of vertices",
+                    arg,
+                    smooth::MAX_ITERATIONS
+                );
+                Err(Error::InvalidNumberFormat)
+            }
+        },
+        None => Err(Error::MissingArgument),
+    }
+}
+
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let iterations = read_iterations(matches)?;
+    let method = matches
+        .value_of("method")
+        .and_then(Method::from_name)
+        .ok_or(Error::MissingArgument)?;
+
+    par::for_stdin_entity(move |e| {
+        let props = e.geojson_properties();
+        let smoothed = smooth::smooth(e.geom(), method, iterations);
+        let feature = geojson::Feature {
+            bbox: None,
+            geometry: Some(geojson::Geometry::new(conversion::geojson_value(&smoothed))),
+            id: e.geojson_id(),
+            properties: Some(props),
+            foreign_members: None,
+        };
+        Ok(vec![output::geojson(feature)])
+    })
+}
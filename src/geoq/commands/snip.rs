@@ -1,4 +1,4 @@
-use crate::geoq::{browser_open, error::Error, reader::Reader};
+use crate::geoq::{browser_open, error::Error, reader::{self, Reader}};
 use geojson::GeoJson;
 use std::io;
 
@@ -7,7 +7,7 @@ const SNIP_LIMIT: usize = 10000000; // 10mb
 pub fn run() -> Result<(), Error> {
     let stdin = io::stdin();
     let mut stdin_reader = stdin.lock();
-    let reader = Reader::new(&mut stdin_reader);
+    let reader = Reader::new(&mut stdin_reader).with_limit(reader::input_limit());
 
     let mut features: Vec<geojson::Feature> = Vec::new();
 
@@ -0,0 +1,49 @@
+use crate::geoq::{centroid, entity::Entity, error::Error, hilbert, reader};
+use clap::ArgMatches;
+use geo_types::Point;
+
+enum Key {
+    Hilbert,
+    Geohash,
+}
+
+impl Key {
+    fn from_name(name: &str) -> Option<Key> {
+        match name {
+            "hilbert" => Some(Key::Hilbert),
+            "geohash" => Some(Key::Geohash),
+            _ => None,
+        }
+    }
+}
+
+// Sort keys are compared as strings so both kinds of key can share a sort.
+// Hilbert indices are zero-padded so that string order matches numeric order.
+fn sort_key(point: &Point<f64>, key: &Key) -> String {
+    match key {
+        Key::Hilbert => format!("{:020}", hilbert::index(point)),
+        Key::Geohash => geohash::encode(point.0, 12).unwrap_or_default(),
+    }
+}
+
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let key = matches
+        .value_of("by")
+        .and_then(Key::from_name)
+        .ok_or(Error::MissingArgument)?;
+
+    // Entities without a centroid (e.g. empty geometries) have no key
+    // and are sorted after all the others
+    let mut keyed: Vec<(Option<String>, Entity)> = Vec::new();
+    reader::for_input_entity(matches.value_of("input"), |e| {
+        let k = centroid::centroid(&e.geom()).map(|c| sort_key(&c, &key));
+        keyed.push((k, e));
+        Ok(())
+    })?;
+
+    keyed.sort_by(|(a, _), (b, _)| (a.is_none(), a).cmp(&(b.is_none(), b)));
+    for (_, e) in keyed {
+        println!("{}", e.raw());
+    }
+    Ok(())
+}
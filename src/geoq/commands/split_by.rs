@@ -0,0 +1,62 @@
+use crate::geoq::{entity, error::Error, input, multi, output, par, split};
+use clap::ArgMatches;
+use geo_types::{Geometry, LineString};
+
+fn read_blades(matches: &ArgMatches) -> Result<Vec<LineString<f64>>, Error> {
+    let raw = matches.value_of("blade").ok_or(Error::MissingArgument)?;
+    let blade_input = input::read_line(raw.to_string())?;
+    let mut blade_entities = entity::from_input(blade_input)?;
+    if blade_entities.len() != 1 {
+        return Err(Error::TooManyFeatures);
+    }
+    match blade_entities.remove(0).geom() {
+        Geometry::Line(l) => Ok(vec![LineString(vec![l.start, l.end])]),
+        Geometry::LineString(ls) => Ok(vec![ls]),
+        Geometry::MultiLineString(mls) => Ok(mls.0),
+        _ => {
+            eprintln!("--blade for split-by must be a LineString. Got: {}", raw);
+            Err(Error::LineStringRequired)
+        }
+    }
+}
+
+fn pieces(geom: Geometry<f64>, blades: &[LineString<f64>]) -> Vec<Geometry<f64>> {
+    match geom {
+        Geometry::Line(l) => pieces(Geometry::LineString(LineString(vec![l.start, l.end])), blades),
+        Geometry::LineString(ls) => split::split_line(&ls, blades)
+            .into_iter()
+            .map(Geometry::LineString)
+            .collect(),
+        Geometry::MultiLineString(mls) => mls
+            .0
+            .iter()
+            .flat_map(|ls| split::split_line(ls, blades))
+            .map(Geometry::LineString)
+            .collect(),
+        Geometry::Polygon(poly) => split::split_polygon(&poly, blades)
+            .into_iter()
+            .map(Geometry::Polygon)
+            .collect(),
+        Geometry::MultiPolygon(mp) => mp
+            .0
+            .iter()
+            .flat_map(|poly| split::split_polygon(poly, blades))
+            .map(Geometry::Polygon)
+            .collect(),
+        other => vec![other],
+    }
+}
+
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let blades = read_blades(matches)?;
+    par::for_input_entity(matches.value_of("input"), move |e| {
+        let geom = e.geom();
+        // Each component gives at least 1 piece, so only cut inputs give more
+        let components = multi::components(geom.clone()).len();
+        let pieces = pieces(geom, &blades);
+        if pieces.len() <= components {
+            return Ok(vec![e.raw()]);
+        }
+        Ok(pieces.iter().map(|piece| output::like_input(&e, piece)).collect())
+    })
+}
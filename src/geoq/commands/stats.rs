@@ -0,0 +1,105 @@
+use crate::geoq::{area, bbox, conversion, coord_count, error::Error, output, reader, units};
+use clap::ArgMatches;
+use geo_types::Rect;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+
+#[derive(Default)]
+struct Totals {
+    count: usize,
+    vertices: usize,
+    area: f64,
+    length: f64,
+}
+
+impl Totals {
+    fn json(&self) -> Map<String, Value> {
+        let mut m = Map::new();
+        m.insert("count".to_string(), json!(self.count));
+        m.insert("vertices".to_string(), json!(self.vertices));
+        m.insert("area".to_string(), json!(self.area));
+        m.insert("length".to_string(), json!(self.length));
+        m
+    }
+
+    fn text(&self, unit: &str) -> String {
+        format!(
+            "count: {}\tvertices: {}\tarea: {} {}^2\tlength: {} {}",
+            self.count, self.vertices, self.area, unit, self.length, unit
+        )
+    }
+}
+
+fn json_report(
+    totals: &Totals,
+    extent: &Option<Rect<f64>>,
+    types: &BTreeMap<&'static str, Totals>,
+    unit: &str,
+) -> Map<String, Value> {
+    let mut m = totals.json();
+    let bbox = extent.map(|b| [b.min.x, b.min.y, b.max.x, b.max.y]);
+    m.insert("bbox".to_string(), json!(bbox));
+    m.insert("unit".to_string(), json!(unit));
+    let by_type: Map<String, Value> = types
+        .iter()
+        .map(|(name, t)| (name.to_string(), Value::Object(t.json())))
+        .collect();
+    m.insert("types".to_string(), Value::Object(by_type));
+    m
+}
+
+fn text_report(
+    totals: &Totals,
+    extent: &Option<Rect<f64>>,
+    types: &BTreeMap<&'static str, Totals>,
+    unit: &str,
+) -> Vec<String> {
+    let bbox = match extent {
+        Some(b) => format!("{},{},{},{}", b.min.x, b.min.y, b.max.x, b.max.y),
+        None => "empty".to_string(),
+    };
+    let mut lines = vec![format!("total\t{}\tbbox: {}", totals.text(unit), bbox)];
+    for (name, t) in types {
+        lines.push(format!("{}\t{}", name, t.text(unit)));
+    }
+    lines
+}
+
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let unit = units::default_distance_unit();
+    let meters_per_unit = units::meters_per(unit).ok_or(Error::InvalidNumberFormat)?;
+
+    let mut totals = Totals::default();
+    let mut extent: Option<Rect<f64>> = None;
+    let mut types: BTreeMap<&'static str, Totals> = BTreeMap::new();
+    reader::for_input_entity(matches.value_of("input"), |e| {
+        let geom = e.geom();
+        let vertices = coord_count::coord_count(&geom);
+        let area = area::area(&geom) / (meters_per_unit * meters_per_unit);
+        let length = area::length(&geom) / meters_per_unit;
+        let by_type = types.entry(conversion::geometry_type(&geom)).or_default();
+        for t in [&mut totals, by_type] {
+            t.count += 1;
+            t.vertices += vertices;
+            t.area += area;
+            t.length += length;
+        }
+        if !coord_count::is_empty(&geom) {
+            extent = Some(match extent {
+                Some(curr) => bbox::merge(&curr, &e.bbox()),
+                None => e.bbox(),
+            });
+        }
+        Ok(())
+    })?;
+
+    if matches.is_present("json") {
+        let report = json_report(&totals, &extent, &types, unit);
+        println!("{}", output::json(report));
+    } else {
+        for line in text_report(&totals, &extent, &types, unit) {
+            println!("{}", line);
+        }
+    }
+    Ok(())
+}
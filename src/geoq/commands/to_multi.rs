@@ -0,0 +1,8 @@
+use crate::geoq::{error::Error, multi, output, par};
+
+pub fn run() -> Result<(), Error> {
+    par::for_stdin_entity(|e| {
+        let geom = multi::to_multi(e.geom());
+        Ok(vec![output::like_input(&e, &geom)])
+    })
+}
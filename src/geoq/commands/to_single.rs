@@ -0,0 +1,14 @@
+use crate::geoq::{error::Error, multi, output, par};
+
+pub fn run() -> Result<(), Error> {
+    par::for_stdin_entity(|e| match multi::to_single(e.geom()) {
+        Ok(geom) => Ok(vec![output::like_input(&e, &geom)]),
+        Err(geom) => {
+            eprintln!(
+                "Warning: can't convert a Multi- geometry without exactly 1 element to a single geometry, so it was left as-is: {}",
+                e.raw()
+            );
+            Ok(vec![output::like_input(&e, &geom)])
+        }
+    })
+}
@@ -0,0 +1,34 @@
+use crate::geoq::{self, error::Error, output, reader};
+use clap::ArgMatches;
+
+fn coords(fix: bool) -> Result<(), Error> {
+    let mut index = 0;
+    reader::for_entity(|e| {
+        index += 1;
+        let geom = e.geom();
+        if geoq::flip::in_lonlat_range(&geom) {
+            println!("{}", e.raw());
+            return Ok(());
+        }
+
+        let flipped = geoq::flip::flip(&geom);
+        if fix && geoq::flip::in_lonlat_range(&flipped) {
+            let fixed = output::like_input(&e, &flipped);
+            eprintln!("Input {}: swapped Lat/Lon: {} -> {}", index, e.raw(), fixed);
+            println!("{}", fixed);
+        } else {
+            eprintln!("Input {}: coordinates out of Lon/Lat range: {}", index, e.raw());
+            println!("{}", e.raw());
+        }
+        Ok(())
+    })
+}
+
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    if matches.is_present("coords") {
+        coords(matches.is_present("fix"))
+    } else {
+        eprintln!("No validations given. Use --coords to check coordinate ranges.");
+        Err(Error::MissingArgument)
+    }
+}
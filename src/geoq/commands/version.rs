@@ -0,0 +1,28 @@
+use crate::geoq::error::Error;
+use clap::ArgMatches;
+use serde_json::json;
+
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+const TARGET: &str = env!("GEOQ_BUILD_TARGET");
+const GIT_COMMIT: Option<&str> = option_env!("GEOQ_GIT_COMMIT");
+const FEATURES: &str = env!("GEOQ_FEATURES");
+
+// Optional cargo features this binary was built with
+fn features() -> Vec<&'static str> {
+    FEATURES.split(',').filter(|f| !f.is_empty()).collect()
+}
+
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    if matches.is_present("json") {
+        let info = json!({
+            "version": VERSION,
+            "target": TARGET,
+            "git_commit": GIT_COMMIT,
+            "features": features(),
+        });
+        println!("{}", info);
+    } else {
+        println!("geoq {}", VERSION);
+    }
+    Ok(())
+}
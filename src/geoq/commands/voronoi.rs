@@ -0,0 +1,110 @@
+use crate::geoq::{
+    bbox::{self, BBoxToPoly},
+    clip,
+    entity::{self, Entity},
+    error::Error,
+    input, output, reader, voronoi,
+};
+use clap::ArgMatches;
+use geo_types::{Coordinate, Geometry, Point, Polygon, Rect};
+use serde_json::json;
+
+fn read_clip_polygon(matches: &ArgMatches) -> Result<Option<Polygon<f64>>, Error> {
+    match matches.value_of("clip") {
+        None => Ok(None),
+        Some(q) => {
+            let query_input = input::read_line(q.to_string())?;
+            let mut query_entities = entity::from_input(query_input)?;
+            if query_entities.len() != 1 {
+                return Err(Error::TooManyFeatures);
+            }
+            match query_entities.remove(0).geom() {
+                Geometry::Polygon(poly) => Ok(Some(poly)),
+                _ => {
+                    eprintln!("--clip boundary for voronoi must be a Polygon. Got: {}", q);
+                    Err(Error::PolygonRequired)
+                }
+            }
+        }
+    }
+}
+
+fn clip_cell(cell: &Polygon<f64>, boundary: &Polygon<f64>) -> Option<Polygon<f64>> {
+    clip::clip_ring(boundary.exterior(), cell.exterior()).map(|exterior| {
+        let interiors = boundary
+            .interiors()
+            .iter()
+            .filter_map(|ring| clip::clip_ring(ring, cell.exterior()))
+            .collect();
+        Polygon::new(exterior, interiors)
+    })
+}
+
+// Collinear (or identical) points have a bbox with no area, which would
+// collapse every cell, so pad its flat sides by half of its longer side,
+// or by 1 degree if it's a single point
+fn pad_degenerate(bounds: &Rect<f64>) -> Rect<f64> {
+    let (width, height) = (bounds.max.x - bounds.min.x, bounds.max.y - bounds.min.y);
+    let pad = if width.max(height) > 0.0 { width.max(height) / 2.0 } else { 1.0 };
+    let pad_x = if width > 0.0 { 0.0 } else { pad };
+    let pad_y = if height > 0.0 { 0.0 } else { pad };
+    Rect {
+        min: Coordinate { x: bounds.min.x - pad_x, y: bounds.min.y - pad_y },
+        max: Coordinate { x: bounds.max.x + pad_x, y: bounds.max.y + pad_y },
+    }
+}
+
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let boundary = read_clip_polygon(matches)?;
+
+    let mut entities: Vec<Entity> = Vec::new();
+    let mut points: Vec<Point<f64>> = Vec::new();
+    reader::for_entity(|e| match e.geom() {
+        Geometry::Point(p) => {
+            points.push(p);
+            entities.push(e);
+            Ok(())
+        }
+        _ => {
+            eprintln!("Voronoi diagrams require Point inputs. Got: {}", e.raw());
+            Err(Error::PointRequired)
+        }
+    })?;
+
+    if entities.is_empty() {
+        return Err(Error::NoInputGiven);
+    }
+
+    let mut bounds = entities
+        .iter()
+        .map(|e| e.bbox())
+        .fold(entities[0].bbox(), |a, b| bbox::merge(&a, &b));
+    if let Some(ref b) = boundary {
+        bounds = bbox::merge(&bounds, &bbox::bbox(&Geometry::Polygon(b.clone())));
+    }
+    let bounds_poly = pad_degenerate(&bounds).to_polygon();
+
+    let cells = voronoi::cells(&points, bounds_poly.exterior());
+    for (index, (e, cell)) in entities.into_iter().zip(cells).enumerate() {
+        let cell = match (cell, &boundary) {
+            (Some(cell), Some(b)) => clip_cell(&cell, b),
+            (cell, None) => cell,
+            (None, _) => None,
+        };
+        if let Some(cell) = cell {
+            let mut props = e.geojson_properties();
+            props.insert("index".to_string(), json!(index));
+            let feature = geojson::Feature {
+                bbox: None,
+                geometry: Some(geojson::Geometry::new(geojson::Value::from(
+                    &Geometry::Polygon(cell),
+                ))),
+                id: None,
+                properties: Some(props),
+                foreign_members: None,
+            };
+            println!("{}", output::geojson(feature));
+        }
+    }
+    Ok(())
+}
@@ -1,9 +1,30 @@
-use crate::geoq::error::Error;
+use crate::geoq::{error::Error, output};
+use clap::ArgMatches;
 use geo_types::Geometry;
 use geo_types::Point;
-use serde_json;
+use serde_json::{self, json};
 
-fn get_ip_geolocation() -> Result<(), Error> {
+// IP geolocation is only accurate to around the city level, so with --ip
+// the result is given as a Feature which says so
+fn ip_feature(point: &Geometry<f64>, response: &serde_json::Value) -> geojson::Feature {
+    let mut props = serde_json::Map::new();
+    props.insert("accuracy".to_string(), json!("low"));
+    props.insert("source".to_string(), json!("ip"));
+    for (key, name) in &[("city", "city"), ("regionName", "region"), ("country", "country")] {
+        if let Some(value) = response[*key].as_str() {
+            props.insert(name.to_string(), json!(value));
+        }
+    }
+    geojson::Feature {
+        bbox: None,
+        geometry: Some(geojson::Geometry::new(geojson::Value::from(point))),
+        id: None,
+        properties: Some(props),
+        foreign_members: None,
+    }
+}
+
+fn get_ip_geolocation(as_feature: bool) -> Result<(), Error> {
     let resp = reqwest::get("http://ip-api.com/json");
     if let Err(e) = resp {
         eprintln!("Error fetching IP geolocation: {:?}", e);
@@ -31,15 +52,50 @@ fn get_ip_geolocation() -> Result<(), Error> {
     match (lat, lon) {
         (Some(lat), Some(lon)) => {
             let point = Geometry::Point(Point::new(lon, lat));
-            let gj_point = geojson::Geometry::new(geojson::Value::from(&point));
-            let geojson = serde_json::to_string(&gj_point).unwrap();
-            println!("{}", geojson);
+            if as_feature {
+                println!("{}", output::geojson(ip_feature(&point, &json)));
+            } else {
+                let gj_point = geojson::Geometry::new(geojson::Value::from(&point));
+                println!("{}", output::geojson(gj_point));
+            }
         }
         _ => eprintln!("Invalid IP location response: {}", json),
     }
     Ok(())
 }
 
-pub fn run() -> Result<(), Error> {
-    get_ip_geolocation()
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    // IP geolocation is currently the only location source, so --ip just
+    // makes the choice (and the result's accuracy) explicit
+    get_ip_geolocation(matches.is_present("ip"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::commands::whereami::ip_feature;
+    use geo_types::{Geometry, Point};
+    use serde_json::json;
+
+    #[test]
+    fn test_ip_feature() {
+        let response = json!({
+            "status": "success",
+            "city": "Los Angeles",
+            "regionName": "California",
+            "country": "United States",
+            "lat": 34.05,
+            "lon": -118.25,
+            "zip": "90012"
+        });
+        let feature = ip_feature(&Geometry::Point(Point::new(-118.25, 34.05)), &response);
+        assert_eq!(
+            r#"{"geometry":{"coordinates":[-118.25,34.05],"type":"Point"},"properties":{"accuracy":"low","city":"Los Angeles","country":"United States","region":"California","source":"ip"},"type":"Feature"}"#,
+            serde_json::to_string(&feature).unwrap()
+        );
+
+        // Missing location names are left out
+        let feature = ip_feature(&Geometry::Point(Point::new(0.0, 0.0)), &json!({"lat": 0.0, "lon": 0.0}));
+        let props = feature.properties.unwrap();
+        assert_eq!(vec!["accuracy", "source"], props.keys().collect::<Vec<_>>());
+    }
 }
@@ -1,5 +1,61 @@
 use crate::geoq::{error::Error, par};
+use clap::ArgMatches;
 
-pub fn run() -> Result<(), Error> {
-    par::for_stdin_entity(|e| Ok(vec![format!("{}", e.wkt())]))
+const TYPES: [&str; 7] = [
+    "POINT",
+    "LINESTRING",
+    "POLYGON",
+    "MULTIPOINT",
+    "MULTILINESTRING",
+    "MULTIPOLYGON",
+    "GEOMETRYCOLLECTION",
+];
+
+fn wkt_type(wkt: &wkt::Geometry) -> &'static str {
+    match wkt {
+        wkt::Geometry::Point(_) => TYPES[0],
+        wkt::Geometry::LineString(_) => TYPES[1],
+        wkt::Geometry::Polygon(_) => TYPES[2],
+        wkt::Geometry::MultiPoint(_) => TYPES[3],
+        wkt::Geometry::MultiLineString(_) => TYPES[4],
+        wkt::Geometry::MultiPolygon(_) => TYPES[5],
+        wkt::Geometry::GeometryCollection(_) => TYPES[6],
+    }
+}
+
+fn read_only_types(matches: &ArgMatches) -> Result<Option<Vec<&'static str>>, Error> {
+    match matches.value_of("only") {
+        None => Ok(None),
+        Some(arg) => arg
+            .split(',')
+            .map(|name| {
+                let name = name.trim().to_uppercase();
+                TYPES.iter().find(|t| **t == name).cloned().ok_or_else(|| {
+                    eprintln!(
+                        "Invalid --only geometry type: {:?} - must be one of {}",
+                        name,
+                        TYPES.join(", ")
+                    );
+                    Error::UnknownGeometryType
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(Some),
+    }
+}
+
+pub fn run(matches: &ArgMatches) -> Result<(), Error> {
+    let only = read_only_types(matches)?;
+    let with_type = matches.is_present("with-type");
+    par::for_stdin_entity(move |e| {
+        let wkt = e.wkt();
+        let geom_type = wkt_type(&wkt);
+        if only.as_ref().is_some_and(|types| !types.contains(&geom_type)) {
+            Ok(vec![])
+        } else if with_type {
+            Ok(vec![format!("{}\t{}", geom_type, wkt)])
+        } else {
+            Ok(vec![format!("{}", wkt)])
+        }
+    })
 }
@@ -13,3 +13,28 @@ pub fn contains(outer: &Polygon<f64>, inner: &Geometry<f64>) -> bool {
         Geometry::GeometryCollection(ref gc) => gc.0.iter().all(|geom| contains(outer, geom)),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::contains::contains;
+    use geo_types::{Geometry, LineString, Point, Polygon};
+
+    #[test]
+    fn test_contains_respects_holes() {
+        let exterior: LineString<f64> =
+            vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0), (0.0, 0.0)].into();
+        let hole: LineString<f64> =
+            vec![(2.0, 2.0), (2.0, 8.0), (8.0, 8.0), (8.0, 2.0), (2.0, 2.0)].into();
+        let donut = Polygon::new(exterior, vec![hole]);
+        let point = |x: f64, y: f64| Geometry::Point(Point::new(x, y));
+
+        assert!(contains(&donut, &point(1.0, 1.0)));
+        assert!(!contains(&donut, &point(5.0, 5.0)));
+        // Boundary points aren't in the interior, so they aren't contained
+        assert!(!contains(&donut, &point(2.0, 5.0)));
+        assert!(!contains(&donut, &point(8.0, 8.0)));
+        // A line crossing the hole
+        let line = Geometry::LineString(vec![(1.0, 1.0), (9.0, 9.0)].into());
+        assert!(!contains(&donut, &line));
+    }
+}
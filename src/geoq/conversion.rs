@@ -0,0 +1,66 @@
+use geo_types::{Geometry, GeometryCollection};
+use std::convert::TryInto;
+
+// geojson's own conversions between geo-types Geometries and GeoJSON
+// Values panic on GeometryCollections (and Lines), so we handle those
+// here, recursing into nested collections.
+
+pub fn geojson_value(geom: &Geometry<f64>) -> geojson::Value {
+    match geom {
+        Geometry::GeometryCollection(gc) => geojson::Value::GeometryCollection(
+            gc.0.iter()
+                .map(|g| geojson::Geometry::new(geojson_value(g)))
+                .collect(),
+        ),
+        Geometry::Line(line) => geojson::Value::from(&geo_types::LineString(vec![
+            line.start,
+            line.end,
+        ])),
+        _ => geojson::Value::from(geom),
+    }
+}
+
+pub fn geometry(value: geojson::Value) -> Result<Geometry<f64>, geojson::Error> {
+    match value {
+        geojson::Value::GeometryCollection(geoms) => {
+            let members = geoms
+                .into_iter()
+                .map(|g| geometry(g.value))
+                .collect::<Result<Vec<Geometry<f64>>, geojson::Error>>()?;
+            Ok(Geometry::GeometryCollection(GeometryCollection(members)))
+        }
+        _ => value.try_into(),
+    }
+}
+
+// GeoJSON-style name of the geometry's type, e.g. "MultiPolygon"
+pub fn geometry_type(geom: &Geometry<f64>) -> &'static str {
+    match geom {
+        Geometry::Point(_) => "Point",
+        Geometry::Line(_) => "Line",
+        Geometry::LineString(_) => "LineString",
+        Geometry::Polygon(_) => "Polygon",
+        Geometry::MultiPoint(_) => "MultiPoint",
+        Geometry::MultiLineString(_) => "MultiLineString",
+        Geometry::MultiPolygon(_) => "MultiPolygon",
+        Geometry::GeometryCollection(_) => "GeometryCollection",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::conversion::{geojson_value, geometry};
+    use geo_types::{Geometry, GeometryCollection, LineString, Point};
+
+    #[test]
+    fn test_round_tripping_nested_collections() {
+        let inner = GeometryCollection(vec![Geometry::Point(Point::new(3.0, 4.0))]);
+        let geom = Geometry::GeometryCollection(GeometryCollection(vec![
+            Geometry::Point(Point::new(1.0, 2.0)),
+            Geometry::LineString(LineString::from(vec![(0.0, 0.0), (1.0, 1.0)])),
+            Geometry::GeometryCollection(inner),
+            Geometry::GeometryCollection(GeometryCollection(vec![])),
+        ]));
+        assert_eq!(geom, geometry(geojson_value(&geom)).unwrap());
+    }
+}
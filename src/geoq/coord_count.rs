@@ -1,4 +1,4 @@
-use geo_types::{Geometry, Polygon};
+use geo_types::{Geometry, LineString, Polygon};
 
 fn poly_coord_count(poly: &Polygon<f64>) -> usize {
     poly.exterior().num_coords() + poly.interiors().iter().map(|ring| ring.num_coords()).sum::<usize>()
@@ -16,3 +16,69 @@ pub fn coord_count(geom: &Geometry<f64>) -> usize {
         Geometry::GeometryCollection(ref gc) =>  gc.0.iter().map(|g| coord_count(g)).sum(),
     }
 }
+
+// Empty geometries (e.g. WKT "POLYGON EMPTY", or GeoJSON Features
+// with a null geometry) have no coordinates at all
+pub fn is_empty(geom: &Geometry<f64>) -> bool {
+    coord_count(geom) == 0
+}
+
+fn line_is_finite(line: &LineString<f64>) -> bool {
+    line.0.iter().all(|c| c.x.is_finite() && c.y.is_finite())
+}
+
+fn poly_is_finite(poly: &Polygon<f64>) -> bool {
+    line_is_finite(poly.exterior()) && poly.interiors().iter().all(line_is_finite)
+}
+
+// Whether none of the geometry's coordinates are NaN or infinite,
+// which can't be written as (GeoJSON) numbers
+pub fn is_finite(geom: &Geometry<f64>) -> bool {
+    match *geom {
+        Geometry::Point(ref p) => p.x().is_finite() && p.y().is_finite(),
+        Geometry::Line(ref l) => line_is_finite(&LineString(vec![l.start, l.end])),
+        Geometry::LineString(ref g) => line_is_finite(g),
+        Geometry::Polygon(ref g) => poly_is_finite(g),
+        Geometry::MultiPoint(ref g) => g.0.iter().all(|p| p.x().is_finite() && p.y().is_finite()),
+        Geometry::MultiLineString(ref g) => g.0.iter().all(line_is_finite),
+        Geometry::MultiPolygon(ref g) => g.0.iter().all(poly_is_finite),
+        Geometry::GeometryCollection(ref gc) => gc.0.iter().all(is_finite),
+    }
+}
+
+// Coordinate counts of each ring of each Polygon in a geometry:
+// the exterior ring first, followed by any interior rings.
+// Non-Polygon geometries have no rings.
+pub fn ring_coord_counts(geom: &Geometry<f64>) -> Vec<Vec<usize>> {
+    match *geom {
+        Geometry::Polygon(ref g) => vec![poly_ring_counts(g)],
+        Geometry::MultiPolygon(ref g) => g.0.iter().map(poly_ring_counts).collect(),
+        Geometry::GeometryCollection(ref gc) => gc.0.iter().flat_map(ring_coord_counts).collect(),
+        _ => vec![],
+    }
+}
+
+fn poly_ring_counts(poly: &Polygon<f64>) -> Vec<usize> {
+    std::iter::once(poly.exterior())
+        .chain(poly.interiors().iter())
+        .map(|ring| ring.num_coords())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::coord_count::is_finite;
+    use geo_types::{Geometry, GeometryCollection, LineString, Point, Polygon};
+
+    #[test]
+    fn test_is_finite() {
+        let square: LineString<f64> = vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 0.0)].into();
+        assert!(is_finite(&Geometry::Polygon(Polygon::new(square.clone(), vec![]))));
+        assert!(is_finite(&Geometry::GeometryCollection(GeometryCollection(vec![]))));
+        assert!(!is_finite(&Geometry::Point(Point::new(f64::NAN, 0.0))));
+        let hole: LineString<f64> = vec![(0.0, 0.0), (f64::INFINITY, 0.0), (0.0, 0.0)].into();
+        assert!(!is_finite(&Geometry::Polygon(Polygon::new(square, vec![hole]))));
+        let gc = GeometryCollection(vec![Geometry::Point(Point::new(0.0, f64::NEG_INFINITY))]);
+        assert!(!is_finite(&Geometry::GeometryCollection(gc)));
+    }
+}
@@ -0,0 +1,163 @@
+use geo::algorithm::contains::Contains;
+use geo_types::{Coordinate, Geometry, LineString, Point, Polygon};
+
+type Coord = Coordinate<f64>;
+
+// Coordinates within this distance (in degrees) of a Polygon's boundary
+// are treated as on it, to allow for rounding when splitting segments
+const BOUNDARY_EPSILON: f64 = 1e-9;
+
+fn sub(a: Coord, b: Coord) -> Coord {
+    Coordinate { x: a.x - b.x, y: a.y - b.y }
+}
+
+fn cross(a: Coord, b: Coord) -> f64 {
+    a.x * b.y - a.y * b.x
+}
+
+fn dot(a: Coord, b: Coord) -> f64 {
+    a.x * b.x + a.y * b.y
+}
+
+fn lerp(a: Coord, b: Coord, t: f64) -> Coord {
+    Coordinate { x: a.x + t * (b.x - a.x), y: a.y + t * (b.y - a.y) }
+}
+
+fn rings(poly: &Polygon<f64>) -> impl Iterator<Item = &LineString<f64>> {
+    std::iter::once(poly.exterior()).chain(poly.interiors().iter())
+}
+
+fn on_segment(c: Coord, p: Coord, q: Coord) -> bool {
+    let pq = sub(q, p);
+    let len2 = dot(pq, pq);
+    let t = if len2 == 0.0 { 0.0 } else { (dot(sub(c, p), pq) / len2).clamp(0.0, 1.0) };
+    let d = sub(c, lerp(p, q, t));
+    dot(d, d).sqrt() <= BOUNDARY_EPSILON
+}
+
+fn on_boundary(poly: &Polygon<f64>, c: Coord) -> bool {
+    rings(poly).any(|ring| ring.0.windows(2).any(|pair| on_segment(c, pair[0], pair[1])))
+}
+
+fn covers_coord(poly: &Polygon<f64>, c: Coord) -> bool {
+    on_boundary(poly, c) || poly.contains(&Point(c))
+}
+
+// Parameters (from 0 at a to 1 at b) where segment ab meets segment pq
+fn crossings(a: Coord, b: Coord, p: Coord, q: Coord, output: &mut Vec<f64>) {
+    let ab = sub(b, a);
+    let pq = sub(q, p);
+    let denom = cross(ab, pq);
+    if denom != 0.0 {
+        let t = cross(sub(p, a), pq) / denom;
+        let u = cross(sub(p, a), ab) / denom;
+        if (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u) {
+            output.push(t);
+        }
+    } else if cross(sub(p, a), ab) == 0.0 {
+        // Collinear: the overlap starts and ends at the ends of pq
+        let len2 = dot(ab, ab);
+        for c in &[p, q] {
+            let t = dot(sub(*c, a), ab) / len2;
+            if (0.0..=1.0).contains(&t) {
+                output.push(t);
+            }
+        }
+    }
+}
+
+// A segment is covered if each piece of it between crossings
+// of the Polygon's boundary is covered
+fn covers_segment(poly: &Polygon<f64>, a: Coord, b: Coord) -> bool {
+    if a == b {
+        return covers_coord(poly, a);
+    }
+    let mut ts = vec![0.0, 1.0];
+    for ring in rings(poly) {
+        for pair in ring.0.windows(2) {
+            crossings(a, b, pair[0], pair[1], &mut ts);
+        }
+    }
+    ts.sort_by(|x, y| x.partial_cmp(y).unwrap());
+    ts.dedup();
+    covers_coord(poly, a)
+        && covers_coord(poly, b)
+        && ts.windows(2).all(|w| covers_coord(poly, lerp(a, b, (w[0] + w[1]) / 2.0)))
+}
+
+fn covers_line(poly: &Polygon<f64>, line: &LineString<f64>) -> bool {
+    match line.0.len() {
+        0 => true,
+        1 => covers_coord(poly, line.0[0]),
+        _ => line.0.windows(2).all(|pair| covers_segment(poly, pair[0], pair[1])),
+    }
+}
+
+fn covers_polygon(outer: &Polygon<f64>, inner: &Polygon<f64>) -> bool {
+    // The inner Polygon's boundary must be covered, and none of the
+    // outer Polygon's holes can fall inside of it
+    covers_line(outer, inner.exterior())
+        && outer.interiors().iter().all(|hole| {
+            hole.0
+                .iter()
+                .all(|&c| on_boundary(inner, c) || !inner.contains(&Point(c)))
+        })
+}
+
+// Whether every point of `inner` falls inside of or on the boundary of `outer`.
+// Unlike contains, geometries which touch the boundary from inside are covered.
+pub fn covers(outer: &Polygon<f64>, inner: &Geometry<f64>) -> bool {
+    match *inner {
+        Geometry::Point(ref g) => covers_coord(outer, g.0),
+        Geometry::Line(ref g) => covers_segment(outer, g.start, g.end),
+        Geometry::LineString(ref g) => covers_line(outer, g),
+        Geometry::Polygon(ref g) => covers_polygon(outer, g),
+        Geometry::MultiPoint(ref mp) => mp.0.iter().all(|p| covers_coord(outer, p.0)),
+        Geometry::MultiLineString(ref mls) => mls.0.iter().all(|ls| covers_line(outer, ls)),
+        Geometry::MultiPolygon(ref mp) => mp.0.iter().all(|poly| covers_polygon(outer, poly)),
+        Geometry::GeometryCollection(ref gc) => gc.0.iter().all(|geom| covers(outer, geom)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::covers::covers;
+    use geo_types::{Geometry, LineString, Point, Polygon};
+
+    fn square() -> Polygon<f64> {
+        Polygon::new(
+            vec![(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0), (0.0, 0.0)].into(),
+            vec![],
+        )
+    }
+
+    #[test]
+    fn test_covering_boundary_points() {
+        assert!(covers(&square(), &Geometry::Point(Point::new(0.0, 1.0))));
+        assert!(covers(&square(), &Geometry::Point(Point::new(1.0, 1.0))));
+        assert!(!covers(&square(), &Geometry::Point(Point::new(3.0, 1.0))));
+    }
+
+    #[test]
+    fn test_covering_lines() {
+        let along_edge: LineString<f64> = vec![(0.0, 0.0), (2.0, 0.0)].into();
+        let crossing: LineString<f64> = vec![(1.0, 1.0), (3.0, 1.0)].into();
+        assert!(covers(&square(), &Geometry::LineString(along_edge)));
+        assert!(!covers(&square(), &Geometry::LineString(crossing)));
+    }
+
+    #[test]
+    fn test_covering_polygons() {
+        let concave = Polygon::new(
+            vec![(0.0, 0.0), (2.0, 0.0), (1.0, 1.0), (2.0, 2.0), (0.0, 2.0), (0.0, 0.0)].into(),
+            vec![],
+        );
+        let shared_edge = Polygon::new(
+            vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0), (0.0, 0.0)].into(),
+            vec![],
+        );
+        assert!(covers(&square(), &Geometry::Polygon(square())));
+        assert!(covers(&square(), &Geometry::Polygon(shared_edge)));
+        assert!(!covers(&concave, &Geometry::Polygon(square())));
+    }
+}
@@ -0,0 +1,75 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::{json, Map, Value};
+
+static EPSG_CODE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)^epsg:(\d+)$").unwrap());
+static WGS84_NAME: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^(epsg:4326|urn:ogc:def:crs:epsg::4326|urn:ogc:def:crs:ogc:1\.3:crs84)$").unwrap()
+});
+
+// Name of a legacy GeoJSON (2008) "crs" member, e.g.
+// {"type": "name", "properties": {"name": "urn:ogc:def:crs:EPSG::3857"}}
+fn crs_name(crs: &Value) -> Option<&str> {
+    crs.get("properties")?.get("name")?.as_str()
+}
+
+// True unless the given foreign members include a "crs" member
+// naming something other than WGS84 Lon/Lat.
+// RFC 7946 removed "crs", and all coordinates are assumed to be WGS84.
+pub fn is_wgs84(foreign_members: &Option<Map<String, Value>>) -> bool {
+    match foreign_members.as_ref().and_then(|m| m.get("crs")) {
+        None | Some(Value::Null) => true,
+        Some(crs) => crs_name(crs).is_some_and(|name| WGS84_NAME.is_match(name)),
+    }
+}
+
+pub fn warn_unless_wgs84(foreign_members: &Option<Map<String, Value>>) {
+    if !is_wgs84(foreign_members) {
+        eprintln!(
+            "Warning: GeoJSON input has a non-WGS84 'crs' member: {}. geoq treats all coordinates as WGS84 Lon/Lat, so results may be wrong.",
+            foreign_members.as_ref().and_then(|m| m.get("crs")).unwrap()
+        );
+    }
+}
+
+// Legacy "crs" member for an EPSG code like "EPSG:3857"
+pub fn legacy_crs_member(code: &str) -> Option<Value> {
+    EPSG_CODE.captures(code).map(|caps| {
+        json!({
+            "type": "name",
+            "properties": {"name": format!("urn:ogc:def:crs:EPSG::{}", &caps[1])}
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::crs::{is_wgs84, legacy_crs_member};
+    use serde_json::{json, Map};
+
+    fn members(crs: serde_json::Value) -> Option<Map<String, serde_json::Value>> {
+        let mut m = Map::new();
+        m.insert("crs".to_string(), crs);
+        Some(m)
+    }
+
+    #[test]
+    fn test_checking_crs_members() {
+        assert!(is_wgs84(&None));
+        assert!(is_wgs84(&members(legacy_crs_member("EPSG:4326").unwrap())));
+        assert!(is_wgs84(&members(
+            json!({"type": "name", "properties": {"name": "urn:ogc:def:crs:OGC:1.3:CRS84"}})
+        )));
+        assert!(!is_wgs84(&members(legacy_crs_member("EPSG:3857").unwrap())));
+        assert!(!is_wgs84(&members(json!({"type": "link"}))));
+    }
+
+    #[test]
+    fn test_legacy_crs_member() {
+        assert_eq!(
+            Some(json!({"type": "name", "properties": {"name": "urn:ogc:def:crs:EPSG::3857"}})),
+            legacy_crs_member("epsg:3857")
+        );
+        assert_eq!(None, legacy_crs_member("3857"));
+    }
+}
@@ -0,0 +1,277 @@
+use geo_types::{Coordinate, LineString, Point, Polygon};
+
+type Coord = Coordinate<f64>;
+
+pub struct Triangle {
+    pub a: Coord,
+    pub b: Coord,
+    pub c: Coord,
+}
+
+impl Triangle {
+    // Planar area in square degrees, for Lon/Lat coordinates
+    pub fn area(&self) -> f64 {
+        ((self.b.x - self.a.x) * (self.c.y - self.a.y)
+            - (self.c.x - self.a.x) * (self.b.y - self.a.y))
+            .abs()
+            / 2.0
+    }
+
+    // Planar circumcircle radius in degrees, for Lon/Lat coordinates
+    pub fn circumradius(&self) -> f64 {
+        circumcircle(self.a, self.b, self.c).1.sqrt()
+    }
+
+    pub fn to_polygon(&self) -> Polygon<f64> {
+        Polygon::new(LineString(vec![self.a, self.b, self.c, self.a]), vec![])
+    }
+}
+
+// Center and squared radius of the circle passing through 3 points
+fn circumcircle(a: Coord, b: Coord, c: Coord) -> (Coord, f64) {
+    let d = 2.0 * (a.x * (b.y - c.y) + b.x * (c.y - a.y) + c.x * (a.y - b.y));
+    let a2 = a.x * a.x + a.y * a.y;
+    let b2 = b.x * b.x + b.y * b.y;
+    let c2 = c.x * c.x + c.y * c.y;
+    let center = Coordinate {
+        x: (a2 * (b.y - c.y) + b2 * (c.y - a.y) + c2 * (a.y - b.y)) / d,
+        y: (a2 * (c.x - b.x) + b2 * (a.x - c.x) + c2 * (b.x - a.x)) / d,
+    };
+    let dx = a.x - center.x;
+    let dy = a.y - center.y;
+    (center, dx * dx + dy * dy)
+}
+
+fn ccw(coords: &[Coord], [i, j, k]: [usize; 3]) -> [usize; 3] {
+    let (a, b, c) = (coords[i], coords[j], coords[k]);
+    if (b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y) < 0.0 {
+        [i, k, j]
+    } else {
+        [i, j, k]
+    }
+}
+
+fn sub(a: Coord, b: Coord) -> Coord {
+    Coordinate { x: a.x - b.x, y: a.y - b.y }
+}
+
+fn dot(a: Coord, b: Coord) -> f64 {
+    a.x * b.x + a.y * b.y
+}
+
+fn cross(a: Coord, b: Coord) -> f64 {
+    a.x * b.y - a.y * b.x
+}
+
+// Directions of the super-triangle's vertices from the middle of the points
+const SUPER_DIRECTIONS: [Coord; 3] = [
+    Coordinate { x: -1.0, y: -1.0 },
+    Coordinate { x: 1.0, y: -1.0 },
+    Coordinate { x: 0.0, y: 1.0 },
+];
+
+// Whether p falls in the circumcircle of the triangle, treating the
+// super-triangle's vertices as infinitely far away in their directions.
+// No finite super-triangle is big enough for every input: nearly collinear
+// points have huge circumcircles which would take in its vertices and lose
+// the triangles along the hull.
+fn in_circumcircle(
+    coords: &[Coord],
+    num_points: usize,
+    mid: Coord,
+    (tri, center, r2): &([usize; 3], Coord, f64),
+    p: Coord,
+) -> bool {
+    let real: Vec<Coord> = tri
+        .iter()
+        .filter(|&&i| i < num_points)
+        .map(|&i| coords[i])
+        .collect();
+    let dirs: Vec<Coord> = tri
+        .iter()
+        .filter(|&&i| i >= num_points)
+        .map(|&i| SUPER_DIRECTIONS[i - num_points])
+        .collect();
+    match real.as_slice() {
+        [_, _, _] => {
+            let dx = p.x - center.x;
+            let dy = p.y - center.y;
+            dx * dx + dy * dy <= *r2
+        }
+        &[a, b] => {
+            // The circle becomes the half-plane on the far vertex's side of
+            // ab, plus the open segment ab itself
+            let ab = sub(b, a);
+            let mut side = cross(ab, dirs[0]);
+            if side == 0.0 {
+                side = cross(ab, sub(mid, a));
+            }
+            let turn = cross(ab, sub(p, a));
+            if turn == 0.0 {
+                dot(sub(p, a), ab) > 0.0 && dot(sub(p, b), ab) < 0.0
+            } else {
+                turn * side > 0.0
+            }
+        }
+        &[a] => {
+            // The center runs off towards c, the circumcenter of the two
+            // directions, leaving the half-plane through a facing c
+            let origin = Coordinate { x: 0.0, y: 0.0 };
+            let (c, _) = circumcircle(origin, dirs[0], dirs[1]);
+            let toward = dot(c, sub(p, a));
+            if toward != 0.0 {
+                return toward > 0.0;
+            }
+            // On the boundary line, fall back to the center's finite offset
+            // e, which satisfies e . d = c . (a - mid) for both directions
+            let t = dot(c, sub(a, mid));
+            let det = cross(dirs[0], dirs[1]);
+            let e = Coordinate {
+                x: t * (dirs[1].y - dirs[0].y) / det,
+                y: t * (dirs[0].x - dirs[1].x) / det,
+            };
+            let (pm, am) = (sub(p, mid), sub(a, mid));
+            2.0 * dot(e, sub(p, a)) >= dot(pm, pm) - dot(am, am)
+        }
+        _ => true,
+    }
+}
+
+fn same_edge(a: (usize, usize), b: (usize, usize)) -> bool {
+    a == b || (a.0 == b.1 && a.1 == b.0)
+}
+
+// Delaunay triangulation of the given points using the Bowyer-Watson
+// algorithm, treating coordinates as planar.
+// Duplicate points are ignored. Fewer than 3 distinct (non-collinear)
+// points produce no triangles.
+pub fn triangulate(points: &[Point<f64>]) -> Vec<Triangle> {
+    let mut coords: Vec<Coord> = Vec::with_capacity(points.len() + 3);
+    for p in points {
+        if !coords.contains(&p.0) {
+            coords.push(p.0);
+        }
+    }
+    let num_points = coords.len();
+    if num_points < 3 {
+        return vec![];
+    }
+
+    let min_x = coords.iter().map(|c| c.x).fold(f64::INFINITY, f64::min);
+    let min_y = coords.iter().map(|c| c.y).fold(f64::INFINITY, f64::min);
+    let max_x = coords.iter().map(|c| c.x).fold(f64::NEG_INFINITY, f64::max);
+    let max_y = coords.iter().map(|c| c.y).fold(f64::NEG_INFINITY, f64::max);
+    let span = (max_x - min_x).max(max_y - min_y).max(1.0) * 20.0;
+    let mid = Coordinate {
+        x: (min_x + max_x) / 2.0,
+        y: (min_y + max_y) / 2.0,
+    };
+
+    // Super-triangle enclosing all of the points. Its coordinates only
+    // orient the triangles; in_circumcircle treats them as infinitely far.
+    for d in &SUPER_DIRECTIONS {
+        coords.push(Coordinate {
+            x: mid.x + d.x * span,
+            y: mid.y + d.y * span,
+        });
+    }
+
+    let mut triangles: Vec<([usize; 3], Coord, f64)> = Vec::new();
+    let sup = [num_points, num_points + 1, num_points + 2];
+    let (center, r2) = circumcircle(coords[sup[0]], coords[sup[1]], coords[sup[2]]);
+    triangles.push((sup, center, r2));
+
+    for idx in 0..num_points {
+        let p = coords[idx];
+        let (bad, good): (Vec<_>, Vec<_>) = triangles
+            .into_iter()
+            .partition(|t| in_circumcircle(&coords, num_points, mid, t, p));
+
+        let edges: Vec<(usize, usize)> = bad
+            .iter()
+            .flat_map(|([i, j, k], _, _)| vec![(*i, *j), (*j, *k), (*k, *i)])
+            .collect();
+        let boundary: Vec<(usize, usize)> = edges
+            .iter()
+            .filter(|e| edges.iter().filter(|other| same_edge(**e, **other)).count() == 1)
+            .cloned()
+            .collect();
+
+        triangles = good;
+        for (i, j) in boundary {
+            let tri = ccw(&coords, [i, j, idx]);
+            let (center, r2) = circumcircle(coords[tri[0]], coords[tri[1]], coords[tri[2]]);
+            if r2.is_finite() || tri.iter().any(|&i| i >= num_points) {
+                triangles.push((tri, center, r2));
+            }
+        }
+    }
+
+    triangles
+        .into_iter()
+        .filter(|(tri, _, _)| tri.iter().all(|&i| i < num_points))
+        .map(|([i, j, k], _, _)| Triangle {
+            a: coords[i],
+            b: coords[j],
+            c: coords[k],
+        })
+        .collect()
+}
+
+// Unique edges of the given triangles
+pub fn edges(triangles: &[Triangle]) -> Vec<(Coord, Coord)> {
+    let mut output: Vec<(Coord, Coord)> = Vec::new();
+    for t in triangles {
+        for &(a, b) in &[(t.a, t.b), (t.b, t.c), (t.c, t.a)] {
+            if !output.iter().any(|&(x, y)| (x == a && y == b) || (x == b && y == a)) {
+                output.push((a, b));
+            }
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::delaunay::{edges, triangulate};
+    use geo_types::Point;
+
+    #[test]
+    fn test_triangulating_square() {
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+            Point::new(1.0, 1.0),
+            Point::new(0.0, 1.0),
+        ];
+        let triangles = triangulate(&points);
+        assert_eq!(2, triangles.len());
+        assert_eq!(5, edges(&triangles).len());
+        let total_area: f64 = triangles.iter().map(|t| t.area()).sum();
+        assert_eq!(1.0, total_area);
+        for t in triangles {
+            assert_eq!(2.0f64.sqrt() / 2.0, t.circumradius());
+        }
+    }
+
+    #[test]
+    fn test_triangulating_too_few_points() {
+        let points = vec![Point::new(0.0, 0.0), Point::new(1.0, 0.0), Point::new(0.0, 0.0)];
+        assert!(triangulate(&points).is_empty());
+    }
+
+    #[test]
+    fn test_triangulating_nearly_collinear_points() {
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(0.000001, 10.0),
+            Point::new(0.000001, 30.0),
+            Point::new(0.0, 40.0),
+            Point::new(-0.000001, 20.0),
+        ];
+        let triangles = triangulate(&points);
+        assert_eq!(3, triangles.len());
+        let total_area: f64 = triangles.iter().map(|t| t.area()).sum();
+        assert!((total_area - 0.00005).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,140 @@
+use crate::geoq::distance::GEODESIC;
+use geo_types::{Coordinate, Geometry, LineString, MultiLineString, MultiPolygon, Polygon};
+use geographiclib_rs::{DirectGeodesic, InverseGeodesic};
+
+type Coord = Coordinate<f64>;
+
+// Point the given geodesic distance (in meters) from a towards b
+fn towards(a: Coord, b: Coord, distance: f64) -> Coord {
+    let (azimuth, _, _): (f64, f64, f64) = GEODESIC.inverse(a.y, a.x, b.y, b.x);
+    let (lat, lon): (f64, f64) = GEODESIC.direct(a.y, a.x, azimuth, distance);
+    Coordinate { x: lon, y: lat }
+}
+
+fn segment_length(a: Coord, b: Coord) -> f64 {
+    GEODESIC.inverse(a.y, a.x, b.y, b.x)
+}
+
+// Add vertices so that no segment is longer than max_segment meters.
+// Each segment is split into equal (geodesic) pieces, and existing vertices are kept.
+pub fn densify_line(line: &LineString<f64>, max_segment: f64) -> LineString<f64> {
+    let mut output = Vec::with_capacity(line.0.len());
+    for pair in line.0.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        output.push(a);
+        let length = segment_length(a, b);
+        let pieces = (length / max_segment).ceil() as usize;
+        for i in 1..pieces {
+            output.push(towards(a, b, length * i as f64 / pieces as f64));
+        }
+    }
+    output.extend(line.0.last());
+    LineString(output)
+}
+
+// Replace the vertices of a line with exactly count vertices, evenly spaced
+// by geodesic distance along it. The first and last vertices stay fixed,
+// so closed rings stay closed.
+pub fn redistribute_line(line: &LineString<f64>, count: usize) -> LineString<f64> {
+    let coords = &line.0;
+    if coords.len() < 2 || count < 2 {
+        return line.clone();
+    }
+    let lengths: Vec<f64> = coords.windows(2).map(|pair| segment_length(pair[0], pair[1])).collect();
+    let total: f64 = lengths.iter().sum();
+
+    let mut output = Vec::with_capacity(count);
+    output.push(coords[0]);
+    let mut segment = 0;
+    // Distance along the line to the start of the current segment
+    let mut start = 0.0;
+    for i in 1..count - 1 {
+        let target = total * i as f64 / (count - 1) as f64;
+        while segment < lengths.len() - 1 && start + lengths[segment] < target {
+            start += lengths[segment];
+            segment += 1;
+        }
+        output.push(towards(coords[segment], coords[segment + 1], target - start));
+    }
+    output.push(coords[coords.len() - 1]);
+    LineString(output)
+}
+
+// Rings need at least 4 vertices, so smaller counts leave them as-is
+fn redistribute_ring(ring: &LineString<f64>, count: usize) -> LineString<f64> {
+    if count < 4 {
+        ring.clone()
+    } else {
+        redistribute_line(ring, count)
+    }
+}
+
+fn map_lines<F, G>(geom: Geometry<f64>, line_fn: F, ring_fn: G) -> Geometry<f64>
+where
+    F: Fn(&LineString<f64>) -> LineString<f64>,
+    G: Fn(&LineString<f64>) -> LineString<f64>,
+{
+    let polygon = |p: &Polygon<f64>| {
+        Polygon::new(ring_fn(p.exterior()), p.interiors().iter().map(&ring_fn).collect())
+    };
+    match geom {
+        Geometry::LineString(g) => Geometry::LineString(line_fn(&g)),
+        Geometry::MultiLineString(g) => {
+            Geometry::MultiLineString(MultiLineString(g.0.iter().map(&line_fn).collect()))
+        }
+        Geometry::Polygon(g) => Geometry::Polygon(polygon(&g)),
+        Geometry::MultiPolygon(g) => Geometry::MultiPolygon(MultiPolygon(g.0.iter().map(polygon).collect())),
+        _ => geom,
+    }
+}
+
+// Only (Multi-)LineStrings and (Multi-)Polygons are affected
+pub fn densify(geom: Geometry<f64>, max_segment: f64) -> Geometry<f64> {
+    let f = |l: &LineString<f64>| densify_line(l, max_segment);
+    map_lines(geom, f, f)
+}
+
+// Only (Multi-)LineStrings and (Multi-)Polygons are affected
+pub fn redistribute(geom: Geometry<f64>, count: usize) -> Geometry<f64> {
+    map_lines(
+        geom,
+        |l: &LineString<f64>| redistribute_line(l, count),
+        |r: &LineString<f64>| redistribute_ring(r, count),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::densify::{densify_line, redistribute_line};
+    use geo_types::LineString;
+
+    #[test]
+    fn test_densifying_long_segments() {
+        // 1 degree of longitude at the equator is ~111km
+        let line: LineString<f64> = vec![(0.0, 0.0), (1.0, 0.0), (1.1, 0.0)].into();
+        let densified = densify_line(&line, 50_000.0);
+        assert_eq!(5, densified.0.len());
+        assert!((densified.0[1].x - 1.0 / 3.0).abs() < 1e-9, "{:?}", densified);
+        assert_eq!(line.0[1], densified.0[3]);
+        assert_eq!(line.0[2], densified.0[4]);
+    }
+
+    #[test]
+    fn test_redistributing_line() {
+        let line: LineString<f64> = vec![(0.0, 0.0), (0.1, 0.0), (1.0, 0.0)].into();
+        let even = redistribute_line(&line, 3);
+        assert_eq!(3, even.0.len());
+        assert_eq!(line.0[0], even.0[0]);
+        assert!((even.0[1].x - 0.5).abs() < 1e-9, "{:?}", even);
+        assert_eq!(line.0[2], even.0[2]);
+    }
+
+    #[test]
+    fn test_redistributing_ring_stays_closed() {
+        let ring: LineString<f64> =
+            vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0), (0.0, 0.0)].into();
+        let even = redistribute_line(&ring, 9);
+        assert_eq!(9, even.0.len());
+        assert_eq!(even.0[0], even.0[8]);
+    }
+}
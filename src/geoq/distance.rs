@@ -1,9 +1,150 @@
+use geo::algorithm::bearing::Bearing;
 use geo::algorithm::closest_point::ClosestPoint;
+use crate::geoq::ellipsoid::{self, Ellipsoid};
+use crate::geoq::{centroid, intersection, node};
 use geo::algorithm::contains::Contains;
-use geo::algorithm::vincenty_distance::VincentyDistance;
-use geo_types::{Geometry, GeometryCollection, MultiPolygon, Point, Polygon};
+use geo::algorithm::euclidean_distance::EuclideanDistance;
+use geo_types::{Geometry, GeometryCollection, LineString, MultiPolygon, Point, Polygon};
+use geographiclib_rs::{Geodesic, InverseGeodesic};
+use once_cell::sync::Lazy;
 use std::cmp::Ordering::Equal;
 
+// Built on first use, after the global --ellipsoid has been set
+pub static GEODESIC: Lazy<Geodesic> = Lazy::new(|| {
+    let e = ellipsoid::ellipsoid();
+    Geodesic::new(e.a, e.flattening())
+});
+
+// Algorithm for calculating distances between 2 points
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Method {
+    // Great-circle distance on a sphere. Fast, but may be off by up to ~0.5%
+    Haversine,
+    // Iterative ellipsoidal distance. Accurate, but can fail to converge
+    // for nearly antipodal points.
+    Vincenty,
+    // Ellipsoidal geodesic distance (Karney 2013). Accurate to within
+    // nanometers, and always converges.
+    Karney,
+    // Euclidean distance, for coordinates which are already projected
+    // into a planar CRS (in meters) rather than Lon/Lat
+    Planar,
+}
+
+impl Method {
+    pub fn from_name(name: &str) -> Option<Method> {
+        match name {
+            "haversine" => Some(Method::Haversine),
+            "vincenty" => Some(Method::Vincenty),
+            "karney" => Some(Method::Karney),
+            _ => None,
+        }
+    }
+}
+
+fn haversine(p: &Point<f64>, q: &Point<f64>, radius: f64) -> f64 {
+    let theta1 = p.y().to_radians();
+    let theta2 = q.y().to_radians();
+    let delta_theta = (q.y() - p.y()).to_radians();
+    let delta_lambda = (q.x() - p.x()).to_radians();
+    let a = (delta_theta / 2.0).sin().powi(2)
+        + theta1.cos() * theta2.cos() * (delta_lambda / 2.0).sin().powi(2);
+    2.0 * a.sqrt().asin() * radius
+}
+
+// Vincenty's inverse formula on the given ellipsoid,
+// following geo's VincentyDistance (which only supports WGS84).
+// Returns None if the iteration fails to converge, as for nearly antipodal points.
+#[allow(non_snake_case)]
+fn vincenty(p: &Point<f64>, q: &Point<f64>, e: &Ellipsoid) -> Option<f64> {
+    let (a, b, f) = (e.a, e.b, e.flattening());
+    let L = (q.x() - p.x()).to_radians();
+    let U1 = ((1.0 - f) * p.y().to_radians().tan()).atan();
+    let U2 = ((1.0 - f) * q.y().to_radians().tan()).atan();
+    let (sinU1, cosU1) = U1.sin_cos();
+    let (sinU2, cosU2) = U2.sin_cos();
+    let mut cosSqAlpha;
+    let mut sinSigma;
+    let mut cos2SigmaM;
+    let mut cosSigma;
+    let mut sigma;
+    let mut lambda = L;
+    let mut iters = 0;
+
+    loop {
+        let (sinLambda, cosLambda) = lambda.sin_cos();
+        sinSigma = ((cosU2 * sinLambda) * (cosU2 * sinLambda)
+            + (cosU1 * sinU2 - sinU1 * cosU2 * cosLambda)
+                * (cosU1 * sinU2 - sinU1 * cosU2 * cosLambda))
+            .sqrt();
+        cosSigma = sinU1 * sinU2 + cosU1 * cosU2 * cosLambda;
+        if sinSigma == 0.0 {
+            // Coincident points are 0 apart, but exactly antipodal ones don't converge
+            return if cosSigma > 0.0 { Some(0.0) } else { None };
+        }
+        sigma = sinSigma.atan2(cosSigma);
+        let sinAlpha = cosU1 * cosU2 * sinLambda / sinSigma;
+        cosSqAlpha = 1.0 - sinAlpha * sinAlpha;
+        // Equatorial lines have cosSqAlpha = 0
+        cos2SigmaM = if cosSqAlpha == 0.0 {
+            0.0
+        } else {
+            cosSigma - 2.0 * sinU1 * sinU2 / cosSqAlpha
+        };
+        let C = f / 16.0 * cosSqAlpha * (4.0 + f * (4.0 - 3.0 * cosSqAlpha));
+        let lambdaP = lambda;
+        lambda = L
+            + (1.0 - C)
+                * f
+                * sinAlpha
+                * (sigma
+                    + C * sinSigma
+                        * (cos2SigmaM + C * cosSigma * (-1.0 + 2.0 * cos2SigmaM * cos2SigmaM)));
+
+        if (lambda - lambdaP).abs() <= 1e-12 {
+            break;
+        }
+        iters += 1;
+        if iters == 100 {
+            return None;
+        }
+    }
+
+    let uSq = cosSqAlpha * (a * a - b * b) / (b * b);
+    let A = 1.0 + uSq / 16384.0 * (4096.0 + uSq * (-768.0 + uSq * (320.0 - 175.0 * uSq)));
+    let B = uSq / 1024.0 * (256.0 + uSq * (-128.0 + uSq * (74.0 - 47.0 * uSq)));
+    let deltaSigma = B
+        * sinSigma
+        * (cos2SigmaM
+            + B / 4.0
+                * (cosSigma * (-1.0 + 2.0 * cos2SigmaM * cos2SigmaM)
+                    - B / 6.0
+                        * cos2SigmaM
+                        * (-3.0 + 4.0 * sinSigma * sinSigma)
+                        * (-3.0 + 4.0 * cos2SigmaM * cos2SigmaM)));
+    Some(b * A * (sigma - deltaSigma))
+}
+
+// Distance in meters between 2 points on the global --ellipsoid
+// (or for Method::Planar, in the units of the coordinates)
+pub fn point_distance(a: &Point<f64>, b: &Point<f64>, method: Method) -> Option<f64> {
+    let e = ellipsoid::ellipsoid();
+    match method {
+        Method::Haversine => Some(haversine(a, b, e.mean_radius())),
+        Method::Vincenty => vincenty(a, b, &e),
+        Method::Karney => Some(GEODESIC.inverse(a.y(), a.x(), b.y(), b.x())),
+        Method::Planar => Some(a.euclidean_distance(b)),
+    }
+}
+
+// Vincenty distance in meters, falling back to Haversine for
+// nearly antipodal points, where Vincenty fails to converge
+pub fn geodesic_distance(a: &Point<f64>, b: &Point<f64>) -> f64 {
+    point_distance(a, b, Method::Vincenty)
+        .or_else(|| point_distance(a, b, Method::Haversine))
+        .unwrap()
+}
+
 // TODO nearest point for other geom types
 
 fn closest_point_to_poly(point: &Point<f64>, poly: &Polygon<f64>) -> geo::Closest<f64> {
@@ -38,7 +179,7 @@ fn closest_point_to_geom_coll(
                 geo::Closest::Intersection(_) => (closest, 0.0),
                 geo::Closest::SinglePoint(p) => (
                     closest,
-                    p.vincenty_distance(point).unwrap_or(std::f64::INFINITY),
+                    geodesic_distance(&p, point),
                 ),
             })
             .collect();
@@ -61,15 +202,129 @@ fn closest_point(a: &Point<f64>, b: &Geometry<f64>) -> geo::Closest<f64> {
     }
 }
 
-pub fn distance(a: &Point<f64>, b: &Geometry<f64>) -> Option<f64> {
+// Distance in meters from a point to the closest point on a geometry,
+// using the given method
+pub fn distance_with(a: &Point<f64>, b: &Geometry<f64>, method: Method) -> Option<f64> {
     let closest = closest_point(a, b);
     match closest {
+        geo::Closest::Intersection(_) => Some(0.0),
+        geo::Closest::SinglePoint(p) => point_distance(&p, a, method),
+        geo::Closest::Indeterminate => None,
+    }
+}
+
+// The lines making up a geometry, including the rings of its polygons
+fn linework(geom: &Geometry<f64>) -> Vec<LineString<f64>> {
+    match *geom {
+        Geometry::Point(_) | Geometry::MultiPoint(_) => vec![],
+        Geometry::Line(ref g) => vec![LineString(vec![g.start, g.end])],
+        Geometry::LineString(ref g) => vec![g.clone()],
+        Geometry::Polygon(ref g) => std::iter::once(g.exterior())
+            .chain(g.interiors().iter())
+            .cloned()
+            .collect(),
+        Geometry::MultiLineString(ref g) => g.0.clone(),
+        Geometry::MultiPolygon(ref g) => g
+            .0
+            .iter()
+            .flat_map(|p| linework(&Geometry::Polygon(p.clone())))
+            .collect(),
+        Geometry::GeometryCollection(ref gc) => gc.0.iter().flat_map(linework).collect(),
+    }
+}
+
+// A point shared by 2 intersecting geometries: a vertex of one lying in
+// the other, or else a point where their lines cross
+fn shared_point(a: &Geometry<f64>, b: &Geometry<f64>) -> Option<Point<f64>> {
+    let in_other = |geom: &Geometry<f64>, other: &Geometry<f64>| {
+        centroid::vertices(geom)
+            .into_iter()
+            .map(Point)
+            .find(|v| intersection::point_intersects(v, other))
+    };
+    in_other(a, b).or_else(|| in_other(b, a)).or_else(|| {
+        let (pieces, _) = node::split(&linework(a), &linework(b));
+        pieces
+            .iter()
+            .find(|p| p.len() > 1)
+            .and_then(|p| p[0].0.last().cloned().map(Point))
+    })
+}
+
+// The vertex of either geometry which is closest (treating coordinates as
+// planar) to the other geometry, along with the closest point to it on the
+// other geometry, and whether the vertex came from `a`
+fn closest_vertex(a: &Geometry<f64>, b: &Geometry<f64>) -> Option<(Point<f64>, Point<f64>, bool)> {
+    let from_a = centroid::vertices(a).into_iter().map(|c| (Point(c), b, true));
+    let from_b = centroid::vertices(b).into_iter().map(|c| (Point(c), a, false));
+    from_a
+        .chain(from_b)
+        .filter_map(|(vertex, other, on_a)| match closest_point(&vertex, other) {
+            geo::Closest::SinglePoint(p) => Some((vertex, p, on_a)),
+            geo::Closest::Intersection(_) => Some((vertex, vertex, on_a)),
+            geo::Closest::Indeterminate => None,
+        })
+        .min_by(|(v1, p1, _), (v2, p2, _)| {
+            v1.euclidean_distance(p1)
+                .partial_cmp(&v2.euclidean_distance(p2))
+                .unwrap_or(Equal)
+        })
+}
+
+// Distance in meters between the closest points of 2 geometries of any type,
+// using the given method. Geometries which intersect, including one
+// lying entirely within a polygon, give 0. Otherwise the closest pair is
+// found (treating coordinates as planar) between a vertex of one geometry
+// and the closest point on the other, which for disjoint geometries is
+// the distance between their boundaries.
+pub fn geometry_distance(a: &Geometry<f64>, b: &Geometry<f64>, method: Method) -> Option<f64> {
+    if let Geometry::Point(ref p) = *a {
+        return distance_with(p, b, method);
+    }
+    if intersection::intersects(a, b) || intersection::intersects(b, a) {
+        return Some(0.0);
+    }
+    let (vertex, closest, _) = closest_vertex(a, b)?;
+    if vertex == closest {
+        Some(0.0)
+    } else {
+        point_distance(&vertex, &closest, method)
+    }
+}
+
+// The closest pair of points between 2 geometries, as (point on a, point on b),
+// found in the same way as geometry_distance. Intersecting geometries
+// give the same point twice.
+pub fn closest_points(a: &Geometry<f64>, b: &Geometry<f64>) -> Option<(Point<f64>, Point<f64>)> {
+    if let Geometry::Point(ref p) = *a {
+        return match closest_point(p, b) {
+            geo::Closest::SinglePoint(q) => Some((*p, q)),
+            geo::Closest::Intersection(_) => Some((*p, *p)),
+            geo::Closest::Indeterminate => None,
+        };
+    }
+    if intersection::intersects(a, b) || intersection::intersects(b, a) {
+        return shared_point(a, b).map(|p| (p, p));
+    }
+    closest_vertex(a, b).map(|(vertex, closest, on_a)| {
+        if on_a {
+            (vertex, closest)
+        } else {
+            (closest, vertex)
+        }
+    })
+}
+
+// Initial bearing in degrees clockwise from North (0-360) from a point to
+// the closest point on a geometry. Geometries covering the point give 0.
+pub fn bearing(a: &Point<f64>, b: &Geometry<f64>) -> Option<f64> {
+    match closest_point(a, b) {
         geo::Closest::Intersection(_) => Some(0.0),
         geo::Closest::SinglePoint(p) => {
-            let vin = p.vincenty_distance(a);
-            match vin {
-                Ok(d) => Some(d),
-                Err(_) => None,
+            if p == *a {
+                Some(0.0)
+            } else {
+                Some((a.bearing(p) + 360.0) % 360.0)
             }
         }
         geo::Closest::Indeterminate => None,
@@ -78,8 +333,10 @@ pub fn distance(a: &Point<f64>, b: &Geometry<f64>) -> Option<f64> {
 
 #[cfg(test)]
 mod tests {
-    use crate::geoq::distance::distance;
-    use geo_types::{Geometry, Point, Polygon};
+    use crate::geoq::distance::{
+        bearing, closest_points, distance_with, geometry_distance, point_distance, Method,
+    };
+    use geo_types::{Geometry, LineString, Point, Polygon};
     use wkt::ToWkt;
 
     #[test]
@@ -88,12 +345,47 @@ mod tests {
         let ny = Point::new(-74.0060, 40.7128);
         let nyg = Geometry::Point(ny);
 
-        match distance(&la, &nyg) {
+        match distance_with(&la, &nyg, Method::Vincenty) {
             Some(d) => assert_eq!(d.round(), 3944422.),
             None => assert!(false, "Should get distance"),
         }
     }
 
+    #[test]
+    fn test_distance_methods() {
+        let la = Point::new(-118.2437, 34.0522);
+        let nyg = Geometry::Point(Point::new(-74.0060, 40.7128));
+
+        let karney = distance_with(&la, &nyg, Method::Karney).unwrap();
+        let haversine = distance_with(&la, &nyg, Method::Haversine).unwrap();
+        assert_eq!(karney.round(), 3944422.);
+        assert!((haversine - karney).abs() / karney < 0.005);
+
+        // Vincenty fails to converge for antipodal points, Karney doesn't
+        let antipode = Geometry::Point(Point::new(180.0, 0.5));
+        let origin = Point::new(0.0, -0.5);
+        assert_eq!(None, distance_with(&origin, &antipode, Method::Vincenty));
+        assert!(distance_with(&origin, &antipode, Method::Karney).is_some());
+    }
+
+    #[test]
+    fn test_vincenty_coincident_points() {
+        let p = Point::new(-118.2437, 34.0522);
+        assert_eq!(Some(0.0), point_distance(&p, &p, Method::Vincenty));
+        let antipode = Point::new(180.0, 0.0);
+        assert_eq!(None, point_distance(&Point::new(0.0, 0.0), &antipode, Method::Vincenty));
+    }
+
+    #[test]
+    fn test_vincenty_along_equator() {
+        let origin = Point::new(0.0, 0.0);
+        let east = Geometry::Point(Point::new(1.0, 0.0));
+        let vincenty = distance_with(&origin, &east, Method::Vincenty).unwrap();
+        let karney = distance_with(&origin, &east, Method::Karney).unwrap();
+        assert!(vincenty.is_finite());
+        assert!((vincenty - karney).abs() < 0.001, "distance was {}", vincenty);
+    }
+
     #[test]
     fn test_containing_polygon() {
         let la = Point::new(-118.2437, 34.0522);
@@ -110,9 +402,94 @@ mod tests {
         );
         let polyg = Geometry::Polygon(poly);
         println!("{}", polyg.to_wkt().items.pop().unwrap());
-        match distance(&la, &polyg) {
+        match distance_with(&la, &polyg, Method::Vincenty) {
             Some(d) => assert_eq!(d.round(), 0.),
             None => assert!(false, "Should get distance"),
         }
     }
+
+    #[test]
+    fn test_bearing() {
+        let origin = Point::new(0.0, 0.0);
+        let east = Geometry::Point(Point::new(1.0, 0.0));
+        let west = Geometry::Point(Point::new(-1.0, 0.0));
+        assert_eq!(Some(90.0), bearing(&origin, &east));
+        assert_eq!(Some(270.0), bearing(&origin, &west));
+        assert_eq!(Some(0.0), bearing(&origin, &Geometry::Point(origin)));
+    }
+
+    fn square(min: f64, max: f64) -> LineString<f64> {
+        vec![(min, min), (max, min), (max, max), (min, max), (min, min)].into()
+    }
+
+    #[test]
+    fn test_geometry_distance_intersecting() {
+        let a = Geometry::Polygon(Polygon::new(square(0.0, 2.0), vec![]));
+        let b = Geometry::Polygon(Polygon::new(square(1.0, 3.0), vec![]));
+        assert_eq!(Some(0.0), geometry_distance(&a, &b, Method::Vincenty));
+
+        // Crossing lines with no vertices in common
+        let c = Geometry::LineString(vec![(0.0, 0.0), (2.0, 2.0)].into());
+        let d = Geometry::LineString(vec![(0.0, 2.0), (2.0, 0.0)].into());
+        assert_eq!(Some(0.0), geometry_distance(&c, &d, Method::Vincenty));
+    }
+
+    #[test]
+    fn test_geometry_distance_disjoint() {
+        let a = Geometry::Polygon(Polygon::new(square(0.0, 1.0), vec![]));
+        let b = Geometry::Polygon(Polygon::new(
+            vec![(2.0, 0.0), (3.0, 0.0), (3.0, 1.0), (2.0, 1.0), (2.0, 0.0)].into(),
+            vec![],
+        ));
+        let exp = point_distance(&Point::new(1.0, 0.0), &Point::new(2.0, 0.0), Method::Vincenty);
+        assert!(exp.is_some());
+        assert_eq!(exp, geometry_distance(&a, &b, Method::Vincenty));
+        assert_eq!(exp, geometry_distance(&b, &a, Method::Vincenty));
+
+        // Closest to the middle of an edge rather than to one of its vertices
+        let line = Geometry::LineString(vec![(0.5, 2.0), (0.5, 3.0)].into());
+        let exp = point_distance(&Point::new(0.5, 2.0), &Point::new(0.5, 1.0), Method::Vincenty);
+        assert!(exp.is_some());
+        assert_eq!(exp, geometry_distance(&line, &a, Method::Vincenty));
+    }
+
+    #[test]
+    fn test_geometry_distance_nested() {
+        let outer = Geometry::Polygon(Polygon::new(square(0.0, 10.0), vec![]));
+        let inner = Geometry::Polygon(Polygon::new(square(4.0, 6.0), vec![]));
+        assert_eq!(Some(0.0), geometry_distance(&outer, &inner, Method::Vincenty));
+        assert_eq!(Some(0.0), geometry_distance(&inner, &outer, Method::Vincenty));
+
+        // Within a hole, the distance is to the hole's boundary
+        let donut = Geometry::Polygon(Polygon::new(square(0.0, 10.0), vec![square(2.0, 8.0)]));
+        let strip = Geometry::Polygon(Polygon::new(
+            vec![(4.0, 2.5), (6.0, 2.5), (6.0, 3.0), (4.0, 3.0), (4.0, 2.5)].into(),
+            vec![],
+        ));
+        let exp = point_distance(&Point::new(4.0, 2.5), &Point::new(4.0, 2.0), Method::Vincenty);
+        assert_eq!(exp, geometry_distance(&strip, &donut, Method::Vincenty));
+        let exp = point_distance(&Point::new(5.0, 4.0), &Point::new(5.0, 2.0), Method::Vincenty);
+        let point = Geometry::Point(Point::new(5.0, 4.0));
+        assert_eq!(exp, geometry_distance(&point, &donut, Method::Vincenty));
+    }
+
+    #[test]
+    fn test_closest_points() {
+        let a = Geometry::Polygon(Polygon::new(square(0.0, 1.0), vec![]));
+        let line = Geometry::LineString(vec![(0.5, 2.0), (0.5, 3.0)].into());
+        let exp = Some((Point::new(0.5, 1.0), Point::new(0.5, 2.0)));
+        assert_eq!(exp, closest_points(&a, &line));
+        let exp = Some((Point::new(0.5, 2.0), Point::new(0.5, 1.0)));
+        assert_eq!(exp, closest_points(&line, &a));
+
+        let point = Geometry::Point(Point::new(3.0, 0.5));
+        let exp = Some((Point::new(3.0, 0.5), Point::new(1.0, 0.5)));
+        assert_eq!(exp, closest_points(&point, &a));
+
+        // Crossing lines meet where they cross
+        let c = Geometry::LineString(vec![(0.0, 0.0), (2.0, 2.0)].into());
+        let d = Geometry::LineString(vec![(0.0, 2.0), (2.0, 0.0)].into());
+        let exp = Some((Point::new(1.0, 1.0), Point::new(1.0, 1.0)));
+        assert_eq!(exp, closest_points(&c, &d));
+    }
 }
@@ -0,0 +1,66 @@
+use once_cell::sync::OnceCell;
+
+// Reference ellipsoid used for geodesic calculations,
+// given by its equatorial (a) and polar (b) radii in meters.
+// A sphere is an ellipsoid with equal radii.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ellipsoid {
+    pub a: f64,
+    pub b: f64,
+}
+
+pub const WGS84: Ellipsoid = Ellipsoid {
+    a: 6_378_137.0,
+    b: 6_356_752.314_245,
+};
+
+pub const GRS80: Ellipsoid = Ellipsoid {
+    a: 6_378_137.0,
+    b: 6_356_752.314_140,
+};
+
+pub const MEAN_EARTH_RADIUS: f64 = 6_371_000.0;
+
+impl Ellipsoid {
+    pub fn sphere(radius: f64) -> Ellipsoid {
+        Ellipsoid {
+            a: radius,
+            b: radius,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Ellipsoid> {
+        match name {
+            "wgs84" => Some(WGS84),
+            "grs80" => Some(GRS80),
+            "sphere" => Some(Ellipsoid::sphere(MEAN_EARTH_RADIUS)),
+            _ => None,
+        }
+    }
+
+    pub fn flattening(&self) -> f64 {
+        (self.a - self.b) / self.a
+    }
+
+    // Radius of the sphere used for spherical (haversine) calculations.
+    // Spheres use their own radius, and the Earth ellipsoids use the
+    // conventional mean Earth radius.
+    pub fn mean_radius(&self) -> f64 {
+        if self.a == self.b {
+            self.a
+        } else {
+            MEAN_EARTH_RADIUS
+        }
+    }
+}
+
+static ELLIPSOID: OnceCell<Ellipsoid> = OnceCell::new();
+
+// Set from the global --ellipsoid and --radius options
+pub fn set_ellipsoid(ellipsoid: Ellipsoid) {
+    ELLIPSOID.set(ellipsoid).expect("Ellipsoid should only be set once");
+}
+
+pub fn ellipsoid() -> Ellipsoid {
+    ELLIPSOID.get().cloned().unwrap_or(WGS84)
+}
@@ -1,10 +1,10 @@
-use crate::geoq::{error::Error, input::Input, bbox};
-use geo_types::{Coordinate, Geometry, LineString, Point, Polygon};
+use crate::geoq::{bbox, conversion, coord_count, crs, error::Error, geohash as gh, input::Input};
+use geo_types::{Geometry, GeometryCollection, MultiPoint, Point};
 use geojson::GeoJson;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use serde_json;
-use std::{convert::TryInto, fmt};
+use std::fmt;
 use wkt::ToWkt;
 
 static LATLON_SPLIT: Lazy<Regex> = Lazy::new(|| Regex::new(",|\t").unwrap());
@@ -13,7 +13,8 @@ static LATLON_SPLIT: Lazy<Regex> = Lazy::new(|| Regex::new(",|\t").unwrap());
 pub enum Entity {
     LatLon(String),
     Geohash(String),
-    Wkt(String, geo_types::Geometry<f64>),
+    // Normalized WKT, the geometry, and the input line it was read from
+    Wkt(String, geo_types::Geometry<f64>, String),
     GeoJsonFeature(String, geojson::Feature),
     GeoJsonGeometry(String, geojson::Geometry),
 }
@@ -23,7 +24,7 @@ impl fmt::Display for Entity {
         match *self {
             Entity::LatLon(ref raw) => write!(f, "LatLon: {}", raw),
             Entity::Geohash(ref raw) => write!(f, "Geohash: {}", raw),
-            Entity::Wkt(ref raw, _) => write!(f, "WKT: {}", raw),
+            Entity::Wkt(ref raw, _, _) => write!(f, "WKT: {}", raw),
             Entity::GeoJsonFeature(ref raw, _) => write!(f, "GeoJSON Feature: {}", raw),
             Entity::GeoJsonGeometry(ref raw, _) => write!(f, "GeoJSON Geometry: {}", raw),
         }
@@ -38,17 +39,26 @@ fn latlon_geom(raw: &String) -> geo_types::Geometry<f64> {
 }
 
 fn geohash_geom(raw: &String) -> geo_types::Geometry<f64> {
-    let rect = geohash::decode_bbox(raw).expect("Invalid geohash");
-    let bl = rect.min;
-    let tr = rect.max;
-    let outer = LineString(vec![
-        Coordinate::from((bl.x, bl.y)),
-        Coordinate::from((tr.x, bl.y)),
-        Coordinate::from((tr.x, tr.y)),
-        Coordinate::from((bl.x, tr.y)),
-        Coordinate::from((bl.x, bl.y)),
-    ]);
-    Geometry::Polygon(Polygon::new(outer, Vec::new()))
+    Geometry::Polygon(gh::bbox(raw).expect("Invalid geohash"))
+}
+
+// Empty Points can't be represented by geo-types, so they are
+// read as empty MultiPoints
+fn wkt_geometry(wkt_geom: &wkt::Geometry) -> Result<Geometry<f64>, Error> {
+    match wkt_geom {
+        wkt::Geometry::Point(wkt::types::Point(None)) => {
+            Ok(Geometry::MultiPoint(MultiPoint(vec![])))
+        }
+        wkt::Geometry::GeometryCollection(gc) => {
+            let members = gc
+                .0
+                .iter()
+                .map(wkt_geometry)
+                .collect::<Result<Vec<Geometry<f64>>, Error>>()?;
+            Ok(Geometry::GeometryCollection(GeometryCollection(members)))
+        }
+        _ => wkt::conversion::try_into_geometry(wkt_geom).map_err(|_| Error::InvalidWkt),
+    }
 }
 
 fn wkt_entities(raw: &String) -> Result<Vec<Entity>, Error> {
@@ -58,8 +68,8 @@ fn wkt_entities(raw: &String) -> Result<Vec<Entity>, Error> {
         Ok(wkts) => {
             for wkt_geom in wkts.items {
                 let wkt_raw = wkt_geom.to_string();
-                let geom = wkt::conversion::try_into_geometry(&wkt_geom).unwrap();
-                entities.push(Entity::Wkt(wkt_raw, geom))
+                let geom = wkt_geometry(&wkt_geom)?;
+                entities.push(Entity::Wkt(wkt_raw, geom, raw.clone()))
             }
         }
         Err(_e) => return Err(Error::InvalidWkt),
@@ -67,22 +77,35 @@ fn wkt_entities(raw: &String) -> Result<Vec<Entity>, Error> {
     Ok(entities)
 }
 
-fn parsed_geojson_entities(raw: String, gj: GeoJson) -> Vec<Entity> {
+// Non-standard top-level members of a FeatureCollection, like a "name"
+type CollectionMembers = Option<serde_json::Map<String, serde_json::Value>>;
+
+fn parsed_geojson_entities(raw: String, gj: GeoJson) -> (Vec<Entity>, CollectionMembers) {
     match gj {
-        GeoJson::Geometry(gj_geom) => vec![Entity::GeoJsonGeometry(raw, gj_geom)],
-        GeoJson::Feature(gj_feature) => vec![Entity::GeoJsonFeature(raw, gj_feature)],
-        GeoJson::FeatureCollection(gj_fc) => gj_fc
-            .features
-            .into_iter()
-            .map(|f| {
-                let gj_raw = serde_json::to_string(&f).unwrap();
-                Entity::GeoJsonFeature(gj_raw, f)
-            })
-            .collect(),
+        GeoJson::Geometry(gj_geom) => {
+            crs::warn_unless_wgs84(&gj_geom.foreign_members);
+            (vec![Entity::GeoJsonGeometry(raw, gj_geom)], None)
+        }
+        GeoJson::Feature(gj_feature) => {
+            crs::warn_unless_wgs84(&gj_feature.foreign_members);
+            (vec![Entity::GeoJsonFeature(raw, gj_feature)], None)
+        }
+        GeoJson::FeatureCollection(gj_fc) => {
+            crs::warn_unless_wgs84(&gj_fc.foreign_members);
+            let entities = gj_fc
+                .features
+                .into_iter()
+                .map(|f| {
+                    let gj_raw = serde_json::to_string(&f).unwrap();
+                    Entity::GeoJsonFeature(gj_raw, f)
+                })
+                .collect();
+            (entities, gj_fc.foreign_members)
+        }
     }
 }
 
-fn geojson_entities(raw: String) -> Result<Vec<Entity>, Error> {
+fn geojson_entities(raw: String) -> Result<(Vec<Entity>, CollectionMembers), Error> {
     match raw.parse() {
         Ok(gj) => Ok(parsed_geojson_entities(raw, gj)),
         Err(e) => {
@@ -97,15 +120,23 @@ impl Entity {
         match self {
             Entity::LatLon(ref raw) => latlon_geom(raw),
             Entity::Geohash(ref raw) => geohash_geom(raw),
-            Entity::Wkt(_, ref geom) => geom.clone(),
-            Entity::GeoJsonGeometry(_, gj_geom) => gj_geom.value.clone().try_into().unwrap(),
-            Entity::GeoJsonFeature(_, gj_feature) => {
-                gj_feature.clone().geometry.unwrap().value.try_into().unwrap()
-            }
+            Entity::Wkt(_, ref geom, _) => geom.clone(),
+            Entity::GeoJsonGeometry(_, gj_geom) => conversion::geometry(gj_geom.value.clone()).unwrap(),
+            Entity::GeoJsonFeature(_, gj_feature) => match gj_feature.geometry {
+                Some(ref gj_geom) => conversion::geometry(gj_geom.value.clone()).unwrap(),
+                // Features with a null geometry are treated as empty
+                None => Geometry::GeometryCollection(GeometryCollection(vec![])),
+            },
         }
     }
 
     pub fn wkt(&self) -> wkt::Geometry {
+        // Re-parse WKT inputs to keep the type of empty geometries
+        if let Entity::Wkt(ref raw, _, _) = *self {
+            if let Ok(mut wkt) = wkt::Wkt::from_str(raw) {
+                return wkt.items.pop().unwrap();
+            }
+        }
         let geom = self.geom();
         let mut wkt = geom.to_wkt();
         wkt.items.pop().unwrap()
@@ -118,7 +149,21 @@ impl Entity {
 
     pub fn geojson_geometry(&self) -> geojson::Geometry {
         let geom = self.geom();
-        geojson::Geometry::new(geojson::Value::from(&geom))
+        // Empty geometries are given as an empty GeometryCollection, like null geometries
+        if coord_count::is_empty(&geom) {
+            return geojson::Geometry::new(geojson::Value::GeometryCollection(vec![]));
+        }
+        geojson::Geometry::new(conversion::geojson_value(&geom))
+    }
+
+    // The geometry of a GeoJSON input as given, keeping any Z coordinates,
+    // which geo-types geometries drop. None for other inputs and null geometries.
+    pub fn input_geojson_value(&self) -> Option<&geojson::Value> {
+        match *self {
+            Entity::GeoJsonGeometry(_, ref g) => Some(&g.value),
+            Entity::GeoJsonFeature(_, ref f) => f.geometry.as_ref().map(|g| &g.value),
+            _ => None,
+        }
     }
 
     pub fn geojson_properties(&self) -> serde_json::Map<String, serde_json::value::Value> {
@@ -134,15 +179,75 @@ impl Entity {
         }
     }
 
+    // Whether this is a GeoJSON Feature with "geometry": null, like an attribute-only row
+    pub fn has_null_geometry(&self) -> bool {
+        match *self {
+            Entity::GeoJsonFeature(_, ref f) => f.geometry.is_none(),
+            _ => false,
+        }
+    }
+
+    // Top-level "id" of a GeoJSON Feature, carried over to output features
+    pub fn geojson_id(&self) -> Option<geojson::feature::Id> {
+        match *self {
+            Entity::GeoJsonFeature(_, ref f) => f.id.clone(),
+            _ => None,
+        }
+    }
+
+    // Feature identifier, taken from a GeoJSON Feature's top-level "id",
+    // or else from an "id" property, if either is present.
+    pub fn id(&self) -> Option<serde_json::Value> {
+        match *self {
+            Entity::GeoJsonFeature(_, ref f) => f
+                .id
+                .as_ref()
+                .map(|id| serde_json::to_value(id).unwrap())
+                .or_else(|| self.geojson_properties().remove("id")),
+            _ => None,
+        }
+    }
+
+    // Value of the named property as a string, for matching features by key.
+    // Numbers and booleans are converted, so 1 and "1" give the same key.
+    // Keying by "id" uses the Feature's identifier (see id()).
+    pub fn key(&self, name: &str) -> Option<String> {
+        let value = if name == "id" {
+            self.id()
+        } else {
+            self.geojson_properties().remove(name)
+        };
+        match value {
+            Some(serde_json::Value::String(s)) => Some(s),
+            Some(serde_json::Value::Number(n)) => Some(n.to_string()),
+            Some(serde_json::Value::Bool(b)) => Some(b.to_string()),
+            _ => None,
+        }
+    }
+
     pub fn geojson_feature(&self) -> geojson::Feature {
         let props = self.geojson_properties();
-        let geom = self.geojson_geometry();
+        let geom = self.geom();
+        // Empty geometries are given as null
+        let geom = if coord_count::is_empty(&geom) {
+            None
+        } else {
+            Some(geojson::Geometry::new(conversion::geojson_value(&geom)))
+        };
         geojson::Feature {
             bbox: None,
-            geometry: Some(geom),
-            id: None,
+            geometry: geom,
+            id: self.geojson_id(),
             properties: Some(props),
-            foreign_members: None,
+            foreign_members: self.geojson_foreign_members(),
+        }
+    }
+
+    // Non-standard top-level members of a GeoJSON Feature, kept on output features
+    pub fn geojson_foreign_members(&self) -> Option<serde_json::Map<String, serde_json::Value>> {
+        match *self {
+            Entity::GeoJsonFeature(_, ref f) => f.foreign_members.clone(),
+            _ => None,
         }
     }
 
@@ -150,18 +255,33 @@ impl Entity {
         match *self {
             Entity::LatLon(ref raw) => raw.clone(),
             Entity::Geohash(ref raw) => raw.clone(),
-            Entity::Wkt(ref raw, _) => raw.clone(),
+            Entity::Wkt(ref raw, _, _) => raw.clone(),
             Entity::GeoJsonGeometry(ref raw, _) => raw.clone(),
             Entity::GeoJsonFeature(ref raw, _) => raw.clone(),
         }
     }
+
+    // The input text this entity was read from, as given. Unlike raw(),
+    // WKT is not normalized, so this is what errors should quote.
+    pub fn input(&self) -> String {
+        match *self {
+            Entity::Wkt(_, _, ref input) => input.clone(),
+            _ => self.raw(),
+        }
+    }
 }
 
 pub fn from_input(i: Input) -> Result<Vec<Entity>, Error> {
+    from_input_with_members(i).map(|(entities, _)| entities)
+}
+
+// The entities of an input, along with the foreign members
+// of the FeatureCollection they came from, if any
+pub fn from_input_with_members(i: Input) -> Result<(Vec<Entity>, CollectionMembers), Error> {
     match i {
-        Input::LatLon(raw) => Ok(vec![Entity::LatLon(raw)]),
-        Input::Geohash(raw) => Ok(vec![Entity::Geohash(raw)]),
-        Input::WKT(raw) => wkt_entities(&raw),
+        Input::LatLon(raw) => Ok((vec![Entity::LatLon(raw)], None)),
+        Input::Geohash(raw) => Ok((vec![Entity::Geohash(raw)], None)),
+        Input::WKT(raw) => Ok((wkt_entities(&raw)?, None)),
         Input::GeoJSON(raw) => geojson_entities(raw),
     }
 }
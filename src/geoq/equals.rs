@@ -0,0 +1,245 @@
+use geo_types::{Coordinate, Geometry, LineString, Polygon};
+use std::cmp::Ordering;
+
+type Coord = Coordinate<f64>;
+
+// Geometries are compared as a sorted list of parts, so that Multi-
+// geometries and GeometryCollections match regardless of element order,
+// and a single geometry matches a 1-element Multi- geometry.
+#[derive(Debug, PartialEq)]
+enum Part {
+    Point(Coord),
+    Line(Vec<Coord>),
+    Polygon(Vec<Vec<Coord>>),
+}
+
+fn cmp_coords(a: &[Coord], b: &[Coord]) -> Ordering {
+    for (x, y) in a.iter().zip(b.iter()) {
+        let ord = x
+            .x
+            .partial_cmp(&y.x)
+            .unwrap_or(Ordering::Equal)
+            .then(x.y.partial_cmp(&y.y).unwrap_or(Ordering::Equal));
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+    a.len().cmp(&b.len())
+}
+
+fn cmp_parts(a: &Part, b: &Part) -> Ordering {
+    match (a, b) {
+        (Part::Point(p), Part::Point(q)) => cmp_coords(&[*p], &[*q]),
+        (Part::Line(p), Part::Line(q)) => cmp_coords(p, q),
+        (Part::Polygon(p), Part::Polygon(q)) => p
+            .iter()
+            .zip(q.iter())
+            .map(|(r, s)| cmp_coords(r, s))
+            .find(|ord| *ord != Ordering::Equal)
+            .unwrap_or_else(|| p.len().cmp(&q.len())),
+        (Part::Point(_), _) => Ordering::Less,
+        (_, Part::Point(_)) => Ordering::Greater,
+        (Part::Line(_), _) => Ordering::Less,
+        (_, Part::Line(_)) => Ordering::Greater,
+    }
+}
+
+fn dedup(coords: &[Coord]) -> Vec<Coord> {
+    let mut output: Vec<Coord> = coords.to_vec();
+    output.dedup();
+    output
+}
+
+// Lines are the same in either direction
+fn normalize_line(line: &LineString<f64>) -> Vec<Coord> {
+    let forward = dedup(&line.0);
+    let mut backward = forward.clone();
+    backward.reverse();
+    if cmp_coords(&backward, &forward) == Ordering::Less {
+        backward
+    } else {
+        forward
+    }
+}
+
+// Rings are the same starting from any vertex, in either direction
+fn normalize_ring(ring: &LineString<f64>) -> Vec<Coord> {
+    let mut coords = dedup(&ring.0);
+    if coords.len() > 1 && coords.first() == coords.last() {
+        coords.pop();
+    }
+    if coords.is_empty() {
+        return coords;
+    }
+    let start = (0..coords.len())
+        .min_by(|&i, &j| cmp_coords(&coords[i..=i], &coords[j..=j]))
+        .unwrap();
+    let n = coords.len();
+    let forward: Vec<Coord> = (0..n).map(|i| coords[(start + i) % n]).collect();
+    let backward: Vec<Coord> = (0..n).map(|i| coords[(start + n - i) % n]).collect();
+    if cmp_coords(&backward, &forward) == Ordering::Less {
+        backward
+    } else {
+        forward
+    }
+}
+
+fn normalize_polygon(poly: &Polygon<f64>) -> Part {
+    let mut interiors: Vec<Vec<Coord>> = poly.interiors().iter().map(normalize_ring).collect();
+    interiors.sort_by(|a, b| cmp_coords(a, b));
+    let mut rings = vec![normalize_ring(poly.exterior())];
+    rings.extend(interiors);
+    Part::Polygon(rings)
+}
+
+fn parts(geom: &Geometry<f64>, output: &mut Vec<Part>) {
+    match geom {
+        Geometry::Point(g) => output.push(Part::Point(g.0)),
+        Geometry::Line(g) => output.push(Part::Line(normalize_line(&LineString(vec![
+            g.start, g.end,
+        ])))),
+        Geometry::LineString(g) => output.push(Part::Line(normalize_line(g))),
+        Geometry::Polygon(g) => output.push(normalize_polygon(g)),
+        Geometry::MultiPoint(g) => output.extend(g.0.iter().map(|p| Part::Point(p.0))),
+        Geometry::MultiLineString(g) => output.extend(g.0.iter().map(|l| Part::Line(normalize_line(l)))),
+        Geometry::MultiPolygon(g) => output.extend(g.0.iter().map(normalize_polygon)),
+        Geometry::GeometryCollection(gc) => gc.0.iter().for_each(|g| parts(g, output)),
+    }
+}
+
+fn normalize(geom: &Geometry<f64>) -> Vec<Part> {
+    let mut output = Vec::new();
+    parts(geom, &mut output);
+    output.sort_by(cmp_parts);
+    output.dedup();
+    output
+}
+
+fn coord_equal(p: &Coord, q: &Coord, tolerance: f64) -> bool {
+    (p.x - q.x).abs() <= tolerance && (p.y - q.y).abs() <= tolerance
+}
+
+fn coords_equal(a: &[Coord], b: &[Coord], tolerance: f64) -> bool {
+    a.len() == b.len() && a.iter().zip(b.iter()).all(|(p, q)| coord_equal(p, q, tolerance))
+}
+
+// The normalized forms pick a direction (and starting vertex) by exact
+// comparison, which may differ for coordinates within the tolerance,
+// so the other direction (and every rotation) is also tried
+fn lines_equal(a: &[Coord], b: &[Coord], tolerance: f64) -> bool {
+    let reversed: Vec<Coord> = b.iter().rev().cloned().collect();
+    coords_equal(a, b, tolerance) || coords_equal(a, &reversed, tolerance)
+}
+
+fn rings_equal(a: &[Coord], b: &[Coord], tolerance: f64) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let n = b.len();
+    let reversed: Vec<Coord> = b.iter().rev().cloned().collect();
+    n == 0
+        || (0..n).any(|start| {
+            [b, &reversed[..]]
+                .iter()
+                .any(|ring| (0..n).all(|i| coord_equal(&a[i], &ring[(start + i) % n], tolerance)))
+        })
+}
+
+// Whether each element of a matches a different element of b
+fn all_matched<T, F>(a: &[T], b: &[T], eq: F) -> bool
+where
+    F: Fn(&T, &T) -> bool,
+{
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut used = vec![false; b.len()];
+    a.iter().all(|x| match (0..b.len()).find(|&j| !used[j] && eq(x, &b[j])) {
+        Some(j) => {
+            used[j] = true;
+            true
+        }
+        None => false,
+    })
+}
+
+fn parts_equal(a: &Part, b: &Part, tolerance: f64) -> bool {
+    match (a, b) {
+        (Part::Point(p), Part::Point(q)) => coord_equal(p, q, tolerance),
+        (Part::Line(p), Part::Line(q)) => lines_equal(p, q, tolerance),
+        (Part::Polygon(p), Part::Polygon(q)) => {
+            p.len() == q.len()
+                && (p.is_empty() || rings_equal(&p[0], &q[0], tolerance))
+                && all_matched(&p[1..], &q[1..], |r, s| rings_equal(r, s, tolerance))
+        }
+        _ => false,
+    }
+}
+
+// Whether 2 geometries have the same vertices (each within `tolerance`
+// on both axes), ignoring the order of Multi- elements, the direction
+// of lines and rings, and the starting vertex of rings
+pub fn equals(a: &Geometry<f64>, b: &Geometry<f64>, tolerance: f64) -> bool {
+    let (a, b) = (normalize(a), normalize(b));
+    if tolerance == 0.0 {
+        return a == b;
+    }
+    all_matched(&a, &b, |p, q| parts_equal(p, q, tolerance))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::equals::equals;
+    use geo_types::{Geometry, LineString, MultiPoint, MultiPolygon, Point, Polygon};
+
+    #[test]
+    fn test_rings_equal_regardless_of_rotation_and_winding() {
+        let a: LineString<f64> = vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 0.0)].into();
+        let b: LineString<f64> = vec![(1.0, 1.0), (1.0, 0.0), (0.0, 0.0), (1.0, 1.0)].into();
+        let c: LineString<f64> = vec![(0.0, 0.0), (2.0, 0.0), (1.0, 1.0), (0.0, 0.0)].into();
+        let poly = |ring: LineString<f64>| Geometry::Polygon(Polygon::new(ring, vec![]));
+        assert!(equals(&poly(a.clone()), &poly(b), 0.0));
+        assert!(!equals(&poly(a), &poly(c), 0.0));
+    }
+
+    #[test]
+    fn test_equality_with_tolerance() {
+        let a = Geometry::Point(Point::new(1.0, 2.0));
+        let b = Geometry::Point(Point::new(1.0001, 2.0));
+        assert!(!equals(&a, &b, 0.0));
+        assert!(equals(&a, &b, 0.001));
+    }
+
+    #[test]
+    fn test_rotated_rings_within_tolerance() {
+        // The perturbed corner sorts after (0 0), so the rings'
+        // normalized forms start from different vertices
+        let a: LineString<f64> =
+            vec![(0.0001, -0.0001), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0), (0.0001, -0.0001)].into();
+        let b: LineString<f64> = vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0), (0.0, 0.0)].into();
+        let poly = |ring: LineString<f64>| Geometry::Polygon(Polygon::new(ring, vec![]));
+        assert!(!equals(&poly(a.clone()), &poly(b.clone()), 0.0));
+        assert!(equals(&poly(a.clone()), &poly(b.clone()), 0.001));
+        assert!(equals(&poly(b.clone()), &poly(a.clone()), 0.001));
+
+        // Parts are matched within the tolerance, whatever order they sort in
+        let multi = |polys: Vec<LineString<f64>>| {
+            Geometry::MultiPolygon(MultiPolygon(polys.into_iter().map(|r| Polygon::new(r, vec![])).collect()))
+        };
+        let c: LineString<f64> = vec![(0.00005, 2.0), (1.0, 2.0), (1.0, 3.0), (0.00005, 2.0)].into();
+        let d: LineString<f64> = vec![(-0.00005, 2.0), (1.0, 2.0), (1.0, 3.0), (-0.00005, 2.0)].into();
+        assert!(equals(&multi(vec![a, d]), &multi(vec![c, b]), 0.001));
+    }
+
+    #[test]
+    fn test_multi_element_order() {
+        let a = Geometry::MultiPoint(MultiPoint(vec![Point::new(1.0, 2.0), Point::new(3.0, 4.0)]));
+        let b = Geometry::MultiPoint(MultiPoint(vec![Point::new(3.0, 4.0), Point::new(1.0, 2.0)]));
+        assert!(equals(&a, &b, 0.0));
+        assert!(equals(
+            &Geometry::Point(Point::new(1.0, 2.0)),
+            &Geometry::MultiPoint(MultiPoint(vec![Point::new(1.0, 2.0)])),
+            0.0
+        ));
+    }
+}
@@ -21,7 +21,13 @@ pub enum Error {
     PointRequired,
     DistanceFailed,
     InvalidGeohashPoint,
-    NoInputGiven
+    NoInputGiven,
+    ConflictingArguments,
+    InvalidCsv,
+    InvalidCrs,
+    UnknownGeometryType,
+    LineStringRequired,
+    NonFiniteCoordinates,
 }
 
 impl From<io::Error> for Error {
@@ -36,6 +42,12 @@ impl From<serde_json::Error> for Error {
     }
 }
 
+impl From<csv::Error> for Error {
+    fn from(_: csv::Error) -> Self {
+        Error::InvalidCsv
+    }
+}
+
 impl From<reqwest::Error> for Error {
     fn from(_: reqwest::Error) -> Self {
         Error::HTTPError
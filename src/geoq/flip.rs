@@ -0,0 +1,37 @@
+use crate::geoq::bbox;
+use geo::algorithm::map_coords::MapCoords;
+use geo_types::Geometry;
+
+// Swap the X and Y (Lon and Lat) of every coordinate
+pub fn flip(geom: &Geometry<f64>) -> Geometry<f64> {
+    geom.map_coords(&|&(x, y)| (y, x))
+}
+
+// Whether every coordinate falls within the valid range for Lon/Lat
+pub fn in_lonlat_range(geom: &Geometry<f64>) -> bool {
+    let rect = bbox::bbox(geom);
+    rect.min.x >= -180.0 && rect.max.x <= 180.0 && rect.min.y >= -90.0 && rect.max.y <= 90.0
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::flip::{flip, in_lonlat_range};
+    use geo_types::{Geometry, LineString, Point};
+
+    #[test]
+    fn test_flipping_geometries() {
+        let line: LineString<f64> = vec![(1.0, 2.0), (3.0, 4.0)].into();
+        let flipped: LineString<f64> = vec![(2.0, 1.0), (4.0, 3.0)].into();
+        assert_eq!(
+            Geometry::LineString(flipped),
+            flip(&Geometry::LineString(line))
+        );
+    }
+
+    #[test]
+    fn test_lonlat_range() {
+        assert!(in_lonlat_range(&Geometry::Point(Point::new(-118.0, 34.0))));
+        assert!(!in_lonlat_range(&Geometry::Point(Point::new(34.0, -118.0))));
+        assert!(!in_lonlat_range(&Geometry::Point(Point::new(181.0, 0.0))));
+    }
+}
@@ -1,6 +1,14 @@
-use crate::geoq::{contains, intersection};
-use geo_types::{Coordinate, Geometry, LineString, Polygon};
-use std::str;
+use crate::geoq::{bbox, contains, coord_count, intersection};
+use geo::algorithm::{area::Area, contains::Contains};
+use geo_types::{Coordinate, Geometry, LineString, MultiPolygon, Point, Polygon};
+use std::{
+    cell::RefCell,
+    collections::{BTreeSet, HashMap},
+    str,
+};
+
+// Geohashes longer than this are finer than f64 coordinates can represent
+pub const MAX_GEOHASH_LENGTH: usize = 12;
 
 pub const BASE_32: [char; 32] = [
     '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'j', 'k',
@@ -11,6 +19,16 @@ pub fn children(gh: &String) -> Vec<String> {
     BASE_32.iter().map(|c| format!("{}{}", gh, c)).collect()
 }
 
+// All Geohashes `levels` characters longer than gh which it contains
+// (32^levels of them), in lexicographic order
+pub fn descendants(gh: &str, levels: usize) -> Vec<String> {
+    let mut cells = vec![gh.to_string()];
+    for _ in 0..levels {
+        cells = cells.iter().flat_map(children).collect();
+    }
+    cells
+}
+
 pub fn neighbors(gh: &String, include_self: bool) -> Vec<String> {
     let mut output: Vec<String> = if include_self {
         Vec::with_capacity(9)
@@ -34,7 +52,7 @@ pub fn neighbors(gh: &String, include_self: bool) -> Vec<String> {
     output
 }
 
-pub fn bbox(gh: &str) -> Option<Polygon<f64>> {
+fn decode_polygon(gh: &str) -> Option<Polygon<f64>> {
     match geohash::decode_bbox(gh) {
         Ok(rect) => {
             let bl = rect.min;
@@ -52,6 +70,32 @@ pub fn bbox(gh: &str) -> Option<Polygon<f64>> {
     }
 }
 
+// Cache of decoded cells, so that geohashes which are decoded repeatedly
+// (like the top levels of every covering) are only decoded once per thread.
+// It's cleared whenever it fills up, to keep memory use bounded for
+// streams of many distinct geohashes.
+const BBOX_CACHE_SIZE: usize = 100_000;
+
+thread_local! {
+    static BBOX_CACHE: RefCell<HashMap<String, Polygon<f64>>> = RefCell::new(HashMap::new());
+}
+
+pub fn bbox(gh: &str) -> Option<Polygon<f64>> {
+    BBOX_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if let Some(poly) = cache.get(gh) {
+            return Some(poly.clone());
+        }
+        let poly = decode_polygon(gh)?;
+        if cache.len() >= BBOX_CACHE_SIZE {
+            cache.clear();
+        }
+        cache.insert(gh.to_string(), poly.clone());
+        Some(poly)
+    })
+}
+
+// The geohashes at the given level intersecting the geometry, sorted lexicographically
 pub fn covering(geom: &Geometry<f64>, level: usize) -> Vec<String> {
     let mut ghs: Vec<String> = vec![];
     let mut queue: Vec<String> = vec!["".to_string()];
@@ -70,9 +114,242 @@ pub fn covering(geom: &Geometry<f64>, level: usize) -> Vec<String> {
             None => (),
         }
     }
+    ghs.sort();
     ghs
 }
 
+// Sort geohashes by their cells, in rows from north to south
+// and from west to east within each row
+pub fn sort_row_major(ghs: &mut [String]) {
+    let corner = |gh: &String| {
+        geohash::decode_bbox(gh)
+            .map(|rect| (-rect.max.y, rect.min.x))
+            .unwrap_or((f64::INFINITY, f64::INFINITY))
+    };
+    ghs.sort_by(|a, b| {
+        let (a_corner, b_corner) = (corner(a), corner(b));
+        a_corner
+            .0
+            .total_cmp(&b_corner.0)
+            .then(a_corner.1.total_cmp(&b_corner.1))
+            .then_with(|| a.cmp(b))
+    });
+}
+
+// Upper bound on the number of geohashes in the covering at the given level,
+// without enumerating them: the number of cells intersecting the geometry's bbox.
+pub fn estimate_covering(geom: &Geometry<f64>, level: usize) -> u64 {
+    if coord_count::is_empty(geom) {
+        return 0;
+    }
+    let rect = bbox::bbox(geom);
+    // Geohash bits alternate between lon and lat, starting with lon
+    let lon_bits = (5 * level as u32).div_ceil(2);
+    let lat_bits = 5 * level as u32 / 2;
+    let cells = |min: f64, max: f64, origin: f64, span: f64, bits: u32| -> u64 {
+        let size = span / 2f64.powi(bits as i32);
+        let last = 2f64.powi(bits as i32) - 1.0;
+        // Cells which only touch the bbox are counted, as they are in the covering
+        let first = (((min - origin) / size).ceil() - 1.0).clamp(0.0, last);
+        let end = ((max - origin) / size).floor().clamp(0.0, last);
+        (end - first) as u64 + 1
+    };
+    cells(rect.min.x, rect.max.x, -180.0, 360.0, lon_bits)
+        * cells(rect.min.y, rect.max.y, -90.0, 180.0, lat_bits)
+}
+
+// Replace every complete set of 32 sibling geohashes with their parent,
+// repeating until no complete sets remain. The result covers the same
+// area with a (usually much smaller) set of mixed-length geohashes.
+pub fn compact(ghs: Vec<String>) -> Vec<String> {
+    let mut set: BTreeSet<String> = ghs.into_iter().collect();
+    loop {
+        let parents: BTreeSet<String> = set
+            .iter()
+            .filter(|gh| !gh.is_empty())
+            .map(|gh| gh[..gh.len() - 1].to_string())
+            .filter(|parent| children(parent).iter().all(|child| set.contains(child)))
+            .collect();
+        if parents.is_empty() {
+            break;
+        }
+        for parent in parents {
+            for child in children(&parent) {
+                set.remove(&child);
+            }
+            set.insert(parent);
+        }
+    }
+    set.into_iter().collect()
+}
+
+// Keep only the geohashes on the edge of a set of equal-length geohashes:
+// those with at least 1 of their 8 neighbors missing from the set.
+pub fn hollow(ghs: &[String]) -> Vec<String> {
+    let set: BTreeSet<&str> = ghs.iter().map(|gh| gh.as_str()).collect();
+    ghs.iter()
+        .filter(|gh| match geohash::neighbors(gh) {
+            Ok(n) => [n.n, n.ne, n.e, n.se, n.s, n.sw, n.w, n.nw]
+                .iter()
+                .any(|neighbor| !set.contains(neighbor.as_str())),
+            Err(_) => true,
+        })
+        .cloned()
+        .collect()
+}
+
+type GridPoint = (i64, i64);
+
+fn turn_left((dx, dy): GridPoint) -> GridPoint {
+    (-dy, dx)
+}
+
+// Follow unvisited boundary edges from the given one until returning to its start.
+// Where several edges leave a vertex (cells touching only at a corner), the leftmost
+// turn is taken so that each ring stays on the edge of a single group of cells.
+fn trace_ring(
+    start: (GridPoint, GridPoint),
+    outgoing: &mut HashMap<GridPoint, Vec<GridPoint>>,
+) -> Vec<GridPoint> {
+    let mut ring = vec![start.0];
+    let (mut from, mut to) = start;
+    while to != start.0 {
+        ring.push(to);
+        let dir = (to.0 - from.0, to.1 - from.1);
+        let options = outgoing.get_mut(&to).unwrap();
+        let left = turn_left(dir);
+        let right = turn_left(turn_left(left));
+        let next_pos = [left, dir, right]
+            .iter()
+            .filter_map(|d| options.iter().position(|p| *p == (to.0 + d.0, to.1 + d.1)))
+            .next()
+            .unwrap();
+        let next = options.remove(next_pos);
+        from = to;
+        to = next;
+    }
+    ring.push(start.0);
+    ring
+}
+
+// Drop vertices in the middle of straight runs of edges
+fn remove_collinear(ring: Vec<GridPoint>) -> Vec<GridPoint> {
+    let n = ring.len() - 1;
+    let mut output: Vec<GridPoint> = (0..n)
+        .filter(|&i| {
+            let prev = ring[(i + n - 1) % n];
+            let curr = ring[i];
+            let next = ring[(i + 1) % n];
+            (curr.0 - prev.0) * (next.1 - curr.1) != (curr.1 - prev.1) * (next.0 - curr.0)
+        })
+        .map(|i| ring[i])
+        .collect();
+    output.push(output[0]);
+    output
+}
+
+// Merge the given (equal-length) geohashes into the Polygon or MultiPolygon
+// covering the same area. Returns None if no valid geohashes are given.
+pub fn dissolve(ghs: &[String]) -> Option<Geometry<f64>> {
+    let rects: Vec<geo_types::Rect<f64>> = ghs
+        .iter()
+        .filter_map(|gh| geohash::decode_bbox(gh).ok())
+        .collect();
+    if rects.is_empty() {
+        return None;
+    }
+    let width = rects[0].max.x - rects[0].min.x;
+    let height = rects[0].max.y - rects[0].min.y;
+
+    // Boundary edges of the cells on an integer grid, oriented
+    // counter-clockwise around each cell. Edges shared between
+    // 2 cells cancel out, leaving only the outline.
+    let mut edges: HashMap<(GridPoint, GridPoint), usize> = HashMap::new();
+    for rect in &rects {
+        let x = ((rect.min.x + 180.0) / width).round() as i64;
+        let y = ((rect.min.y + 90.0) / height).round() as i64;
+        let corners = [(x, y), (x + 1, y), (x + 1, y + 1), (x, y + 1)];
+        for i in 0..4 {
+            let edge = (corners[i], corners[(i + 1) % 4]);
+            let reverse = (edge.1, edge.0);
+            if let Some(count) = edges.get_mut(&reverse) {
+                *count -= 1;
+                if *count == 0 {
+                    edges.remove(&reverse);
+                }
+            } else {
+                *edges.entry(edge).or_insert(0) += 1;
+            }
+        }
+    }
+
+    let mut outgoing: HashMap<GridPoint, Vec<GridPoint>> = HashMap::new();
+    let mut starts: Vec<(GridPoint, GridPoint)> = edges.keys().cloned().collect();
+    starts.sort();
+    for &(from, to) in &starts {
+        outgoing.entry(from).or_default().push(to);
+    }
+
+    let to_coord = |(x, y): GridPoint| Coordinate {
+        x: x as f64 * width - 180.0,
+        y: y as f64 * height - 90.0,
+    };
+    let mut exteriors: Vec<LineString<f64>> = Vec::new();
+    let mut holes: Vec<(LineString<f64>, Point<f64>)> = Vec::new();
+    for (from, to) in starts {
+        // Skip edges already used by a previously traced ring
+        let options = outgoing.get_mut(&from).unwrap();
+        match options.iter().position(|p| *p == to) {
+            Some(pos) => options.remove(pos),
+            None => continue,
+        };
+        let ring = remove_collinear(trace_ring((from, to), &mut outgoing));
+        let line = LineString(ring.iter().map(|p| to_coord(*p)).collect());
+        if Polygon::new(line.clone(), vec![]).area() > 0.0 {
+            exteriors.push(line);
+        } else {
+            // Cells are always to the left of their boundary edges, so a point
+            // just left of the midpoint of a hole's first edge falls within the
+            // group of cells bordering that hole.
+            let (a, b) = (ring[0], ring[1]);
+            let (dx, dy) = turn_left((b.0 - a.0, b.1 - a.1));
+            let mid = to_coord(a);
+            let end = to_coord(b);
+            let probe = Point::new(
+                (mid.x + end.x) / 2.0 + 0.25 * width * dx.signum() as f64,
+                (mid.y + end.y) / 2.0 + 0.25 * height * dy.signum() as f64,
+            );
+            holes.push((line, probe));
+        }
+    }
+
+    let mut polygons: Vec<Polygon<f64>> = exteriors
+        .into_iter()
+        .map(|ext| Polygon::new(ext, vec![]))
+        .collect();
+    for (hole, probe) in holes {
+        let owner = polygons
+            .iter()
+            .enumerate()
+            .filter(|(_, poly)| Polygon::new(poly.exterior().clone(), vec![]).contains(&probe))
+            .min_by(|(_, a), (_, b)| {
+                a.area().abs()
+                    .partial_cmp(&b.area().abs())
+                    .unwrap()
+            })
+            .map(|(i, _)| i);
+        if let Some(i) = owner {
+            polygons[i].interiors_push(hole);
+        }
+    }
+
+    if polygons.len() == 1 {
+        Some(Geometry::Polygon(polygons.remove(0)))
+    } else {
+        Some(Geometry::MultiPolygon(MultiPolygon(polygons)))
+    }
+}
+
 // Copied from https://github.com/tidwall/geohash-rs/blob/master/src/base32.rs
 // TODO: Move remaining geohash usage to this crate, but for now I just
 // wanted the encoding stuff without importing 2 separate geohash libs
@@ -89,3 +366,58 @@ pub fn encode_long(mut x: u64) -> String {
     }
     str::from_utf8(&bytes).unwrap().to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::geohash::{bbox, children, decode_polygon, descendants, hollow, neighbors, sort_row_major};
+
+    #[test]
+    fn test_hollow() {
+        // A 3x3 block of cells around its center
+        let block = neighbors(&"9q5c".to_string(), true);
+        let center = block[0].clone();
+        let edges = &block[1..];
+        assert_eq!(edges.to_vec(), hollow(&block));
+        assert!(!hollow(&block).contains(&center));
+        assert_eq!(vec![center.clone()], hollow(&[center]));
+    }
+
+    #[test]
+    fn test_sort_row_major() {
+        // The 3x3 block around 9q5c, out of order
+        let mut block = neighbors(&"9q5c".to_string(), true);
+        sort_row_major(&mut block);
+        let exp: Vec<String> = ["9q5d", "9q5f", "9qh4", "9q59", "9q5c", "9qh1", "9q58", "9q5b", "9qh0"]
+            .iter()
+            .map(|gh| gh.to_string())
+            .collect();
+        assert_eq!(exp, block);
+    }
+
+    #[test]
+    fn test_cached_bbox() {
+        let exp = decode_polygon("9q5");
+        assert!(exp.is_some());
+        assert_eq!(exp, bbox("9q5"));
+        // Served from the cache the second time
+        assert_eq!(exp, bbox("9q5"));
+        assert_eq!(None, bbox("9qa"));
+    }
+
+    #[test]
+    fn test_descendants() {
+        let gh = "9q".to_string();
+        assert_eq!(vec![gh.clone()], descendants(&gh, 0));
+        assert_eq!(children(&gh), descendants(&gh, 1));
+        let cells = descendants(&gh, 2);
+        assert_eq!(1024, cells.len());
+        assert_eq!("9q00", cells[0]);
+        assert_eq!("9q01", cells[1]);
+        assert_eq!("9q0z", cells[31]);
+        assert_eq!("9q10", cells[32]);
+        assert_eq!("9qzz", cells[1023]);
+        let mut sorted = cells.clone();
+        sorted.sort();
+        assert_eq!(sorted, cells);
+    }
+}
@@ -0,0 +1,104 @@
+use geo_types::{Coordinate, LineString, Point, Polygon, Rect};
+
+// Length of a degree of latitude on a sphere with the mean Earth radius
+const METERS_PER_DEGREE: f64 = 2.0 * std::f64::consts::PI * 6_371_008.8 / 360.0;
+
+// Cells of a grid which are roughly `size` meters square.
+// Rows are a fixed number of degrees of latitude tall, and each row is
+// divided into cells whose width in degrees grows towards the poles,
+// so that cells stay close to square.
+pub struct Grid {
+    size: f64,
+}
+
+impl Grid {
+    pub fn new(size: f64) -> Grid {
+        Grid { size }
+    }
+
+    fn row_height(&self) -> f64 {
+        self.size / METERS_PER_DEGREE
+    }
+
+    fn cell_width(&self, row: i64) -> f64 {
+        let center = ((row as f64 + 0.5) * self.row_height()).clamp(-89.0, 89.0);
+        self.size / (METERS_PER_DEGREE * center.to_radians().cos())
+    }
+
+    // (row, column) of the cell containing the point
+    pub fn cell(&self, p: &Point<f64>) -> (i64, i64) {
+        let row = (p.y() / self.row_height()).floor() as i64;
+        let col = (p.x() / self.cell_width(row)).floor() as i64;
+        (row, col)
+    }
+
+    // (row, column) of every cell overlapping the rectangle, row by row
+    pub fn cells_covering(&self, rect: &Rect<f64>) -> Vec<(i64, i64)> {
+        let height = self.row_height();
+        let first_row = (rect.min.y / height).floor() as i64;
+        let last_row = (rect.max.y / height).floor() as i64;
+        (first_row..=last_row)
+            .flat_map(|row| {
+                let width = self.cell_width(row);
+                let first_col = (rect.min.x / width).floor() as i64;
+                let last_col = (rect.max.x / width).floor() as i64;
+                (first_col..=last_col).map(move |col| (row, col))
+            })
+            .collect()
+    }
+
+    pub fn cell_polygon(&self, (row, col): (i64, i64)) -> Polygon<f64> {
+        let height = self.row_height();
+        let width = self.cell_width(row);
+        let (x, y) = (col as f64 * width, row as f64 * height);
+        let outer = LineString(vec![
+            Coordinate::from((x, y)),
+            Coordinate::from((x + width, y)),
+            Coordinate::from((x + width, y + height)),
+            Coordinate::from((x, y + height)),
+            Coordinate::from((x, y)),
+        ]);
+        Polygon::new(outer, vec![])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::{bbox::BBoxToPoly, grid::Grid};
+    use geo::algorithm::contains::Contains;
+    use geo::algorithm::intersects::Intersects;
+    use geo_types::{Coordinate, Point, Rect};
+
+    #[test]
+    fn test_cells_contain_their_points() {
+        let grid = Grid::new(1000.0);
+        for p in &[Point::new(-118.25, 34.05), Point::new(151.2, -33.87), Point::new(0.0001, 60.0)] {
+            let cell = grid.cell_polygon(grid.cell(p));
+            assert!(cell.contains(p), "{:?} not in {:?}", p, cell);
+        }
+    }
+
+    #[test]
+    fn test_nearby_points_share_cells() {
+        let grid = Grid::new(1000.0);
+        assert_eq!(grid.cell(&Point::new(10.0001, 10.0001)), grid.cell(&Point::new(10.0002, 10.0002)));
+        assert_ne!(grid.cell(&Point::new(10.0, 10.0)), grid.cell(&Point::new(10.1, 10.0)));
+    }
+
+    #[test]
+    fn test_cells_covering_rect() {
+        let grid = Grid::new(1000.0);
+        let rect = Rect {
+            min: Coordinate { x: 10.0, y: 10.0 },
+            max: Coordinate { x: 10.05, y: 10.02 },
+        };
+        let cells = grid.cells_covering(&rect);
+        for p in &[Point::new(10.0, 10.0), Point::new(10.05, 10.02), Point::new(10.025, 10.01)] {
+            assert!(cells.contains(&grid.cell(p)));
+        }
+        for cell in cells {
+            let poly = grid.cell_polygon(cell);
+            assert!(poly.intersects(&rect.to_polygon()));
+        }
+    }
+}
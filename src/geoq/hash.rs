@@ -0,0 +1,135 @@
+use crate::geoq::{area, wkb};
+use geo_types::{
+    Coordinate, Geometry, GeometryCollection, LineString, MultiLineString, MultiPolygon, Polygon,
+};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use std::cmp::Ordering;
+
+fn compare(a: &Coordinate<f64>, b: &Coordinate<f64>) -> Ordering {
+    a.x.partial_cmp(&b.x)
+        .unwrap_or(Ordering::Equal)
+        .then(a.y.partial_cmp(&b.y).unwrap_or(Ordering::Equal))
+}
+
+fn dedup_line(line: &LineString<f64>) -> LineString<f64> {
+    let mut coords = line.0.clone();
+    coords.dedup();
+    LineString(coords)
+}
+
+// Drop repeated vertices (including the closing one), wind the ring
+// counter-clockwise (or clockwise for holes), and start it at its
+// minimum vertex, so the same ring always gives the same coordinates
+fn normalize_ring(ring: &LineString<f64>, counter_clockwise: bool) -> LineString<f64> {
+    let mut coords = ring.0.clone();
+    coords.dedup();
+    while coords.len() > 1 && coords.first() == coords.last() {
+        coords.pop();
+    }
+    if (area::signed_area(&coords) > 0.0) != counter_clockwise {
+        coords.reverse();
+    }
+    if let Some(start) = (0..coords.len()).min_by(|&i, &j| compare(&coords[i], &coords[j])) {
+        coords.rotate_left(start);
+    }
+    if let Some(&first) = coords.first() {
+        coords.push(first);
+    }
+    LineString(coords)
+}
+
+fn normalize_polygon(poly: &Polygon<f64>) -> Polygon<f64> {
+    let mut interiors: Vec<LineString<f64>> =
+        poly.interiors().iter().map(|ring| normalize_ring(ring, false)).collect();
+    interiors.sort_by(|a, b| {
+        a.0.iter()
+            .zip(b.0.iter())
+            .map(|(p, q)| compare(p, q))
+            .find(|o| *o != Ordering::Equal)
+            .unwrap_or_else(|| a.0.len().cmp(&b.0.len()))
+    });
+    Polygon::new(normalize_ring(poly.exterior(), true), interiors)
+}
+
+// Geometry with a canonical form for each ring, so that equal geometries
+// written with different ring orientations, start vertices, hole orders,
+// or repeated points give the same WKB
+pub fn normalize(geom: &Geometry<f64>) -> Geometry<f64> {
+    match geom {
+        Geometry::Line(l) => Geometry::LineString(dedup_line(&LineString(vec![l.start, l.end]))),
+        Geometry::LineString(ls) => Geometry::LineString(dedup_line(ls)),
+        Geometry::MultiLineString(mls) => {
+            Geometry::MultiLineString(MultiLineString(mls.0.iter().map(dedup_line).collect()))
+        }
+        Geometry::Polygon(poly) => Geometry::Polygon(normalize_polygon(poly)),
+        Geometry::MultiPolygon(mp) => {
+            Geometry::MultiPolygon(MultiPolygon(mp.0.iter().map(normalize_polygon).collect()))
+        }
+        Geometry::GeometryCollection(gc) => {
+            Geometry::GeometryCollection(GeometryCollection(gc.0.iter().map(normalize).collect()))
+        }
+        _ => geom.clone(),
+    }
+}
+
+// Stable SHA-256 hash (hex-encoded) of a geometry's normalized WKB encoding,
+// optionally followed by its properties as JSON.
+// Property keys are serialized in sorted order, so the hash doesn't
+// depend on the order properties were given in.
+pub fn hash(geom: &Geometry<f64>, properties: Option<&Map<String, Value>>) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(wkb::to_wkb(&normalize(geom)));
+    if let Some(props) = properties {
+        hasher.update(serde_json::to_vec(props).unwrap());
+    }
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::hash::hash;
+    use geo_types::{Geometry, LineString, Point, Polygon};
+    use serde_json::{json, Map, Value};
+
+    #[test]
+    fn test_hashing_properties() {
+        let geom = Geometry::Point(Point::new(1.0, 2.0));
+        let mut a: Map<String, Value> = Map::new();
+        a.insert("a".to_string(), json!(1));
+        a.insert("b".to_string(), json!(2));
+        let mut b: Map<String, Value> = Map::new();
+        b.insert("b".to_string(), json!(2));
+        b.insert("a".to_string(), json!(1));
+        assert_eq!(hash(&geom, Some(&a)), hash(&geom, Some(&b)));
+        assert_ne!(hash(&geom, None), hash(&geom, Some(&a)));
+        assert_eq!(64, hash(&geom, None).len());
+    }
+
+    #[test]
+    fn test_hashing_equal_polygons_written_differently() {
+        let poly = |ext: Vec<(f64, f64)>, holes: Vec<Vec<(f64, f64)>>| {
+            let holes = holes.into_iter().map(LineString::from).collect();
+            Geometry::Polygon(Polygon::new(LineString::from(ext), holes))
+        };
+        let hole_a = vec![(1.0, 1.0), (1.0, 2.0), (2.0, 2.0), (1.0, 1.0)];
+        let hole_b = vec![(6.0, 6.0), (6.0, 7.0), (7.0, 7.0), (6.0, 6.0)];
+        let a = poly(
+            vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0), (0.0, 0.0)],
+            vec![hole_a.clone(), hole_b.clone()],
+        );
+        // Clockwise, starting from another vertex, with a repeated point and the holes swapped
+        let b = poly(
+            vec![(10.0, 10.0), (10.0, 0.0), (10.0, 0.0), (0.0, 0.0), (0.0, 10.0), (10.0, 10.0)],
+            vec![hole_b, hole_a.into_iter().rev().collect()],
+        );
+        assert_eq!(hash(&a, None), hash(&b, None));
+
+        let smaller = poly(vec![(0.0, 0.0), (5.0, 0.0), (5.0, 5.0), (0.0, 5.0), (0.0, 0.0)], vec![]);
+        assert_ne!(hash(&a, None), hash(&smaller, None));
+    }
+}
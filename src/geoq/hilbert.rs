@@ -0,0 +1,67 @@
+use geo_types::Point;
+
+// Number of bits of precision for each of the x and y axes
+pub const ORDER: u32 = 16;
+
+// Position along a Hilbert curve filling a 2^order x 2^order grid
+fn xy_to_index(mut x: u64, mut y: u64, order: u32) -> u64 {
+    let n = 1u64 << order;
+    let mut index = 0;
+    let mut s = n / 2;
+    while s > 0 {
+        let rx = u64::from(x & s > 0);
+        let ry = u64::from(y & s > 0);
+        index += s * s * ((3 * rx) ^ ry);
+        // Rotate the quadrant so the curve stays continuous
+        if ry == 0 {
+            if rx == 1 {
+                x = s - 1 - (x & (s - 1));
+                y = s - 1 - (y & (s - 1));
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+        s /= 2;
+    }
+    index
+}
+
+// Hilbert curve index of a Lon/Lat point, on a global grid of 2^ORDER cells per axis.
+// Nearby points usually have nearby indices, so sorting by it gives good locality.
+pub fn index(p: &Point<f64>) -> u64 {
+    let cells = (1u64 << ORDER) as f64;
+    let cell = |value: f64, min: f64, span: f64| -> u64 {
+        (((value - min) / span * cells).floor()).clamp(0.0, cells - 1.0) as u64
+    };
+    xy_to_index(cell(p.x(), -180.0, 360.0), cell(p.y(), -90.0, 180.0), ORDER)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::hilbert::{index, xy_to_index};
+    use geo_types::Point;
+
+    #[test]
+    fn test_order_1_curve() {
+        // Starts in the bottom left, moves up, across, and back down
+        let order: Vec<u64> = [(0, 0), (0, 1), (1, 1), (1, 0)]
+            .iter()
+            .map(|&(x, y)| xy_to_index(x, y, 1))
+            .collect();
+        assert_eq!(vec![0, 1, 2, 3], order);
+    }
+
+    #[test]
+    fn test_curve_visits_each_cell_once() {
+        let mut indices: Vec<u64> = (0..16)
+            .flat_map(|x| (0..16).map(move |y| xy_to_index(x, y, 4)))
+            .collect();
+        indices.sort();
+        assert_eq!((0..256).collect::<Vec<u64>>(), indices);
+    }
+
+    #[test]
+    fn test_point_index_bounds() {
+        assert_eq!(0, index(&Point::new(-180.0, -90.0)));
+        assert!(index(&Point::new(180.0, 90.0)) < 1 << 32);
+    }
+}
@@ -7,7 +7,10 @@ static LATLON: Lazy<Regex> = Lazy::new(|| Regex::new(r"^-?\d+\.?\d*[,\t]-?\d+\.?
 static GH: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?i)^[0-9a-z--a--i--l--o]+$").unwrap());
 static JSON: Lazy<Regex> = Lazy::new(|| Regex::new(r"\{").unwrap());
 static WKT: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r"(?ix)^point|linestring|polygon|multipoint|multilinestring|multipolygon").unwrap()
+    Regex::new(
+        r"(?i)^\s*(point|linestring|polygon|multipoint|multilinestring|multipolygon|geometrycollection)",
+    )
+    .unwrap()
 });
 
 #[derive(Debug, Clone)]
@@ -53,4 +56,12 @@ fn reading_input_formats() {
         Ok(Input::LatLon(_)) => assert!(true),
         _ => assert!(false),
     }
+    assert!(matches!(
+        read_line("GEOMETRYCOLLECTION(POINT(1 2))".to_string()),
+        Ok(Input::WKT(_))
+    ));
+    assert!(matches!(
+        read_line("GEOMETRYCOLLECTION EMPTY".to_string()),
+        Ok(Input::WKT(_))
+    ));
 }
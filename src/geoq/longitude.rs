@@ -0,0 +1,256 @@
+use crate::geoq::clip;
+use geo::algorithm::area::Area;
+use geo::algorithm::map_coords::MapCoords;
+use geo_types::{
+    Coordinate, Geometry, GeometryCollection, LineString, MultiLineString, MultiPolygon, Polygon,
+};
+
+type Coord = Coordinate<f64>;
+
+// Wrap a longitude into [-180, 180]. In-range values are left as-is,
+// so 180 and -180 are both kept.
+pub fn wrap(lon: f64) -> f64 {
+    if (-180.0..=180.0).contains(&lon) {
+        lon
+    } else {
+        (lon + 180.0).rem_euclid(360.0) - 180.0
+    }
+}
+
+// Wrap a longitude into [0, 360]
+pub fn wrap_0_360(lon: f64) -> f64 {
+    if (0.0..=360.0).contains(&lon) {
+        lon
+    } else {
+        lon.rem_euclid(360.0)
+    }
+}
+
+pub fn normalize(geom: &Geometry<f64>) -> Geometry<f64> {
+    geom.map_coords(&|&(x, y)| (wrap(x), y))
+}
+
+pub fn normalize_0_360(geom: &Geometry<f64>) -> Geometry<f64> {
+    geom.map_coords(&|&(x, y)| (wrap_0_360(x), y))
+}
+
+// Shift longitudes so that no segment jumps more than 180 degrees,
+// extending past [-180, 180] where a line or ring crosses the antimeridian.
+// The first longitude is taken to within 180 degrees of `start`.
+fn unwrap(coords: &[Coord], start: f64) -> Vec<Coord> {
+    let mut output: Vec<Coord> = Vec::with_capacity(coords.len());
+    let mut prev = start;
+    for c in coords {
+        let x = prev + wrap(c.x - prev);
+        output.push(Coordinate { x, y: c.y });
+        prev = x;
+    }
+    output
+}
+
+// Index of the 360 degree band (centered on 0) containing a longitude.
+// Band 0 is [-180, 180).
+fn band(lon: f64) -> f64 {
+    ((lon + 180.0) / 360.0).floor()
+}
+
+// Shift unwrapped coordinates into [-180, 180], based on the band
+// containing the middle of their longitude range
+fn rewrap(coords: Vec<Coord>) -> Vec<Coord> {
+    let min_x = coords.iter().map(|c| c.x).fold(f64::INFINITY, f64::min);
+    let max_x = coords.iter().map(|c| c.x).fold(f64::NEG_INFINITY, f64::max);
+    let offset = band((min_x + max_x) / 2.0) * 360.0;
+    coords
+        .into_iter()
+        .map(|c| Coordinate { x: c.x - offset, y: c.y })
+        .collect()
+}
+
+// Whether an unwrapped longitude lies on the antimeridian, or one of
+// its copies 360 degrees apart
+fn on_seam(lon: f64) -> bool {
+    (lon - 180.0).rem_euclid(360.0) == 0.0
+}
+
+fn split_line(line: &LineString<f64>) -> Vec<LineString<f64>> {
+    let start = line.0.first().map(|c| wrap(c.x)).unwrap_or(0.0);
+    let coords = unwrap(&line.0, start);
+    let mut pieces: Vec<Vec<Coord>> = Vec::new();
+    let mut current: Vec<Coord> = coords.iter().take(1).cloned().collect();
+    // Longitude of the last vertex which wasn't on a seam
+    let mut prev_off_seam: Option<f64> = None;
+    for pair in coords.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        if !on_seam(a.x) {
+            prev_off_seam = Some(a.x);
+        }
+        // Lines which reach a seam at a vertex and carry on to the other side
+        // are split at that vertex, rather than only touching it
+        if on_seam(a.x) && !on_seam(b.x) {
+            if let Some(prev) = prev_off_seam {
+                if (prev - a.x) * (b.x - a.x) < 0.0 {
+                    pieces.push(current);
+                    current = vec![a];
+                }
+            }
+        }
+        // Segments span at most 180 degrees, so cross at most 1 seam
+        let seam = 180.0 + band(a.x.min(b.x)) * 360.0;
+        if (a.x - seam) * (b.x - seam) < 0.0 {
+            let t = (seam - a.x) / (b.x - a.x);
+            let crossing = Coordinate { x: seam, y: a.y + t * (b.y - a.y) };
+            current.push(crossing);
+            pieces.push(current);
+            current = vec![crossing];
+        }
+        current.push(b);
+    }
+    pieces.push(current);
+    pieces.into_iter().map(|p| LineString(rewrap(p))).collect()
+}
+
+fn split_polygon(poly: &Polygon<f64>) -> Vec<Polygon<f64>> {
+    let first = poly.exterior().0.first().map(|c| wrap(c.x)).unwrap_or(0.0);
+    let exterior = unwrap(&poly.exterior().0, first);
+    let start = exterior.first().map(|c| c.x).unwrap_or(0.0);
+    let interiors: Vec<LineString<f64>> = poly
+        .interiors()
+        .iter()
+        .map(|ring| LineString(unwrap(&ring.0, start)))
+        .collect();
+    let exterior = LineString(exterior);
+
+    let min_x = exterior.0.iter().map(|c| c.x).fold(f64::INFINITY, f64::min);
+    let max_x = exterior.0.iter().map(|c| c.x).fold(f64::NEG_INFINITY, f64::max);
+    let mut pieces = Vec::new();
+    let mut b = band(min_x);
+    while b <= band(max_x) {
+        let (lo, hi) = (b * 360.0 - 180.0, b * 360.0 + 180.0);
+        let clip_ring = |ring: &LineString<f64>| {
+            clip::clip_ring_to_half_plane(ring, |c| c.x - lo)
+                .and_then(|r| clip::clip_ring_to_half_plane(&r, |c| hi - c.x))
+                .map(|r| {
+                    let offset = b * 360.0;
+                    LineString(
+                        r.0.into_iter()
+                            .map(|c| Coordinate { x: c.x - offset, y: c.y })
+                            .collect(),
+                    )
+                })
+        };
+        if let Some(ext) = clip_ring(&exterior) {
+            let piece = Polygon::new(ext, interiors.iter().filter_map(clip_ring).collect());
+            if piece.area() != 0.0 {
+                pieces.push(piece);
+            }
+        }
+        b += 1.0;
+    }
+    pieces
+}
+
+// Wrap longitudes into [-180, 180], splitting LineStrings and Polygons
+// which cross the antimeridian into Multi- geometries
+pub fn split(geom: &Geometry<f64>) -> Geometry<f64> {
+    match geom {
+        Geometry::LineString(g) => {
+            let mut lines = split_line(g);
+            if lines.len() == 1 {
+                Geometry::LineString(lines.remove(0))
+            } else {
+                Geometry::MultiLineString(MultiLineString(lines))
+            }
+        }
+        Geometry::MultiLineString(g) => Geometry::MultiLineString(MultiLineString(
+            g.0.iter().flat_map(split_line).collect(),
+        )),
+        Geometry::Polygon(g) => {
+            let mut polys = split_polygon(g);
+            if polys.len() == 1 {
+                Geometry::Polygon(polys.remove(0))
+            } else {
+                Geometry::MultiPolygon(MultiPolygon(polys))
+            }
+        }
+        Geometry::MultiPolygon(g) => Geometry::MultiPolygon(MultiPolygon(
+            g.0.iter().flat_map(split_polygon).collect(),
+        )),
+        Geometry::GeometryCollection(gc) => {
+            Geometry::GeometryCollection(GeometryCollection(gc.0.iter().map(split).collect()))
+        }
+        _ => normalize(geom),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::longitude::{split, wrap, wrap_0_360};
+    use geo_types::{Geometry, LineString, MultiLineString, Polygon};
+
+    #[test]
+    fn test_wrapping_longitudes() {
+        assert_eq!(-170.0, wrap(190.0));
+        assert_eq!(180.0, wrap(180.0));
+        assert_eq!(-180.0, wrap(-180.0));
+        assert_eq!(10.0, wrap(370.0));
+        assert_eq!(190.0, wrap_0_360(-170.0));
+        assert_eq!(0.0, wrap_0_360(0.0));
+    }
+
+    #[test]
+    fn test_splitting_lines_at_antimeridian() {
+        let line: LineString<f64> = vec![(170.0, 0.0), (190.0, 10.0)].into();
+        let exp = MultiLineString(vec![
+            vec![(170.0, 0.0), (180.0, 5.0)].into(),
+            vec![(-180.0, 5.0), (-170.0, 10.0)].into(),
+        ]);
+        assert_eq!(
+            Geometry::MultiLineString(exp),
+            split(&Geometry::LineString(line))
+        );
+    }
+
+    #[test]
+    fn test_splitting_lines_at_vertices_on_antimeridian() {
+        let line: LineString<f64> = vec![(170.0, 0.0), (180.0, 5.0), (190.0, 10.0)].into();
+        let exp = MultiLineString(vec![
+            vec![(170.0, 0.0), (180.0, 5.0)].into(),
+            vec![(-180.0, 5.0), (-170.0, 10.0)].into(),
+        ]);
+        assert_eq!(Geometry::MultiLineString(exp), split(&Geometry::LineString(line)));
+
+        let westward: LineString<f64> =
+            vec![(-170.0, 0.0), (-180.0, 0.0), (-180.0, 5.0), (170.0, 5.0)].into();
+        let exp = MultiLineString(vec![
+            vec![(-170.0, 0.0), (-180.0, 0.0), (-180.0, 5.0)].into(),
+            vec![(180.0, 5.0), (170.0, 5.0)].into(),
+        ]);
+        assert_eq!(Geometry::MultiLineString(exp), split(&Geometry::LineString(westward)));
+
+        // Lines which only touch the antimeridian aren't split
+        let touching: LineString<f64> = vec![(170.0, 0.0), (180.0, 5.0), (170.0, 10.0)].into();
+        assert_eq!(Geometry::LineString(touching.clone()), split(&Geometry::LineString(touching)));
+    }
+
+    #[test]
+    fn test_splitting_polygons_at_antimeridian() {
+        let ring: LineString<f64> =
+            vec![(170.0, 0.0), (190.0, 0.0), (190.0, 10.0), (170.0, 10.0), (170.0, 0.0)].into();
+        match split(&Geometry::Polygon(Polygon::new(ring, vec![]))) {
+            Geometry::MultiPolygon(mp) => {
+                assert_eq!(2, mp.0.len());
+                for p in mp.0 {
+                    assert!(p.exterior().0.iter().all(|c| c.x.abs() <= 180.0));
+                }
+            }
+            g => panic!("Expected a MultiPolygon, got {:?}", g),
+        }
+    }
+
+    #[test]
+    fn test_leaving_lines_which_dont_cross() {
+        let line: LineString<f64> = vec![(350.0, 0.0), (355.0, 10.0)].into();
+        let exp: LineString<f64> = vec![(-10.0, 0.0), (-5.0, 10.0)].into();
+        assert_eq!(Geometry::LineString(exp), split(&Geometry::LineString(line)));
+    }
+}
@@ -1,16 +1,46 @@
+pub mod area;
 pub mod browser_open;
+pub mod buffer;
 pub mod centroid;
+pub mod clip;
+pub mod cluster;
 pub mod commands;
 pub mod contains;
+pub mod conversion;
 pub mod distance;
 pub mod coord_count;
+pub mod covers;
+pub mod crs;
+pub mod delaunay;
+pub mod densify;
 pub mod entity;
+pub mod ellipsoid;
+pub mod equals;
 pub mod error;
+pub mod flip;
 pub mod geohash;
+pub mod grid;
+pub mod hash;
+pub mod hilbert;
 pub mod input;
+pub mod longitude;
+pub mod multi;
+pub mod node;
 pub mod intersection;
+pub mod output;
 pub mod par;
+pub mod polygon_index;
+pub mod polygonize;
 pub mod reader;
+pub mod resample;
+pub mod rfc7946;
 pub mod simplify;
+pub mod smooth;
+pub mod split;
 pub mod text;
+pub mod units;
+pub mod valid;
+pub mod voronoi;
+pub mod winding;
+pub mod wkb;
 pub mod bbox;
@@ -0,0 +1,65 @@
+use geo_types::{Geometry, LineString, MultiLineString, MultiPoint, MultiPolygon};
+
+// Promote single geometries to their 1-element Multi- equivalent.
+// Multi- geometries and GeometryCollections are left as-is.
+pub fn to_multi(geom: Geometry<f64>) -> Geometry<f64> {
+    match geom {
+        Geometry::Point(g) => Geometry::MultiPoint(MultiPoint(vec![g])),
+        Geometry::Line(g) => {
+            Geometry::MultiLineString(MultiLineString(vec![LineString(vec![g.start, g.end])]))
+        }
+        Geometry::LineString(g) => Geometry::MultiLineString(MultiLineString(vec![g])),
+        Geometry::Polygon(g) => Geometry::MultiPolygon(MultiPolygon(vec![g])),
+        _ => geom,
+    }
+}
+
+// Demote 1-element Multi- geometries to their single equivalent.
+// Multi- geometries with more (or fewer) elements can't be demoted,
+// so they are returned as the Err value.
+pub fn to_single(geom: Geometry<f64>) -> Result<Geometry<f64>, Geometry<f64>> {
+    match geom {
+        Geometry::MultiPoint(mut g) if g.0.len() == 1 => Ok(Geometry::Point(g.0.remove(0))),
+        Geometry::MultiLineString(mut g) if g.0.len() == 1 => {
+            Ok(Geometry::LineString(g.0.remove(0)))
+        }
+        Geometry::MultiPolygon(mut g) if g.0.len() == 1 => Ok(Geometry::Polygon(g.0.remove(0))),
+        Geometry::MultiPoint(_) | Geometry::MultiLineString(_) | Geometry::MultiPolygon(_) => {
+            Err(geom)
+        }
+        _ => Ok(geom),
+    }
+}
+
+// Members of Multi- geometries and GeometryCollections, or else the geometry itself
+pub fn components(geom: Geometry<f64>) -> Vec<Geometry<f64>> {
+    match geom {
+        Geometry::MultiPoint(mp) => mp.0.into_iter().map(Geometry::Point).collect(),
+        Geometry::MultiLineString(mls) => mls.0.into_iter().map(Geometry::LineString).collect(),
+        Geometry::MultiPolygon(mp) => mp.0.into_iter().map(Geometry::Polygon).collect(),
+        Geometry::GeometryCollection(gc) => gc.0,
+        _ => vec![geom],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::multi::{to_multi, to_single};
+    use geo_types::{Geometry, MultiPoint, Point};
+
+    #[test]
+    fn test_round_tripping_single_geometries() {
+        let point = Geometry::Point(Point::new(1.0, 2.0));
+        let multi = to_multi(point.clone());
+        assert_eq!(Geometry::MultiPoint(MultiPoint(vec![Point::new(1.0, 2.0)])), multi);
+        assert_eq!(multi.clone(), to_multi(multi.clone()));
+        assert_eq!(Ok(point.clone()), to_single(multi));
+        assert_eq!(Ok(point.clone()), to_single(point));
+    }
+
+    #[test]
+    fn test_multi_element_geometries_cant_be_demoted() {
+        let multi = Geometry::MultiPoint(MultiPoint(vec![Point::new(1.0, 2.0), Point::new(3.0, 4.0)]));
+        assert_eq!(Err(multi.clone()), to_single(multi));
+    }
+}
@@ -0,0 +1,268 @@
+use geo_types::{Coordinate, LineString};
+use std::cmp::Ordering::Equal;
+
+type Coord = Coordinate<f64>;
+// Pieces of each of a list of lines
+type Pieces = Vec<Vec<LineString<f64>>>;
+
+// A point at which to split a line: `t` of the way along segment `segment`
+struct Split {
+    segment: usize,
+    t: f64,
+    coord: Coord,
+}
+
+fn bounds_overlap(a: (Coord, Coord), b: (Coord, Coord)) -> bool {
+    a.0.x.min(a.1.x) <= b.0.x.max(b.1.x)
+        && b.0.x.min(b.1.x) <= a.0.x.max(a.1.x)
+        && a.0.y.min(a.1.y) <= b.0.y.max(b.1.y)
+        && b.0.y.min(b.1.y) <= a.0.y.max(a.1.y)
+}
+
+fn cross(a: Coord, b: Coord) -> f64 {
+    a.x * b.y - a.y * b.x
+}
+
+fn sub(a: Coord, b: Coord) -> Coord {
+    Coordinate { x: a.x - b.x, y: a.y - b.y }
+}
+
+// Points where segments ab and cd meet, as (t along ab, u along cd, point).
+// Collinear overlapping segments meet at the ends of the overlap.
+fn intersections(a: Coord, b: Coord, c: Coord, d: Coord) -> Vec<(f64, f64, Coord)> {
+    if !bounds_overlap((a, b), (c, d)) {
+        return vec![];
+    }
+    let ab = sub(b, a);
+    let cd = sub(d, c);
+    let denom = cross(ab, cd);
+    if denom != 0.0 {
+        let t = cross(sub(c, a), cd) / denom;
+        let u = cross(sub(c, a), ab) / denom;
+        if !(0.0..=1.0).contains(&t) || !(0.0..=1.0).contains(&u) {
+            return vec![];
+        }
+        // Use the exact vertex where the segments meet at one
+        let point = if t == 0.0 {
+            a
+        } else if t == 1.0 {
+            b
+        } else if u == 0.0 {
+            c
+        } else if u == 1.0 {
+            d
+        } else {
+            Coordinate { x: a.x + t * ab.x, y: a.y + t * ab.y }
+        };
+        return vec![(t, u, point)];
+    }
+    if cross(sub(c, a), ab) != 0.0 {
+        // Parallel, but not collinear
+        return vec![];
+    }
+    let param = |p: Coord, origin: Coord, dir: Coord| {
+        let len2 = dir.x * dir.x + dir.y * dir.y;
+        if len2 == 0.0 {
+            0.0
+        } else {
+            let offset = sub(p, origin);
+            (offset.x * dir.x + offset.y * dir.y) / len2
+        }
+    };
+    let mut output = Vec::new();
+    for &p in &[a, b] {
+        let u = param(p, c, cd);
+        if (0.0..=1.0).contains(&u) {
+            output.push((param(p, a, ab), u, p));
+        }
+    }
+    for &p in &[c, d] {
+        let t = param(p, a, ab);
+        if (0.0..=1.0).contains(&t) {
+            output.push((t, param(p, c, cd), p));
+        }
+    }
+    output
+}
+
+fn split_line(line: &LineString<f64>, mut splits: Vec<Split>) -> Vec<LineString<f64>> {
+    splits.sort_by(|a, b| (a.segment, a.t).partial_cmp(&(b.segment, b.t)).unwrap());
+    let coords = &line.0;
+    let mut pieces = Vec::new();
+    let mut current = vec![coords[0]];
+    let mut splits = splits.into_iter().peekable();
+    for segment in 0..coords.len() - 1 {
+        while let Some(split) = splits.next_if(|s| s.segment == segment) {
+            if split.coord != *current.last().unwrap() {
+                current.push(split.coord);
+            }
+            if current.len() > 1 {
+                pieces.push(LineString(current));
+                current = vec![split.coord];
+            }
+        }
+        if coords[segment + 1] != *current.last().unwrap() {
+            current.push(coords[segment + 1]);
+        }
+    }
+    if current.len() > 1 {
+        pieces.push(LineString(current));
+    }
+    pieces
+}
+
+// Split lines at the points where their segments meet, comparing
+// only the pairs of lines (by index) for which `compare` holds.
+fn node_where<F>(lines: &[LineString<f64>], compare: F) -> Pieces
+where
+    F: Fn(usize, usize) -> bool,
+{
+    let segments: Vec<(usize, usize, Coord, Coord)> = lines
+        .iter()
+        .enumerate()
+        .flat_map(|(l, line)| {
+            line.0
+                .windows(2)
+                .enumerate()
+                .map(move |(s, pair)| (l, s, pair[0], pair[1]))
+        })
+        .collect();
+
+    // Sweep across the segments from west to east, so that each is only
+    // compared against those overlapping it in longitude
+    let min_x = |seg: &(usize, usize, Coord, Coord)| seg.2.x.min(seg.3.x);
+    let max_x = |seg: &(usize, usize, Coord, Coord)| seg.2.x.max(seg.3.x);
+    let mut order: Vec<usize> = (0..segments.len()).collect();
+    order.sort_by(|&i, &j| min_x(&segments[i]).partial_cmp(&min_x(&segments[j])).unwrap_or(Equal));
+
+    let mut splits: Vec<Vec<Split>> = lines.iter().map(|_| Vec::new()).collect();
+    for (pos, &i) in order.iter().enumerate() {
+        let east = max_x(&segments[i]);
+        for &j in order[pos + 1..].iter().take_while(|&&j| min_x(&segments[j]) <= east) {
+            // Compare each pair in the order the segments appear in the lines
+            let (&(l1, s1, a, b), &(l2, s2, c, d)) = if i < j {
+                (&segments[i], &segments[j])
+            } else {
+                (&segments[j], &segments[i])
+            };
+            if !compare(l1, l2) {
+                continue;
+            }
+            // Consecutive segments of a line always meet at their shared vertex
+            let adjacent = l1 == l2 && s2 == s1 + 1;
+            for (t, u, coord) in intersections(a, b, c, d) {
+                if adjacent && t == 1.0 && u == 0.0 {
+                    continue;
+                }
+                splits[l1].push(Split { segment: s1, t, coord });
+                splits[l2].push(Split { segment: s2, t: u, coord });
+            }
+        }
+    }
+
+    lines
+        .iter()
+        .zip(splits)
+        .map(|(line, splits)| {
+            if line.0.len() < 2 {
+                vec![line.clone()]
+            } else {
+                split_line(line, splits)
+            }
+        })
+        .collect()
+}
+
+// Split lines wherever they meet or cross one another (or themselves),
+// so that lines only meet at their endpoints. Gives the pieces of each line, in order.
+pub fn node(lines: &[LineString<f64>]) -> Pieces {
+    node_where(lines, |_, _| true)
+}
+
+// Split the subject lines where they meet any of the blades, and the blades
+// where they meet any of the subjects. Subjects aren't split where they meet
+// each other (or themselves), and neither are blades.
+// Gives the pieces of each subject, then the pieces of each blade.
+pub fn split(
+    subjects: &[LineString<f64>],
+    blades: &[LineString<f64>],
+) -> (Pieces, Pieces) {
+    let num_subjects = subjects.len();
+    let lines: Vec<LineString<f64>> = subjects.iter().chain(blades).cloned().collect();
+    let mut pieces = node_where(&lines, |l1, l2| (l1 < num_subjects) != (l2 < num_subjects));
+    let blade_pieces = pieces.split_off(num_subjects);
+    (pieces, blade_pieces)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::node::{node, split};
+    use geo_types::LineString;
+
+    fn line(coords: &[(f64, f64)]) -> LineString<f64> {
+        coords.to_vec().into()
+    }
+
+    #[test]
+    fn test_noding_crossing_lines() {
+        let noded = node(&[line(&[(0.0, 0.0), (2.0, 2.0)]), line(&[(0.0, 2.0), (2.0, 0.0)])]);
+        assert_eq!(
+            vec![
+                vec![line(&[(0.0, 0.0), (1.0, 1.0)]), line(&[(1.0, 1.0), (2.0, 2.0)])],
+                vec![line(&[(0.0, 2.0), (1.0, 1.0)]), line(&[(1.0, 1.0), (2.0, 0.0)])],
+            ],
+            noded
+        );
+    }
+
+    #[test]
+    fn test_noding_at_existing_vertex() {
+        // The second line ends on a vertex in the middle of the first
+        let noded = node(&[
+            line(&[(0.0, 0.0), (1.0, 0.0), (2.0, 0.0)]),
+            line(&[(1.0, 0.0), (1.0, 1.0)]),
+        ]);
+        assert_eq!(
+            vec![line(&[(0.0, 0.0), (1.0, 0.0)]), line(&[(1.0, 0.0), (2.0, 0.0)])],
+            noded[0]
+        );
+        assert_eq!(vec![line(&[(1.0, 0.0), (1.0, 1.0)])], noded[1]);
+    }
+
+    #[test]
+    fn test_noding_self_intersection() {
+        let bowtie = line(&[(0.0, 0.0), (2.0, 2.0), (2.0, 0.0), (0.0, 2.0)]);
+        let noded = node(&[bowtie]);
+        assert_eq!(
+            vec![
+                line(&[(0.0, 0.0), (1.0, 1.0)]),
+                line(&[(1.0, 1.0), (2.0, 2.0), (2.0, 0.0), (1.0, 1.0)]),
+                line(&[(1.0, 1.0), (0.0, 2.0)]),
+            ],
+            noded[0]
+        );
+    }
+
+    #[test]
+    fn test_noding_closed_ring_unchanged() {
+        let ring = line(&[(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 0.0)]);
+        assert_eq!(vec![vec![ring.clone()]], node(&[ring]));
+    }
+
+    #[test]
+    fn test_splitting_only_against_blades() {
+        let bowtie = line(&[(0.0, 0.0), (2.0, 2.0), (2.0, 0.0), (0.0, 2.0)]);
+        let blade = line(&[(1.5, -1.0), (1.5, 3.0)]);
+        let (pieces, blade_pieces) = split(&[bowtie], &[blade]);
+        // Not split at its own crossing at (1, 1)
+        assert_eq!(
+            vec![
+                line(&[(0.0, 0.0), (1.5, 1.5)]),
+                line(&[(1.5, 1.5), (2.0, 2.0), (2.0, 0.0), (1.5, 0.5)]),
+                line(&[(1.5, 0.5), (0.0, 2.0)]),
+            ],
+            pieces[0]
+        );
+        assert_eq!(3, blade_pieces[0].len());
+    }
+}
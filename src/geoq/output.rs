@@ -0,0 +1,84 @@
+use crate::geoq::{conversion, coord_count, entity::Entity, error::Error};
+use geo_types::{Geometry, Point};
+use geojson::GeoJson;
+use once_cell::sync::OnceCell;
+use wkt::ToWkt;
+
+static PRETTY: OnceCell<bool> = OnceCell::new();
+
+// Set from the global --pretty flag
+pub fn set_pretty(pretty: bool) {
+    PRETTY.set(pretty).expect("Pretty-printing should only be set once");
+}
+
+pub fn pretty() -> bool {
+    PRETTY.get().cloned().unwrap_or(false)
+}
+
+// Serialize a GeoJSON Geometry, Feature, or FeatureCollection for output.
+// GeoJSON is compact (1 per line) unless --pretty was given.
+pub fn geojson<T: Into<GeoJson>>(value: T) -> String {
+    let gj: GeoJson = value.into();
+    if pretty() {
+        serde_json::to_string_pretty(&gj).unwrap()
+    } else {
+        serde_json::to_string(&gj).unwrap()
+    }
+}
+
+// Serialize other JSON output, like measurements and reports, the same way
+pub fn json<T: Into<serde_json::Value>>(value: T) -> String {
+    let v: serde_json::Value = value.into();
+    if pretty() {
+        serde_json::to_string_pretty(&v).unwrap()
+    } else {
+        serde_json::to_string(&v).unwrap()
+    }
+}
+
+// Check that a geometry computed for output has no NaN or infinite
+// coordinates, which serde_json would write as null. Otherwise reports
+// the problem as a JSON object on STDERR, with the offending input if given:
+// {"error":"non-finite coordinates","input":"POINT(1e400 0)","output":"POINT(inf 0)"}
+pub fn check_finite(geom: &Geometry<f64>, input: Option<&str>) -> Result<(), Error> {
+    if coord_count::is_finite(geom) {
+        return Ok(());
+    }
+    let mut wkt = geom.to_wkt();
+    let mut m = serde_json::Map::new();
+    m.insert("error".to_string(), serde_json::json!("non-finite coordinates"));
+    m.insert("output".to_string(), serde_json::json!(format!("{}", wkt.items.pop().unwrap())));
+    if let Some(input) = input {
+        m.insert("input".to_string(), serde_json::json!(input));
+    }
+    eprintln!("{}", serde_json::to_string(&m).unwrap());
+    Err(Error::NonFiniteCoordinates)
+}
+
+// Format a geometry derived from the given entity in the same format
+// as the entity was read in, so that e.g. WKT inputs give WKT outputs.
+// Lat/Lon inputs are only kept as Lat/Lon if the geometry is still a Point,
+// and Geohashes (which can't represent arbitrary geometries) give GeoJSON.
+// GeoJSON Features keep their properties.
+pub fn like_input(e: &Entity, geom: &Geometry<f64>) -> String {
+    match (e, geom) {
+        (Entity::LatLon(raw), Geometry::Point(p)) => latlon(raw, p),
+        (Entity::Wkt(_, _, _), _) => {
+            let mut wkt = geom.to_wkt();
+            format!("{}", wkt.items.pop().unwrap())
+        }
+        (Entity::GeoJsonGeometry(_, _), _) => {
+            geojson(geojson::Geometry::new(conversion::geojson_value(geom)))
+        }
+        _ => {
+            let mut feature = e.geojson_feature();
+            feature.geometry = Some(geojson::Geometry::new(conversion::geojson_value(geom)));
+            geojson(feature)
+        }
+    }
+}
+
+fn latlon(raw: &str, p: &Point<f64>) -> String {
+    let sep = if raw.contains('\t') { "\t" } else { "," };
+    format!("{}{}{}", p.y(), sep, p.x())
+}
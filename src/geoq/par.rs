@@ -8,6 +8,7 @@ use std::io;
 use std::{
     io::BufRead,
     sync::{
+        atomic::{AtomicBool, Ordering},
         mpsc::{sync_channel, Receiver, RecvError, SyncSender},
         Arc,
     },
@@ -19,8 +20,10 @@ enum WorkerInput {
     Done,
 }
 
+// Output lines are grouped per entity so that the printer
+// can count entities when applying the input limit
 enum WorkerOutput {
-    Item(Result<Vec<String>, Error>),
+    Item(Result<Vec<Vec<String>>, Error>),
     Done,
 }
 
@@ -59,11 +62,30 @@ where
 {
     let stdin = io::stdin();
     let mut stdin_reader = stdin.lock();
-    for_entity_par(&mut stdin_reader, handler)
+    for_entity_par(&mut stdin_reader, reader::input_limit(), handler)
+}
+
+// Read entities from a command-line argument if one was given,
+// falling back to STDIN otherwise
+pub fn for_input_entity<F>(arg: Option<&str>, handler: F) -> Result<(), Error>
+where
+    F: 'static + Send + Sync + Fn(Entity) -> Result<Vec<String>, Error>,
+{
+    match arg {
+        Some(arg) => {
+            let mut arg_reader = arg.as_bytes();
+            for_entity_par(&mut arg_reader, reader::input_limit(), handler)
+        }
+        None => for_stdin_entity(handler),
+    }
 }
 
 const WORKER_BUF_SIZE: usize = 5000;
-pub fn for_entity_par<'a, F: 'static>(input: &'a mut dyn BufRead, handler: F) -> Result<(), Error>
+pub fn for_entity_par<'a, F: 'static>(
+    input: &'a mut dyn BufRead,
+    limit: Option<usize>,
+    handler: F,
+) -> Result<(), Error>
 where
     F: Send + Sync + Fn(Entity) -> Result<Vec<String>, Error>,
 {
@@ -72,6 +94,7 @@ where
     let mut threads: Vec<JoinHandle<_>> = vec![];
     let mut output_channels: Vec<Receiver<WorkerOutput>> = vec![];
     let handler_arc = Arc::new(handler);
+    let limit_reached = Arc::new(AtomicBool::new(limit == Some(0)));
 
     (0..num_workers).for_each(|_| {
         let (input_sender, input_receiver) = sync_channel(WORKER_BUF_SIZE);
@@ -101,7 +124,7 @@ where
                                                     .unwrap();
                                                 break;
                                             }
-                                            Ok(lines) => results.extend(lines),
+                                            Ok(lines) => results.push(lines),
                                         }
                                     }
                                     output_sender.send(WorkerOutput::Item(Ok(results))).unwrap();
@@ -122,15 +145,28 @@ where
         threads.push(t);
     });
 
+    let printer_limit_reached = limit_reached.clone();
     let printer_thread = thread::spawn(move || {
+        let mut remaining = limit;
         while !output_channels.is_empty() {
             for i in 0..output_channels.len() {
                 let output = output_channels[i].recv();
                 match output {
                     Err(RecvError) => continue,
-                    Ok(WorkerOutput::Item(Ok(lines))) => {
-                        for l in lines {
-                            println!("{}", l);
+                    // Once the limit is reached, keep draining workers until they finish
+                    Ok(WorkerOutput::Item(_)) if remaining == Some(0) => continue,
+                    Ok(WorkerOutput::Item(Ok(entity_outputs))) => {
+                        for lines in entity_outputs {
+                            if remaining == Some(0) {
+                                break;
+                            }
+                            for l in lines {
+                                println!("{}", l);
+                            }
+                            remaining = remaining.map(|r| r - 1);
+                        }
+                        if remaining == Some(0) {
+                            printer_limit_reached.store(true, Ordering::Relaxed);
                         }
                     }
                     Ok(WorkerOutput::Item(Err(e))) => {
@@ -148,6 +184,9 @@ where
 
     let reader = LineReader::new(input);
     for (i, line) in reader.enumerate() {
+        if limit_reached.load(Ordering::Relaxed) {
+            break;
+        }
         input_channels[i % num_workers]
             .send(WorkerInput::Item(line))
             .unwrap();
@@ -181,7 +220,7 @@ mod tests {
 "#.as_bytes();
 
         // let mut input = "9q5\n9q4".as_bytes();
-        let res = for_entity_par(&mut input, move |entity| {
+        let res = for_entity_par(&mut input, None, move |entity| {
             Ok(vec![format!("handling entity {}", entity).to_owned()])
         });
         assert!(res.is_ok());
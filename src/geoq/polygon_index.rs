@@ -0,0 +1,77 @@
+use crate::geoq::{bbox, contains};
+use geo_types::{Geometry, Point, Polygon};
+use rstar::{RTree, RTreeObject, AABB};
+
+struct IndexedPolygon {
+    id: usize,
+    polygon: Polygon<f64>,
+    envelope: AABB<[f64; 2]>,
+}
+
+impl RTreeObject for IndexedPolygon {
+    type Envelope = AABB<[f64; 2]>;
+
+    fn envelope(&self) -> Self::Envelope {
+        self.envelope
+    }
+}
+
+// R-tree of polygons for point-in-polygon lookups.
+// Each polygon is tagged with an id, so that several polygons
+// (e.g. the members of a MultiPolygon) can share the same id.
+pub struct PolygonIndex {
+    tree: RTree<IndexedPolygon>,
+}
+
+impl PolygonIndex {
+    pub fn new(polygons: Vec<(usize, Polygon<f64>)>) -> PolygonIndex {
+        let entries = polygons
+            .into_iter()
+            .map(|(id, polygon)| {
+                let rect = bbox::bbox(&Geometry::Polygon(polygon.clone()));
+                let envelope =
+                    AABB::from_corners([rect.min.x, rect.min.y], [rect.max.x, rect.max.y]);
+                IndexedPolygon {
+                    id,
+                    polygon,
+                    envelope,
+                }
+            })
+            .collect();
+        PolygonIndex {
+            tree: RTree::bulk_load(entries),
+        }
+    }
+
+    // Lowest id of the polygons containing the point, if any
+    pub fn containing(&self, point: &Point<f64>) -> Option<usize> {
+        let geom = Geometry::Point(*point);
+        self.tree
+            .locate_in_envelope_intersecting(&AABB::from_point([point.x(), point.y()]))
+            .filter(|entry| contains::contains(&entry.polygon, &geom))
+            .map(|entry| entry.id)
+            .min()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::polygon_index::PolygonIndex;
+    use geo_types::{Point, Polygon};
+
+    fn square(min: f64, max: f64) -> Polygon<f64> {
+        Polygon::new(
+            vec![(min, min), (max, min), (max, max), (min, max), (min, min)].into(),
+            vec![],
+        )
+    }
+
+    #[test]
+    fn test_containing_point() {
+        let index = PolygonIndex::new(vec![(0, square(0.0, 2.0)), (1, square(1.0, 3.0))]);
+        assert_eq!(Some(0), index.containing(&Point::new(0.5, 0.5)));
+        assert_eq!(Some(0), index.containing(&Point::new(1.5, 1.5)));
+        assert_eq!(Some(1), index.containing(&Point::new(2.5, 2.5)));
+        assert_eq!(None, index.containing(&Point::new(5.0, 5.0)));
+    }
+}
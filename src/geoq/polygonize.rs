@@ -0,0 +1,254 @@
+use crate::geoq::area::signed_area;
+use geo::algorithm::contains::Contains;
+use geo_types::{Coordinate, LineString, Point, Polygon};
+use std::collections::HashMap;
+
+type Coord = Coordinate<f64>;
+type NodeKey = (u64, u64);
+
+pub struct Polygonized {
+    pub polygons: Vec<Polygon<f64>>,
+    // Edges with an end which isn't connected to any other edge
+    pub dangles: Vec<LineString<f64>>,
+    // Edges connecting rings without forming part of any ring themselves
+    pub cut_edges: Vec<LineString<f64>>,
+}
+
+fn node_key(c: &Coord) -> NodeKey {
+    (c.x.to_bits(), c.y.to_bits())
+}
+
+// Planar graph of edges, where each edge is a line between 2 nodes.
+// Each edge has 2 half-edges: 2 * edge is the edge's own direction,
+// and 2 * edge + 1 is its reverse.
+struct Graph {
+    edges: Vec<Vec<Coord>>,
+    alive: Vec<bool>,
+}
+
+impl Graph {
+    fn coords(&self, half: usize) -> Vec<Coord> {
+        let mut coords = self.edges[half / 2].clone();
+        if half % 2 == 1 {
+            coords.reverse();
+        }
+        coords
+    }
+
+    fn origin(&self, half: usize) -> Coord {
+        let edge = &self.edges[half / 2];
+        if half % 2 == 1 { edge[edge.len() - 1] } else { edge[0] }
+    }
+
+    fn dest(&self, half: usize) -> Coord {
+        self.origin(half ^ 1)
+    }
+
+    // Direction in which a half-edge leaves its origin
+    fn angle(&self, half: usize) -> f64 {
+        let coords = self.coords(half);
+        let start = coords[0];
+        let next = coords.iter().find(|c| **c != start).unwrap_or(&start);
+        (next.y - start.y).atan2(next.x - start.x)
+    }
+
+    // Outgoing half-edges of each node, sorted by angle
+    fn nodes(&self) -> HashMap<NodeKey, Vec<usize>> {
+        let mut nodes: HashMap<NodeKey, Vec<usize>> = HashMap::new();
+        for edge in (0..self.edges.len()).filter(|e| self.alive[*e]) {
+            for half in &[2 * edge, 2 * edge + 1] {
+                nodes.entry(node_key(&self.origin(*half))).or_default().push(*half);
+            }
+        }
+        for halves in nodes.values_mut() {
+            halves.sort_by(|a, b| self.angle(*a).partial_cmp(&self.angle(*b)).unwrap());
+        }
+        nodes
+    }
+
+    // Repeatedly remove edges with an unconnected end, returning them
+    fn remove_dangles(&mut self) -> Vec<usize> {
+        let mut removed = Vec::new();
+        loop {
+            let mut dangles: Vec<usize> = self
+                .nodes()
+                .values()
+                .filter(|halves| halves.len() == 1)
+                .map(|halves| halves[0] / 2)
+                .collect();
+            // Keep the output order independent of HashMap ordering
+            dangles.sort_unstable();
+            if dangles.is_empty() {
+                return removed;
+            }
+            for edge in dangles {
+                if self.alive[edge] {
+                    self.alive[edge] = false;
+                    removed.push(edge);
+                }
+            }
+        }
+    }
+
+    // Follow each half-edge around the face on its left, giving each
+    // face's ring as the half-edges along it
+    fn rings(&self) -> Vec<Vec<usize>> {
+        let nodes = self.nodes();
+        let mut visited = vec![false; self.edges.len() * 2];
+        let mut rings = Vec::new();
+        for start in 0..self.edges.len() * 2 {
+            if visited[start] || !self.alive[start / 2] {
+                continue;
+            }
+            let mut ring = Vec::new();
+            let mut half = start;
+            while !visited[half] {
+                visited[half] = true;
+                ring.push(half);
+                // The next half-edge is the one leaving our destination
+                // just clockwise of the way we came in
+                let outgoing = &nodes[&node_key(&self.dest(half))];
+                let twin = outgoing.iter().position(|h| *h == half ^ 1).unwrap();
+                half = outgoing[(twin + outgoing.len() - 1) % outgoing.len()];
+            }
+            rings.push(ring);
+        }
+        rings
+    }
+
+    fn ring_coords(&self, ring: &[usize]) -> Vec<Coord> {
+        let mut coords = vec![self.origin(ring[0])];
+        for half in ring {
+            coords.extend(self.coords(*half).into_iter().skip(1));
+        }
+        coords
+    }
+}
+
+// Assemble polygons from lines which meet only at their endpoints,
+// as in PostGIS ST_Polygonize. Rings nested inside of a polygon
+// (without touching it) become its holes, as well as polygons of their own.
+pub fn polygonize(lines: Vec<LineString<f64>>) -> Polygonized {
+    let mut edges: Vec<Vec<Coord>> = Vec::new();
+    for line in lines {
+        let mut coords = line.0;
+        coords.dedup();
+        if coords.len() < 2 {
+            continue;
+        }
+        let reversed: Vec<Coord> = coords.iter().rev().cloned().collect();
+        if !edges.iter().any(|e| *e == coords || *e == reversed) {
+            edges.push(coords);
+        }
+    }
+    let alive = vec![true; edges.len()];
+    let mut graph = Graph { edges, alive };
+
+    let mut dangles = Vec::new();
+    let mut cut_edges = Vec::new();
+    let rings = loop {
+        dangles.extend(graph.remove_dangles());
+        let rings = graph.rings();
+        // An edge with both of its sides on the same face is a cut edge
+        let cuts: Vec<usize> = rings
+            .iter()
+            .flat_map(|ring| ring.iter().filter(move |h| ring.contains(&(*h ^ 1))).map(|h| h / 2))
+            .collect();
+        if cuts.is_empty() {
+            break rings;
+        }
+        for edge in cuts {
+            if graph.alive[edge] {
+                graph.alive[edge] = false;
+                cut_edges.push(edge);
+            }
+        }
+    };
+
+    // Faces on the left of counter-clockwise rings are polygons,
+    // and clockwise rings are the outside of each connected set of edges
+    let (shells, outers): (Vec<Vec<Coord>>, Vec<Vec<Coord>>) = rings
+        .iter()
+        .map(|ring| graph.ring_coords(ring))
+        .filter(|coords| signed_area(coords) != 0.0)
+        .partition(|coords| signed_area(coords) > 0.0);
+    let mut polygons: Vec<Polygon<f64>> = shells
+        .into_iter()
+        .map(|coords| Polygon::new(LineString(coords), vec![]))
+        .collect();
+
+    for outer in outers {
+        let point = Point(outer[0]);
+        let container = polygons
+            .iter_mut()
+            .filter(|poly| poly.contains(&point))
+            .min_by(|a, b| {
+                signed_area(&a.exterior().0)
+                    .partial_cmp(&signed_area(&b.exterior().0))
+                    .unwrap()
+            });
+        if let Some(poly) = container {
+            let mut interiors = poly.interiors().to_vec();
+            interiors.push(LineString(outer));
+            *poly = Polygon::new(poly.exterior().clone(), interiors);
+        }
+    }
+
+    let to_lines = |ids: Vec<usize>| -> Vec<LineString<f64>> {
+        ids.into_iter().map(|e| LineString(graph.edges[e].clone())).collect()
+    };
+    Polygonized {
+        polygons,
+        dangles: to_lines(dangles),
+        cut_edges: to_lines(cut_edges),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::polygonize::polygonize;
+    use geo_types::LineString;
+
+    fn line(coords: &[(f64, f64)]) -> LineString<f64> {
+        coords.to_vec().into()
+    }
+
+    #[test]
+    fn test_polygonizing_adjacent_squares() {
+        let result = polygonize(vec![
+            line(&[(0.0, 0.0), (1.0, 0.0)]),
+            line(&[(1.0, 0.0), (2.0, 0.0), (2.0, 1.0), (1.0, 1.0)]),
+            line(&[(1.0, 1.0), (0.0, 1.0), (0.0, 0.0)]),
+            line(&[(1.0, 0.0), (1.0, 1.0)]),
+        ]);
+        assert_eq!(2, result.polygons.len());
+        assert!(result.dangles.is_empty() && result.cut_edges.is_empty());
+        for poly in result.polygons {
+            assert_eq!(5, poly.exterior().0.len());
+        }
+    }
+
+    #[test]
+    fn test_polygonizing_dangles_and_cut_edges() {
+        let result = polygonize(vec![
+            line(&[(1.0, 1.0), (0.0, 0.0), (1.0, 0.0), (1.0, 1.0)]),
+            line(&[(1.0, 1.0), (5.0, 5.0)]),
+            line(&[(5.0, 5.0), (6.0, 5.0), (6.0, 6.0), (5.0, 5.0)]),
+            line(&[(1.0, 1.0), (1.0, 2.0)]),
+        ]);
+        assert_eq!(2, result.polygons.len());
+        assert_eq!(vec![line(&[(1.0, 1.0), (1.0, 2.0)])], result.dangles);
+        assert_eq!(vec![line(&[(1.0, 1.0), (5.0, 5.0)])], result.cut_edges);
+    }
+
+    #[test]
+    fn test_polygonizing_nested_rings() {
+        let result = polygonize(vec![
+            line(&[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0), (0.0, 0.0)]),
+            line(&[(4.0, 4.0), (6.0, 4.0), (6.0, 6.0), (4.0, 4.0)]),
+        ]);
+        assert_eq!(2, result.polygons.len());
+        let interiors: Vec<usize> = result.polygons.iter().map(|p| p.interiors().len()).collect();
+        assert!(interiors.contains(&1) && interiors.contains(&0));
+    }
+}
@@ -3,14 +3,29 @@ extern crate geo_types;
 use crate::geoq::entity::{self, Entity};
 use crate::geoq::error::Error;
 use crate::geoq::input;
+use once_cell::sync::OnceCell;
+use serde_json::{Map, Value};
 use std::collections::VecDeque;
 use std::io;
 use std::io::BufRead;
 use std::iter::FromIterator;
 
+static INPUT_LIMIT: OnceCell<usize> = OnceCell::new();
+
+// Set from the global --limit flag: the max number of entities to read from STDIN
+pub fn set_input_limit(limit: usize) {
+    INPUT_LIMIT.set(limit).expect("Input limit should only be set once");
+}
+
+pub fn input_limit() -> Option<usize> {
+    INPUT_LIMIT.get().cloned()
+}
+
 pub struct Reader<'a> {
     reader: &'a mut dyn BufRead,
     entities: VecDeque<Entity>,
+    remaining: Option<usize>,
+    collection_members: Map<String, Value>,
 }
 
 impl<'a> Reader<'a> {
@@ -18,32 +33,37 @@ impl<'a> Reader<'a> {
         Reader {
             reader,
             entities: VecDeque::new(),
+            remaining: None,
+            collection_members: Map::new(),
         }
     }
-}
 
-pub fn read_line(buf_read: &mut dyn BufRead) -> Option<String> {
-    let mut buf = String::new();
-    let bytes_read = buf_read.read_line(&mut buf);
-    match bytes_read {
-        Ok(0) => None,
-        Ok(_) => Some(buf.trim().to_string()),
-        _ => None,
+    // Foreign members of the FeatureCollections read so far. Where
+    // several give the same member, the first one read is kept.
+    pub fn collection_members(&self) -> &Map<String, Value> {
+        &self.collection_members
     }
-}
 
-impl<'a> Iterator for Reader<'a> {
-    type Item = Result<Entity, Error>;
+    // Stop after reading the given number of entities.
+    // Entities are counted individually, so a FeatureCollection
+    // containing several Features counts as several entities.
+    pub fn with_limit(mut self, limit: Option<usize>) -> Reader<'a> {
+        self.remaining = limit;
+        self
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
+    fn next_entity(&mut self) -> Option<Result<Entity, Error>> {
         if let Some(entity) = self.entities.pop_front() {
             return Some(Ok(entity));
         }
 
         while let Some(line) = read_line(&mut *self.reader) {
             match input::read_line(line) {
-                Ok(i) => match entity::from_input(i) {
-                    Ok(e_vec) => {
+                Ok(i) => match entity::from_input_with_members(i) {
+                    Ok((e_vec, members)) => {
+                        for (key, value) in members.into_iter().flatten() {
+                            self.collection_members.entry(key).or_insert(value);
+                        }
                         let mut entities = VecDeque::from_iter(e_vec);
                         if entities.is_empty() {
                             continue;
@@ -64,13 +84,41 @@ impl<'a> Iterator for Reader<'a> {
     }
 }
 
+pub fn read_line(buf_read: &mut dyn BufRead) -> Option<String> {
+    let mut buf = String::new();
+    let bytes_read = buf_read.read_line(&mut buf);
+    match bytes_read {
+        Ok(0) => None,
+        Ok(_) => Some(buf.trim().to_string()),
+        _ => None,
+    }
+}
+
+impl<'a> Iterator for Reader<'a> {
+    type Item = Result<Entity, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.remaining {
+            Some(0) => None,
+            Some(n) => {
+                let next = self.next_entity();
+                if let Some(Ok(_)) = next {
+                    self.remaining = Some(n - 1);
+                }
+                next
+            }
+            None => self.next_entity(),
+        }
+    }
+}
+
 pub fn entities<F>(mut handler: F) -> Result<(), Error>
 where
     F: FnMut(&mut dyn Iterator<Item = Result<Entity, Error>>) -> Result<(), Error>,
 {
     let stdin = io::stdin();
     let mut stdin_reader = stdin.lock();
-    let mut reader = Reader::new(&mut stdin_reader);
+    let mut reader = Reader::new(&mut stdin_reader).with_limit(input_limit());
     handler(&mut reader)
 }
 
@@ -93,6 +141,25 @@ where
     })
 }
 
+// Read entities from a command-line argument if one was given,
+// falling back to STDIN otherwise
+pub fn for_input_entity<F>(arg: Option<&str>, mut handler: F) -> Result<(), Error>
+where
+    F: FnMut(Entity) -> Result<(), Error>,
+{
+    match arg {
+        Some(arg) => {
+            let mut arg_reader = arg.as_bytes();
+            let reader = Reader::new(&mut arg_reader).with_limit(input_limit());
+            for e_res in reader {
+                handler(e_res?)?;
+            }
+            Ok(())
+        }
+        None => for_entity(handler),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::geoq::reader::Reader;
@@ -119,6 +186,22 @@ mod tests {
         assert_eq!("9q5", gh.raw());
     }
 
+    #[test]
+    fn test_reading_with_limit() {
+        let mut pointer = "9q5\n9q4\n9q3".as_bytes();
+        let reader = Reader::new(&mut pointer).with_limit(Some(2));
+        let ghs: Vec<String> = reader.map(|e| e.unwrap().raw()).collect();
+        assert_eq!(vec!["9q5", "9q4"], ghs);
+    }
+
+    #[test]
+    fn test_limit_counts_entities_rather_than_lines() {
+        let mut pointer = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{},"geometry":{"type":"Point","coordinates":[34.0,12.0]}},{"type":"Feature","properties":{},"geometry":{"type":"Point","coordinates":[78.0,56.0]}}]}
+9q5"#.as_bytes();
+        let reader = Reader::new(&mut pointer).with_limit(Some(2));
+        assert_eq!(2, reader.count());
+    }
+
     #[test]
     fn test_reading_2_lines() {
         let mut pointer = "9q5\n9q4".as_bytes();
@@ -0,0 +1,84 @@
+use crate::geoq::distance::geodesic_distance;
+use geo_types::{Geometry, LineString, MultiLineString, MultiPolygon, Point, Polygon};
+
+// Drop vertices closer than min_distance meters to the previous kept vertex.
+// The first and last vertices are always kept.
+pub fn resample_line(line: &LineString<f64>, min_distance: f64) -> LineString<f64> {
+    let coords = &line.0;
+    if coords.len() < 3 {
+        return line.clone();
+    }
+    let last = coords.len() - 1;
+    let mut output = vec![coords[0]];
+    for &c in &coords[1..last] {
+        let prev = output[output.len() - 1];
+        if geodesic_distance(&Point(prev), &Point(c)) >= min_distance {
+            output.push(c);
+        }
+    }
+    output.push(coords[last]);
+    LineString(output)
+}
+
+// Rings which would be left with fewer than 4 vertices are kept as-is
+fn resample_ring(ring: &LineString<f64>, min_distance: f64) -> LineString<f64> {
+    let resampled = resample_line(ring, min_distance);
+    if resampled.0.len() < 4 {
+        ring.clone()
+    } else {
+        resampled
+    }
+}
+
+fn resample_polygon(poly: &Polygon<f64>, min_distance: f64) -> Polygon<f64> {
+    Polygon::new(
+        resample_ring(poly.exterior(), min_distance),
+        poly.interiors()
+            .iter()
+            .map(|ring| resample_ring(ring, min_distance))
+            .collect(),
+    )
+}
+
+// Only (Multi-)LineStrings and (Multi-)Polygons are affected
+pub fn resample(geom: Geometry<f64>, min_distance: f64) -> Geometry<f64> {
+    match geom {
+        Geometry::LineString(g) => Geometry::LineString(resample_line(&g, min_distance)),
+        Geometry::MultiLineString(g) => Geometry::MultiLineString(MultiLineString(
+            g.0.iter().map(|l| resample_line(l, min_distance)).collect(),
+        )),
+        Geometry::Polygon(g) => Geometry::Polygon(resample_polygon(&g, min_distance)),
+        Geometry::MultiPolygon(g) => Geometry::MultiPolygon(MultiPolygon(
+            g.0.iter().map(|p| resample_polygon(p, min_distance)).collect(),
+        )),
+        _ => geom,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::resample::resample_line;
+    use geo_types::LineString;
+
+    #[test]
+    fn test_dropping_close_vertices() {
+        // 0.0001 degrees of longitude at the equator is ~11 meters
+        let line: LineString<f64> = vec![
+            (0.0, 0.0),
+            (0.00001, 0.0),
+            (0.0001, 0.0),
+            (0.00011, 0.0),
+            (0.00012, 0.0),
+        ]
+        .into();
+        let exp: LineString<f64> = vec![(0.0, 0.0), (0.0001, 0.0), (0.00012, 0.0)].into();
+        assert_eq!(exp, resample_line(&line, 10.0));
+    }
+
+    #[test]
+    fn test_keeping_endpoints() {
+        let line: LineString<f64> = vec![(0.0, 0.0), (0.00001, 0.0), (0.00002, 0.0)].into();
+        let exp: LineString<f64> = vec![(0.0, 0.0), (0.00002, 0.0)].into();
+        assert_eq!(exp, resample_line(&line, 10.0));
+    }
+}
@@ -0,0 +1,378 @@
+use crate::geoq::area;
+use geo_types::Coordinate;
+use serde_json::Value;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Violation {
+    pub severity: Severity,
+    // Location of the violation within the object, e.g. features[0].geometry.coordinates
+    pub path: String,
+    pub message: String,
+    pub fixed: bool,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        let path = if self.path.is_empty() { "(root)" } else { &self.path };
+        write!(f, "{} at {}: {}", severity, path, self.message)?;
+        if self.fixed {
+            write!(f, " (fixed)")?;
+        }
+        Ok(())
+    }
+}
+
+struct Checker {
+    fix: bool,
+    violations: Vec<Violation>,
+}
+
+fn member(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", path, name)
+    }
+}
+
+fn index(path: &str, i: usize) -> String {
+    format!("{}[{}]", path, i)
+}
+
+// Signed (planar) area of a ring of positions, positive when counter-clockwise
+fn signed_area(ring: &[Value]) -> f64 {
+    let coords: Vec<Coordinate<f64>> = ring
+        .iter()
+        .map(|p| Coordinate { x: p[0].as_f64().unwrap_or(0.0), y: p[1].as_f64().unwrap_or(0.0) })
+        .collect();
+    area::signed_area(&coords)
+}
+
+impl Checker {
+    fn report(&mut self, severity: Severity, path: &str, message: String, fixed: bool) {
+        self.violations.push(Violation {
+            severity,
+            path: path.to_string(),
+            message,
+            fixed,
+        });
+    }
+
+    fn members(&mut self, obj: &serde_json::Map<String, Value>, path: &str, allowed: &[&str]) {
+        for key in obj.keys() {
+            if allowed.contains(&key.as_str()) {
+                continue;
+            }
+            if key == "crs" {
+                let message = "\"crs\" member is not part of RFC 7946 (coordinates must be WGS84)";
+                self.report(Severity::Warning, path, message.to_string(), false);
+            } else {
+                let message = format!("foreign member {:?}", key);
+                self.report(Severity::Warning, path, message, false);
+            }
+        }
+        if let Some(bbox) = obj.get("bbox") {
+            let valid = bbox
+                .as_array()
+                .map(|b| (b.len() == 4 || b.len() == 6) && b.iter().all(Value::is_number))
+                .unwrap_or(false);
+            if !valid {
+                let message = "bbox must be an array of 4 (or 6) numbers".to_string();
+                self.report(Severity::Error, &member(path, "bbox"), message, false);
+            }
+        }
+    }
+
+    fn position(&mut self, pos: &Value, path: &str) -> bool {
+        let coords = match pos.as_array() {
+            Some(coords) if coords.iter().all(Value::is_number) => coords,
+            _ => {
+                self.report(Severity::Error, path, "position must be an array of numbers".to_string(), false);
+                return false;
+            }
+        };
+        if coords.len() < 2 {
+            let message = format!("position has {} elements, but needs at least 2", coords.len());
+            self.report(Severity::Error, path, message, false);
+            return false;
+        }
+        if coords.len() > 3 {
+            let message = format!("position has {} elements, but should have no more than 3", coords.len());
+            self.report(Severity::Warning, path, message, false);
+        }
+        let (lon, lat) = (coords[0].as_f64().unwrap(), coords[1].as_f64().unwrap());
+        if !(-180.0..=180.0).contains(&lon) || !(-90.0..=90.0).contains(&lat) {
+            let message = format!("position [{}, {}] is out of Lon/Lat range", lon, lat);
+            self.report(Severity::Error, path, message, false);
+        }
+        true
+    }
+
+    // Checks an array of positions, returning them if they're all valid
+    fn positions<'a>(&mut self, value: &'a mut Value, path: &str) -> Option<&'a mut Vec<Value>> {
+        let positions = match value.as_array_mut() {
+            Some(positions) => positions,
+            None => {
+                self.report(Severity::Error, path, "expected an array of positions".to_string(), false);
+                return None;
+            }
+        };
+        let mut valid = true;
+        for (i, pos) in positions.iter().enumerate() {
+            valid &= self.position(pos, &index(path, i));
+        }
+        if valid {
+            Some(positions)
+        } else {
+            None
+        }
+    }
+
+    fn line(&mut self, value: &mut Value, path: &str) {
+        if let Some(positions) = self.positions(value, path) {
+            if positions.len() < 2 {
+                let message = "LineString must have at least 2 positions".to_string();
+                self.report(Severity::Error, path, message, false);
+            }
+        }
+    }
+
+    fn ring(&mut self, value: &mut Value, path: &str, exterior: bool) {
+        let fix = self.fix;
+        let positions = match self.positions(value, path) {
+            Some(positions) => positions,
+            None => return,
+        };
+        if !positions.is_empty() && positions.first() != positions.last() {
+            if fix {
+                positions.push(positions[0].clone());
+            }
+            self.report(Severity::Error, path, "ring is not closed".to_string(), fix);
+        }
+        if positions.len() < 4 {
+            let message = format!("ring has {} positions, but needs at least 4", positions.len());
+            self.report(Severity::Error, path, message, false);
+            return;
+        }
+        let area = signed_area(positions);
+        let reversed = if exterior { area < 0.0 } else { area > 0.0 };
+        if reversed {
+            if fix {
+                positions.reverse();
+            }
+            let message = if exterior {
+                "exterior ring is clockwise, but should be counter-clockwise"
+            } else {
+                "interior ring is counter-clockwise, but should be clockwise"
+            };
+            self.report(Severity::Warning, path, message.to_string(), fix);
+        }
+    }
+
+    fn polygon(&mut self, value: &mut Value, path: &str) {
+        match value.as_array_mut() {
+            Some(rings) => {
+                for (i, ring) in rings.iter_mut().enumerate() {
+                    self.ring(ring, &index(path, i), i == 0);
+                }
+            }
+            None => self.report(Severity::Error, path, "expected an array of rings".to_string(), false),
+        }
+    }
+
+    fn each<F>(&mut self, value: &mut Value, path: &str, what: &str, mut check: F)
+    where
+        F: FnMut(&mut Checker, &mut Value, &str),
+    {
+        match value.as_array_mut() {
+            Some(items) => {
+                for (i, item) in items.iter_mut().enumerate() {
+                    check(self, item, &index(path, i));
+                }
+            }
+            None => self.report(Severity::Error, path, format!("expected an array of {}", what), false),
+        }
+    }
+
+    fn geometry(&mut self, value: &mut Value, path: &str) {
+        let geom_type = value["type"].as_str().unwrap_or("").to_string();
+        if geom_type == "GeometryCollection" {
+            if let Some(obj) = value.as_object() {
+                self.members(obj, path, &["type", "geometries", "bbox"]);
+            }
+            let geoms_path = member(path, "geometries");
+            match value.get_mut("geometries") {
+                Some(geoms) => self.each(geoms, &geoms_path, "geometries", |c, g, p| c.geometry(g, p)),
+                None => self.report(Severity::Error, path, "missing \"geometries\" member".to_string(), false),
+            }
+            return;
+        }
+        if !["Point", "MultiPoint", "LineString", "MultiLineString", "Polygon", "MultiPolygon"]
+            .contains(&geom_type.as_str())
+        {
+            let message = format!("unknown geometry type {:?}", value["type"]);
+            self.report(Severity::Error, path, message, false);
+            return;
+        }
+        if let Some(obj) = value.as_object() {
+            self.members(obj, path, &["type", "coordinates", "bbox"]);
+        }
+        let coords_path = member(path, "coordinates");
+        let coords = match value.get_mut("coordinates") {
+            Some(coords) => coords,
+            None => {
+                self.report(Severity::Error, path, "missing \"coordinates\" member".to_string(), false);
+                return;
+            }
+        };
+        match geom_type.as_str() {
+            "Point" => {
+                self.position(coords, &coords_path);
+            }
+            "MultiPoint" => {
+                self.positions(coords, &coords_path);
+            }
+            "LineString" => self.line(coords, &coords_path),
+            "MultiLineString" => self.each(coords, &coords_path, "lines", |c, l, p| c.line(l, p)),
+            "Polygon" => self.polygon(coords, &coords_path),
+            _ => self.each(coords, &coords_path, "polygons", |c, poly, p| c.polygon(poly, p)),
+        }
+    }
+
+    fn feature(&mut self, value: &mut Value, path: &str) {
+        if value["type"] != "Feature" {
+            self.report(Severity::Error, path, "expected a Feature".to_string(), false);
+            return;
+        }
+        if let Some(obj) = value.as_object() {
+            self.members(obj, path, &["type", "geometry", "properties", "id", "bbox"]);
+            if let Some(id) = obj.get("id") {
+                if !id.is_string() && !id.is_number() {
+                    let message = "id must be a string or number".to_string();
+                    self.report(Severity::Error, &member(path, "id"), message, false);
+                }
+            }
+            match obj.get("properties") {
+                Some(Value::Object(_)) | Some(Value::Null) => {}
+                Some(_) => {
+                    let message = "properties must be an object or null".to_string();
+                    self.report(Severity::Error, &member(path, "properties"), message, false);
+                }
+                None => self.report(Severity::Error, path, "missing \"properties\" member".to_string(), false),
+            }
+        }
+        let geom_path = member(path, "geometry");
+        match value.get_mut("geometry") {
+            Some(Value::Null) => {}
+            Some(geom) => self.geometry(geom, &geom_path),
+            None => self.report(Severity::Error, path, "missing \"geometry\" member".to_string(), false),
+        }
+    }
+
+    fn object(&mut self, value: &mut Value) {
+        match value["type"].as_str() {
+            Some("FeatureCollection") => {
+                if let Some(obj) = value.as_object() {
+                    self.members(obj, "", &["type", "features", "bbox"]);
+                }
+                match value.get_mut("features") {
+                    Some(features) => self.each(features, "features", "Features", |c, f, p| c.feature(f, p)),
+                    None => self.report(Severity::Error, "", "missing \"features\" member".to_string(), false),
+                }
+            }
+            Some("Feature") => self.feature(value, ""),
+            _ => self.geometry(value, ""),
+        }
+    }
+}
+
+// Check a GeoJSON object against RFC 7946, giving each violation of the spec.
+// With `fix`, rings are closed and rewound in place where needed,
+// and those violations are marked as fixed.
+pub fn validate(value: &mut Value, fix: bool) -> Vec<Violation> {
+    let mut checker = Checker {
+        fix,
+        violations: Vec::new(),
+    };
+    if value.is_object() {
+        checker.object(value);
+    } else {
+        checker.report(Severity::Error, "", "expected a GeoJSON object".to_string(), false);
+    }
+    checker.violations
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::rfc7946::{validate, Severity};
+    use serde_json::json;
+
+    #[test]
+    fn test_valid_feature() {
+        let mut f = json!({
+            "type": "Feature",
+            "id": 1,
+            "properties": null,
+            "geometry": {"type": "Polygon", "coordinates": [[[0, 0], [1, 0], [1, 1], [0, 0]]]}
+        });
+        assert!(validate(&mut f, false).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_positions() {
+        let mut g = json!({"type": "LineString", "coordinates": [[0], [200, 0], [1, 2, 3, 4]]});
+        let violations = validate(&mut g, false);
+        let paths: Vec<(&str, Severity)> =
+            violations.iter().map(|v| (v.path.as_str(), v.severity)).collect();
+        assert_eq!(
+            vec![
+                ("coordinates[0]", Severity::Error),
+                ("coordinates[1]", Severity::Error),
+                ("coordinates[2]", Severity::Warning),
+            ],
+            paths
+        );
+    }
+
+    #[test]
+    fn test_fixing_rings() {
+        // Unclosed and clockwise
+        let mut g = json!({"type": "Polygon", "coordinates": [[[0, 0], [0, 1], [1, 1], [1, 0]]], "crs": {}});
+        let violations = validate(&mut g, true);
+        assert_eq!(3, violations.len());
+        assert_eq!(Severity::Warning, violations[0].severity);
+        assert!(violations[1..].iter().all(|v| v.fixed));
+        assert_eq!(
+            json!([[[0, 0], [1, 0], [1, 1], [0, 1], [0, 0]]]),
+            g["coordinates"]
+        );
+
+        let mut unfixed = json!({"type": "Polygon", "coordinates": [[[0, 0], [0, 1], [1, 1], [1, 0]]]});
+        validate(&mut unfixed, false);
+        assert_eq!(4, unfixed["coordinates"][0].as_array().unwrap().len());
+    }
+
+    #[test]
+    fn test_feature_members() {
+        let mut f = json!({"type": "Feature", "geometry": null, "title": "x"});
+        let messages: Vec<String> = validate(&mut f, false).iter().map(|v| v.to_string()).collect();
+        assert_eq!(
+            vec![
+                "warning at (root): foreign member \"title\"".to_string(),
+                "error at (root): missing \"properties\" member".to_string(),
+            ],
+            messages
+        );
+    }
+}
@@ -1,5 +1,7 @@
-use geo::algorithm::simplifyvw::SimplifyVWPreserve;
-use geo_types::Geometry;
+use geo::algorithm::simplifyvw::{SimplifyVW, SimplifyVWPreserve};
+use geo_types::{Coordinate, Geometry, LineString, MultiLineString, MultiPolygon, Polygon};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
 
 pub fn simplify(geom: Geometry<f64>, epsilon: f64) -> Geometry<f64> {
     match geom {
@@ -10,3 +12,470 @@ pub fn simplify(geom: Geometry<f64>, epsilon: f64) -> Geometry<f64> {
         _ => geom,
     }
 }
+
+// Fewest coordinates to leave in each ring (including its closing
+// coordinate) and each line, which can't go below what keeps them valid
+#[derive(Clone, Copy)]
+pub struct MinPoints {
+    pub ring: usize,
+    pub line: usize,
+}
+
+impl MinPoints {
+    pub fn new(min: Option<usize>) -> MinPoints {
+        MinPoints {
+            ring: min.unwrap_or(4).max(4),
+            line: min.unwrap_or(2).max(2),
+        }
+    }
+}
+
+// A vertex which could be removed, ordered so that the smallest area is popped first
+struct Candidate {
+    area: f64,
+    index: usize,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Candidate) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Candidate) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Candidate) -> Ordering {
+        other
+            .area
+            .partial_cmp(&self.area)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
+
+fn triangle_area(a: Coordinate<f64>, b: Coordinate<f64>, c: Coordinate<f64>) -> f64 {
+    ((b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y)).abs() / 2.0
+}
+
+// Visvalingam-Whyatt without a threshold: repeatedly drop the vertex forming
+// the smallest triangle with its neighbors until only `keep` remain.
+// The endpoints of lines are always kept. Ring coordinates must be open.
+fn most_significant(coords: &[Coordinate<f64>], keep: usize, ring: bool) -> Vec<Coordinate<f64>> {
+    let n = coords.len();
+    if n <= keep {
+        return coords.to_vec();
+    }
+    let mut prev: Vec<usize> = (0..n).map(|i| (i + n - 1) % n).collect();
+    let mut next: Vec<usize> = (0..n).map(|i| (i + 1) % n).collect();
+    let removable = |i: usize| ring || (i != 0 && i != n - 1);
+    let mut areas = vec![f64::INFINITY; n];
+    let mut heap = BinaryHeap::new();
+    for i in (0..n).filter(|&i| removable(i)) {
+        areas[i] = triangle_area(coords[prev[i]], coords[i], coords[next[i]]);
+        heap.push(Candidate { area: areas[i], index: i });
+    }
+    let mut removed = vec![false; n];
+    let mut remaining = n;
+    while remaining > keep {
+        let Candidate { area, index } = match heap.pop() {
+            Some(c) => c,
+            None => break,
+        };
+        // Skip vertices already removed or whose area has since changed
+        if removed[index] || area != areas[index] {
+            continue;
+        }
+        removed[index] = true;
+        remaining -= 1;
+        let (p, q) = (prev[index], next[index]);
+        next[p] = q;
+        prev[q] = p;
+        for &i in &[p, q] {
+            if removable(i) {
+                areas[i] = triangle_area(coords[prev[i]], coords[i], coords[next[i]]);
+                heap.push(Candidate { area: areas[i], index: i });
+            }
+        }
+    }
+    (0..n).filter(|&i| !removed[i]).map(|i| coords[i]).collect()
+}
+
+// The simplified line, or if it has fewer than `min` coordinates,
+// the most significant `min` coordinates of the original instead
+fn floor_line(
+    simplified: LineString<f64>,
+    original: &LineString<f64>,
+    min: usize,
+    ring: bool,
+    floored: &mut usize,
+) -> LineString<f64> {
+    if simplified.0.len() >= min || original.0.len() <= simplified.0.len() {
+        return simplified;
+    }
+    *floored += 1;
+    if !ring {
+        return LineString(most_significant(&original.0, min, false));
+    }
+    let mut open = original.0.clone();
+    if open.len() > 1 && open.first() == open.last() {
+        open.pop();
+    }
+    let mut coords = most_significant(&open, min - 1, true);
+    coords.push(coords[0]);
+    LineString(coords)
+}
+
+fn floor_polygon(
+    simplified: Polygon<f64>,
+    original: &Polygon<f64>,
+    min: MinPoints,
+    floored: &mut usize,
+) -> Polygon<f64> {
+    let (exterior, interiors) = simplified.into_inner();
+    let exterior = floor_line(exterior, original.exterior(), min.ring, true, floored);
+    let interiors = interiors
+        .into_iter()
+        .zip(original.interiors())
+        .map(|(ring, orig)| floor_line(ring, orig, min.ring, true, floored))
+        .collect();
+    Polygon::new(exterior, interiors)
+}
+
+// Simplify a geometry without reducing any of its rings or lines below the
+// minimum number of points. Also gives the number of rings or lines which
+// would have gone below it, and kept their most significant points instead.
+pub fn simplify_with_min_points(
+    geom: Geometry<f64>,
+    epsilon: f64,
+    min: MinPoints,
+) -> (Geometry<f64>, usize) {
+    let mut floored = 0;
+    let simplified = match (simplify(geom.clone(), epsilon), geom) {
+        (Geometry::LineString(s), Geometry::LineString(ref o)) => {
+            Geometry::LineString(floor_line(s, o, min.line, false, &mut floored))
+        }
+        (Geometry::Polygon(s), Geometry::Polygon(ref o)) => {
+            Geometry::Polygon(floor_polygon(s, o, min, &mut floored))
+        }
+        (Geometry::MultiLineString(s), Geometry::MultiLineString(ref o)) => {
+            Geometry::MultiLineString(MultiLineString(
+                s.0.into_iter()
+                    .zip(&o.0)
+                    .map(|(s, o)| floor_line(s, o, min.line, false, &mut floored))
+                    .collect(),
+            ))
+        }
+        (Geometry::MultiPolygon(s), Geometry::MultiPolygon(ref o)) => {
+            Geometry::MultiPolygon(MultiPolygon(
+                s.0.into_iter()
+                    .zip(&o.0)
+                    .map(|(s, o)| floor_polygon(s, o, min, &mut floored))
+                    .collect(),
+            ))
+        }
+        (simplified, _) => simplified,
+    };
+    (simplified, floored)
+}
+
+type CoordKey = (u64, u64);
+
+fn coord_key(c: Coordinate<f64>) -> CoordKey {
+    (c.x.to_bits(), c.y.to_bits())
+}
+
+// A ring as the arcs it's made of, each of which may be reversed
+type RingArcs = Vec<(usize, bool)>;
+
+// Ring coordinates without their closing coordinate or repeated coordinates
+fn open_ring(ring: &LineString<f64>) -> Vec<Coordinate<f64>> {
+    let mut coords: Vec<Coordinate<f64>> = Vec::with_capacity(ring.0.len());
+    for &c in &ring.0 {
+        if coords.last() != Some(&c) {
+            coords.push(c);
+        }
+    }
+    if coords.len() > 1 && coords.first() == coords.last() {
+        coords.pop();
+    }
+    coords
+}
+
+// The distinct arcs of a set of rings, and each ring as its arcs. Rings are
+// split into arcs at junctions, where they meet rings following a different
+// path. Arcs used by several rings (like the shared border of neighboring
+// polygons) are only stored once, in whichever direction orders first.
+struct Topology {
+    arcs: Vec<Vec<Coordinate<f64>>>,
+    rings: Vec<RingArcs>,
+}
+
+impl Topology {
+    fn new(rings: &[Vec<Coordinate<f64>>]) -> Topology {
+        let mut neighbors: HashMap<CoordKey, Vec<CoordKey>> = HashMap::new();
+        for ring in rings {
+            let n = ring.len();
+            for i in 0..n {
+                let adjacent = neighbors.entry(coord_key(ring[i])).or_default();
+                for c in &[ring[(i + n - 1) % n], ring[(i + 1) % n]] {
+                    let key = coord_key(*c);
+                    if !adjacent.contains(&key) {
+                        adjacent.push(key);
+                    }
+                }
+            }
+        }
+        let is_junction = |c: &Coordinate<f64>| neighbors[&coord_key(*c)].len() > 2;
+
+        let mut topology = Topology {
+            arcs: Vec::new(),
+            rings: Vec::new(),
+        };
+        let mut arc_ids: HashMap<Vec<CoordKey>, usize> = HashMap::new();
+        for ring in rings {
+            let mut ring_arcs = Vec::new();
+            match ring.iter().position(is_junction) {
+                Some(start) => {
+                    // Walk around the ring from its first junction, ending an arc at each junction
+                    let n = ring.len();
+                    let mut arc = vec![ring[start]];
+                    for i in 1..=n {
+                        let c = ring[(start + i) % n];
+                        arc.push(c);
+                        if is_junction(&c) {
+                            ring_arcs.push(topology.add(&mut arc_ids, arc));
+                            arc = vec![c];
+                        }
+                    }
+                }
+                None => {
+                    // Without junctions, the whole ring is 1 arc, starting from
+                    // its lowest coordinate so it matches the same ring elsewhere
+                    let start = (0..ring.len())
+                        .min_by_key(|&i| coord_key(ring[i]))
+                        .unwrap_or(0);
+                    if ring.len() > 1 {
+                        let arc = ring[start..].iter().chain(&ring[..=start]).cloned().collect();
+                        ring_arcs.push(topology.add(&mut arc_ids, arc));
+                    }
+                }
+            }
+            topology.rings.push(ring_arcs);
+        }
+        topology
+    }
+
+    fn add(&mut self, ids: &mut HashMap<Vec<CoordKey>, usize>, mut arc: Vec<Coordinate<f64>>) -> (usize, bool) {
+        let forward: Vec<CoordKey> = arc.iter().map(|c| coord_key(*c)).collect();
+        let backward: Vec<CoordKey> = forward.iter().rev().cloned().collect();
+        let (key, reversed) = if backward < forward {
+            arc.reverse();
+            (backward, true)
+        } else {
+            (forward, false)
+        };
+        let next = self.arcs.len();
+        let id = *ids.entry(key).or_insert(next);
+        if id == next {
+            self.arcs.push(arc);
+        }
+        (id, reversed)
+    }
+
+    // A closed ring joining its arcs end to end
+    fn ring(&self, ring: &RingArcs, arcs: &[Vec<Coordinate<f64>>]) -> LineString<f64> {
+        let mut coords: Vec<Coordinate<f64>> = Vec::new();
+        for &(id, reversed) in ring {
+            let mut arc = arcs[id].clone();
+            if reversed {
+                arc.reverse();
+            }
+            let skip = if coords.is_empty() { 0 } else { 1 };
+            coords.extend(arc.into_iter().skip(skip));
+        }
+        LineString(coords)
+    }
+}
+
+// Simplify the Polygons and MultiPolygons among a set of geometries together,
+// so that neighbors keep matching borders. Each arc of their rings between
+// junctions is simplified once and shared by all of the rings using it.
+// The junctions are kept, and a ring which would fall below 4 coordinates
+// keeps its original arcs instead (along with any of its neighbors sharing them).
+// Other geometries are returned as they are.
+pub fn simplify_shared_topology(geoms: Vec<Geometry<f64>>, epsilon: f64) -> Vec<Geometry<f64>> {
+    let polygons = |geom: &Geometry<f64>| -> Vec<Polygon<f64>> {
+        match *geom {
+            Geometry::Polygon(ref p) => vec![p.clone()],
+            Geometry::MultiPolygon(ref mp) => mp.0.clone(),
+            _ => vec![],
+        }
+    };
+    let mut rings: Vec<Vec<Coordinate<f64>>> = Vec::new();
+    for geom in &geoms {
+        for poly in polygons(geom) {
+            rings.push(open_ring(poly.exterior()));
+            rings.extend(poly.interiors().iter().map(open_ring));
+        }
+    }
+    let topology = Topology::new(&rings);
+
+    let mut arcs: Vec<Vec<Coordinate<f64>>> = topology
+        .arcs
+        .iter()
+        .map(|arc| LineString(arc.clone()).simplifyvw(&epsilon).0)
+        .collect();
+    for ring in &topology.rings {
+        if !ring.is_empty() && topology.ring(ring, &arcs).0.len() < 4 {
+            for &(id, _) in ring {
+                arcs[id] = topology.arcs[id].clone();
+            }
+        }
+    }
+
+    // Degenerate rings have no arcs, and are kept as they are
+    let mut simplified_rings = topology.rings.iter().map(|ring| {
+        if ring.is_empty() {
+            None
+        } else {
+            Some(topology.ring(ring, &arcs))
+        }
+    });
+    let mut next_ring = |original: &LineString<f64>| {
+        simplified_rings.next().flatten().unwrap_or_else(|| original.clone())
+    };
+    let mut simplified_polygon = |poly: &Polygon<f64>| {
+        let exterior = next_ring(poly.exterior());
+        let interiors = poly.interiors().iter().map(&mut next_ring).collect();
+        Polygon::new(exterior, interiors)
+    };
+    geoms
+        .into_iter()
+        .map(|geom| match geom {
+            Geometry::Polygon(ref p) => Geometry::Polygon(simplified_polygon(p)),
+            Geometry::MultiPolygon(ref mp) => {
+                Geometry::MultiPolygon(MultiPolygon(mp.0.iter().map(&mut simplified_polygon).collect()))
+            }
+            other => other,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::simplify::{simplify_shared_topology, simplify_with_min_points, MinPoints};
+    use geo_types::{Geometry, LineString, Polygon};
+
+    fn poly(coords: Vec<(f64, f64)>, holes: Vec<Vec<(f64, f64)>>) -> Geometry<f64> {
+        Geometry::Polygon(Polygon::new(
+            coords.into(),
+            holes.into_iter().map(|h| h.into()).collect(),
+        ))
+    }
+
+    #[test]
+    fn test_min_points_for_rings() {
+        // A square with slight bumps along 2 of its sides
+        let ring: LineString<f64> = vec![
+            (0.0, 0.0),
+            (1.0, 0.0),
+            (2.0, 0.01),
+            (3.0, 0.0),
+            (3.0, 3.0),
+            (1.5, 3.02),
+            (0.0, 3.0),
+            (0.0, 0.0),
+        ]
+        .into();
+        let poly = Geometry::Polygon(Polygon::new(ring, vec![]));
+
+        let (simplified, floored) = simplify_with_min_points(poly.clone(), 0.1, MinPoints::new(None));
+        assert_eq!(0, floored);
+        match simplified {
+            Geometry::Polygon(p) => assert_eq!(5, p.exterior().0.len()),
+            other => panic!("Expected a Polygon, got {:?}", other),
+        }
+
+        // The bumps are the next most significant points
+        let (simplified, floored) = simplify_with_min_points(poly, 0.1, MinPoints::new(Some(7)));
+        assert_eq!(1, floored);
+        let exp: LineString<f64> = vec![
+            (0.0, 0.0),
+            (2.0, 0.01),
+            (3.0, 0.0),
+            (3.0, 3.0),
+            (1.5, 3.02),
+            (0.0, 3.0),
+            (0.0, 0.0),
+        ]
+        .into();
+        match simplified {
+            Geometry::Polygon(p) => assert_eq!(&exp, p.exterior()),
+            other => panic!("Expected a Polygon, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_min_points_for_lines() {
+        let line = Geometry::LineString(
+            vec![(0.0, 0.0), (1.0, 0.1), (2.0, 0.0), (3.0, 0.5), (4.0, 0.0)].into(),
+        );
+        let (simplified, floored) = simplify_with_min_points(line, 10.0, MinPoints::new(Some(3)));
+        assert_eq!(1, floored);
+        let exp = Geometry::LineString(vec![(0.0, 0.0), (3.0, 0.5), (4.0, 0.0)].into());
+        assert_eq!(exp, simplified);
+    }
+
+    #[test]
+    fn test_min_points_never_below_valid() {
+        let min = MinPoints::new(Some(1));
+        assert_eq!(4, min.ring);
+        assert_eq!(2, min.line);
+    }
+
+    #[test]
+    fn test_shared_topology_keeps_borders() {
+        // Squares sharing a border with a bump, which is too small to keep
+        let a = poly(
+            vec![(0.0, 0.0), (1.0, 0.0), (1.0, 0.5), (1.01, 0.6), (1.0, 0.7), (1.0, 1.0), (0.0, 1.0), (0.0, 0.0)],
+            vec![],
+        );
+        let b = poly(
+            vec![(1.0, 0.0), (2.0, 0.0), (2.0, 1.0), (1.0, 1.0), (1.0, 0.7), (1.01, 0.6), (1.0, 0.5), (1.0, 0.0)],
+            vec![],
+        );
+        let simplified = simplify_shared_topology(vec![a, b], 0.01);
+        let exp = vec![
+            poly(vec![(1.0, 0.0), (1.0, 1.0), (0.0, 1.0), (0.0, 0.0), (1.0, 0.0)], vec![]),
+            poly(vec![(1.0, 0.0), (2.0, 0.0), (2.0, 1.0), (1.0, 1.0), (1.0, 0.0)], vec![]),
+        ];
+        assert_eq!(exp, simplified);
+    }
+
+    #[test]
+    fn test_shared_topology_for_islands() {
+        // A polygon with a hole, which is filled exactly by another polygon
+        let hole = vec![(1.0, 1.0), (2.0, 1.05), (3.0, 1.0), (3.0, 3.0), (1.0, 3.0), (1.0, 1.0)];
+        let outer = vec![(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0), (0.0, 0.0)];
+        let reversed: Vec<(f64, f64)> = hole.iter().rev().cloned().collect();
+        let geoms = vec![poly(outer.clone(), vec![hole]), poly(reversed, vec![])];
+        let simplified = simplify_shared_topology(geoms, 0.1);
+        let square = vec![(1.0, 1.0), (3.0, 1.0), (3.0, 3.0), (1.0, 3.0), (1.0, 1.0)];
+        let exp = vec![
+            poly(outer, vec![square.clone()]),
+            poly(square.into_iter().rev().collect(), vec![]),
+        ];
+        assert_eq!(exp, simplified);
+
+        // Rings simplified below a triangle are kept as they were
+        let triangle = poly(vec![(0.0, 0.0), (1.0, 0.0), (0.0, 1.0), (0.0, 0.0)], vec![]);
+        assert_eq!(vec![triangle.clone()], simplify_shared_topology(vec![triangle], 10.0));
+    }
+}
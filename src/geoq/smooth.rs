@@ -0,0 +1,183 @@
+use geo_types::{Coordinate, Geometry, LineString, MultiLineString, MultiPolygon, Polygon};
+
+type Coord = Coordinate<f64>;
+
+// Both methods double the vertices of each line every iteration
+pub const MAX_ITERATIONS: usize = 16;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Method {
+    // Chaikin's corner-cutting. Each iteration replaces every vertex
+    // with 2 points a quarter of the way along its adjacent edges.
+    Chaikin,
+    // Uniform Catmull-Rom spline through the original vertices. Each segment
+    // is split into 2^iterations pieces.
+    Spline,
+}
+
+impl Method {
+    pub fn from_name(name: &str) -> Option<Method> {
+        match name {
+            "chaikin" => Some(Method::Chaikin),
+            "spline" => Some(Method::Spline),
+            _ => None,
+        }
+    }
+}
+
+fn lerp(a: Coord, b: Coord, t: f64) -> Coord {
+    Coordinate {
+        x: a.x + t * (b.x - a.x),
+        y: a.y + t * (b.y - a.y),
+    }
+}
+
+fn chaikin_step(coords: &[Coord], closed: bool) -> Vec<Coord> {
+    let mut output = Vec::with_capacity(coords.len() * 2);
+    if !closed {
+        output.push(coords[0]);
+    }
+    for pair in coords.windows(2) {
+        output.push(lerp(pair[0], pair[1], 0.25));
+        output.push(lerp(pair[0], pair[1], 0.75));
+    }
+    if closed {
+        output.push(output[0]);
+    } else {
+        output.push(coords[coords.len() - 1]);
+    }
+    output
+}
+
+fn catmull_rom(p0: Coord, p1: Coord, p2: Coord, p3: Coord, t: f64) -> Coord {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    let f = |a: f64, b: f64, c: f64, d: f64| {
+        0.5 * (2.0 * b
+            + (c - a) * t
+            + (2.0 * a - 5.0 * b + 4.0 * c - d) * t2
+            + (3.0 * b - a - 3.0 * c + d) * t3)
+    };
+    Coordinate {
+        x: f(p0.x, p1.x, p2.x, p3.x),
+        y: f(p0.y, p1.y, p2.y, p3.y),
+    }
+}
+
+fn spline(coords: &[Coord], closed: bool, iterations: usize) -> Vec<Coord> {
+    let steps = 1 << iterations.min(MAX_ITERATIONS);
+    let n = coords.len();
+    // Neighboring vertex, wrapping around rings and clamping open lines
+    let at = |i: isize| -> Coord {
+        if closed {
+            // The last coord of a ring repeats the first
+            coords[i.rem_euclid(n as isize - 1) as usize]
+        } else {
+            coords[i.max(0).min(n as isize - 1) as usize]
+        }
+    };
+    let mut output = Vec::with_capacity((n - 1) * steps + 1);
+    for i in 0..(n - 1) as isize {
+        let (p0, p1, p2, p3) = (at(i - 1), at(i), at(i + 1), at(i + 2));
+        for step in 0..steps {
+            output.push(catmull_rom(p0, p1, p2, p3, step as f64 / steps as f64));
+        }
+    }
+    output.push(coords[n - 1]);
+    output
+}
+
+fn smooth_coords(line: &LineString<f64>, method: Method, iterations: usize) -> LineString<f64> {
+    let closed = line.0.len() > 3 && line.0.first() == line.0.last();
+    if line.0.len() < 3 {
+        return line.clone();
+    }
+    match method {
+        Method::Chaikin => {
+            let mut coords = line.0.clone();
+            for _ in 0..iterations.min(MAX_ITERATIONS) {
+                coords = chaikin_step(&coords, closed);
+            }
+            LineString(coords)
+        }
+        Method::Spline => LineString(spline(&line.0, closed, iterations)),
+    }
+}
+
+fn smooth_polygon(poly: &Polygon<f64>, method: Method, iterations: usize) -> Polygon<f64> {
+    Polygon::new(
+        smooth_coords(poly.exterior(), method, iterations),
+        poly.interiors()
+            .iter()
+            .map(|ring| smooth_coords(ring, method, iterations))
+            .collect(),
+    )
+}
+
+// Only (Multi-)LineStrings and (Multi-)Polygons are affected.
+// Open lines keep their endpoints, and rings stay closed.
+pub fn smooth(geom: Geometry<f64>, method: Method, iterations: usize) -> Geometry<f64> {
+    match geom {
+        Geometry::LineString(g) => Geometry::LineString(smooth_coords(&g, method, iterations)),
+        Geometry::MultiLineString(g) => Geometry::MultiLineString(MultiLineString(
+            g.0.iter().map(|l| smooth_coords(l, method, iterations)).collect(),
+        )),
+        Geometry::Polygon(g) => Geometry::Polygon(smooth_polygon(&g, method, iterations)),
+        Geometry::MultiPolygon(g) => Geometry::MultiPolygon(MultiPolygon(
+            g.0.iter().map(|p| smooth_polygon(p, method, iterations)).collect(),
+        )),
+        _ => geom,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::smooth::{smooth, Method};
+    use geo_types::{Geometry, LineString, Polygon};
+
+    #[test]
+    fn test_chaikin_open_line() {
+        let line: LineString<f64> = vec![(0.0, 0.0), (4.0, 0.0), (4.0, 4.0)].into();
+        let exp: LineString<f64> = vec![
+            (0.0, 0.0),
+            (1.0, 0.0),
+            (3.0, 0.0),
+            (4.0, 1.0),
+            (4.0, 3.0),
+            (4.0, 4.0),
+        ]
+        .into();
+        assert_eq!(
+            Geometry::LineString(exp),
+            smooth(Geometry::LineString(line), Method::Chaikin, 1)
+        );
+    }
+
+    #[test]
+    fn test_chaikin_ring_stays_closed() {
+        let ring: LineString<f64> =
+            vec![(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0), (0.0, 0.0)].into();
+        match smooth(Geometry::Polygon(Polygon::new(ring, vec![])), Method::Chaikin, 2) {
+            Geometry::Polygon(p) => {
+                let coords = &p.exterior().0;
+                assert_eq!(17, coords.len());
+                assert_eq!(coords.first(), coords.last());
+            }
+            _ => panic!("Expected a Polygon"),
+        }
+    }
+
+    #[test]
+    fn test_spline_passes_through_vertices() {
+        let line: LineString<f64> = vec![(0.0, 0.0), (4.0, 0.0), (4.0, 4.0)].into();
+        match smooth(Geometry::LineString(line.clone()), Method::Spline, 1) {
+            Geometry::LineString(l) => {
+                assert_eq!(5, l.0.len());
+                assert_eq!(line.0[0], l.0[0]);
+                assert_eq!(line.0[1], l.0[2]);
+                assert_eq!(line.0[2], l.0[4]);
+            }
+            _ => panic!("Expected a LineString"),
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use crate::geoq::{centroid, node, polygonize};
+use geo::algorithm::contains::Contains;
+use geo_types::{Coordinate, Geometry, LineString, Point, Polygon};
+
+// Split a line at each point where it meets the blades, as in PostGIS ST_Split
+pub fn split_line(line: &LineString<f64>, blades: &[LineString<f64>]) -> Vec<LineString<f64>> {
+    node::split(std::slice::from_ref(line), blades).0.remove(0)
+}
+
+// Split a polygon into the pieces on either side of the blades, as in PostGIS ST_Split.
+// A polygon which the blades don't cut all the way across is returned as-is.
+pub fn split_polygon(poly: &Polygon<f64>, blades: &[LineString<f64>]) -> Vec<Polygon<f64>> {
+    let rings: Vec<LineString<f64>> = std::iter::once(poly.exterior())
+        .chain(poly.interiors().iter())
+        .cloned()
+        .collect();
+    let (ring_pieces, blade_pieces) = node::split(&rings, blades);
+
+    // Only the parts of blades inside of the polygon form new edges
+    let inside: Vec<LineString<f64>> = blade_pieces
+        .into_iter()
+        .flatten()
+        .filter(|piece| {
+            let (a, b) = (piece.0[0], piece.0[1]);
+            let mid = Coordinate { x: (a.x + b.x) / 2.0, y: (a.y + b.y) / 2.0 };
+            poly.contains(&Point(mid))
+        })
+        .collect();
+    if inside.is_empty() {
+        return vec![poly.clone()];
+    }
+
+    let edges: Vec<LineString<f64>> = ring_pieces.into_iter().flatten().chain(inside).collect();
+    // Faces formed by the polygon's holes are dropped
+    let faces: Vec<Polygon<f64>> = polygonize::polygonize(edges)
+        .polygons
+        .into_iter()
+        .filter(|face| {
+            centroid::midpoint(&Geometry::Polygon(face.clone()))
+                .map(|p| poly.contains(&p))
+                .unwrap_or(false)
+        })
+        .collect();
+    if faces.len() < 2 {
+        vec![poly.clone()]
+    } else {
+        faces
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::split::{split_line, split_polygon};
+    use geo::algorithm::area::Area;
+    use geo_types::{LineString, Polygon};
+
+    fn square() -> Polygon<f64> {
+        Polygon::new(
+            vec![(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0), (0.0, 0.0)].into(),
+            vec![],
+        )
+    }
+
+    #[test]
+    fn test_splitting_line() {
+        let line: LineString<f64> = vec![(0.0, 0.0), (4.0, 0.0)].into();
+        let blade: LineString<f64> = vec![(1.0, -1.0), (1.0, 1.0)].into();
+        let exp: Vec<LineString<f64>> = vec![
+            vec![(0.0, 0.0), (1.0, 0.0)].into(),
+            vec![(1.0, 0.0), (4.0, 0.0)].into(),
+        ];
+        assert_eq!(exp, split_line(&line, &[blade]));
+    }
+
+    #[test]
+    fn test_splitting_polygon_in_half() {
+        let blade: LineString<f64> = vec![(1.0, -1.0), (1.0, 5.0)].into();
+        let pieces = split_polygon(&square(), &[blade]);
+        assert_eq!(2, pieces.len());
+        let mut areas: Vec<f64> = pieces.iter().map(|p| p.area().abs()).collect();
+        areas.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(vec![4.0, 12.0], areas);
+    }
+
+    #[test]
+    fn test_splitting_polygon_with_hole() {
+        let poly = Polygon::new(
+            square().exterior().clone(),
+            vec![vec![(1.5, 1.5), (2.5, 1.5), (2.5, 2.5), (1.5, 2.5), (1.5, 1.5)].into()],
+        );
+        let blade: LineString<f64> = vec![(2.0, -1.0), (2.0, 5.0)].into();
+        let pieces = split_polygon(&poly, &[blade]);
+        assert_eq!(2, pieces.len());
+        for piece in pieces {
+            assert_eq!(7.5, piece.area().abs());
+        }
+    }
+
+    #[test]
+    fn test_blade_not_crossing_polygon() {
+        let blade: LineString<f64> = vec![(1.0, 1.0), (1.0, 5.0)].into();
+        assert_eq!(vec![square()], split_polygon(&square(), &[blade]));
+        let outside: LineString<f64> = vec![(5.0, -1.0), (5.0, 5.0)].into();
+        assert_eq!(vec![square()], split_polygon(&square(), &[outside]));
+    }
+}
@@ -8,6 +8,8 @@ The checks include:
 * WKT strings under the geometry or wkt keys
 * GeoJSON geometries as strings under the geometry or geojson keys
 * GeoJSON geometries as objects under the geometry or geojson keys
+* Failing those, GeoJSON geometry or WKT strings under any other key,
+  checking keys in sorted order
 
 The original JSON object, minus the matched geometry keys, will be embedded in the GeoJSON 'properties' key.
 
@@ -17,6 +19,92 @@ $ echo '{"latitude":12.0,"longitude":34.0,"key":"val"}' | geoq json munge
 {"geometry":{"coordinates":[34.0,12.0],"type":"Point"}, "properties":{"key":"val"},"type":"Feature"}
 "#;
 
+pub const GEOJSON_VALIDATE_AFTER_HELP: &str = r#"
+Checks each line of input (a GeoJSON Feature, Geometry, or
+FeatureCollection) against the GeoJSON spec, RFC 7946, and reports
+each violation to STDERR, giving its input number and location:
+
+Input 2: error at geometry.coordinates[0]: ring is not closed
+
+Errors are violations of the spec:
+
+  * positions with fewer than 2 numbers, or out of Lon/Lat range
+  * LineStrings with fewer than 2 positions
+  * unclosed Polygon rings, or rings with fewer than 4 positions
+  * missing or malformed members (like a Feature with no "properties")
+
+Warnings are things the spec discourages, which most tools still accept:
+
+  * positions with more than 3 elements
+  * rings wound the wrong way (exteriors should be counter-clockwise,
+    and holes clockwise)
+  * foreign members, like a legacy "crs"
+
+Every input is passed through to STDOUT. geoq exits with a non-zero
+status if any input has errors, so this can be used as a check in CI.
+Unlike 'geoq validate', which checks geometries themselves, this only
+checks their GeoJSON encoding.
+
+With --fix, the safe corrections are applied: unclosed rings are closed,
+and wrongly wound rings are reversed. Fixed inputs are output with the
+corrections, and their violations are marked (fixed) and no longer
+count as failures.
+"#;
+pub const GEOJSON_VALIDATE_FIX_ARG_HELP: &str =
+    "Close unclosed rings and reverse wrongly wound rings in the output.";
+
+pub const GEOJSON_AFTER_HELP: &str = r#"
+'gj geom' and 'gj f' output 1 compact GeoJSON Geometry or Feature
+per line of output, with no separators between them. This is
+also known as newline-delimited GeoJSON (NDJSON / GeoJSONSeq
+without record separators), and can be consumed by tools like
+tippecanoe, or read back in by geoq.
+
+Use --ndjson to make this explicit: it guarantees each output line
+is exactly 1 complete Feature, and is rejected if combined with
+options which would break that guarantee (--pretty, or 'gj fc',
+which collects all inputs into a single FeatureCollection).
+
+$ printf "12,34
+56,78
+" | geoq gj f --ndjson
+{"geometry":{"coordinates":[34.0,12.0],"type":"Point"},"properties":{},"type":"Feature"}
+{"geometry":{"coordinates":[78.0,56.0],"type":"Point"},"properties":{},"type":"Feature"}
+
+Like every geoq command, 'gj geom' reads a FeatureCollection as its
+individual Features, so it outputs 1 Geometry per Feature (never
+just the first, and never combined). Features with a null geometry,
+and empty geometries like POINT EMPTY, give an empty GeometryCollection. With --flatten, the members of
+GeometryCollections are output as separate Geometries instead, and
+empty collections give no output.
+
+GeoJSON (RFC 7946) coordinates are always WGS84 Lon/Lat, but some
+older tools write data in other projections with a legacy "crs"
+member. geoq doesn't reproject, so it warns on STDERR when it reads
+GeoJSON with a non-WGS84 "crs". Reprojecting needs a projection library
+like PROJ, which geoq doesn't include, so convert such data to WGS84
+first, e.g. with 'ogr2ogr -t_srs EPSG:4326'. For tools which still require it,
+'gj fc --write-crs EPSG:4326' includes a legacy "crs" member in the
+output FeatureCollection (without changing any coordinates).
+
+'gj fc' writes its FeatureCollection as it reads, holding only 1 Feature
+in memory at a time, so it can be used on inputs of any size. Since the
+bbox given by 'gj fc --bbox' isn't known until every input has been read,
+it's written as the last member of the FeatureCollection.
+
+Non-standard members of Features (keys other than "type", "id", "geometry",
+"properties", and "bbox") are kept on output Features. Those of input
+FeatureCollections, like a "name", are kept by 'gj fc', written after the
+Features (before any bbox). Where several inputs give the same member, the
+first is kept, and --write-crs replaces any input "crs".
+"#;
+pub const GEOJSON_WRITE_CRS_ARG_HELP: &str =
+    "Include a legacy 'crs' member naming the given EPSG code in the FeatureCollection. Coordinates are not reprojected.";
+pub const GEOJSON_FLATTEN_ARG_HELP: &str =
+    "Output each member of a GeometryCollection as a separate Geometry.";
+pub const GEOJSON_NDJSON_ARG_HELP: &str =
+    "Output newline-delimited GeoJSON: exactly 1 compact Feature (or Geometry) per line. Can't be combined with 'gj fc' or --pretty.";
+
 pub const READ_AFTER_HELP: &str = r#"
 Geoq reads the following geospatial text formats:
 
@@ -44,6 +132,13 @@ handling is given to GeoJSON 'FeatureCollections'. These
 will be "unrolled" by Geoq's processing, and treated as
 if their features had been passed individually.
 
+To read only the first N entities of a large input, use the
+global --limit flag. Unlike 'head -n', this counts entities
+rather than lines, so each Feature of a FeatureCollection
+counts as one entity:
+
+$ cat huge.geojson | geoq wkt --limit 10
+
 You can also use this command ('geoq read') to test out
 input formats and see how geoq reads the values you feed it:
 
@@ -66,13 +161,93 @@ See 'geoq read --help' for more information on supported
 input formats.
 "#;
 
+pub const LIMIT_ARG_HELP: &str = "Stop after reading N entities from STDIN. Entities are counted individually rather than by line, so each Feature in a FeatureCollection counts separately.";
+
+pub const UNITS_ARG_HELP: &str =
+    "Unit system for distances given or output without an explicit unit: meters (metric, the default) or feet (imperial). Per-command --unit options take precedence.";
+pub const DISTANCE_METHOD_ARG_HELP: &str = "Algorithm for calculating distances. haversine is fastest, but treats the Earth as a sphere, so may be off by up to ~0.5%. vincenty and karney use the WGS84 ellipsoid; karney is the most accurate, and also works for nearly antipodal points, which vincenty can fail on.";
+pub const DISTANCE_UNIT_ARG_HELP: &str =
+    "Unit to give distances in. Defaults to meters, or feet with --units imperial.";
+pub const ELLIPSOID_ARG_HELP: &str =
+    "Reference ellipsoid for geodesic calculations like 'measure distance'. Defaults to wgs84. sphere uses the mean Earth radius (6371km).";
+pub const RADIUS_ARG_HELP: &str =
+    "Use a sphere with the given radius (in meters) for geodesic calculations, e.g. for non-Earth data.";
+pub const PRETTY_ARG_HELP: &str = "Pretty-print (indent) GeoJSON and other JSON output, like that of --json. By default JSON is printed compactly, 1 per line, so it can be read back in by geoq.";
+
+pub const INPUT_ARG_HELP: &str =
+    "Entity to use as input instead of reading from STDIN.\nMust be Lat/Lon, Geohash, WKT, or GeoJSON.";
+
 pub const CENTROID_ABOUT: &str = "Print centroid of the given geometry";
-pub const CENTROID_AFTER_HELP: &str = r"
-Output is given as a GeoJSON Point.
-";
+pub const CENTROID_AFTER_HELP: &str = r#"
+Output is given as a GeoJSON Point. Use --raw to print the point as
+plain 'lon,lat' text instead, e.g. for use in a shell script:
+
+$ echo 'LINESTRING(0 0,2 0)' | geoq centroid --raw
+1,0
+
+Note this is longitude first, like GeoJSON coordinates, whereas geoq
+reads Lat/Lon text input latitude first.
+
+The centroid of a LineString or concave Polygon often falls outside
+of it. With --midpoint, LineStrings give the point halfway along their
+geodesic length, and Polygons give a point guaranteed to be inside them
+(for MultiPolygons, inside the largest one), which are more useful
+for placing labels.
+
+With --all, a single Point is output for all of the inputs combined:
+the mean of each input's centroid (or midpoint, with --midpoint),
+weighted according to --weight:
+
+  * type   - (default) Polygons are weighted by their area (in square
+             meters), lines by their length (in meters), and Points by 1.
+             Since areas dwarf lengths, Polygons dominate in mixed inputs.
+  * area   - Polygons by their area. Other geometries count for nothing.
+  * length - LineStrings by their length. Other geometries count for nothing.
+  * equal  - Every input counts the same.
+
+If the weights of all of the inputs add up to 0 (for example, weighting
+only Points by area), they're all weighted equally instead. Areas and
+lengths are geodesic, but the centroids are averaged using raw Lon/Lat
+coordinates.
+
+With --multi, each component of a multi-geometry (or GeometryCollection)
+gets its own centroid, e.g. to label each island of a MultiPolygon. These
+are output as GeoJSON Features with the input's id and properties, and
+a "component" property giving the index of the component (from 0). Other
+geometries have a single component. With --raw, each point is followed
+by its component index, separated by a tab:
+
+$ echo 'MULTIPOLYGON(((0 0,2 0,2 2,0 2,0 0)),((10 10,12 10,12 12,10 12,10 10)))' | geoq centroid --multi --raw
+1,1	0
+11,11	1
+
+GeoJSON Features with a null geometry are skipped.
+
+Inputs with huge coordinates can give a centroid with infinite or NaN
+coordinates, which can't be written as JSON numbers. Rather than output
+these, geoq stops with an error on STDERR, given as a JSON object with
+the offending input:
+
+$ echo 'POLYGON((0 0,1e308 0,1e308 1e308,0 0))' | geoq centroid
+{"error":"non-finite coordinates","input":"POLYGON((0 0,1e308 0,1e308 1e308,0 0))","output":"POINT(inf 33333333333333330000...)"}
+
+(The output WKT is shortened here.)
+"#;
+pub const NULL_GEOMETRY_VERBOSE_ARG_HELP: &str =
+    "Print a note to STDERR for each Feature skipped because its geometry is null.";
+pub const CENTROID_ALL_ARG_HELP: &str =
+    "Output a single centroid for all of the inputs combined, rather than 1 per input.";
+pub const CENTROID_MULTI_ARG_HELP: &str =
+    "Output a centroid for each component of multi-geometries, as a Feature with the component's index.";
+pub const CENTROID_WEIGHT_ARG_HELP: &str =
+    "How to weight each input with --all: by area for Polygons, length for lines, and 1 for Points (type), or only by area, only by length, or equally.";
+pub const CENTROID_RAW_ARG_HELP: &str =
+    "Print each centroid as 'lon,lat' text instead of a GeoJSON Point.";
+pub const CENTROID_MIDPOINT_ARG_HELP: &str =
+    "Give the point halfway along LineStrings, and a point inside Polygons, instead of the centroid.";
 
 pub const WHEREAMI_ABOUT: &str = "Get IP-based current lat/lon.";
-pub const WHEREAMI_AFTER_HELP: &str = r"
+pub const WHEREAMI_AFTER_HELP: &str = r#"
 Get IP-based current lat/lon.
 
 Location is reported using a free service at ip-api.com.
@@ -80,20 +255,40 @@ Location is reported using a free service at ip-api.com.
 Rate-limited to 150 requests per minute from a given IP.
 
 Output is given as a GeoJSON Point.
-";
+
+IP-based locations are only approximate (usually to within a city).
+Use --ip to give the location as a GeoJSON Feature marking this,
+with properties for its accuracy and source, and the city, region,
+and country where available:
+
+{"geometry":{"coordinates":[-122.4,37.8],"type":"Point"},"properties":{"accuracy":"low","city":"San Francisco","country":"United States","region":"California","source":"ip"},"type":"Feature"}
+"#;
+pub const WHEREAMI_IP_ARG_HELP: &str =
+    "Use IP-based geolocation, giving a Feature marked with its (low) accuracy.";
 
 pub const MEASURE_ABOUT: &str = "Get spatial measurements of or between features.";
+pub const MEASURE_JSON_ARG_HELP: &str = r#"Give each result as a JSON object, e.g. {"value":12.3,"unit":"m","id":"a"}.
+The id is taken from the Feature's GeoJSON id, or an "id" property, and is omitted if neither is present."#;
+pub const MEASURE_AREA_PER_COMPONENT_ARG_HELP: &str =
+    "Give the area of each Polygon of a MultiPolygon on its own line, with its index, instead of their total.";
+pub const MEASURE_LABELED_ARG_HELP: &str =
+    "Follow each value with its unit, e.g. '1234.5 m'. Values are bare numbers by default, for piping to other commands.";
+pub const MEASURE_PLANAR_ARG_HELP: &str =
+    "Treat coordinates as planar X/Y in meters (i.e. already in a projected CRS) and use Euclidean math, instead of geodesic measurements on Lon/Lat.";
 pub const DISTANCE_ABOUT: &str =
     "Output distance between features (from STDIN) and a QUERY feature (as command-line ARG)";
 pub const DISTANCE_QUERY_ARG_HELP: &str = r"
 Feature to measure from.
 
-Query Feature must be a POINT, and can be provided as
+Query Feature can be any geometry type (it must be a POINT
+when using --with-bearing), and can be provided as
 Lat/Lon, WKT, or GeoJSON.
 ";
 
-pub const DISTANCE_AFTER_HELP: &str = r"
+pub const DISTANCE_AFTER_HELP: &str = r#"
 Gives the distance in meters between each input Feature and the given QUERY Feature.
+Use --unit to give distances in another unit, or the global --units imperial
+to give them in feet by default.
 
 Output is given in the format:
 
@@ -106,11 +301,75 @@ e.g.
 This enables the output to be processed with other unix commands
 like 'sort', 'cut', etc.
 
-Distances will be given between the QUERY point and the closest
-point on each input Feature.
+Use --labeled to follow each distance with its unit for reading instead:
 
-Distance for Features that cover the QUERY point (like a Polygon containing the point) will be 0.
-";
+12.3 m	LINESTRING (30 10, 10 30, 40 40)
+
+Distances will be given between the closest points of the QUERY
+and each input Feature, depending on their geometry types:
+
+  Point to Point:           distance between the points
+  Point to Line/Polygon:    distance to the closest point on the Feature,
+                            or 0 if the Polygon contains the point
+  Line to Line:             distance between the closest points of
+                            the lines, or 0 if they cross or touch
+  Line to Polygon:          distance to the Polygon's boundary, or 0 if
+                            the line crosses or lies within the Polygon
+  Polygon to Polygon:       minimum distance between their boundaries,
+                            or 0 if they intersect or one lies within the other
+
+Multi-geometries and GeometryCollections give the minimum distance to any
+of their members. A Feature lying within a Polygon's hole is measured to
+the boundary of the hole. Closest points are found treating coordinates as planar,
+and the distance between them is then measured on the ellipsoid.
+
+Distances are calculated on the WGS84 ellipsoid (or the one given by the
+global --ellipsoid or --radius options) using Karney's geodesic algorithm
+by default, which is the most accurate and works for nearly antipodal points.
+Use --method to choose haversine (spherical, faster) or vincenty.
+
+With --with-bearing, the initial bearing from the QUERY point to the
+closest point on each Feature is included as a second column:
+
+<Distance><TAB><Bearing><TAB><Feature>
+
+e.g.
+
+92384.41943498426	89.72039867005344	34,-117
+
+Bearings are given in degrees clockwise from North (0 to 360).
+Features covering the QUERY point are given a bearing of 0.
+
+With --closest-points, each input instead gives a GeoJSON Feature with a
+LineString from the closest point on the QUERY to the closest point on
+the input, with the distance and unit as properties. Geometries which
+intersect give a line of 0 length at a point they share.
+
+$ geoq measure distance --closest-points 'POINT(0 0)' 'LINESTRING(1 -1,1 1)'
+{"geometry":{"coordinates":[[0.0,0.0],[1.0,0.0]],"type":"LineString"},"properties":{"distance":111319.49079327357,"unit":"m"},"type":"Feature"}
+
+For inputs in a projected CRS with coordinates in meters (reprojected
+with another tool, since geoq doesn't reproject), --planar measures Euclidean distances between the raw
+coordinates instead, overriding --method. The default stays geodesic,
+for Lon/Lat inputs.
+
+With --cumulative, no QUERY is needed. Instead, each input LineString
+gives 1 line of output per vertex, with the distance along the line
+up to that vertex (its chainage), starting from 0. Each LineString
+(including each part of a MultiLineString) starts again from 0.
+
+$ geoq measure distance --cumulative 'LINESTRING(0 0,0 1,0 2)'
+0
+110574.38855779252
+221149.45337212243
+"#;
+
+pub const DISTANCE_CUMULATIVE_ARG_HELP: &str =
+    "Instead of measuring from a QUERY point, give the running total distance along each input LineString at each of its vertices.";
+pub const DISTANCE_CLOSEST_POINTS_ARG_HELP: &str =
+    "Output a GeoJSON LineString Feature connecting the closest points of the QUERY and each input, with the distance as a property.";
+pub const DISTANCE_WITH_BEARING_ARG_HELP: &str =
+    "Include the initial bearing (degrees clockwise from North) from the QUERY point as a column after the distance.";
 
 pub const FILTER_AFTER_HELP: &str = r"
 Select features based on geospatial predicates
@@ -133,6 +392,131 @@ Or, to check against inputs from the command line:
 geoq filter intersects 9q5
 
 geoq filter contains 'Polygon ((30 10, 10 30, 40 40, 30 10))'
+
+To filter entities from a file instead, use --data-file. The query can
+then be given via STDIN, when no positional query is given:
+
+geoq bbox 9q5 | geoq filter intersects --data-file /path/to/data
+
+To count the matching entities instead of printing them:
+
+geoq filter intersects --count-only 9q5
+
+With --verbose, the total number of entities checked is included:
+
+passing: 12	total: 340
+
+With --repair, Polygons which are invalid because their rings cross or
+touch (themselves or each other), like a bowtie, are repaired before
+checking the spatial predicates (intersects, contains, covered-by, and
+covers). The rings are split where they meet, and the areas falling inside
+an odd number of them are kept. This applies to both inputs and queries,
+and the original input is still what's output. Checking each Polygon's
+validity takes extra time, so it's off by default. Add --verbose to report
+the number of inputs repaired (if any) to STDERR:
+
+Repaired 3 invalid features
+
+'equals' and 'type' always use the geometries as given.
+
+To check several predicates in a single pass over the inputs, add more
+with --and PREDICATE QUERY. Entities must match all of them to pass:
+
+geoq filter intersects 9q5 --and type polygon --and within-bbox-of 9q
+
+Each --and gives 1 query (or for 'type', the geometry types) on the command
+line. --negate applies to the combined predicate, so it passes the entities
+which fail any of them.
+
+--and takes exactly a PREDICATE and a QUERY, so a clause can't have options
+of its own, like a distance or an equals --tolerance. There's no 'dwithin'
+predicate: for distances, --query-buffer (below) widens the queries of every
+clause, e.g. to find Polygons within 1km of a point:
+
+geoq filter intersects --query-buffer 1km 'POINT(-118.25 34.05)' --and type polygon
+
+To check a predicate within some distance of the queries, use --query-buffer
+to buffer each of them first (including those given with --and). Distances
+are in meters, or the default unit of the global --units, unless they give
+their own (e.g. 500m, 2km, 1mi). For example, to find the inputs within 500m
+of a point, or those further away with --negate:
+
+geoq filter intersects --query-buffer 500m 'POINT(-118.25 34.05)'
+
+Buffers are approximated with 32 segments per circle, on the global --ellipsoid.
+";
+
+pub const FILTER_QUERY_BUFFER_ARG_HELP: &str =
+    "Buffer each query by DISTANCE (e.g. 500m or 2km) before checking predicates.";
+pub const FILTER_AND_ARG_HELP: &str =
+    "Also require entities to match PREDICATE (e.g. intersects or type) against QUERY. May be given more than once.\nTakes exactly these 2 values, so predicates can't be given options (like a distance or --tolerance) per clause; use the global --query-buffer for distances.";
+
+pub const FILTER_WITHIN_BBOX_OF_AFTER_HELP: &str = r"
+Passes entities whose bbox lies within (or on the edge of) the bbox of
+any of the QUERY entities. Only the bboxes are compared, so this is an
+approximate but cheap filter: it's a fast way to narrow down large inputs
+before checking an exact (and more expensive) predicate, e.g.
+
+geoq filter within-bbox-of 9q5 | geoq filter covered-by 9q5
+
+Every entity matching the exact predicate also passes. Bboxes crossing
+the antimeridian are not handled specially.
+";
+
+pub const FILTER_NEAREST_AFTER_HELP: &str = r"
+Passes the K entities with the smallest geodesic distance to any of the
+QUERY entities (measured as by 'geoq measure distance --method karney'),
+in the order they were read. Where several are the same distance away,
+the earlier ones are kept. For example, to find the 10 stores nearest to
+any of a set of customers:
+
+geoq filter nearest 10 --query-file customers.geojson < stores.geojson
+
+Only the K nearest entities seen so far are held in memory, or with
+--negate (which passes all of the others), every entity. Since the
+entities are ranked against each other, 'nearest' can't be combined
+with --and. Use 'geoq measure distance' to get the distances themselves.
+";
+
+pub const FILTER_COVERED_BY_AFTER_HELP: &str = r"
+Like 'contains', but also matches entities which touch the boundary
+of the QUERY from the inside, such as a Point on its boundary or
+a Polygon sharing one of its edges.
+
+geoq filter covered-by 'POLYGON((0 0,2 0,2 2,0 2,0 0))'
+";
+
+pub const FILTER_COVERS_AFTER_HELP: &str = r"
+Matches (Multi)Polygon entities for which every point of the QUERY
+falls inside or on their boundary. Other geometry types never match.
+
+geoq filter covers 'POINT(0 1)'
+";
+
+pub const FILTER_TYPE_AFTER_HELP: &str = r"
+Geometry types are the GeoJSON type names, matched case-insensitively:
+Point, LineString, Polygon, MultiPoint, MultiLineString, MultiPolygon,
+and GeometryCollection.
+
+Lat/Lon inputs are Points, and Geohashes are Polygons.
+
+geoq filter type polygon,multipolygon
+";
+
+pub const FILTER_EQUALS_AFTER_HELP: &str = r"
+Geometries are equal if they have the same vertices, regardless of:
+
+  * the starting vertex or winding direction of Polygon rings
+  * the direction of LineStrings
+  * the order of the elements of Multi- geometries
+  * repeated consecutive vertices
+
+A single geometry is also equal to a Multi- geometry containing only it.
+
+With --tolerance, coordinates match if their Lon and Lat each
+differ by no more than the tolerance (in degrees).
+
+geoq filter equals 'POLYGON((0 0,1 0,1 1,0 0))' --tolerance 0.000001
 ";
 
 pub const SIMPLIFY_ABOUT: &str = "Simplify geometries.";
@@ -146,6 +530,13 @@ Only (Multi-)LineStrings and (Multi-)Polygons will be affected.
 
 Takes Epsilon as a command-line parameter
 
+To simplify (Multi-)LineStrings and (Multi-)Polygons in a mixed input
+by different amounts, --epsilon-line and --epsilon-polygon override the
+positional epsilon for their type. Polygons often need gentler
+simplification, to avoid opening gaps between neighbors:
+
+geoq simplify 0.01 --epsilon-polygon 0.001 < features.geojson
+
 If the optional --to-size arg is given, geoq will iteratively simplify each
 given geometry until it is under this target number of vertices, starting
 from the provided epsilon and doubling on each attempt.
@@ -153,6 +544,26 @@ from the provided epsilon and doubling on each attempt.
 The iterative simplification will stop after 20 attempts, so it's still
 good to check the coord-count of each geometry afterward to determine
 if any rows were unable to be simplified under the desired threshold.
+
+Simplification never leaves a ring with fewer than 4 coordinates (a
+triangle, including its closing coordinate) or a line with fewer than 2.
+Use --min-points to raise this floor. A ring or line which would be
+simplified below it keeps its most significant points instead (those
+forming the largest triangles with their neighbors), and is reported
+on STDERR:
+
+geoq simplify 1.0 --min-points 8 < features.geojson
+
+Neighboring polygons simplified independently can open gaps or overlaps
+along their shared borders. With --shared-topology, all of the inputs are
+read first, and their (Multi-)Polygon rings are split into arcs where they
+meet. Each arc is simplified once, so polygons sharing it keep matching
+borders, and the points where 3 or more of them meet are kept. A ring which
+would be simplified below 4 coordinates keeps its original arcs instead.
+Polygons use the --epsilon-polygon if given, and other geometries are
+simplified on their own:
+
+geoq simplify 0.001 --shared-topology < regions.geojson
 ";
 
 pub const SIMPLIFY_EPSILON_ARG_HELP: &str = r"
@@ -163,10 +574,839 @@ pub const SIMPLIFY_TO_COORD_COUNT_ARG_HELP: &str = r"
 Target number of coords to simplify to.
 ";
 
+pub const SIMPLIFY_EPSILON_LINE_ARG_HELP: &str =
+    "Epsilon for (Multi-)LineStrings, overriding the positional epsilon.";
+pub const SIMPLIFY_EPSILON_POLYGON_ARG_HELP: &str =
+    "Epsilon for (Multi-)Polygons, overriding the positional epsilon.";
+pub const SIMPLIFY_SHARED_TOPOLOGY_ARG_HELP: &str =
+    "Simplify the borders shared by neighboring polygons once, so they keep matching. Reads all of the inputs before giving any output.";
+pub const SIMPLIFY_MIN_POINTS_ARG_HELP: &str =
+    "Fewest coordinates to keep in each ring or line. Defaults to (and can't go below) 4 for rings and 2 for lines.";
+
+pub const MEASURE_AREA_ABOUT: &str = "Measure the area of Polygons.";
+pub const MEASURE_AREA_AFTER_HELP: &str = r#"
+Gives the geodesic area of each input, on the global --ellipsoid,
+followed by the input itself, separated by a tab. Holes are subtracted.
+Points and LineStrings have an area of 0.
+
+With --signed, gives the signed planar area of Polygon exterior rings
+instead, computed from raw Lon/Lat coordinates in square degrees.
+This is positive for counter-clockwise rings and negative for clockwise
+ones, which is useful for diagnosing winding order problems.
+Holes are ignored, and the parts of MultiPolygons are summed.
+
+GeoJSON Features with a null geometry are skipped.
+Use --labeled to follow each area with its unit, e.g. '10000 m^2'.
+
+To find tiny slivers among the parts of a MultiPolygon, --per-component
+gives the area of each of its Polygons on its own line, instead of their
+total, followed by the Polygon's index (from 0) and the input:
+
+geoq measure area --per-component 'MULTIPOLYGON(((0 0,1 0,1 1,0 0)),((2 2,2.001 2,2 2.001,2 2)))'
+6154854786.721083	0	MULTIPOLYGON(((0 0,1 0,1 1,0 0)),((2 2,2.001 2,2 2.001,2 2)))
+6150.8872071020305	1	MULTIPOLYGON(((0 0,1 0,1 1,0 0)),((2 2,2.001 2,2 2.001,2 2)))
+
+With --json, each component's measurement includes its "component" index.
+Other geometries have a single component, and the members of
+GeometryCollections are each a component.
+
+geoq measure area --signed 'POLYGON((0 0,0 1,1 1,1 0,0 0))'
+-1	POLYGON((0 0,0 1,1 1,1 0,0 0))
+
+For inputs already in a projected CRS with coordinates in meters,
+--planar gives their planar area instead (with holes subtracted),
+in square meters or the square of the given --unit:
+
+geoq measure area --planar 'POLYGON((0 0,0 100,100 100,100 0,0 0))'
+10000	POLYGON((0 0,0 100,100 100,100 0,0 0))
+"#;
+pub const MEASURE_AREA_UNIT_ARG_HELP: &str =
+    "Unit to give areas in (in square units). Defaults to meters, or feet with --units imperial.";
+pub const MEASURE_AREA_SIGNED_ARG_HELP: &str =
+    "Give the signed planar area of exterior rings in square degrees: positive for counter-clockwise rings, negative for clockwise.";
+
+pub const MEASURE_LENGTH_ABOUT: &str = "Measure the length of LineStrings.";
+pub const MEASURE_LENGTH_AFTER_HELP: &str = r#"
+Gives the geodesic length of each input, on the global --ellipsoid,
+followed by the input itself, separated by a tab. The lengths of
+MultiLineStrings and GeometryCollections are summed, and Points and
+Polygons have a length of 0. GeoJSON Features with a null geometry
+are skipped.
+
+geoq measure length 'LINESTRING(0 0,0 0.001)'
+110.57427582170098	LINESTRING(0 0,0 0.001)
+
+For GPS tracks, trails, or pipes where elevation matters, --3d includes
+the change in elevation along each segment, taking Z coordinates as
+meters. Each segment's length is the hypotenuse of its geodesic length
+and its change in Z. Z coordinates are only read from GeoJSON inputs.
+Inputs without a Z for every coordinate are given their 2D length,
+with a note on STDERR.
+
+echo '{"type":"LineString","coordinates":[[0,0,0],[0,0.001,100]]}' | geoq measure length --3d
+149.08611764176302	{"type":"LineString","coordinates":[[0,0,0],[0,0.001,100]]}
+
+For inputs already in a projected CRS with coordinates in meters,
+--planar gives their planar length instead, in meters or the given --unit:
+
+geoq measure length --planar 'LINESTRING(0 0,30 40)'
+50	LINESTRING(0 0,30 40)
+"#;
+pub const MEASURE_LENGTH_3D_ARG_HELP: &str =
+    "Include the change in elevation, from GeoJSON Z coordinates in meters, in the length of each segment.";
+
 pub const MEASURE_COORDS_ABOUT: &str =
     "Count number of total coordinates/vertices in each feature. Use --geojson to get results embedded in a GeoJSON Feature as a property.";
 pub const MEASURE_COORDS_GEOJSON_ARG_HELP: &str =
     "Give result as an embedded property in a GeoJSON feature, rather than as a single number.";
+pub const MEASURE_COORDS_PER_RING_ARG_HELP: &str =
+    "Count the coordinates of each ring of each Polygon separately, giving 1 line per ring:\n<polygon index>\t<exterior|interior>\t<count>\nNon-Polygon geometries give no output.";
+
+pub const BBOX_AFTER_HELP: &str = r"Generate bbox for a geometry
+
+With --all --antimeridian, the combined bbox may wrap across the
+antimeridian (180/-180 degrees longitude) if that gives a narrower
+box, as for datasets spanning the Pacific. A wrapping bbox is given
+as a MultiPolygon split at the antimeridian.
+
+As with centroid, a bbox with infinite or NaN coordinates stops geoq
+with a JSON error on STDERR, rather than being written as GeoJSON.
+
+With --all --embed, give a single GeoJSON FeatureCollection of the
+inputs, each with its own bbox embedded, plus the bbox of all inputs
+as the collection's bbox. With --antimeridian as well, a collection
+bbox wrapping across the antimeridian is given with west > east, as
+RFC 7946 describes.
+
+geoq bbox --all --embed
+";
+pub const BBOX_ANTIMERIDIAN_ARG_HELP: &str =
+    "With --all, allow the bbox to wrap across the antimeridian if that gives a narrower bbox";
+
+pub const CLUSTER_ABOUT: &str = "Group Points into clusters using DBSCAN.";
+pub const CLUSTER_AFTER_HELP: &str = r#"
+Reads Points from STDIN and clusters them using the DBSCAN
+algorithm, measuring geodesic distances between points.
+
+A point is part of a cluster if at least --min-points points
+(including itself) fall within --eps of it, or if it is within
+--eps of such a point.
+
+By default each input is printed as a GeoJSON Feature with a
+'cluster' property giving its cluster number (starting from 0).
+Points which don't belong to any cluster ("noise") are given
+a cluster of -1.
+
+With --hulls, geoq instead prints one Polygon Feature per
+cluster giving the convex hull of its points, along with
+'cluster' and 'count' properties. Noise points are omitted,
+as are clusters of fewer than 3 points (possible with a
+--min-points of 1 or 2), which have no hull with an area.
+
+Note that all input points are held in memory, and the
+neighbor search compares every pair of points, so this is
+best suited to modestly sized inputs.
+
+Example:
+
+$ printf "34.0,-118.0\n34.0001,-118.0001\n40.7,-74.0\n" | geoq cluster --eps 50m --min-points 2
+{"geometry":{"coordinates":[-118.0,34.0],"type":"Point"},"properties":{"cluster":0},"type":"Feature"}
+{"geometry":{"coordinates":[-118.0001,34.0001],"type":"Point"},"properties":{"cluster":0},"type":"Feature"}
+{"geometry":{"coordinates":[-74.0,40.7],"type":"Point"},"properties":{"cluster":-1},"type":"Feature"}
+"#;
+pub const CLUSTER_EPS_ARG_HELP: &str =
+    "Maximum distance between neighboring points, e.g. 500m, 1.5km, 2mi, or 30ft. Bare numbers are treated as meters (or feet with --units imperial).";
+pub const CLUSTER_MIN_POINTS_ARG_HELP: &str =
+    "Minimum number of points (including itself) within --eps of a point for it to start a cluster. Must be at least 1.";
+pub const CLUSTER_HULLS_ARG_HELP: &str =
+    "Output the convex hull of each cluster as a Polygon rather than printing each point.";
+
+pub const VORONOI_ABOUT: &str = "Generate Voronoi polygons for a set of Points.";
+pub const VORONOI_AFTER_HELP: &str = r#"
+Reads Points from STDIN and outputs the Voronoi cell of each
+point as a GeoJSON Polygon Feature: the region which is closer
+to that point than to any other input point.
+
+Cells are clipped to the bounding box of the input points (padded
+to have some area if the points are collinear), or,
+if --clip is given, to the provided boundary Polygon.
+
+Each cell carries the properties of its generating point
+(if it was a GeoJSON Feature), along with an 'index' property
+giving the position of the point in the input (starting from 0).
+Cells falling entirely outside the --clip boundary are omitted.
+
+Distances are computed on the plane using raw Lon/Lat
+coordinates, so cells far from the equator will be distorted
+relative to true geodesic distances.
+
+All input points are held in memory.
+
+Example:
+
+$ printf "0,0\n0,2\n" | geoq voronoi --clip 'POLYGON((-1 -1,3 -1,3 1,-1 1,-1 -1))'
+"#;
+pub const VORONOI_CLIP_ARG_HELP: &str =
+    "Polygon to clip the cells to, as Geohash, WKT, or GeoJSON. Defaults to the bounding box of the input points.";
+
+pub const DELAUNAY_ABOUT: &str = "Generate the Delaunay triangulation of a set of Points.";
+pub const DELAUNAY_AFTER_HELP: &str = r#"
+Reads Points from STDIN and outputs the triangles of their
+Delaunay triangulation as GeoJSON Polygon Features.
+
+Each triangle includes 'area' and 'circumradius' properties.
+These are computed on the plane using raw Lon/Lat coordinates,
+so 'area' is in square degrees and 'circumradius' in degrees,
+not meters. They are meant for comparing triangles, e.g. to
+filter out slivers. For geodesic areas in square meters,
+pipe the triangles to 'geoq measure area'.
+
+With --edges, the unique edges of the triangulation are
+output as LineString Features instead.
+
+Duplicate points are ignored, and all input points are held
+in memory.
+
+Example:
+
+$ printf "0,0\n0,1\n1,1\n" | geoq delaunay
+{"geometry":{"coordinates":[[[0.0,0.0],[1.0,0.0],[1.0,1.0],[0.0,0.0]]],"type":"Polygon"},"properties":{"area":0.5,"circumradius":0.7071067811865476},"type":"Feature"}
+"#;
+pub const DELAUNAY_EDGES_ARG_HELP: &str =
+    "Output the edges of the triangulation as LineStrings rather than the triangles as Polygons.";
+
+pub const JOIN_ABOUT: &str = "Merge columns from a CSV file into feature properties by a shared key.";
+pub const JOIN_AFTER_HELP: &str = r#"
+Reads features from STDIN and, for each one, looks up the CSV row
+whose --on column matches the feature's property of the same name.
+The other columns of the matching row are merged into the feature's
+properties. CSV values are always added as strings.
+
+Keys are compared as strings, so a numeric property 1 matches a
+CSV value "1". When joining --on id, a GeoJSON Feature's top-level
+"id" is used if present, before falling back to an "id" property.
+
+Features with no matching row are output unchanged, or dropped
+if --inner is given.
+
+The CSV file must have a header row, and is held in memory.
+If multiple rows share a key, the last one is used.
+
+Example:
+
+$ cat data.csv
+id,name
+1,Paris
+
+$ echo '{"type":"Feature","properties":{"id":1},"geometry":{"type":"Point","coordinates":[2.35,48.85]}}' | geoq join --csv data.csv --on id
+{"geometry":{"coordinates":[2.35,48.85],"type":"Point"},"properties":{"id":1,"name":"Paris"},"type":"Feature"}
+"#;
+pub const JOIN_CSV_ARG_HELP: &str = "Path to a CSV file, with a header row, to join against.";
+pub const JOIN_ON_ARG_HELP: &str =
+    "Name of the CSV column and feature property to match rows and features by.";
+pub const JOIN_INNER_ARG_HELP: &str = "Drop features which have no matching CSV row.";
+
+pub const SJOIN_ABOUT: &str = "Merge properties from query features into the input features they spatially relate to.";
+pub const SJOIN_AFTER_HELP: &str = r#"
+Reads features from STDIN and checks each one against the query
+features read from --query-file, using the given --predicate:
+
+  * intersects - the input intersects the query feature
+  * contains   - the input falls within the query feature,
+                 which must be a POLYGON or MULTIPOLYGON
+
+For each matching query feature, the input is output as a GeoJSON
+Feature with the query feature's properties merged into its own.
+Query property names are given a --prefix ("query_" by default)
+so they don't overwrite properties of the input.
+
+With --mode all (the default) an input matching several query
+features is output once per match. With --mode first it is output
+once, joined with the first matching query feature in the file.
+
+As with 'filter', inputs which match no query features are omitted.
+
+Example:
+
+$ cat regions.geojson
+{"type":"Feature","properties":{"name":"SF"},"geometry":{"type":"Polygon","coordinates":[[[-123,37],[-122,37],[-122,38],[-123,38],[-123,37]]]}}
 
-pub const BBOX_AFTER_HELP: &str =
-    "Generate bbox for a geometry";
+$ echo 37.77,-122.42 | geoq sjoin --query-file regions.geojson
+{"geometry":{"coordinates":[-122.42,37.77],"type":"Point"},"properties":{"query_name":"SF"},"type":"Feature"}
+"#;
+pub const SJOIN_QUERY_FILE_ARG_HELP: &str = "Input file for reading query feature(s) to join against.";
+pub const SJOIN_PREDICATE_ARG_HELP: &str = "Spatial relationship an input must have with a query feature to match it.";
+pub const SJOIN_MODE_ARG_HELP: &str =
+    "Output inputs once per matching query feature (all), or only once with the first match (first).";
+pub const SJOIN_PREFIX_ARG_HELP: &str = "Prefix added to the names of properties merged from query features.";
+
+pub const PIP_ABOUT: &str = "Tag Points with a property of the Polygon containing them.";
+pub const PIP_AFTER_HELP: &str = r#"
+Reads Points from STDIN and outputs each as a GeoJSON Feature,
+with the --field property of the polygon containing it (read from
+the --polygons file) added to its properties. Points not contained
+by any polygon are given a null value.
+
+If several polygons contain a point, the first one in the file
+is used. The polygons are held in memory in an R-tree index,
+so this is much faster than 'sjoin' for large polygon sets.
+
+Example:
+
+$ cat regions.geojson
+{"type":"Feature","properties":{"region_name":"SF"},"geometry":{"type":"Polygon","coordinates":[[[-123,37],[-122,37],[-122,38],[-123,38],[-123,37]]]}}
+
+$ printf "37.77,-122.42
+40.7,-74.0
+" | geoq pip --polygons regions.geojson --field region_name
+{"geometry":{"coordinates":[-122.42,37.77],"type":"Point"},"properties":{"region_name":"SF"},"type":"Feature"}
+{"geometry":{"coordinates":[-74.0,40.7],"type":"Point"},"properties":{"region_name":null},"type":"Feature"}
+"#;
+pub const PIP_POLYGONS_ARG_HELP: &str = "Input file for reading POLYGON or MULTIPOLYGON features.";
+pub const PIP_FIELD_ARG_HELP: &str = "Name of the polygon property to tag points with.";
+
+pub const FLIP_ABOUT: &str = "Swap the Lat and Lon of every coordinate.";
+pub const FLIP_AFTER_HELP: &str = r#"
+Fixes data which was written in Lat/Lon order rather than the
+Lon/Lat (X/Y) order used by WKT and GeoJSON, by swapping the
+first 2 values of every coordinate of each input.
+
+Outputs are given in the same format as inputs: WKT inputs give
+WKT, GeoJSON Geometries give GeoJSON Geometries, and GeoJSON
+Features keep their properties. Lat/Lon inputs give Lat/Lon
+outputs, and Geohashes give GeoJSON Features.
+
+With --detect, a warning is printed to STDERR for any input whose
+flipped coordinates fall outside the valid range for Lon/Lat
+(|Lon| <= 180, |Lat| <= 90), as it was probably not swapped
+to begin with. Those inputs are still flipped.
+
+Example:
+
+$ echo 'LINESTRING(34 -118,35 -117)' | geoq flip
+LINESTRING(-118 34,-117 35)
+"#;
+pub const FLIP_DETECT_ARG_HELP: &str =
+    "Warn on STDERR about inputs which give out-of-range Lon/Lat coordinates when flipped.";
+
+pub const VALIDATE_ABOUT: &str = "Check inputs for common data problems.";
+pub const VALIDATE_AFTER_HELP: &str = r#"
+Reads inputs from STDIN and passes them through to STDOUT,
+reporting any problems found on STDERR, prefixed by the
+(1-based) number of the input.
+
+Validations:
+
+  --coords  Flag inputs with coordinates out of the valid range
+            for Lon/Lat (|Lon| > 180 or |Lat| > 90). These are
+            often caused by data with Lat and Lon transposed.
+
+With --fix, inputs whose coordinates would be in range with
+Lat and Lon swapped (see 'geoq flip') are output flipped instead.
+Fixed inputs are given in the same format they were read in.
+
+Example:
+
+$ printf "POINT(-118 34)\nPOINT(34 -118)\n" | geoq validate --coords --fix
+POINT(-118 34)
+Input 2: swapped Lat/Lon: POINT(34 -118) -> POINT(-118 34)
+POINT(-118 34)
+"#;
+pub const VALIDATE_COORDS_ARG_HELP: &str = "Check that coordinates are within the valid range for Lon/Lat.";
+pub const VALIDATE_FIX_ARG_HELP: &str =
+    "Swap the Lat and Lon of out-of-range inputs when this brings them into range.";
+
+pub const HASH_ABOUT: &str = "Give a stable content hash for each input geometry.";
+pub const HASH_AFTER_HELP: &str = r#"
+Outputs the hex-encoded SHA-256 hash of each input's geometry,
+encoded as (little-endian, 2D) WKB, in the format:
+
+<Hash><TAB><Feature>
+
+Hashes depend only on the geometry itself, not the format it was
+given in, so e.g. 'POINT(1 2)' and '{"type":"Point","coordinates":[1,2]}'
+give the same hash. This makes them usable as cache keys, or for
+finding duplicate or changed features between datasets.
+
+Geometries are normalized before hashing, so that equal geometries
+written differently give the same hash: repeated consecutive points
+are dropped, Polygon exterior rings are wound counter-clockwise and
+holes clockwise, each ring starts from its minimum (lowest X, then Y)
+vertex, and holes are sorted. The order of line vertices and of the
+parts of Multi- geometries is kept, as it's part of the geometry.
+
+With --with-properties, the properties of GeoJSON Features are
+included in the hash as well. Property order doesn't matter.
+
+Example:
+
+$ echo 'POINT(1 2)' | geoq hash
+<64 hex characters><TAB>POINT(1 2)
+"#;
+pub const HASH_WITH_PROPERTIES_ARG_HELP: &str = "Include Feature properties in the hash as well as the geometry.";
+
+pub const MERGE_PROPERTIES_ABOUT: &str = "Combine features with the same geometry, merging their properties.";
+pub const MERGE_PROPERTIES_AFTER_HELP: &str = r#"
+Reads all of the inputs, and outputs 1 GeoJSON Feature for each distinct
+geometry (matched by the same hash as 'geoq hash'), in the order they were
+first seen. Its properties are the combined properties of every input with
+that geometry, and its id is the id of the first of them.
+
+When features give different values for the same property, --conflict
+chooses which to keep: the first one seen (the default), the last one, or
+an array of all of the distinct values, in the order they were seen.
+
+Example:
+
+$ printf '%s
+'   '{"type":"Feature","properties":{"name":"a","pop":1},"geometry":{"type":"Point","coordinates":[1,2]}}'   '{"type":"Feature","properties":{"name":"b","area":3},"geometry":{"type":"Point","coordinates":[1,2]}}'   | geoq merge-properties --conflict array
+{"geometry":{"coordinates":[1.0,2.0],"type":"Point"},"properties":{"area":3,"name":["a","b"],"pop":1},"type":"Feature"}
+"#;
+pub const MERGE_PROPERTIES_CONFLICT_ARG_HELP: &str =
+    "Which value to keep when features with the same geometry give different values for a property.";
+
+pub const DIFF_ABOUT: &str = "Compare 2 sets of features to find added, removed, and modified features.";
+pub const DIFF_AFTER_HELP: &str = r#"
+Reads features from files A (before) and B (after), and matches
+them up by the --key property (by default "id", which also uses
+the top-level "id" of GeoJSON Features).
+
+Outputs GeoJSON Features with a "_diff" property giving the change:
+
+  * added    - only in B
+  * removed  - only in A
+  * modified - in both, but with a different geometry or properties
+
+Added and modified features are given as they are in B, removed
+features as they are in A. Unchanged features are omitted unless
+--unchanged is given.
+
+Features are compared using the same content hash as 'geoq hash
+--with-properties', so differences in formatting don't matter.
+Features without a key are skipped with a warning on STDERR.
+Both files are held in memory.
+
+Example:
+
+$ geoq diff before.geojson after.geojson --key id
+"#;
+pub const DIFF_A_ARG_HELP: &str = "File containing the original (before) features.";
+pub const DIFF_B_ARG_HELP: &str = "File containing the updated (after) features.";
+pub const DIFF_KEY_ARG_HELP: &str = "Property used to match features between the 2 files.";
+pub const DIFF_UNCHANGED_ARG_HELP: &str = "Also output unchanged features, with a _diff of 'unchanged'.";
+
+pub const RESAMPLE_ABOUT: &str = "Drop vertices which are closer together than a minimum distance.";
+pub const RESAMPLE_AFTER_HELP: &str = r"
+Reads features from STDIN.
+
+Walks each LineString (or Polygon ring) and drops vertices which are
+closer than --min-distance to the previous kept vertex. This is useful
+for thinning out GPS traces with many redundant points.
+
+The first and last vertex of each LineString are always kept, and rings
+which would be left with fewer than 4 vertices are left unchanged.
+Only (Multi-)LineStrings and (Multi-)Polygons will be affected.
+
+Distances are geodesic (in meters unless a unit suffix is given).
+
+Example:
+
+$ geoq resample --min-distance 10m < trace.geojson
+";
+pub const RESAMPLE_MIN_DISTANCE_ARG_HELP: &str =
+    "Minimum distance between kept vertices, e.g. 10m, 0.5km, or 30ft. Bare numbers are treated as meters (or feet with --units imperial).";
+
+pub const DENSIFY_ABOUT: &str = "Add vertices to lines and polygons.";
+pub const DENSIFY_AFTER_HELP: &str = r"
+With --max-segment, splits each segment of every LineString (or Polygon
+ring) into equal pieces no longer than the given distance. Existing
+vertices are kept. This is useful before reprojecting, so that long
+edges follow the curve of the earth.
+
+With --to-count N, replaces the vertices of each LineString (and ring)
+with exactly N vertices, evenly spaced along it. This is useful when
+lines need matching vertex counts, like for animating between them.
+The first and last vertices stay fixed, so rings stay closed. Rings
+need at least 4 vertices, so they're left unchanged for N below 4.
+
+Only (Multi-)LineStrings and (Multi-)Polygons will be affected.
+Distances are geodesic (in meters unless a unit suffix is given),
+and new vertices are placed along the geodesic between existing ones.
+
+Example:
+
+$ geoq densify --max-segment 10km < routes.geojson
+$ geoq densify --to-count 100 < routes.geojson
+";
+pub const DENSIFY_MAX_SEGMENT_ARG_HELP: &str =
+    "Maximum length of each segment, e.g. 100m, 10km, or 30ft. Bare numbers are treated as meters (or feet with --units imperial).";
+pub const DENSIFY_TO_COUNT_ARG_HELP: &str =
+    "Redistribute each line and ring to exactly this many evenly spaced vertices.";
+
+pub const SMOOTH_ABOUT: &str = "Smooth jagged lines and polygons for display.";
+pub const SMOOTH_AFTER_HELP: &str = r"
+Reads features from STDIN.
+
+Methods:
+
+  * chaikin - Chaikin's corner-cutting algorithm. Each iteration
+              cuts every corner, doubling the number of vertices.
+  * spline  - Catmull-Rom spline through the original vertices.
+              Each segment is split into 2^iterations pieces.
+
+Open LineStrings keep their first and last vertex, and Polygon rings
+stay closed. Only (Multi-)LineStrings and (Multi-)Polygons will be affected.
+
+Smoothing treats coordinates as planar, and is meant for display
+rather than analysis. See 'geoq simplify' to go the other way.
+
+Example:
+
+$ geoq smooth --iterations 2 < lines.geojson
+";
+pub const SMOOTH_ITERATIONS_ARG_HELP: &str = "Number of smoothing iterations, up to 16.";
+pub const SMOOTH_METHOD_ARG_HELP: &str = "Smoothing algorithm to use.";
+
+pub const EXTENT_ABOUT: &str = "Print the bounding box of each input as plain numbers.";
+pub const EXTENT_AFTER_HELP: &str = r"
+Prints the bounding box of each input as a comma-separated line
+of minlon,minlat,maxlon,maxlat.
+
+With --all, prints a single line giving the bounding box of all
+inputs combined.
+
+With --all --antimeridian, the extent may instead wrap across the
+antimeridian (180/-180 degrees longitude) if that gives a narrower
+extent. Wrapping extents are given with minlon > maxlon.
+
+Unlike 'geoq bbox', which outputs bounding box Polygons, this output
+can be passed directly to other tools, e.g. GDAL's -te option.
+
+Example:
+
+$ echo 'LINESTRING (-118 34, -117 35)' | geoq extent
+-118,34,-117,35
+";
+pub const EXTENT_ALL_ARG_HELP: &str =
+    "Give a single extent for all input geometries rather than 1 extent per input";
+pub const EXTENT_ANTIMERIDIAN_ARG_HELP: &str =
+    "With --all, allow the extent to wrap across the antimeridian (giving minlon > maxlon) if that gives a narrower extent";
+
+pub const NORMALIZE_LON_ABOUT: &str = "Wrap longitudes into the range [-180, 180].";
+pub const NORMALIZE_LON_AFTER_HELP: &str = r#"
+Reads inputs from STDIN and wraps every longitude into [-180, 180],
+as for climate and ocean datasets which use 0-360 longitudes.
+Longitudes already in range (including both 180 and -180) are
+left unchanged. With --to-0-360, longitudes are instead wrapped
+into [0, 360].
+
+Lines and polygons which cross the antimeridian (180/-180 degrees)
+will jump across the map once wrapped. With --split, they are
+instead cut at the antimeridian into MultiLineStrings and
+MultiPolygons whose pieces stay within [-180, 180].
+
+Outputs are given in the same format as inputs (see 'geoq flip').
+
+Example:
+
+$ echo 'LINESTRING(170 0,190 10)' | geoq normalize-lon --split
+MULTILINESTRING((170 0,180 5),(-180 5,-170 10))
+"#;
+pub const NORMALIZE_LON_TO_0_360_ARG_HELP: &str = "Wrap longitudes into [0, 360] instead.";
+pub const NORMALIZE_LON_SPLIT_ARG_HELP: &str =
+    "Split lines and polygons which cross the antimeridian into Multi- geometries.";
+
+pub const TO_MULTI_ABOUT: &str = "Convert single geometries to their Multi- equivalent.";
+pub const TO_MULTI_AFTER_HELP: &str = r#"
+Promotes Points to MultiPoints, LineStrings to MultiLineStrings,
+and Polygons to MultiPolygons, so that every output has a consistent
+geometry type, as required by some formats (e.g. Shapefiles or
+GeoPackages). Multi- geometries and GeometryCollections are left as-is.
+
+Outputs are given in the same format as inputs (see 'geoq flip').
+
+Example:
+
+$ echo 'POLYGON((0 0,1 0,1 1,0 0))' | geoq to-multi
+MULTIPOLYGON(((0 0,1 0,1 1,0 0)))
+"#;
+
+pub const TO_SINGLE_ABOUT: &str = "Convert 1-element Multi- geometries to single geometries.";
+pub const TO_SINGLE_AFTER_HELP: &str = r#"
+Demotes MultiPoints, MultiLineStrings, and MultiPolygons which contain
+exactly 1 element to a Point, LineString, or Polygon. This is the
+inverse of 'geoq to-multi'.
+
+Multi- geometries with more than 1 element can't be demoted: they
+are left as-is, with a warning on STDERR.
+
+Example:
+
+$ echo 'MULTIPOLYGON(((0 0,1 0,1 1,0 0)))' | geoq to-single
+POLYGON((0 0,1 0,1 1,0 0))
+"#;
+
+pub const INFO_ABOUT: &str = "Print a summary of each input.";
+pub const INFO_AFTER_HELP: &str = r#"
+Prints a summary of each input, giving its geometry type, number of
+vertices, bbox (as minlon,minlat,maxlon,maxlat), geodesic area and
+length, and number of properties. This is useful when first
+exploring an unknown dataset.
+
+Only (Multi-)Polygons have an area, and only (Multi-)LineStrings
+have a length; other types give 0. Areas are given in square meters
+and lengths in meters (or square feet and feet with --units imperial).
+
+With --json, each summary is given as a JSON object, including the
+input's "id", if it has one.
+
+Example:
+
+$ echo 'LINESTRING(0 0,1 0)' | geoq info
+LineString	vertices: 2	bbox: 0,0,1,0	area: 0 m^2	length: 111319.49079327357 m	properties: 0
+"#;
+pub const INFO_JSON_ARG_HELP: &str = "Output each summary as a JSON object.";
+
+pub const STATS_ABOUT: &str = "Print statistics for all of the inputs combined.";
+pub const STATS_AFTER_HELP: &str = r#"
+Reads all of the inputs and prints totals for the whole dataset: the
+number of features, their combined bbox (as minlon,minlat,maxlon,maxlat),
+and their total geodesic area, length, and number of vertices. The
+same totals (without the bbox) follow for each geometry type.
+This complements 'geoq info', which summarizes each input separately.
+
+Areas and lengths are measured as in 'geoq info'.
+
+Example:
+
+$ printf 'LINESTRING(0 0,1 0)
+0,0
+' | geoq stats
+total	count: 2	vertices: 3	area: 0 m^2	length: 111319.49079327357 m	bbox: 0,0,1,0
+LineString	count: 1	vertices: 2	area: 0 m^2	length: 111319.49079327357 m
+Point	count: 1	vertices: 1	area: 0 m^2	length: 0 m
+
+With --json, the report is given as a single JSON object:
+
+{"area":0.0,"bbox":[0.0,0.0,1.0,0.0],"count":2,"length":111319.49079327357,"types":{"LineString":{...},"Point":{...}},"unit":"m","vertices":3}
+"#;
+pub const STATS_JSON_ARG_HELP: &str = "Output the statistics as a JSON object.";
+
+pub const WKT_AFTER_HELP: &str = r"
+Geometry types (for --with-type and --only) are the WKT type names:
+POINT, LINESTRING, POLYGON, MULTIPOINT, MULTILINESTRING, MULTIPOLYGON,
+and GEOMETRYCOLLECTION. --only is case-insensitive.
+
+geoq wkt --with-type --only point,polygon | cut -f 2
+";
+
+pub const MAP_URL_ARG_HELP: &str =
+    "Print the geojson.io URL instead of opening it in a browser.\nIf the features are too large for a URL, prints the path of the generated map file instead.";
+pub const MAP_SIMPLIFY_ARG_HELP: &str =
+    "Simplify geometries with the given epsilon before mapping them (see 'geoq help simplify').";
+pub const MAP_SAMPLE_ARG_HELP: &str =
+    "Map a random sample of N of the features, rather than all of them.";
+pub const MAP_AFTER_HELP: &str = r"
+Small sets of features are sent to geojson.io in the URL. Larger ones
+are written into a temporary HTML file which loads them into geojson.io.
+
+Very large inputs (over about 5MB of GeoJSON) can make geojson.io
+slow or unable to load, so geoq warns about them. To make them usable,
+reduce their size with --simplify-for-display EPSILON, which simplifies
+each geometry, or --sample N, which maps N randomly chosen features.
+
+geoq map --sample 1000 --simplify-for-display 0.001 < parcels.geojson
+";
+
+pub const SORT_ABOUT: &str = "Sort inputs by a spatial key of their centroids.";
+pub const SORT_AFTER_HELP: &str = r"
+Sorts inputs so that features which are near each other are usually
+near each other in the output. This improves locality for tiling
+and compression of downstream formats.
+
+--by hilbert (the default) sorts by the position of each centroid
+along a Hilbert curve covering the globe, which keeps nearby features
+together better than --by geohash, which sorts by Geohash string.
+
+Inputs are output unchanged. Inputs without a centroid (e.g. empty
+geometries) are given last, and ties keep their input order.
+
+Note that sorting requires reading all of the input into memory
+before giving any output.
+
+geoq sort --by hilbert < buildings.geojson > sorted.geojson
+";
+pub const SORT_BY_ARG_HELP: &str = "Spatial key to sort by.";
+
+pub const BIN_ABOUT: &str = "Aggregate points into Geohash or grid cells.";
+pub const BIN_AFTER_HELP: &str = r#"
+Groups input Points into cells, and outputs a GeoJSON Polygon Feature
+for each cell containing at least 1 point, with the number of points
+in its "count" property. Useful for heatmaps and density mapping.
+
+Cells are either Geohashes at the given level (--geohash 6), or
+cells of a grid roughly the given distance across (--grid 1km).
+Grid cells are sized using a spherical Earth, and get wider in
+degrees of longitude towards the poles to stay roughly square.
+
+--sum PROPERTY and --mean PROPERTY add "PROPERTY_sum" and
+"PROPERTY_mean" properties aggregating a numeric property of the
+points in each cell. Points where the property is missing or not a
+number are counted, but not included in the sum or mean.
+Both can be given multiple times.
+
+All input is read before output is given, in order of cell.
+
+geoq bin --geohash 6 --sum population --mean age < people.geojson
+"#;
+pub const BIN_GEOHASH_ARG_HELP: &str = "Bin points into Geohashes of the given level, from 1 to 12.";
+pub const BIN_GRID_ARG_HELP: &str =
+    "Bin points into grid cells of roughly the given size, e.g. 500m or 1km. Bare numbers are treated as meters (or feet with --units imperial).";
+pub const BIN_SUM_ARG_HELP: &str = "Sum the given numeric property of the points in each cell.";
+pub const BIN_MEAN_ARG_HELP: &str = "Average the given numeric property of the points in each cell.";
+
+pub const GH_CHILDREN_LEVEL_ARG_HELP: &str =
+    "Give all descendants N levels deeper (32^N Geohashes per input) rather than the immediate children, in lexicographic order.\nN can be at most 4, which gives 1,048,576 Geohashes per input.";
+
+pub const GRID_ABOUT: &str = "Generate the grid cells covering the extent of the inputs.";
+pub const GRID_AFTER_HELP: &str = r#"
+Reads all of the inputs, and outputs a GeoJSON Polygon Feature for each
+cell of a grid covering their combined bounding box. Useful for making
+an empty grid to aggregate data into, or to join against.
+
+With --geohash LEVEL, the cells are the Geohashes of that level, so they
+line up with an existing Geohash index. Each one has its Geohash as its
+id and in its "geohash" property, the same as the cells from
+'geoq bin --geohash', so the two can be matched up:
+
+geoq grid --geohash 2 'POLYGON((1 1,30 1,30 14,1 14,1 1))'
+
+With --size DISTANCE, the cells are those of the grid used by
+'geoq bin --grid', roughly the given distance across, with their
+"row" and "col" in the grid as properties.
+"#;
+pub const GRID_GEOHASH_ARG_HELP: &str = "Use the Geohashes of the given level, from 1 to 12, as the grid cells.";
+pub const GRID_SIZE_ARG_HELP: &str =
+    "Use grid cells of roughly the given size, e.g. 500m or 1km. Bare numbers are treated as meters (or feet with --units imperial).";
+
+pub const INDEX_ABOUT: &str = "Tag each input with the Geohash of its centroid.";
+pub const INDEX_AFTER_HELP: &str = r#"
+Outputs each input as a GeoJSON Feature, with the Geohash containing
+its centroid added as a property named for the level, e.g. "geohash_7".
+Geometries are left unchanged. Give --geohash more than once to tag
+several levels, e.g. for bucketing features in a data warehouse:
+
+geoq index --geohash 5 --geohash 7 < features.geojson
+
+Features with a null geometry are tagged with null.
+"#;
+pub const INDEX_GEOHASH_ARG_HELP: &str =
+    "Add the Geohash of the given level (1 to 12) for each input's centroid. May be given more than once.";
+
+pub const POLYGONIZE_ABOUT: &str = "Build Polygons from LineStrings which form closed rings.";
+pub const POLYGONIZE_AFTER_HELP: &str = r#"
+Reads (Multi)LineStrings and outputs the Polygons formed by the faces
+between them, as in PostGIS ST_Polygonize. For example, road edges
+give the Polygons of the blocks between the roads.
+
+Lines must be noded: they may only meet or cross at their endpoints.
+Lines which meet in the middle of another line won't be joined.
+
+Rings nested inside of another Polygon (without touching it) are
+output as Polygons, and also become holes in the Polygon around them.
+
+With --report-edges, lines which couldn't form part of any Polygon are
+also output, as LineString Features with an "edge" property of:
+
+  * "dangle" - an end of the line isn't connected to any other line
+  * "cut" - the line connects 2 rings, without forming part of either
+
+All input is read before output is given.
+
+geoq polygonize --report-edges < edges.geojson
+"#;
+pub const POLYGONIZE_REPORT_EDGES_ARG_HELP: &str =
+    "Also output dangling and cut edges which didn't form Polygons.";
+pub const NODE_ABOUT: &str = "Split LineStrings wherever they cross or touch.";
+pub const NODE_AFTER_HELP: &str = r#"
+Reads (Multi)LineStrings and splits them at every point where they
+meet another line, or cross themselves, so that lines only meet at
+their endpoints. This prepares lines for polygonize, as in PostGIS
+ST_Node.
+
+Each piece is output separately, in the same format as the line it
+came from. Features keep their properties.
+
+Every segment is compared against every other, and all input is read
+before output is given, so this is best suited to modest inputs.
+
+geoq node < roads.geojson | geoq polygonize
+"#;
+pub const SPLIT_BY_ABOUT: &str = "Cut LineStrings and Polygons into pieces along a blade line.";
+pub const SPLIT_BY_AFTER_HELP: &str = r#"
+Splits each input along the --blade LineString and outputs the
+resulting pieces, as in PostGIS ST_Split:
+
+  * (Multi)LineStrings are split at each point where they meet the blade
+  * (Multi)Polygons are cut into the pieces on either side of the blade
+
+Each piece is output separately, in the same format as its input.
+Features keep their properties.
+
+Inputs which the blade doesn't cut (including Polygons which it doesn't
+cross all the way through, and Points) are output unchanged.
+
+Splitting is done on the plane using raw Lon/Lat coordinates.
+
+geoq split-by --blade 'LINESTRING(-122.4 37.7,-122.4 37.8)' < parcels.geojson
+"#;
+pub const SPLIT_BY_BLADE_ARG_HELP: &str =
+    "(Multi)LineString to cut the inputs along, as WKT or GeoJSON.";
+pub const CLIP_ABOUT: &str = "Crop geometries to a rectangular window.";
+pub const CLIP_AFTER_HELP: &str = r#"
+Clips each input to the rectangle minlon,minlat,maxlon,maxlat and
+outputs the part which falls inside it, in the same format as the input.
+Inputs which fall entirely outside of the rectangle are dropped.
+
+Polygons are clipped with Sutherland-Hodgman clipping, and lines with
+Liang-Barsky clipping, which makes this much cheaper than a general
+Polygon intersection. A concave Polygon which leaves the window and
+comes back will still be a single Polygon, with the pieces joined by
+edges along the window's boundary. A LineString which leaves the
+window and comes back becomes a MultiLineString.
+
+Clipping is done on the plane using raw Lon/Lat coordinates.
+
+geoq clip -122.5,37.7,-122.3,37.8 < features.geojson
+"#;
+pub const CLIP_BBOX_ARG_HELP: &str =
+    "Rectangle to clip to, as minlon,minlat,maxlon,maxlat.";
+pub const REPAIR_WINDING_ABOUT: &str = "Fix Polygon ring orientation to follow the GeoJSON right-hand rule.";
+pub const REPAIR_WINDING_AFTER_HELP: &str = r#"
+Reorients Polygon rings to follow the right-hand rule of RFC 7946:
+exterior rings are made counter-clockwise, and holes clockwise. Some
+renderers (like Mapbox GL) draw Polygons wound the other way incorrectly.
+
+Inputs which are already wound correctly (including all non-Polygon
+inputs) are output unchanged. Repaired inputs are output in the same
+format they were given in.
+
+Once all input has been read, the number of features which needed
+repairs is printed to STDERR.
+
+Orientation is determined on the plane using raw Lon/Lat coordinates.
+Use 'geoq measure area --signed' to check the winding of exterior rings.
+
+geoq repair-winding < polygons.geojson > fixed.geojson
+"#;
+pub const VERSION_ABOUT: &str = "Print version and build information.";
+pub const VERSION_AFTER_HELP: &str = r#"
+Prints the geoq version. With --json, also gives the target the
+binary was built for, the git commit it was built from (if it was
+built from a git checkout), and the optional cargo features it was
+compiled with:
+
+geoq version --json
+{"features":[],"git_commit":"1a2b3c4","target":"x86_64-unknown-linux-gnu","version":"0.0.18"}
+"#;
+pub const VERSION_JSON_ARG_HELP: &str = "Give version and build information as a JSON object.";
@@ -0,0 +1,97 @@
+use crate::geoq::error::Error;
+use once_cell::sync::{Lazy, OnceCell};
+use regex::Regex;
+
+static DISTANCE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(?i)(\d+\.?\d*|\.\d+)\s*(m|km|mi|ft)?$").unwrap());
+
+const METERS_PER_KM: f64 = 1000.0;
+const METERS_PER_MILE: f64 = 1609.344;
+const METERS_PER_FOOT: f64 = 0.3048;
+
+pub const DISTANCE_UNITS: [&str; 4] = ["m", "km", "mi", "ft"];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UnitSystem {
+    Metric,
+    Imperial,
+}
+
+static UNIT_SYSTEM: OnceCell<UnitSystem> = OnceCell::new();
+
+pub fn set_unit_system(system: UnitSystem) {
+    UNIT_SYSTEM.set(system).expect("Unit system should only be set once");
+}
+
+fn unit_system() -> UnitSystem {
+    UNIT_SYSTEM.get().cloned().unwrap_or(UnitSystem::Metric)
+}
+
+// Unit used for distances given or output without an explicit unit
+pub fn default_distance_unit() -> &'static str {
+    match unit_system() {
+        UnitSystem::Metric => "m",
+        UnitSystem::Imperial => "ft",
+    }
+}
+
+// Number of meters in 1 of the given distance unit
+pub fn meters_per(unit: &str) -> Option<f64> {
+    match unit.to_lowercase().as_str() {
+        "m" => Some(1.0),
+        "km" => Some(METERS_PER_KM),
+        "mi" => Some(METERS_PER_MILE),
+        "ft" => Some(METERS_PER_FOOT),
+        _ => None,
+    }
+}
+
+// Parse a distance like "500", "500m", "1.5km", "2mi", or "30ft" into meters.
+// Bare numbers are treated as the default unit for the current --units
+// system (meters for metric, feet for imperial).
+pub fn parse_distance(raw: &str) -> Result<f64, Error> {
+    parse_distance_in(raw, default_distance_unit())
+}
+
+fn parse_distance_in(raw: &str, default_unit: &str) -> Result<f64, Error> {
+    match DISTANCE.captures(raw.trim()) {
+        Some(caps) => {
+            let value = caps[1]
+                .parse::<f64>()
+                .map_err(|_| Error::InvalidNumberFormat)?;
+            let unit = caps.get(2).map_or(default_unit, |m| m.as_str());
+            meters_per(unit)
+                .map(|meters| value * meters)
+                .ok_or(Error::InvalidNumberFormat)
+        }
+        None => {
+            eprintln!(
+                "Invalid distance: {:?} - must be a number with optional unit (m, km, mi, ft), e.g. 500m",
+                raw
+            );
+            Err(Error::InvalidNumberFormat)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::units::{parse_distance, parse_distance_in};
+
+    #[test]
+    fn test_parsing_distances() {
+        assert_eq!(500.0, parse_distance("500").unwrap());
+        assert_eq!(500.0, parse_distance("500m").unwrap());
+        assert_eq!(1500.0, parse_distance("1.5km").unwrap());
+        assert_eq!(1609.344, parse_distance("1mi").unwrap());
+        assert_eq!(3.048, parse_distance("10ft").unwrap());
+        assert!(parse_distance("pizza").is_err());
+        assert!(parse_distance("10 parsecs").is_err());
+    }
+
+    #[test]
+    fn test_parsing_distances_with_default_unit() {
+        assert_eq!(3.048, parse_distance_in("10", "ft").unwrap());
+        assert_eq!(1000.0, parse_distance_in("1km", "ft").unwrap());
+    }
+}
@@ -0,0 +1,221 @@
+use crate::geoq::{centroid, node, polygonize};
+use geo::algorithm::contains::Contains;
+use geo_types::{Geometry, GeometryCollection, LineString, MultiPolygon, Point, Polygon};
+use std::collections::HashMap;
+
+type SegmentKey = ((u64, u64), (u64, u64));
+
+fn rings(poly: &Polygon<f64>) -> Vec<LineString<f64>> {
+    std::iter::once(poly.exterior())
+        .chain(poly.interiors().iter())
+        .map(|ring| {
+            let mut ring = ring.clone();
+            if ring.0.len() > 1 && ring.0.first() != ring.0.last() {
+                ring.0.push(ring.0[0]);
+            }
+            ring
+        })
+        .collect()
+}
+
+fn is_valid_polygon(poly: &Polygon<f64>) -> bool {
+    let rings = rings(poly);
+    rings.iter().all(|ring| ring.0.len() >= 4)
+        && node::node(&rings).iter().all(|pieces| pieces.len() == 1)
+}
+
+// Whether the rings of each of a geometry's polygons are simple:
+// closed, with at least 3 distinct vertices, and not crossing or
+// touching themselves or each other. Other geometry types are always valid.
+pub fn is_valid(geom: &Geometry<f64>) -> bool {
+    match *geom {
+        Geometry::Polygon(ref poly) => is_valid_polygon(poly),
+        Geometry::MultiPolygon(ref mp) => mp.0.iter().all(is_valid_polygon),
+        Geometry::GeometryCollection(ref gc) => gc.0.iter().all(is_valid),
+        _ => true,
+    }
+}
+
+// Points which fall inside an odd number of the rings are inside the polygon
+fn inside(point: &Point<f64>, rings: &[LineString<f64>]) -> bool {
+    rings
+        .iter()
+        .filter(|ring| Polygon::new((*ring).clone(), vec![]).contains(point))
+        .count()
+        % 2
+        == 1
+}
+
+// The faces formed by the lines whose interior passes the test
+pub fn faces_where<F>(lines: Vec<LineString<f64>>, keep: F) -> Vec<Polygon<f64>>
+where
+    F: Fn(&Point<f64>) -> bool,
+{
+    polygonize::polygonize(lines)
+        .polygons
+        .into_iter()
+        .filter(|face| {
+            centroid::midpoint(&Geometry::Polygon(face.clone()))
+                .map(|p| keep(&p))
+                .unwrap_or(false)
+        })
+        .collect()
+}
+
+fn faces_inside(lines: Vec<LineString<f64>>, rings: &[LineString<f64>]) -> Vec<Polygon<f64>> {
+    faces_where(lines, |p| inside(p, rings))
+}
+
+fn segment_key(a: (f64, f64), b: (f64, f64)) -> SegmentKey {
+    let a = (a.0.to_bits(), a.1.to_bits());
+    let b = (b.0.to_bits(), b.1.to_bits());
+    if a < b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+// Merge faces which share edges, by dropping the shared edges and assembling
+// the polygons formed by the rest. The test tells the faces of the merged
+// polygons apart from the holes they enclose.
+pub fn dissolve_faces<F>(faces: &[Polygon<f64>], keep: F) -> Vec<Polygon<f64>>
+where
+    F: Fn(&Point<f64>) -> bool,
+{
+    let mut counts: HashMap<SegmentKey, usize> = HashMap::new();
+    let mut segments = Vec::new();
+    for face in faces {
+        for ring in std::iter::once(face.exterior()).chain(face.interiors().iter()) {
+            for line in ring.lines() {
+                let key = segment_key(line.start.x_y(), line.end.x_y());
+                let count = counts.entry(key).or_default();
+                if *count == 0 {
+                    segments.push((key, line));
+                }
+                *count += 1;
+            }
+        }
+    }
+    let boundary: Vec<LineString<f64>> = segments
+        .into_iter()
+        .filter(|(key, _)| counts[key] % 2 == 1)
+        .map(|(_, line)| LineString(vec![line.start, line.end]))
+        .collect();
+    faces_where(boundary, keep)
+}
+
+// Rebuild a polygon with crossing or touching rings from its linework,
+// as in PostGIS ST_MakeValid. The rings are split where they meet, and
+// the faces they form are kept where they fall inside an odd number
+// of the original rings, which undoes self-intersections like bowties.
+fn repair_polygon(poly: &Polygon<f64>) -> Vec<Polygon<f64>> {
+    let rings: Vec<LineString<f64>> = rings(poly).into_iter().filter(|r| r.0.len() >= 4).collect();
+    let pieces: Vec<LineString<f64>> = node::node(&rings).into_iter().flatten().collect();
+    let faces = faces_inside(pieces, &rings);
+    dissolve_faces(&faces, |p| inside(p, &rings))
+}
+
+// Repair any invalid polygons in a geometry (see is_valid).
+// A Polygon which repairs into several pieces becomes a MultiPolygon,
+// and one with no area left becomes an empty GeometryCollection.
+pub fn make_valid(geom: &Geometry<f64>) -> Geometry<f64> {
+    match *geom {
+        Geometry::Polygon(ref poly) if !is_valid_polygon(poly) => {
+            let mut polys = repair_polygon(poly);
+            match polys.len() {
+                0 => Geometry::GeometryCollection(GeometryCollection(vec![])),
+                1 => Geometry::Polygon(polys.remove(0)),
+                _ => Geometry::MultiPolygon(MultiPolygon(polys)),
+            }
+        }
+        Geometry::MultiPolygon(ref mp) if !is_valid(geom) => Geometry::MultiPolygon(MultiPolygon(
+            mp.0.iter()
+                .flat_map(|poly| {
+                    if is_valid_polygon(poly) {
+                        vec![poly.clone()]
+                    } else {
+                        repair_polygon(poly)
+                    }
+                })
+                .collect(),
+        )),
+        Geometry::GeometryCollection(ref gc) => {
+            Geometry::GeometryCollection(GeometryCollection(gc.0.iter().map(make_valid).collect()))
+        }
+        _ => geom.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::valid::{is_valid, make_valid};
+    use geo::algorithm::area::Area;
+    use geo_types::{Geometry, Polygon};
+
+    fn poly(coords: Vec<(f64, f64)>, holes: Vec<Vec<(f64, f64)>>) -> Geometry<f64> {
+        Geometry::Polygon(Polygon::new(
+            coords.into(),
+            holes.into_iter().map(|h| h.into()).collect(),
+        ))
+    }
+
+    #[test]
+    fn test_valid_polygons() {
+        let square = poly(vec![(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0), (0.0, 0.0)], vec![]);
+        assert!(is_valid(&square));
+        assert_eq!(square, make_valid(&square));
+        let donut = poly(
+            vec![(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0), (0.0, 0.0)],
+            vec![vec![(1.0, 1.0), (1.0, 3.0), (3.0, 3.0), (3.0, 1.0), (1.0, 1.0)]],
+        );
+        assert!(is_valid(&donut));
+        assert!(is_valid(&Geometry::Point((1.0, 1.0).into())));
+    }
+
+    #[test]
+    fn test_repairing_bowtie() {
+        let bowtie = poly(vec![(0.0, 0.0), (2.0, 2.0), (2.0, 0.0), (0.0, 2.0), (0.0, 0.0)], vec![]);
+        assert!(!is_valid(&bowtie));
+        match make_valid(&bowtie) {
+            Geometry::MultiPolygon(mp) => {
+                assert_eq!(2, mp.0.len());
+                assert_eq!(2.0, mp.0.iter().map(|p| p.area().abs()).sum::<f64>());
+            }
+            other => panic!("Expected a MultiPolygon, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_repairing_self_touching_ring() {
+        // The ring touches itself at (2 0), enclosing 2 triangles
+        let touching = poly(
+            vec![(0.0, 0.0), (2.0, 0.0), (4.0, 0.0), (3.0, 1.0), (2.0, 0.0), (1.0, 1.0), (0.0, 0.0)],
+            vec![],
+        );
+        assert!(!is_valid(&touching));
+        let repaired = make_valid(&touching);
+        assert!(is_valid(&repaired));
+        match repaired {
+            Geometry::MultiPolygon(mp) => assert_eq!(2, mp.0.len()),
+            other => panic!("Expected a MultiPolygon, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_repairing_hole_crossing_exterior() {
+        let crossing = poly(
+            vec![(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0), (0.0, 0.0)],
+            vec![vec![(3.0, 1.0), (5.0, 1.0), (5.0, 3.0), (3.0, 3.0), (3.0, 1.0)]],
+        );
+        assert!(!is_valid(&crossing));
+        // The part of the hole outside of the exterior is inside of 1 ring, so it's kept
+        match make_valid(&crossing) {
+            Geometry::MultiPolygon(mp) => {
+                assert_eq!(2, mp.0.len());
+                assert_eq!(16.0, mp.0.iter().map(|p| p.area().abs()).sum::<f64>());
+            }
+            other => panic!("Expected a MultiPolygon, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use crate::geoq::clip;
+use geo_types::{Coordinate, LineString, Point, Polygon};
+
+// Voronoi cell for each of the given points, limited to the given convex bounding ring.
+// Cells are computed by clipping the bounds to the half-plane closer to
+// each point than to every other point, treating coordinates as planar.
+// Cells are returned in the same order as the points; a cell is None
+// if it falls completely outside the bounds.
+pub fn cells(points: &[Point<f64>], bounds: &LineString<f64>) -> Vec<Option<Polygon<f64>>> {
+    points
+        .iter()
+        .map(|p| {
+            let mut cell = Some(bounds.clone());
+            for other in points {
+                if other == p {
+                    continue;
+                }
+                let mid = Coordinate {
+                    x: (p.x() + other.x()) / 2.0,
+                    y: (p.y() + other.y()) / 2.0,
+                };
+                let dir = Coordinate {
+                    x: other.x() - p.x(),
+                    y: other.y() - p.y(),
+                };
+                cell = cell.and_then(|ring| {
+                    clip::clip_ring_to_half_plane(&ring, |c| {
+                        dir.x * (mid.x - c.x) + dir.y * (mid.y - c.y)
+                    })
+                });
+            }
+            cell.map(|ring| Polygon::new(ring, vec![]))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::voronoi::cells;
+    use geo_types::{LineString, Point, Polygon};
+
+    #[test]
+    fn test_two_point_cells() {
+        let bounds: LineString<f64> =
+            vec![(0.0, 0.0), (4.0, 0.0), (4.0, 2.0), (0.0, 2.0), (0.0, 0.0)].into();
+        let points = vec![Point::new(1.0, 1.0), Point::new(3.0, 1.0)];
+        let left = Polygon::new(
+            vec![(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0), (0.0, 0.0)].into(),
+            vec![],
+        );
+        let right = Polygon::new(
+            vec![(2.0, 0.0), (4.0, 0.0), (4.0, 2.0), (2.0, 2.0), (2.0, 0.0)].into(),
+            vec![],
+        );
+        assert_eq!(vec![Some(left), Some(right)], cells(&points, &bounds));
+    }
+
+    #[test]
+    fn test_bisector_through_bounds_corners() {
+        // The bisector passes through 2 corners, which shouldn't be repeated
+        let bounds: LineString<f64> =
+            vec![(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0), (0.0, 0.0)].into();
+        let points = vec![Point::new(0.0, 0.0), Point::new(2.0, 2.0)];
+        let lower = Polygon::new(vec![(0.0, 0.0), (2.0, 0.0), (0.0, 2.0), (0.0, 0.0)].into(), vec![]);
+        let upper = Polygon::new(vec![(2.0, 0.0), (2.0, 2.0), (0.0, 2.0), (2.0, 0.0)].into(), vec![]);
+        assert_eq!(vec![Some(lower), Some(upper)], cells(&points, &bounds));
+    }
+}
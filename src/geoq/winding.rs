@@ -0,0 +1,124 @@
+use crate::geoq::area::ring_signed_area;
+use geo_types::{Geometry, GeometryCollection, LineString, MultiPolygon, Polygon};
+
+fn orient_ring(ring: &LineString<f64>, ccw: bool) -> Option<LineString<f64>> {
+    let area = ring_signed_area(ring);
+    // Rings with no area have no orientation to fix
+    if area == 0.0 || (area > 0.0) == ccw {
+        None
+    } else {
+        Some(LineString(ring.0.iter().rev().cloned().collect()))
+    }
+}
+
+fn rewind_polygon(poly: &Polygon<f64>) -> Option<Polygon<f64>> {
+    let exterior = orient_ring(poly.exterior(), true);
+    let interiors: Vec<Option<LineString<f64>>> =
+        poly.interiors().iter().map(|ring| orient_ring(ring, false)).collect();
+    if exterior.is_none() && interiors.iter().all(Option::is_none) {
+        return None;
+    }
+    Some(Polygon::new(
+        exterior.unwrap_or_else(|| poly.exterior().clone()),
+        interiors
+            .into_iter()
+            .zip(poly.interiors())
+            .map(|(fixed, ring)| fixed.unwrap_or_else(|| ring.clone()))
+            .collect(),
+    ))
+}
+
+// Reorient polygon rings to follow the RFC 7946 right-hand rule:
+// exterior rings counter-clockwise, and holes clockwise.
+// Returns None if the geometry's rings are already wound correctly
+// (or it has no rings).
+pub fn rewind(geom: &Geometry<f64>) -> Option<Geometry<f64>> {
+    match *geom {
+        Geometry::Polygon(ref poly) => rewind_polygon(poly).map(Geometry::Polygon),
+        Geometry::MultiPolygon(ref mp) => {
+            let fixed: Vec<Option<Polygon<f64>>> = mp.0.iter().map(rewind_polygon).collect();
+            if fixed.iter().all(Option::is_none) {
+                return None;
+            }
+            let polys = fixed
+                .into_iter()
+                .zip(&mp.0)
+                .map(|(fixed, poly)| fixed.unwrap_or_else(|| poly.clone()))
+                .collect();
+            Some(Geometry::MultiPolygon(MultiPolygon(polys)))
+        }
+        Geometry::GeometryCollection(ref gc) => {
+            let fixed: Vec<Option<Geometry<f64>>> = gc.0.iter().map(rewind).collect();
+            if fixed.iter().all(Option::is_none) {
+                return None;
+            }
+            let geoms = fixed
+                .into_iter()
+                .zip(&gc.0)
+                .map(|(fixed, geom)| fixed.unwrap_or_else(|| geom.clone()))
+                .collect();
+            Some(Geometry::GeometryCollection(GeometryCollection(geoms)))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::area::ring_signed_area;
+    use crate::geoq::winding::rewind;
+    use geo_types::{Geometry, LineString, MultiPolygon, Polygon};
+
+    fn reversed(ring: &LineString<f64>) -> LineString<f64> {
+        LineString(ring.0.iter().rev().cloned().collect())
+    }
+
+    fn outer() -> LineString<f64> {
+        vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0), (0.0, 0.0)].into()
+    }
+
+    fn hole() -> LineString<f64> {
+        vec![(2.0, 2.0), (2.0, 8.0), (8.0, 8.0), (8.0, 2.0), (2.0, 2.0)].into()
+    }
+
+    fn island() -> LineString<f64> {
+        vec![(4.0, 4.0), (6.0, 4.0), (6.0, 6.0), (4.0, 6.0), (4.0, 4.0)].into()
+    }
+
+    #[test]
+    fn test_correct_winding_is_untouched() {
+        let poly = Polygon::new(outer(), vec![hole()]);
+        assert_eq!(None, rewind(&Geometry::Polygon(poly)));
+        assert_eq!(None, rewind(&Geometry::Point((1.0, 1.0).into())));
+    }
+
+    #[test]
+    fn test_rewinding_nested_holes() {
+        // A polygon with a hole, and an island polygon inside the hole,
+        // with every ring wound the wrong way
+        let mp = MultiPolygon(vec![
+            Polygon::new(reversed(&outer()), vec![reversed(&hole())]),
+            Polygon::new(reversed(&island()), vec![]),
+        ]);
+        let exp = MultiPolygon(vec![
+            Polygon::new(outer(), vec![hole()]),
+            Polygon::new(island(), vec![]),
+        ]);
+        assert_eq!(
+            Some(Geometry::MultiPolygon(exp)),
+            rewind(&Geometry::MultiPolygon(mp))
+        );
+    }
+
+    #[test]
+    fn test_rewinding_only_holes() {
+        let poly = Polygon::new(outer(), vec![reversed(&hole())]);
+        match rewind(&Geometry::Polygon(poly)) {
+            Some(Geometry::Polygon(fixed)) => {
+                assert_eq!(&outer(), fixed.exterior());
+                assert!(ring_signed_area(&fixed.interiors()[0]) < 0.0);
+            }
+            other => panic!("Expected a rewound polygon, got {:?}", other),
+        }
+    }
+}
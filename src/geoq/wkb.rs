@@ -0,0 +1,114 @@
+use geo_types::{Coordinate, Geometry, LineString, Polygon};
+
+// Minimal (little-endian, 2D) Well-Known Binary encoding of geometries
+
+const POINT: u32 = 1;
+const LINESTRING: u32 = 2;
+const POLYGON: u32 = 3;
+const MULTIPOINT: u32 = 4;
+const MULTILINESTRING: u32 = 5;
+const MULTIPOLYGON: u32 = 6;
+const GEOMETRYCOLLECTION: u32 = 7;
+
+fn header(buf: &mut Vec<u8>, geom_type: u32) {
+    buf.push(1);
+    buf.extend_from_slice(&geom_type.to_le_bytes());
+}
+
+fn count(buf: &mut Vec<u8>, n: usize) {
+    buf.extend_from_slice(&(n as u32).to_le_bytes());
+}
+
+fn coord(buf: &mut Vec<u8>, c: &Coordinate<f64>) {
+    // Adding 0.0 normalizes -0.0 to 0.0
+    buf.extend_from_slice(&(c.x + 0.0).to_le_bytes());
+    buf.extend_from_slice(&(c.y + 0.0).to_le_bytes());
+}
+
+fn coords(buf: &mut Vec<u8>, ls: &LineString<f64>) {
+    count(buf, ls.0.len());
+    for c in &ls.0 {
+        coord(buf, c);
+    }
+}
+
+fn polygon(buf: &mut Vec<u8>, poly: &Polygon<f64>) {
+    count(buf, poly.interiors().len() + 1);
+    coords(buf, poly.exterior());
+    for ring in poly.interiors() {
+        coords(buf, ring);
+    }
+}
+
+fn write_geom(buf: &mut Vec<u8>, geom: &Geometry<f64>) {
+    match geom {
+        Geometry::Point(p) => {
+            header(buf, POINT);
+            coord(buf, &p.0);
+        }
+        Geometry::Line(l) => {
+            header(buf, LINESTRING);
+            coords(buf, &LineString(vec![l.start, l.end]));
+        }
+        Geometry::LineString(ls) => {
+            header(buf, LINESTRING);
+            coords(buf, ls);
+        }
+        Geometry::Polygon(poly) => {
+            header(buf, POLYGON);
+            polygon(buf, poly);
+        }
+        Geometry::MultiPoint(mp) => {
+            header(buf, MULTIPOINT);
+            count(buf, mp.0.len());
+            for p in &mp.0 {
+                header(buf, POINT);
+                coord(buf, &p.0);
+            }
+        }
+        Geometry::MultiLineString(mls) => {
+            header(buf, MULTILINESTRING);
+            count(buf, mls.0.len());
+            for ls in &mls.0 {
+                header(buf, LINESTRING);
+                coords(buf, ls);
+            }
+        }
+        Geometry::MultiPolygon(mp) => {
+            header(buf, MULTIPOLYGON);
+            count(buf, mp.0.len());
+            for poly in &mp.0 {
+                header(buf, POLYGON);
+                polygon(buf, poly);
+            }
+        }
+        Geometry::GeometryCollection(gc) => {
+            header(buf, GEOMETRYCOLLECTION);
+            count(buf, gc.0.len());
+            for g in &gc.0 {
+                write_geom(buf, g);
+            }
+        }
+    }
+}
+
+pub fn to_wkb(geom: &Geometry<f64>) -> Vec<u8> {
+    let mut buf = Vec::new();
+    write_geom(&mut buf, geom);
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geoq::wkb::to_wkb;
+    use geo_types::{Geometry, Point};
+
+    #[test]
+    fn test_point_wkb() {
+        let wkb = to_wkb(&Geometry::Point(Point::new(1.0, -0.0)));
+        let mut exp = vec![1, 1, 0, 0, 0];
+        exp.extend_from_slice(&1.0f64.to_le_bytes());
+        exp.extend_from_slice(&0.0f64.to_le_bytes());
+        assert_eq!(exp, wkb);
+    }
+}
@@ -1,27 +1,113 @@
 #![feature(try_blocks)]
 mod geoq;
 use geoq::commands;
+use geoq::ellipsoid::{self, Ellipsoid};
 use geoq::error::Error;
+use geoq::output;
+use geoq::reader;
 use geoq::text;
+use geoq::units::{self, UnitSystem};
 
 use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
 use std::process;
 
+// Global args may be given either before or after
+// the subcommand, so check each level of subcommand matches
+fn global_arg<'a>(matches: &'a ArgMatches<'a>, name: &str) -> Option<&'a ArgMatches<'a>> {
+    if matches.is_present(name) {
+        Some(matches)
+    } else {
+        matches.subcommand().1.and_then(|m| global_arg(m, name))
+    }
+}
+
+fn set_global_options(matches: &ArgMatches) -> Result<(), Error> {
+    output::set_pretty(global_arg(matches, "pretty").is_some());
+    if let Some(system) = global_arg(matches, "units").and_then(|m| m.value_of("units")) {
+        units::set_unit_system(match system {
+            "imperial" => UnitSystem::Imperial,
+            _ => UnitSystem::Metric,
+        });
+    }
+    let ellipsoid_name = global_arg(matches, "ellipsoid").and_then(|m| m.value_of("ellipsoid"));
+    match global_arg(matches, "radius").and_then(|m| m.value_of("radius")) {
+        Some(radius) => {
+            if ellipsoid_name.is_some_and(|name| name != "sphere") {
+                eprintln!("--radius gives a custom sphere, so it can only be combined with --ellipsoid sphere.");
+                return Err(Error::ConflictingArguments);
+            }
+            match radius.parse::<f64>() {
+                Ok(radius) if radius > 0.0 => ellipsoid::set_ellipsoid(Ellipsoid::sphere(radius)),
+                _ => {
+                    eprintln!("Invalid --radius: {:?} - must be a positive number of meters", radius);
+                    return Err(Error::InvalidNumberFormat);
+                }
+            }
+        }
+        None => {
+            if let Some(e) = ellipsoid_name.and_then(Ellipsoid::from_name) {
+                ellipsoid::set_ellipsoid(e);
+            }
+        }
+    }
+    if let Some(limit) = global_arg(matches, "limit").and_then(|m| m.value_of("limit")) {
+        match limit.parse::<usize>() {
+            Ok(limit) => reader::set_input_limit(limit),
+            Err(_) => {
+                eprintln!("Invalid --limit: {:?} - must be a positive integer", limit);
+                return Err(Error::InvalidNumberFormat);
+            }
+        }
+    }
+    Ok(())
+}
+
 fn run(matches: ArgMatches) -> Result<(), Error> {
+    set_global_options(&matches)?;
     match matches.subcommand() {
-        ("wkt", Some(_)) => commands::wkt::run(),
+        ("wkt", Some(m)) => commands::wkt::run(m),
         ("read", Some(_)) => commands::read::run(),
         ("gj", Some(m)) => commands::geojson_cmd::run(m),
         ("gh", Some(m)) => commands::geohash::run(m),
-        ("map", Some(_)) => commands::map::run(),
+        ("map", Some(m)) => commands::map::run(m),
         ("snip", Some(_)) => commands::snip::run(),
         ("filter", Some(m)) => commands::filter::run(m),
         ("json", Some(m)) => commands::json::run(m),
-        ("centroid", Some(_)) => commands::centroid::run(),
-        ("whereami", Some(_)) => commands::whereami::run(),
+        ("centroid", Some(m)) => commands::centroid::run(m),
+        ("whereami", Some(m)) => commands::whereami::run(m),
         ("simplify", Some(m)) => commands::simplify::run(m),
         ("measure", Some(m)) => commands::measure::run(m),
         ("bbox", Some(m)) => commands::bbox::run(m),
+        ("cluster", Some(m)) => commands::cluster::run(m),
+        ("voronoi", Some(m)) => commands::voronoi::run(m),
+        ("delaunay", Some(m)) => commands::delaunay::run(m),
+        ("join", Some(m)) => commands::join::run(m),
+        ("sjoin", Some(m)) => commands::sjoin::run(m),
+        ("pip", Some(m)) => commands::pip::run(m),
+        ("flip", Some(m)) => commands::flip::run(m),
+        ("validate", Some(m)) => commands::validate::run(m),
+        ("hash", Some(m)) => commands::hash::run(m),
+        ("merge-properties", Some(m)) => commands::merge_properties::run(m),
+        ("diff", Some(m)) => commands::diff::run(m),
+        ("resample", Some(m)) => commands::resample::run(m),
+        ("smooth", Some(m)) => commands::smooth::run(m),
+        ("extent", Some(m)) => commands::extent::run(m),
+        ("normalize-lon", Some(m)) => commands::normalize_lon::run(m),
+        ("to-multi", Some(_)) => commands::to_multi::run(),
+        ("to-single", Some(_)) => commands::to_single::run(),
+        ("info", Some(m)) => commands::info::run(m),
+        ("stats", Some(m)) => commands::stats::run(m),
+        ("sort", Some(m)) => commands::sort::run(m),
+        ("bin", Some(m)) => commands::bin::run(m),
+        ("grid", Some(m)) => commands::grid::run(m),
+        ("index", Some(m)) => commands::index::run(m),
+        ("polygonize", Some(m)) => commands::polygonize::run(m),
+        ("node", Some(m)) => commands::node::run(m),
+        ("split-by", Some(m)) => commands::split_by::run(m),
+        ("clip", Some(m)) => commands::clip::run(m),
+        ("repair-winding", Some(m)) => commands::repair_winding::run(m),
+        ("densify", Some(m)) => commands::densify::run(m),
+        ("version", Some(m)) => commands::version::run(m),
         _ => Err(Error::UnknownCommand),
     }
 }
@@ -31,11 +117,36 @@ const VERSION: &'static str = env!("CARGO_PKG_VERSION");
 fn main() {
     let geojson = SubCommand::with_name("gj")
         .about("Output features as GeoJSON")
-        .subcommand(SubCommand::with_name("geom").about("Output entity as a GeoJSON geometry"))
+        .after_help(text::GEOJSON_AFTER_HELP)
+        .arg(Arg::with_name("ndjson")
+             .long("ndjson")
+             .global(true)
+             .help(text::GEOJSON_NDJSON_ARG_HELP))
+        .subcommand(SubCommand::with_name("geom")
+                    .about("Output entity as a GeoJSON geometry")
+                    .arg(Arg::with_name("flatten")
+                         .long("flatten")
+                         .help(text::GEOJSON_FLATTEN_ARG_HELP)))
         .subcommand(SubCommand::with_name("f").about("Output entity as a GeoJSON Feature"))
         .subcommand(
             SubCommand::with_name("fc")
-                .about("Collect all given entities into a GeoJSON Feature Collection"),
+                .about("Collect all given entities into a GeoJSON Feature Collection")
+                .arg(Arg::with_name("bbox")
+                     .long("bbox")
+                     .help("Compute the bounding box of all features and include it in the FeatureCollection's bbox field"))
+                .arg(Arg::with_name("write-crs")
+                     .long("write-crs")
+                     .takes_value(true)
+                     .value_name("EPSG:CODE")
+                     .help(text::GEOJSON_WRITE_CRS_ARG_HELP)),
+        )
+        .subcommand(
+            SubCommand::with_name("validate")
+                .about("Check GeoJSON inputs for compliance with RFC 7946")
+                .after_help(text::GEOJSON_VALIDATE_AFTER_HELP)
+                .arg(Arg::with_name("fix")
+                     .long("fix")
+                     .help(text::GEOJSON_VALIDATE_FIX_ARG_HELP)),
         );
 
     let geohash = SubCommand::with_name("gh")
@@ -48,6 +159,11 @@ fn main() {
                         .help("Characters of geohash precision")
                         .required(true)
                         .index(1),
+                )
+                .arg(
+                    Arg::with_name("bbox")
+                        .long("bbox")
+                        .help("Follow each Geohash with its cell's bbox, as <Geohash><TAB>minlon,minlat,maxlon,maxlat"),
                 ),
         )
         .subcommand(
@@ -61,9 +177,56 @@ fn main() {
                 ).arg(Arg::with_name("original")
                       .long("original")
                       .short("o")
-                      .help("Also print the query entity in the output.\nUseful for mapping a geometry along with its covering Geohashes.")),
+                      .help("Also print the query entity in the output.\nUseful for mapping a geometry along with its covering Geohashes."))
+                .arg(Arg::with_name("dissolve")
+                      .long("dissolve")
+                      .short("d")
+                      .help("Output the outline of the covering as a single GeoJSON Polygon or MultiPolygon, rather than the individual Geohashes."))
+                .arg(Arg::with_name("compact")
+                      .long("compact")
+                      .short("c")
+                      .conflicts_with("dissolve")
+                      .help("Replace each complete set of 32 sibling Geohashes in the covering with their parent.\nGives a smaller set of mixed-length Geohashes covering the same area."))
+                .arg(Arg::with_name("hollow")
+                      .long("hollow")
+                      .conflicts_with_all(&["dissolve", "compact"])
+                      .help("Only output the Geohashes on the boundary of the covering (those next to a Geohash outside of it), dropping the interior ones.\nUnlike --dissolve, the boundary cells are kept as individual Geohashes."))
+                .arg(Arg::with_name("order")
+                      .long("order")
+                      .takes_value(true)
+                      .possible_values(&["lexicographic", "rowmajor"])
+                      .default_value("lexicographic")
+                      .help("Order of the Geohashes in the covering: sorted lexicographically, or 'rowmajor' for rows of cells from north to south, each from west to east."))
+                .arg(Arg::with_name("estimate")
+                      .long("estimate")
+                      .conflicts_with_all(&["original", "dissolve", "compact", "hollow"])
+                      .help("Print an estimate of the number of Geohashes in the covering instead of generating it.\nCounts the Geohashes covering the entity's bounding box, so it may overestimate for sparse geometries."))
+                .arg(Arg::with_name("bbox")
+                      .long("bbox")
+                      .takes_value(true)
+                      .value_name("MINLON,MINLAT,MAXLON,MAXLAT")
+                      .conflicts_with("original")
+                      .allow_hyphen_values(true)
+                      .help("Cover the given bounding box instead of reading entities from STDIN."))
+                .arg(Arg::with_name("parents")
+                      .long("parents")
+                      .takes_value(true)
+                      .value_name("M")
+                      .conflicts_with_all(&["dissolve", "compact", "estimate"])
+                      .help("Also output the distinct level M ancestors of the covering Geohashes.\nEach Geohash is followed by its level, separated by a tab."))
+                .arg(Arg::with_name("parents-only")
+                      .long("parents-only")
+                      .requires("parents")
+                      .help("With --parents, only output the level M ancestors, and not the covering itself.")),
         )
-        .subcommand(SubCommand::with_name("children").about("Get children for the given geohash"))
+        .subcommand(SubCommand::with_name("children")
+                    .about("Get children for the given geohash")
+                    .arg(Arg::with_name("level")
+                         .long("level")
+                         .short("l")
+                         .takes_value(true)
+                         .value_name("N")
+                         .help(text::GH_CHILDREN_LEVEL_ARG_HELP)))
         .subcommand(SubCommand::with_name("roots").about("List the Base32 Geohash root characters"))
         .subcommand(SubCommand::with_name("encode-long").about("Convert a 64 bit geohash from Base 10 numeric representation to Base 32."))
         .subcommand(SubCommand::with_name("neighbors")
@@ -73,6 +236,18 @@ fn main() {
                          .short("e")
                          .help("Exclude the given geohash from its neighbors.\nBy default it will be included in the output,\ngiving a 3x3 grid centered on the provided geohash.")));
 
+    let wkt = SubCommand::with_name("wkt")
+        .about("Output features as Well-Known Text")
+        .after_help(text::WKT_AFTER_HELP)
+        .arg(Arg::with_name("with-type")
+             .long("with-type")
+             .help("Prefix each line with its geometry type, separated by a tab."))
+        .arg(Arg::with_name("only")
+             .long("only")
+             .takes_value(true)
+             .value_name("TYPES")
+             .help("Comma-separated geometry types to output, e.g. point,polygon.\nOther geometry types are dropped."));
+
     let filter = SubCommand::with_name("filter")
         .about("Select features based on geospatial predicates")
         .after_help(text::FILTER_AFTER_HELP)
@@ -82,11 +257,48 @@ fn main() {
              .global(true)
              .long("query-file")
              .short("q"))
+        .arg(Arg::with_name("data-file")
+             .help("Input file for reading the entities to filter, instead of STDIN.\nQuery feature(s) can then be given via STDIN.")
+             .takes_value(true)
+             .global(true)
+             .long("data-file")
+             .short("d")
+             .conflicts_with("query-file"))
         .arg(Arg::with_name("negate")
              .help("Negate the filter, so intersects becomes 'not intersects', etc.")
              .global(true)
              .long("negate")
              .short("n"))
+        .arg(Arg::with_name("count-only")
+             .help("Only print the number of matching entities, rather than the entities themselves.")
+             .global(true)
+             .long("count-only")
+             .short("c"))
+        .arg(Arg::with_name("verbose")
+             .help("Report the number of invalid features repaired with --repair (to STDERR), and with --count-only, also print the total number of entities checked.")
+             .global(true)
+             .long("verbose")
+             .short("v"))
+        .arg(Arg::with_name("repair")
+             .help("Repair invalid Polygons before checking spatial predicates, rather than checking them as given.")
+             .global(true)
+             .long("repair"))
+        .arg(Arg::with_name("query-buffer")
+             .help(text::FILTER_QUERY_BUFFER_ARG_HELP)
+             .global(true)
+             .long("query-buffer")
+             .takes_value(true)
+             .value_name("DISTANCE"))
+        .arg(Arg::with_name("and")
+             .help(text::FILTER_AND_ARG_HELP)
+             .global(true)
+             .long("and")
+             .takes_value(true)
+             .multiple(true)
+             .number_of_values(2)
+             .use_delimiter(false)
+             .allow_hyphen_values(true)
+             .value_names(&["PREDICATE", "QUERY"]))
         .subcommand(
             SubCommand::with_name("intersects")
                 .about("Output only entities (from STDIN) which intersect a QUERY entity (as command-line ARG)")
@@ -102,6 +314,65 @@ fn main() {
                         .help("Entity to check intersections.\nMust be Geohash, WKT, or GeoJSON.\nMust be a POLYGON or MULTIPOLYGON.")
                         .index(1)
                 )
+        )
+        .subcommand(
+            SubCommand::with_name("covered-by")
+                .about("Output only entities (from STDIN) which fall within or on the boundary of a QUERY entity (as command-line ARG)")
+                .after_help(text::FILTER_COVERED_BY_AFTER_HELP)
+                .arg(
+                    Arg::with_name("query")
+                        .help("Entity to check coverage.\nMust be Geohash, WKT, or GeoJSON.\nMust be a POLYGON or MULTIPOLYGON.")
+                        .index(1)
+                )
+        )
+        .subcommand(
+            SubCommand::with_name("covers")
+                .about("Output only entities (from STDIN) which cover a QUERY entity (as command-line ARG)")
+                .after_help(text::FILTER_COVERS_AFTER_HELP)
+                .arg(Arg::with_name("query")
+                     .help("Entity to check coverage.\nMust be Lat/Lon, Geohash, WKT, or GeoJSON.")
+                     .index(1))
+        )
+        .subcommand(
+            SubCommand::with_name("equals")
+                .about("Output only entities (from STDIN) which are geometrically equal to a QUERY entity (as command-line ARG)")
+                .after_help(text::FILTER_EQUALS_AFTER_HELP)
+                .arg(Arg::with_name("query")
+                     .help("Entity to compare with.\nMust be Lat/Lon, Geohash, WKT, or GeoJSON.")
+                     .index(1))
+                .arg(Arg::with_name("tolerance")
+                     .help("Maximum difference (in degrees) between matching coordinates.")
+                     .long("tolerance")
+                     .takes_value(true))
+        )
+        .subcommand(
+            SubCommand::with_name("type")
+                .about("Output only entities (from STDIN) with one of the given geometry types")
+                .after_help(text::FILTER_TYPE_AFTER_HELP)
+                .arg(Arg::with_name("types")
+                     .help("Comma-separated geometry types, e.g. polygon,multipolygon.")
+                     .required(true)
+                     .index(1))
+        )
+        .subcommand(
+            SubCommand::with_name("nearest")
+                .about("Output only the K entities (from STDIN) nearest to any QUERY entity (as command-line ARG)")
+                .after_help(text::FILTER_NEAREST_AFTER_HELP)
+                .arg(Arg::with_name("k")
+                     .help("Number of entities to output.")
+                     .required(true)
+                     .index(1))
+                .arg(Arg::with_name("query")
+                     .help("Entity to measure distances from.\nMust be Lat/Lon, Geohash, WKT, or GeoJSON.")
+                     .index(2))
+        )
+        .subcommand(
+            SubCommand::with_name("within-bbox-of")
+                .about("Output only entities (from STDIN) whose bbox falls within the bbox of a QUERY entity (as command-line ARG)")
+                .after_help(text::FILTER_WITHIN_BBOX_OF_AFTER_HELP)
+                .arg(Arg::with_name("query")
+                     .help("Entity whose bbox to check against.\nMust be Lat/Lon, Geohash, WKT, or GeoJSON.")
+                     .index(1))
         );
 
     let json = SubCommand::with_name("json")
@@ -116,13 +387,45 @@ fn main() {
         .about("Information about reading inputs with geoq")
         .after_help(text::READ_AFTER_HELP);
 
+    // Optional positional input, for one-off commands which would otherwise read STDIN
+    let input_arg = Arg::with_name("input")
+        .help(text::INPUT_ARG_HELP)
+        .index(1);
+
     let centroid = SubCommand::with_name("centroid")
         .about(text::CENTROID_ABOUT)
-        .after_help(text::CENTROID_AFTER_HELP);
+        .after_help(text::CENTROID_AFTER_HELP)
+        .arg(input_arg.clone())
+        .arg(Arg::with_name("midpoint")
+             .long("midpoint")
+             .help(text::CENTROID_MIDPOINT_ARG_HELP))
+        .arg(Arg::with_name("raw")
+             .long("raw")
+             .help(text::CENTROID_RAW_ARG_HELP))
+        .arg(Arg::with_name("verbose")
+             .long("verbose")
+             .short("v")
+             .help(text::NULL_GEOMETRY_VERBOSE_ARG_HELP))
+        .arg(Arg::with_name("all")
+             .long("all")
+             .help(text::CENTROID_ALL_ARG_HELP))
+        .arg(Arg::with_name("multi")
+             .long("multi")
+             .conflicts_with("all")
+             .help(text::CENTROID_MULTI_ARG_HELP))
+        .arg(Arg::with_name("weight")
+             .long("weight")
+             .takes_value(true)
+             .possible_values(&["type", "area", "length", "equal"])
+             .requires("all")
+             .help(text::CENTROID_WEIGHT_ARG_HELP));
 
     let whereami = SubCommand::with_name("whereami")
         .about(text::WHEREAMI_ABOUT)
-        .after_help(text::WHEREAMI_AFTER_HELP);
+        .after_help(text::WHEREAMI_AFTER_HELP)
+        .arg(Arg::with_name("ip")
+             .long("ip")
+             .help(text::WHEREAMI_IP_ARG_HELP));
 
     let measure = SubCommand::with_name("measure")
         .about(text::MEASURE_ABOUT)
@@ -133,19 +436,164 @@ fn main() {
                 .arg(
                     Arg::with_name("query")
                         .help(text::DISTANCE_QUERY_ARG_HELP)
-                        .required(true)
+                        .required_unless("cumulative")
                         .index(1),
+                )
+                .arg(input_arg.clone().index(2))
+                .arg(
+                    Arg::with_name("unit")
+                        .long("unit")
+                        .takes_value(true)
+                        .possible_values(&units::DISTANCE_UNITS)
+                        .help(text::DISTANCE_UNIT_ARG_HELP),
+                )
+                .arg(
+                    Arg::with_name("method")
+                        .long("method")
+                        .takes_value(true)
+                        .possible_values(&["haversine", "vincenty", "karney"])
+                        .default_value("karney")
+                        .help(text::DISTANCE_METHOD_ARG_HELP),
+                )
+                .arg(
+                    Arg::with_name("with-bearing")
+                        .long("with-bearing")
+                        .help(text::DISTANCE_WITH_BEARING_ARG_HELP),
+                )
+                .arg(
+                    Arg::with_name("planar")
+                        .long("planar")
+                        .conflicts_with("with-bearing")
+                        .help(text::MEASURE_PLANAR_ARG_HELP),
+                )
+                .arg(
+                    Arg::with_name("cumulative")
+                        .long("cumulative")
+                        .conflicts_with_all(&["with-bearing", "json"])
+                        .help(text::DISTANCE_CUMULATIVE_ARG_HELP),
+                )
+                .arg(
+                    Arg::with_name("closest-points")
+                        .long("closest-points")
+                        .conflicts_with_all(&["with-bearing", "cumulative", "json"])
+                        .help(text::DISTANCE_CLOSEST_POINTS_ARG_HELP),
+                )
+                .arg(
+                    Arg::with_name("json")
+                        .long("json")
+                        .help(text::MEASURE_JSON_ARG_HELP),
+                )
+                .arg(
+                    Arg::with_name("labeled")
+                        .long("labeled")
+                        .conflicts_with_all(&["json", "closest-points"])
+                        .help(text::MEASURE_LABELED_ARG_HELP),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("area")
+                .about(text::MEASURE_AREA_ABOUT)
+                .after_help(text::MEASURE_AREA_AFTER_HELP)
+                .arg(input_arg.clone())
+                .arg(
+                    Arg::with_name("unit")
+                        .long("unit")
+                        .takes_value(true)
+                        .possible_values(&units::DISTANCE_UNITS)
+                        .help(text::MEASURE_AREA_UNIT_ARG_HELP),
+                )
+                .arg(
+                    Arg::with_name("signed")
+                        .long("signed")
+                        .conflicts_with("unit")
+                        .help(text::MEASURE_AREA_SIGNED_ARG_HELP),
+                )
+                .arg(
+                    Arg::with_name("planar")
+                        .long("planar")
+                        .conflicts_with("signed")
+                        .help(text::MEASURE_PLANAR_ARG_HELP),
+                )
+                .arg(
+                    Arg::with_name("verbose")
+                        .long("verbose")
+                        .short("v")
+                        .help(text::NULL_GEOMETRY_VERBOSE_ARG_HELP),
+                )
+                .arg(
+                    Arg::with_name("json")
+                        .long("json")
+                        .help(text::MEASURE_JSON_ARG_HELP),
+                )
+                .arg(
+                    Arg::with_name("labeled")
+                        .long("labeled")
+                        .conflicts_with("json")
+                        .help(text::MEASURE_LABELED_ARG_HELP),
+                )
+                .arg(
+                    Arg::with_name("per-component")
+                        .long("per-component")
+                        .help(text::MEASURE_AREA_PER_COMPONENT_ARG_HELP),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("length")
+                .about(text::MEASURE_LENGTH_ABOUT)
+                .after_help(text::MEASURE_LENGTH_AFTER_HELP)
+                .arg(input_arg.clone())
+                .arg(
+                    Arg::with_name("unit")
+                        .long("unit")
+                        .takes_value(true)
+                        .possible_values(&units::DISTANCE_UNITS)
+                        .help(text::DISTANCE_UNIT_ARG_HELP),
+                )
+                .arg(
+                    Arg::with_name("3d")
+                        .long("3d")
+                        .help(text::MEASURE_LENGTH_3D_ARG_HELP),
+                )
+                .arg(
+                    Arg::with_name("planar")
+                        .long("planar")
+                        .conflicts_with("3d")
+                        .help(text::MEASURE_PLANAR_ARG_HELP),
+                )
+                .arg(
+                    Arg::with_name("json")
+                        .long("json")
+                        .help(text::MEASURE_JSON_ARG_HELP),
+                )
+                .arg(
+                    Arg::with_name("labeled")
+                        .long("labeled")
+                        .conflicts_with("json")
+                        .help(text::MEASURE_LABELED_ARG_HELP),
                 ),
         )
         .subcommand(
             SubCommand::with_name("coord-count")
                 .about(text::MEASURE_COORDS_ABOUT)
+                .arg(input_arg.clone())
                 .arg(
                     Arg::with_name("geojson")
                         .long("geojson")
                         .required(false)
                         .takes_value(false)
                         .help(text::MEASURE_COORDS_GEOJSON_ARG_HELP),
+                )
+                .arg(
+                    Arg::with_name("json")
+                        .long("json")
+                        .conflicts_with("geojson")
+                        .help(text::MEASURE_JSON_ARG_HELP),
+                )
+                .arg(
+                    Arg::with_name("per-ring")
+                        .long("per-ring")
+                        .conflicts_with_all(&["geojson", "json"])
+                        .help(text::MEASURE_COORDS_PER_RING_ARG_HELP),
                 ),
         );
 
@@ -163,10 +611,92 @@ fn main() {
                 .required(false)
                 .takes_value(true)
                 .help(text::SIMPLIFY_TO_COORD_COUNT_ARG_HELP),
+        ).arg(
+            Arg::with_name("epsilon-line")
+                .long("epsilon-line")
+                .takes_value(true)
+                .help(text::SIMPLIFY_EPSILON_LINE_ARG_HELP),
+        ).arg(
+            Arg::with_name("epsilon-polygon")
+                .long("epsilon-polygon")
+                .takes_value(true)
+                .help(text::SIMPLIFY_EPSILON_POLYGON_ARG_HELP),
+        ).arg(
+            Arg::with_name("min-points")
+                .long("min-points")
+                .takes_value(true)
+                .value_name("N")
+                .help(text::SIMPLIFY_MIN_POINTS_ARG_HELP),
+        ).arg(
+            Arg::with_name("shared-topology")
+                .long("shared-topology")
+                .conflicts_with_all(&["to_coord_count", "min-points"])
+                .help(text::SIMPLIFY_SHARED_TOPOLOGY_ARG_HELP),
+        );
+
+    let resample = SubCommand::with_name("resample")
+        .about(text::RESAMPLE_ABOUT)
+        .after_help(text::RESAMPLE_AFTER_HELP)
+        .arg(
+            Arg::with_name("min-distance")
+                .long("min-distance")
+                .required(true)
+                .takes_value(true)
+                .help(text::RESAMPLE_MIN_DISTANCE_ARG_HELP),
+        );
+
+    let version = SubCommand::with_name("version")
+        .about(text::VERSION_ABOUT)
+        .after_help(text::VERSION_AFTER_HELP)
+        .arg(
+            Arg::with_name("json")
+                .long("json")
+                .help(text::VERSION_JSON_ARG_HELP),
+        );
+
+    let densify = SubCommand::with_name("densify")
+        .about(text::DENSIFY_ABOUT)
+        .after_help(text::DENSIFY_AFTER_HELP)
+        .arg(input_arg.clone())
+        .arg(
+            Arg::with_name("max-segment")
+                .long("max-segment")
+                .takes_value(true)
+                .required_unless("to-count")
+                .help(text::DENSIFY_MAX_SEGMENT_ARG_HELP),
+        )
+        .arg(
+            Arg::with_name("to-count")
+                .long("to-count")
+                .takes_value(true)
+                .value_name("N")
+                .conflicts_with("max-segment")
+                .help(text::DENSIFY_TO_COUNT_ARG_HELP),
+        );
+
+    let smooth = SubCommand::with_name("smooth")
+        .about(text::SMOOTH_ABOUT)
+        .after_help(text::SMOOTH_AFTER_HELP)
+        .arg(
+            Arg::with_name("iterations")
+                .long("iterations")
+                .short("i")
+                .takes_value(true)
+                .default_value("1")
+                .help(text::SMOOTH_ITERATIONS_ARG_HELP),
+        )
+        .arg(
+            Arg::with_name("method")
+                .long("method")
+                .takes_value(true)
+                .possible_values(&["chaikin", "spline"])
+                .default_value("chaikin")
+                .help(text::SMOOTH_METHOD_ARG_HELP),
         );
 
     let bbox = SubCommand::with_name("bbox")
         .about("Generate bounding boxes for geometries")
+        .arg(input_arg.clone())
         .arg(Arg::with_name("embed")
              .long("embed")
              .short("e")
@@ -175,15 +705,453 @@ fn main() {
              .long("all")
              .short("a")
              .help("Give a single bbox for all input geometries rather than 1 bbox per input"))
+        .arg(Arg::with_name("antimeridian")
+             .long("antimeridian")
+             .requires("all")
+             .help(text::BBOX_ANTIMERIDIAN_ARG_HELP))
         .after_help(text::BBOX_AFTER_HELP);
 
+    let extent = SubCommand::with_name("extent")
+        .about(text::EXTENT_ABOUT)
+        .after_help(text::EXTENT_AFTER_HELP)
+        .arg(input_arg.clone())
+        .arg(
+            Arg::with_name("all")
+                .long("all")
+                .short("a")
+                .help(text::EXTENT_ALL_ARG_HELP),
+        )
+        .arg(
+            Arg::with_name("antimeridian")
+                .long("antimeridian")
+                .requires("all")
+                .help(text::EXTENT_ANTIMERIDIAN_ARG_HELP),
+        );
+
+    let sort = SubCommand::with_name("sort")
+        .about(text::SORT_ABOUT)
+        .after_help(text::SORT_AFTER_HELP)
+        .arg(input_arg.clone())
+        .arg(
+            Arg::with_name("by")
+                .long("by")
+                .takes_value(true)
+                .possible_values(&["hilbert", "geohash"])
+                .default_value("hilbert")
+                .help(text::SORT_BY_ARG_HELP),
+        );
+
+    let bin = SubCommand::with_name("bin")
+        .about(text::BIN_ABOUT)
+        .after_help(text::BIN_AFTER_HELP)
+        .arg(input_arg.clone())
+        .arg(
+            Arg::with_name("geohash")
+                .long("geohash")
+                .takes_value(true)
+                .value_name("LEVEL")
+                .conflicts_with("grid")
+                .help(text::BIN_GEOHASH_ARG_HELP),
+        )
+        .arg(
+            Arg::with_name("grid")
+                .long("grid")
+                .takes_value(true)
+                .value_name("DISTANCE")
+                .help(text::BIN_GRID_ARG_HELP),
+        )
+        .arg(
+            Arg::with_name("sum")
+                .long("sum")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("PROPERTY")
+                .help(text::BIN_SUM_ARG_HELP),
+        )
+        .arg(
+            Arg::with_name("mean")
+                .long("mean")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("PROPERTY")
+                .help(text::BIN_MEAN_ARG_HELP),
+        );
+
+    let grid = SubCommand::with_name("grid")
+        .about(text::GRID_ABOUT)
+        .after_help(text::GRID_AFTER_HELP)
+        .arg(input_arg.clone())
+        .arg(
+            Arg::with_name("geohash")
+                .long("geohash")
+                .takes_value(true)
+                .value_name("LEVEL")
+                .conflicts_with("size")
+                .help(text::GRID_GEOHASH_ARG_HELP),
+        )
+        .arg(
+            Arg::with_name("size")
+                .long("size")
+                .takes_value(true)
+                .value_name("DISTANCE")
+                .help(text::GRID_SIZE_ARG_HELP),
+        );
+
+    let index = SubCommand::with_name("index")
+        .about(text::INDEX_ABOUT)
+        .after_help(text::INDEX_AFTER_HELP)
+        .arg(input_arg.clone())
+        .arg(
+            Arg::with_name("geohash")
+                .long("geohash")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("LEVEL")
+                .required(true)
+                .help(text::INDEX_GEOHASH_ARG_HELP),
+        );
+
+    let polygonize = SubCommand::with_name("polygonize")
+        .about(text::POLYGONIZE_ABOUT)
+        .after_help(text::POLYGONIZE_AFTER_HELP)
+        .arg(input_arg.clone())
+        .arg(
+            Arg::with_name("report-edges")
+                .long("report-edges")
+                .help(text::POLYGONIZE_REPORT_EDGES_ARG_HELP),
+        );
+
+    let node = SubCommand::with_name("node")
+        .about(text::NODE_ABOUT)
+        .after_help(text::NODE_AFTER_HELP)
+        .arg(input_arg.clone());
+
+    let split_by = SubCommand::with_name("split-by")
+        .about(text::SPLIT_BY_ABOUT)
+        .after_help(text::SPLIT_BY_AFTER_HELP)
+        .arg(input_arg.clone())
+        .arg(
+            Arg::with_name("blade")
+                .long("blade")
+                .takes_value(true)
+                .required(true)
+                .help(text::SPLIT_BY_BLADE_ARG_HELP),
+        );
+
+    let clip = SubCommand::with_name("clip")
+        .about(text::CLIP_ABOUT)
+        .after_help(text::CLIP_AFTER_HELP)
+        // Allow negative coordinates in the bbox
+        .setting(AppSettings::AllowLeadingHyphen)
+        .arg(
+            Arg::with_name("bbox")
+                .index(1)
+                .required(true)
+                .allow_hyphen_values(true)
+                .value_name("MINLON,MINLAT,MAXLON,MAXLAT")
+                .help(text::CLIP_BBOX_ARG_HELP),
+        )
+        .arg(input_arg.clone().index(2));
+
+    let repair_winding = SubCommand::with_name("repair-winding")
+        .about(text::REPAIR_WINDING_ABOUT)
+        .after_help(text::REPAIR_WINDING_AFTER_HELP)
+        .arg(input_arg.clone());
+
+    let info = SubCommand::with_name("info")
+        .about(text::INFO_ABOUT)
+        .after_help(text::INFO_AFTER_HELP)
+        .arg(input_arg.clone())
+        .arg(
+            Arg::with_name("json")
+                .long("json")
+                .help(text::INFO_JSON_ARG_HELP),
+        );
+
+    let stats = SubCommand::with_name("stats")
+        .about(text::STATS_ABOUT)
+        .after_help(text::STATS_AFTER_HELP)
+        .arg(input_arg.clone())
+        .arg(
+            Arg::with_name("json")
+                .long("json")
+                .help(text::STATS_JSON_ARG_HELP),
+        );
+
+    let cluster = SubCommand::with_name("cluster")
+        .about(text::CLUSTER_ABOUT)
+        .after_help(text::CLUSTER_AFTER_HELP)
+        .arg(
+            Arg::with_name("eps")
+                .long("eps")
+                .required(true)
+                .takes_value(true)
+                .help(text::CLUSTER_EPS_ARG_HELP),
+        )
+        .arg(
+            Arg::with_name("min-points")
+                .long("min-points")
+                .required(true)
+                .takes_value(true)
+                .help(text::CLUSTER_MIN_POINTS_ARG_HELP),
+        )
+        .arg(
+            Arg::with_name("hulls")
+                .long("hulls")
+                .help(text::CLUSTER_HULLS_ARG_HELP),
+        );
+
+    let voronoi = SubCommand::with_name("voronoi")
+        .about(text::VORONOI_ABOUT)
+        .after_help(text::VORONOI_AFTER_HELP)
+        .arg(
+            Arg::with_name("clip")
+                .long("clip")
+                .takes_value(true)
+                .help(text::VORONOI_CLIP_ARG_HELP),
+        );
+
+    let delaunay = SubCommand::with_name("delaunay")
+        .about(text::DELAUNAY_ABOUT)
+        .after_help(text::DELAUNAY_AFTER_HELP)
+        .arg(
+            Arg::with_name("edges")
+                .long("edges")
+                .help(text::DELAUNAY_EDGES_ARG_HELP),
+        );
+
+    let join = SubCommand::with_name("join")
+        .about(text::JOIN_ABOUT)
+        .after_help(text::JOIN_AFTER_HELP)
+        .arg(
+            Arg::with_name("csv")
+                .long("csv")
+                .required(true)
+                .takes_value(true)
+                .help(text::JOIN_CSV_ARG_HELP),
+        )
+        .arg(
+            Arg::with_name("on")
+                .long("on")
+                .required(true)
+                .takes_value(true)
+                .help(text::JOIN_ON_ARG_HELP),
+        )
+        .arg(
+            Arg::with_name("inner")
+                .long("inner")
+                .help(text::JOIN_INNER_ARG_HELP),
+        );
+
+    let sjoin = SubCommand::with_name("sjoin")
+        .about(text::SJOIN_ABOUT)
+        .after_help(text::SJOIN_AFTER_HELP)
+        .arg(
+            Arg::with_name("query-file")
+                .long("query-file")
+                .short("q")
+                .required(true)
+                .takes_value(true)
+                .help(text::SJOIN_QUERY_FILE_ARG_HELP),
+        )
+        .arg(
+            Arg::with_name("predicate")
+                .long("predicate")
+                .takes_value(true)
+                .possible_values(&["intersects", "contains"])
+                .default_value("intersects")
+                .help(text::SJOIN_PREDICATE_ARG_HELP),
+        )
+        .arg(
+            Arg::with_name("mode")
+                .long("mode")
+                .takes_value(true)
+                .possible_values(&["first", "all"])
+                .default_value("all")
+                .help(text::SJOIN_MODE_ARG_HELP),
+        )
+        .arg(
+            Arg::with_name("prefix")
+                .long("prefix")
+                .takes_value(true)
+                .default_value("query_")
+                .help(text::SJOIN_PREFIX_ARG_HELP),
+        );
+
+    let pip = SubCommand::with_name("pip")
+        .about(text::PIP_ABOUT)
+        .after_help(text::PIP_AFTER_HELP)
+        .arg(
+            Arg::with_name("polygons")
+                .long("polygons")
+                .required(true)
+                .takes_value(true)
+                .help(text::PIP_POLYGONS_ARG_HELP),
+        )
+        .arg(
+            Arg::with_name("field")
+                .long("field")
+                .required(true)
+                .takes_value(true)
+                .help(text::PIP_FIELD_ARG_HELP),
+        );
+
+    let flip = SubCommand::with_name("flip")
+        .about(text::FLIP_ABOUT)
+        .after_help(text::FLIP_AFTER_HELP)
+        .arg(
+            Arg::with_name("detect")
+                .long("detect")
+                .help(text::FLIP_DETECT_ARG_HELP),
+        );
+
+    let normalize_lon = SubCommand::with_name("normalize-lon")
+        .about(text::NORMALIZE_LON_ABOUT)
+        .after_help(text::NORMALIZE_LON_AFTER_HELP)
+        .arg(
+            Arg::with_name("to-0-360")
+                .long("to-0-360")
+                .help(text::NORMALIZE_LON_TO_0_360_ARG_HELP),
+        )
+        .arg(
+            Arg::with_name("split")
+                .long("split")
+                .conflicts_with("to-0-360")
+                .help(text::NORMALIZE_LON_SPLIT_ARG_HELP),
+        );
+
+    let to_multi = SubCommand::with_name("to-multi")
+        .about(text::TO_MULTI_ABOUT)
+        .after_help(text::TO_MULTI_AFTER_HELP);
+
+    let to_single = SubCommand::with_name("to-single")
+        .about(text::TO_SINGLE_ABOUT)
+        .after_help(text::TO_SINGLE_AFTER_HELP);
+
+    let validate = SubCommand::with_name("validate")
+        .about(text::VALIDATE_ABOUT)
+        .after_help(text::VALIDATE_AFTER_HELP)
+        .arg(
+            Arg::with_name("coords")
+                .long("coords")
+                .help(text::VALIDATE_COORDS_ARG_HELP),
+        )
+        .arg(
+            Arg::with_name("fix")
+                .long("fix")
+                .help(text::VALIDATE_FIX_ARG_HELP),
+        );
+
+    let hash = SubCommand::with_name("hash")
+        .about(text::HASH_ABOUT)
+        .after_help(text::HASH_AFTER_HELP)
+        .arg(
+            Arg::with_name("with-properties")
+                .long("with-properties")
+                .help(text::HASH_WITH_PROPERTIES_ARG_HELP),
+        );
+
+    let merge_properties = SubCommand::with_name("merge-properties")
+        .about(text::MERGE_PROPERTIES_ABOUT)
+        .after_help(text::MERGE_PROPERTIES_AFTER_HELP)
+        .arg(input_arg.clone())
+        .arg(
+            Arg::with_name("conflict")
+                .long("conflict")
+                .takes_value(true)
+                .possible_values(&["first", "last", "array"])
+                .help(text::MERGE_PROPERTIES_CONFLICT_ARG_HELP),
+        );
+
+    let diff = SubCommand::with_name("diff")
+        .about(text::DIFF_ABOUT)
+        .after_help(text::DIFF_AFTER_HELP)
+        .arg(
+            Arg::with_name("a")
+                .help(text::DIFF_A_ARG_HELP)
+                .required(true)
+                .index(1),
+        )
+        .arg(
+            Arg::with_name("b")
+                .help(text::DIFF_B_ARG_HELP)
+                .required(true)
+                .index(2),
+        )
+        .arg(
+            Arg::with_name("key")
+                .long("key")
+                .takes_value(true)
+                .default_value("id")
+                .help(text::DIFF_KEY_ARG_HELP),
+        )
+        .arg(
+            Arg::with_name("unchanged")
+                .long("unchanged")
+                .help(text::DIFF_UNCHANGED_ARG_HELP),
+        );
+
     let matches = App::new("geoq")
         .version(VERSION)
         .setting(AppSettings::SubcommandRequiredElseHelp)
         .about("geoq - GeoSpatial utility belt")
         .after_help(text::MAIN_AFTER_HELP)
-        .subcommand(SubCommand::with_name("wkt").about("Output features as Well-Known Text"))
-        .subcommand(SubCommand::with_name("map").about("View features on a map using geojson.io"))
+        .arg(
+            Arg::with_name("limit")
+                .long("limit")
+                .takes_value(true)
+                .global(true)
+                .help(text::LIMIT_ARG_HELP),
+        )
+        .arg(
+            Arg::with_name("pretty")
+                .long("pretty")
+                .global(true)
+                .help(text::PRETTY_ARG_HELP),
+        )
+        .arg(
+            Arg::with_name("units")
+                .long("units")
+                .takes_value(true)
+                .global(true)
+                .possible_values(&["metric", "imperial"])
+                .help(text::UNITS_ARG_HELP),
+        )
+        .arg(
+            Arg::with_name("ellipsoid")
+                .long("ellipsoid")
+                .takes_value(true)
+                .global(true)
+                .possible_values(&["wgs84", "grs80", "sphere"])
+                .help(text::ELLIPSOID_ARG_HELP),
+        )
+        .arg(
+            Arg::with_name("radius")
+                .long("radius")
+                .takes_value(true)
+                .global(true)
+                .help(text::RADIUS_ARG_HELP),
+        )
+        .subcommand(wkt)
+        .subcommand(SubCommand::with_name("map")
+                    .about("View features on a map using geojson.io")
+                    .after_help(text::MAP_AFTER_HELP)
+                    .arg(Arg::with_name("url")
+                         .long("url")
+                         .help(text::MAP_URL_ARG_HELP))
+                    .arg(Arg::with_name("simplify-for-display")
+                         .long("simplify-for-display")
+                         .takes_value(true)
+                         .value_name("EPSILON")
+                         .help(text::MAP_SIMPLIFY_ARG_HELP))
+                    .arg(Arg::with_name("sample")
+                         .long("sample")
+                         .takes_value(true)
+                         .value_name("N")
+                         .help(text::MAP_SAMPLE_ARG_HELP)))
         .subcommand(read)
         .subcommand(geohash)
         .subcommand(geojson)
@@ -193,7 +1161,37 @@ fn main() {
         .subcommand(whereami)
         .subcommand(measure)
         .subcommand(simplify)
+        .subcommand(resample)
+        .subcommand(smooth)
         .subcommand(bbox)
+        .subcommand(extent)
+        .subcommand(info)
+        .subcommand(stats)
+        .subcommand(sort)
+        .subcommand(bin)
+        .subcommand(grid)
+        .subcommand(index)
+        .subcommand(polygonize)
+        .subcommand(node)
+        .subcommand(split_by)
+        .subcommand(clip)
+        .subcommand(repair_winding)
+        .subcommand(densify)
+        .subcommand(version)
+        .subcommand(cluster)
+        .subcommand(voronoi)
+        .subcommand(delaunay)
+        .subcommand(join)
+        .subcommand(sjoin)
+        .subcommand(pip)
+        .subcommand(flip)
+        .subcommand(normalize_lon)
+        .subcommand(to_multi)
+        .subcommand(to_single)
+        .subcommand(validate)
+        .subcommand(hash)
+        .subcommand(merge_properties)
+        .subcommand(diff)
         .get_matches();
 
     if let Err(e) = run(matches) {
@@ -142,7 +142,7 @@ LINESTRING (30 10, 10 30, 40 40)
 {"type":"Feature","properties":{"a": "b"},"geometry":{"type":"Point","coordinates":[125.6, 10.1]}}
 "#;
 
-    let output = r#"{"features":[{"geometry":{"coordinates":[34.0,12.0],"type":"Point"},"properties":{},"type":"Feature"},{"geometry":{"coordinates":[34.0,12.0],"type":"Point"},"properties":{},"type":"Feature"},{"geometry":{"coordinates":[[[-119.53125,33.75],[-118.125,33.75],[-118.125,35.15625],[-119.53125,35.15625],[-119.53125,33.75]]],"type":"Polygon"},"properties":{},"type":"Feature"},{"geometry":{"coordinates":[[30.0,10.0],[10.0,30.0],[40.0,40.0]],"type":"LineString"},"properties":{},"type":"Feature"},{"geometry":{"coordinates":[125.6,10.1],"type":"Point"},"properties":{},"type":"Feature"},{"geometry":{"coordinates":[125.6,10.1],"type":"Point"},"properties":{"a":"b"},"type":"Feature"}],"type":"FeatureCollection"}"#;
+    let output = r#"{"type":"FeatureCollection","features":[{"geometry":{"coordinates":[34.0,12.0],"type":"Point"},"properties":{},"type":"Feature"},{"geometry":{"coordinates":[34.0,12.0],"type":"Point"},"properties":{},"type":"Feature"},{"geometry":{"coordinates":[[[-119.53125,33.75],[-118.125,33.75],[-118.125,35.15625],[-119.53125,35.15625],[-119.53125,33.75]]],"type":"Polygon"},"properties":{},"type":"Feature"},{"geometry":{"coordinates":[[30.0,10.0],[10.0,30.0],[40.0,40.0]],"type":"LineString"},"properties":{},"type":"Feature"},{"geometry":{"coordinates":[125.6,10.1],"type":"Point"},"properties":{},"type":"Feature"},{"geometry":{"coordinates":[125.6,10.1],"type":"Point"},"properties":{"a":"b"},"type":"Feature"}]}"#;
     Assert::main_binary()
         .with_args(&["gj", "fc"])
         .stdin(input)
@@ -170,6 +170,16 @@ sf0hm8w
         .unwrap();
 }
 
+#[test]
+fn geohash_point_with_bbox() {
+    Assert::main_binary()
+        .with_args(&["gh", "point", "5", "--bbox"])
+        .stdin("34.1,-118.2")
+        .stdout()
+        .is("9q5cy\t-118.212890625,34.0576171875,-118.1689453125,34.1015625")
+        .unwrap();
+}
+
 #[test]
 fn geohash_not_allowed_for_non_point() {
     let input = "LINESTRING (30 10, 10 30, 40 40)\n";
@@ -290,6 +300,16 @@ fn geohash_neighbors() {
         .unwrap();
 }
 
+#[test]
+fn geohash_covering_hollow() {
+    // A 3x3 block of cells, without the one in the middle
+    Assert::main_binary()
+        .with_args(&["gh", "covering", "2", "--hollow", "--bbox=1,1,30,14"])
+        .stdout()
+        .is("s0\ns1\ns2\ns4\ns6\ns8\ns9\nsd\n")
+        .unwrap();
+}
+
 #[test]
 fn geohash_covering() {
     let input = r#"12,34
@@ -306,73 +326,73 @@ sf0
 12	34
 sf0
 9q5
-9qk
-9qh
-9q7
-9q6
-9q5
-9q4
-9mu
-9mg
 9mf
+9mg
+9mu
+9q4
+9q5
+9q6
+9q7
+9qh
+9qk
 LINESTRING(30 10,10 30,40 40)
-szh
-sz5
-syg
-syf
-syc
-syb
-sy8
-swx
-sww
-swt
-swm
-swk
-sw7
-sw6
-sw4
-sw1
-sw0
-sqp
-smz
-smy
-smv
-smu
-sms
-sme
-smd
-sm9
-sm3
-sm2
-sm0
-skh
-skd
-skc
-skb
-sk9
-sk7
-sk6
-sk5
-sjr
-sjp
-se0
-sdh
-sdd
-sdc
-sdb
-sd9
-sd7
-sd6
-sd5
-s9v
-s9u
-s7w
-s7v
-s7u
-s7t
-s7r
-s7q
 s7p
+s7q
+s7r
+s7t
+s7u
+s7v
+s7w
+s9u
+s9v
+sd5
+sd6
+sd7
+sd9
+sdb
+sdc
+sdd
+sdh
+se0
+sjp
+sjr
+sk5
+sk6
+sk7
+sk9
+skb
+skc
+skd
+skh
+sm0
+sm2
+sm3
+sm9
+smd
+sme
+sms
+smu
+smv
+smy
+smz
+sqp
+sw0
+sw1
+sw4
+sw6
+sw7
+swk
+swm
+swt
+sww
+swx
+sy8
+syb
+syc
+syf
+syg
+sz5
+szh
 {"type":"Point","coordinates":[125.6, 10.1]}
 wcc
 {"type":"Feature","properties":{"a": "b"},"geometry":{"type":"Point","coordinates":[125.6, 10.1]}}
@@ -385,116 +405,116 @@ v9z
 
     let output_without = r#"sf0
 sf0
-9qk
-9qh
-9q7
-9q6
-9q5
-9q4
-9mu
-9mg
 9mf
-szh
-sz5
-syg
-syf
-syc
-syb
-sy8
-swx
-sww
-swt
-swm
-swk
-sw7
-sw6
-sw4
-sw1
-sw0
-sqp
-smz
-smy
-smv
-smu
-sms
-sme
-smd
-sm9
-sm3
-sm2
-sm0
-skh
-skd
-skc
-skb
-sk9
-sk7
-sk6
-sk5
-sjr
-sjp
-se0
-sdh
-sdd
-sdc
-sdb
-sd9
-sd7
-sd6
-sd5
-s9v
-s9u
-s7w
-s7v
-s7u
-s7t
-s7r
-s7q
+9mg
+9mu
+9q4
+9q5
+9q6
+9q7
+9qh
+9qk
 s7p
-wcc
-wcc
-sf0
-v9z
-"#;
-    Assert::main_binary()
-        .with_args(&["gh", "covering", "3"])
-        .stdin(input)
-        .stdout()
-        .is(output_without)
-        .unwrap();
-
-    Assert::main_binary()
-        .with_args(&["gh", "covering", "3", "-o"])
-        .stdin(input)
-        .stdout()
-        .is(output_with)
-        .unwrap();
-}
-
-#[test]
-fn gh_covering_errors() {
-    let input = "LINESTRING (30 10, 10 30, 40 40)\n";
-    Assert::main_binary()
-        .with_args(&["gh", "covering", "pizza"])
-        .stdin(input)
-        .fails()
-        .unwrap();
-
-    Assert::main_binary()
-        .with_args(&["gh", "covering"])
-        .stdin(input)
-        .fails()
-        .unwrap();
-}
-
-#[test]
-fn gj_geom_edge_case() {
-    let input = r#"{"coordinates":[[[-87.97874531338036,34.92420945798039],[-87.9785957342853,34.92418491767909],[-87.97857664070615,34.9242631544226],[-87.97872621980122,34.92428769470051],[-87.97874531338036,34.92420945798039]]],"type":"Polygon"}
-"#;
-    Assert::main_binary()
-        .with_args(&["gj", "geom"])
-        .stdin(input)
-        .stdout()
+s7q
+s7r
+s7t
+s7u
+s7v
+s7w
+s9u
+s9v
+sd5
+sd6
+sd7
+sd9
+sdb
+sdc
+sdd
+sdh
+se0
+sjp
+sjr
+sk5
+sk6
+sk7
+sk9
+skb
+skc
+skd
+skh
+sm0
+sm2
+sm3
+sm9
+smd
+sme
+sms
+smu
+smv
+smy
+smz
+sqp
+sw0
+sw1
+sw4
+sw6
+sw7
+swk
+swm
+swt
+sww
+swx
+sy8
+syb
+syc
+syf
+syg
+sz5
+szh
+wcc
+wcc
+sf0
+v9z
+"#;
+    Assert::main_binary()
+        .with_args(&["gh", "covering", "3"])
+        .stdin(input)
+        .stdout()
+        .is(output_without)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["gh", "covering", "3", "-o"])
+        .stdin(input)
+        .stdout()
+        .is(output_with)
+        .unwrap();
+}
+
+#[test]
+fn gh_covering_errors() {
+    let input = "LINESTRING (30 10, 10 30, 40 40)\n";
+    Assert::main_binary()
+        .with_args(&["gh", "covering", "pizza"])
+        .stdin(input)
+        .fails()
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["gh", "covering"])
+        .stdin(input)
+        .fails()
+        .unwrap();
+}
+
+#[test]
+fn gj_geom_edge_case() {
+    let input = r#"{"coordinates":[[[-87.97874531338036,34.92420945798039],[-87.9785957342853,34.92418491767909],[-87.97857664070615,34.9242631544226],[-87.97872621980122,34.92428769470051],[-87.97874531338036,34.92420945798039]]],"type":"Polygon"}
+"#;
+    Assert::main_binary()
+        .with_args(&["gj", "geom"])
+        .stdin(input)
+        .stdout()
         .is(input)
         .unwrap();
 }
@@ -714,6 +734,29 @@ Polygon ((30 10, 10 30, 40 40, 30 10))
         .unwrap();
 }
 
+#[test]
+fn geohash_inputs_match_their_polygons() {
+    // Commands should give the same results for a geohash as for its cell's polygon
+    let poly = "POLYGON((-119.53125 33.75,-118.125 33.75,-118.125 35.15625,-119.53125 35.15625,-119.53125 33.75))";
+    let cases: Vec<(Vec<&str>, &str)> = vec![
+        (vec!["centroid"], r#"{"coordinates":[-118.828125,34.453125],"type":"Point"}"#),
+        (vec!["centroid", "--midpoint"], r#"{"coordinates":[-118.828125,34.453125],"type":"Point"}"#),
+        (vec!["extent"], "-119.53125,33.75,-118.125,35.15625"),
+        (vec!["measure", "area", "--json"], r#"{"unit":"m^2","value":20157702579.95984}"#),
+        (vec!["measure", "distance", "34,-118", "--json"], r#"{"unit":"m","value":11548.097547035872}"#),
+    ];
+    for (args, output) in cases {
+        for input in &["9q5", poly] {
+            Assert::main_binary()
+                .with_args(&args)
+                .stdin(*input)
+                .stdout()
+                .is(output)
+                .unwrap();
+        }
+    }
+}
+
 #[test]
 fn gh_roots() {
     let output = r#"0
@@ -756,3 +799,2131 @@ z
         .is(output)
         .unwrap();
 }
+
+#[test]
+fn cluster() {
+    let input = r#"34.0,-118.0
+34.0001,-118.0001
+34.0,-118.0002
+40.7,-74.0
+"#;
+
+    let output = r#"{"geometry":{"coordinates":[-118.0,34.0],"type":"Point"},"properties":{"cluster":0},"type":"Feature"}
+{"geometry":{"coordinates":[-118.0001,34.0001],"type":"Point"},"properties":{"cluster":0},"type":"Feature"}
+{"geometry":{"coordinates":[-118.0002,34.0],"type":"Point"},"properties":{"cluster":0},"type":"Feature"}
+{"geometry":{"coordinates":[-74.0,40.7],"type":"Point"},"properties":{"cluster":-1},"type":"Feature"}
+"#;
+
+    Assert::main_binary()
+        .with_args(&["cluster", "--eps", "50m", "--min-points", "2"])
+        .stdin(input)
+        .stdout()
+        .is(output)
+        .unwrap();
+
+    let hulls = r#"{"geometry":{"coordinates":[[[-118.0,34.0],[-118.0001,34.0001],[-118.0002,34.0],[-118.0,34.0]]],"type":"Polygon"},"properties":{"cluster":0,"count":3},"type":"Feature"}
+"#;
+
+    Assert::main_binary()
+        .with_args(&["cluster", "--eps", "50m", "--min-points", "2", "--hulls"])
+        .stdin(input)
+        .stdout()
+        .is(hulls)
+        .unwrap();
+
+    // Clusters of 1 point, and the noise point, have no hull
+    Assert::main_binary()
+        .with_args(&["cluster", "--eps", "50m", "--min-points", "1", "--hulls"])
+        .stdin(input)
+        .stdout()
+        .is(hulls)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["cluster", "--eps", "50m", "--min-points", "0"])
+        .stdin(input)
+        .fails()
+        .stderr()
+        .contains(r#"Invalid --min-points: "0" - must be a positive integer"#)
+        .unwrap();
+}
+
+#[test]
+fn voronoi() {
+    let input = "0,0\n0,2\n";
+
+    let output = r#"{"geometry":{"coordinates":[[[-1.0,-1.0],[1.0,-1.0],[1.0,1.0],[-1.0,1.0],[-1.0,-1.0]]],"type":"Polygon"},"properties":{"index":0},"type":"Feature"}
+{"geometry":{"coordinates":[[[1.0,-1.0],[3.0,-1.0],[3.0,1.0],[1.0,1.0],[1.0,-1.0]]],"type":"Polygon"},"properties":{"index":1},"type":"Feature"}
+"#;
+
+    Assert::main_binary()
+        .with_args(&["voronoi", "--clip", "POLYGON((-1 -1,3 -1,3 1,-1 1,-1 -1))"])
+        .stdin(input)
+        .stdout()
+        .is(output)
+        .unwrap();
+}
+
+#[test]
+fn delaunay() {
+    let output = r#"{"geometry":{"coordinates":[[[0.0,0.0],[1.0,0.0],[1.0,1.0],[0.0,0.0]]],"type":"Polygon"},"properties":{"area":0.5,"circumradius":0.7071067811865476},"type":"Feature"}
+"#;
+
+    Assert::main_binary()
+        .with_args(&["delaunay"])
+        .stdin("0,0\n0,1\n1,1\n")
+        .stdout()
+        .is(output)
+        .unwrap();
+
+    let edges = r#"{"geometry":{"coordinates":[[0.0,0.0],[1.0,0.0]],"type":"LineString"},"properties":{},"type":"Feature"}
+{"geometry":{"coordinates":[[1.0,0.0],[1.0,1.0]],"type":"LineString"},"properties":{},"type":"Feature"}
+{"geometry":{"coordinates":[[1.0,1.0],[0.0,0.0]],"type":"LineString"},"properties":{},"type":"Feature"}
+"#;
+
+    Assert::main_binary()
+        .with_args(&["delaunay", "--edges"])
+        .stdin("0,0\n0,1\n1,1\n")
+        .stdout()
+        .is(edges)
+        .unwrap();
+}
+
+#[test]
+fn measure_distance_with_bearing() {
+    let input = "35,-118\n34,-117\n34,-118\n";
+    let output = "110931.45952287687\t0\t35,-118
+92384.41943498426\t89.72039867005344\t34,-117
+0\t0\t34,-118
+";
+
+    Assert::main_binary()
+        .with_args(&["measure", "distance", "34,-118", "--with-bearing"])
+        .stdin(input)
+        .stdout()
+        .is(output)
+        .unwrap();
+}
+
+#[test]
+fn measure_distance_between_geometries() {
+    // Intersecting, disjoint, nested, and a line off one edge
+    let input = "POLYGON((0.5 0.5,2 0.5,2 2,0.5 2,0.5 0.5))
+POLYGON((2 0,3 0,3 1,2 1,2 0))
+POLYGON((0.25 0.25,0.75 0.25,0.75 0.75,0.25 0.75,0.25 0.25))
+LINESTRING(0.5 2,0.5 3)
+";
+    let output = "0\tPOLYGON((0.5 0.5,2 0.5,2 2,0.5 2,0.5 0.5))
+111319.49079327357\tPOLYGON((2 0,3 0,3 1,2 1,2 0))
+0\tPOLYGON((0.25 0.25,0.75 0.25,0.75 0.75,0.25 0.75,0.25 0.25))
+110575.06481432989\tLINESTRING(0.5 2,0.5 3)
+";
+
+    Assert::main_binary()
+        .with_args(&["measure", "distance", "POLYGON((0 0,1 0,1 1,0 1,0 0))"])
+        .stdin(input)
+        .stdout()
+        .is(output)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["measure", "distance", "POLYGON((0 0,1 0,1 1,0 1,0 0))", "--with-bearing"])
+        .stdin("1,1")
+        .fails()
+        .and()
+        .stderr()
+        .contains("must be a Point")
+        .unwrap();
+}
+
+#[test]
+fn measure_distance_closest_points() {
+    let input = r#"POLYGON((2 0,3 0,3 1,2 1,2 0))
+{"type":"Feature","id":7,"properties":{},"geometry":{"type":"LineString","coordinates":[[0,2],[2,0]]}}
+"#;
+    let output = r#"{"geometry":{"coordinates":[[1.0,0.0],[2.0,0.0]],"type":"LineString"},"properties":{"distance":111319.49079327357,"unit":"m"},"type":"Feature"}
+{"geometry":{"coordinates":[[1.0,1.0],[1.0,1.0]],"type":"LineString"},"id":7,"properties":{"distance":0.0,"unit":"m"},"type":"Feature"}
+"#;
+
+    Assert::main_binary()
+        .with_args(&["measure", "distance", "LINESTRING(0 0,1 0,1 1)", "--closest-points"])
+        .stdin(input)
+        .stdout()
+        .is(output)
+        .unwrap();
+}
+
+#[test]
+fn measure_planar() {
+    Assert::main_binary()
+        .with_args(&["measure", "area", "--planar", "--json"])
+        .stdin("POLYGON((0 0,0 100,100 100,100 0,0 0),(10 10,20 10,20 20,10 20,10 10))")
+        .stdout()
+        .is(r#"{"unit":"m^2","value":9900.0}"#)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["measure", "distance", "--planar", "POINT(0 0)"])
+        .stdin("LINESTRING(3 4,10 10)")
+        .stdout()
+        .is("5\tLINESTRING(3 4,10 10)")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["measure", "distance", "--planar", "--cumulative", "--unit", "km", "LINESTRING(0 0,3000 4000,3000 10000)"])
+        .stdout()
+        .is("0\n5\n11")
+        .unwrap();
+}
+
+#[test]
+fn measure_json() {
+    let input = r#"35,-118
+{"type":"Feature","id":7,"properties":{},"geometry":{"type":"Point","coordinates":[-117,34]}}
+{"type":"Feature","properties":{"id":"b"},"geometry":{"type":"Point","coordinates":[-117,34]}}
+"#;
+    let output = r#"{"unit":"m","value":110931.45952287687}
+{"id":7,"unit":"m","value":92384.41943498426}
+{"id":"b","unit":"m","value":92384.41943498426}
+"#;
+
+    Assert::main_binary()
+        .with_args(&["measure", "distance", "34,-118", "--json"])
+        .stdin(input)
+        .stdout()
+        .is(output)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["measure", "coord-count", "--json"])
+        .stdin("9q5")
+        .stdout()
+        .is(r#"{"unit":"coords","value":5}"#)
+        .unwrap();
+}
+
+#[test]
+fn limit_counts_entities() {
+    let input = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{},"geometry":{"type":"Point","coordinates":[34.0,12.0]}},{"type":"Feature","properties":{},"geometry":{"type":"Point","coordinates":[78.0,56.0]}}]}
+9q5
+"#;
+
+    Assert::main_binary()
+        .with_args(&["wkt", "--limit", "1"])
+        .stdin(input)
+        .stdout()
+        .is("POINT(34 12)\n")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["--limit", "2", "gj", "fc"])
+        .stdin(input)
+        .stdout()
+        .is(r#"{"type":"FeatureCollection","features":[{"geometry":{"coordinates":[34.0,12.0],"type":"Point"},"properties":{},"type":"Feature"},{"geometry":{"coordinates":[78.0,56.0],"type":"Point"},"properties":{},"type":"Feature"}]}"#)
+        .unwrap();
+}
+
+#[test]
+fn geojson_featurecollection_bbox() {
+    let output = r#"{"type":"FeatureCollection","features":[{"geometry":{"coordinates":[34.0,12.0],"type":"Point"},"properties":{},"type":"Feature"},{"geometry":{"coordinates":[78.0,56.0],"type":"Point"},"properties":{},"type":"Feature"}],"bbox":[34.0,12.0,78.0,56.0]}"#;
+
+    Assert::main_binary()
+        .with_args(&["gj", "fc", "--bbox"])
+        .stdin("12,34\n56,78\n")
+        .stdout()
+        .is(output)
+        .unwrap();
+}
+
+#[test]
+fn pretty_geojson_featurecollection() {
+    let output = r#"{
+  "type": "FeatureCollection",
+  "features": [
+    {
+      "geometry": {
+        "coordinates": [
+          34.0,
+          12.0
+        ],
+        "type": "Point"
+      },
+      "properties": {},
+      "type": "Feature"
+    }
+  ],
+  "bbox": [
+    34.0,
+    12.0,
+    34.0,
+    12.0
+  ]
+}
+"#;
+
+    Assert::main_binary()
+        .with_args(&["gj", "fc", "--bbox", "--pretty"])
+        .stdin("12,34")
+        .stdout()
+        .is(output)
+        .unwrap();
+}
+
+#[test]
+fn pretty_geojson() {
+    let output = r#"{
+  "coordinates": [
+    34.0,
+    12.0
+  ],
+  "type": "Point"
+}
+"#;
+
+    Assert::main_binary()
+        .with_args(&["gj", "geom", "--pretty"])
+        .stdin("12,34")
+        .stdout()
+        .is(output)
+        .unwrap();
+}
+
+#[test]
+fn geojson_ndjson() {
+    let output = r#"{"geometry":{"coordinates":[34.0,12.0],"type":"Point"},"properties":{},"type":"Feature"}
+{"geometry":{"coordinates":[78.0,56.0],"type":"Point"},"properties":{},"type":"Feature"}
+"#;
+
+    Assert::main_binary()
+        .with_args(&["gj", "f", "--ndjson"])
+        .stdin("12,34\n56,78\n")
+        .stdout()
+        .is(output)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["gj", "--ndjson", "fc"])
+        .stdin("12,34\n")
+        .fails()
+        .unwrap();
+}
+
+#[test]
+fn join_csv() {
+    let input = r#"{"type":"Feature","properties":{"id":1},"geometry":{"type":"Point","coordinates":[2.35,48.85]}}
+{"type":"Feature","properties":{"id":3},"geometry":{"type":"Point","coordinates":[13.4,52.52]}}
+"#;
+    let output = r#"{"geometry":{"coordinates":[2.35,48.85],"type":"Point"},"properties":{"id":1,"name":"Paris"},"type":"Feature"}
+{"geometry":{"coordinates":[13.4,52.52],"type":"Point"},"properties":{"id":3},"type":"Feature"}
+"#;
+
+    Assert::main_binary()
+        .with_args(&["join", "--csv", "./tests/resources/join.csv", "--on", "id"])
+        .stdin(input)
+        .stdout()
+        .is(output)
+        .unwrap();
+
+    let inner_output = r#"{"geometry":{"coordinates":[2.35,48.85],"type":"Point"},"properties":{"id":1,"name":"Paris"},"type":"Feature"}
+"#;
+    Assert::main_binary()
+        .with_args(&["join", "--csv", "./tests/resources/join.csv", "--on", "id", "--inner"])
+        .stdin(input)
+        .stdout()
+        .is(inner_output)
+        .unwrap();
+}
+
+#[test]
+fn sjoin() {
+    let output = r#"{"geometry":{"coordinates":[1.5,1.0],"type":"Point"},"properties":{"query_name":"west"},"type":"Feature"}
+{"geometry":{"coordinates":[1.5,1.0],"type":"Point"},"properties":{"query_name":"east"},"type":"Feature"}
+"#;
+
+    Assert::main_binary()
+        .with_args(&["sjoin", "--query-file", "./tests/resources/sjoin_regions.geojson"])
+        .stdin("1,1.5\n5,5\n")
+        .stdout()
+        .is(output)
+        .unwrap();
+
+    let first_output = r#"{"geometry":{"coordinates":[1.5,1.0],"type":"Point"},"properties":{"region_name":"west"},"type":"Feature"}
+"#;
+    Assert::main_binary()
+        .with_args(&[
+            "sjoin",
+            "--query-file",
+            "./tests/resources/sjoin_regions.geojson",
+            "--predicate",
+            "contains",
+            "--mode",
+            "first",
+            "--prefix",
+            "region_",
+        ])
+        .stdin("1,1.5\n")
+        .stdout()
+        .is(first_output)
+        .unwrap();
+}
+
+#[test]
+fn pip() {
+    let output = r#"{"geometry":{"coordinates":[0.5,1.0],"type":"Point"},"properties":{"name":"west"},"type":"Feature"}
+"#;
+
+    Assert::main_binary()
+        .with_args(&["pip", "--polygons", "./tests/resources/sjoin_regions.geojson", "--field", "name"])
+        .stdin("1,0.5\n")
+        .stdout()
+        .is(output)
+        .unwrap();
+
+    let outside_output = r#"{"geometry":{"coordinates":[5.0,5.0],"type":"Point"},"properties":{"name":null},"type":"Feature"}
+"#;
+    Assert::main_binary()
+        .with_args(&["pip", "--polygons", "./tests/resources/sjoin_regions.geojson", "--field", "name"])
+        .stdin("5,5\n")
+        .stdout()
+        .is(outside_output)
+        .unwrap();
+}
+
+#[test]
+fn gh_covering_dissolve() {
+    Assert::main_binary()
+        .with_args(&["gh", "covering", "3", "--dissolve"])
+        .stdin("POLYGON((0 0,10 0,10 10,0 10,0 0),(2 2,8 2,8 8,2 8,2 2))")
+        .stdout()
+        .is(r#"{"coordinates":[[[-1.40625,-1.40625],[11.25,-1.40625],[11.25,11.25],[-1.40625,11.25],[-1.40625,-1.40625]],[[2.8125,2.8125],[2.8125,7.03125],[7.03125,7.03125],[7.03125,2.8125],[2.8125,2.8125]]],"type":"Polygon"}"#)
+        .unwrap();
+
+    // Cells touching only at a corner give separate polygons
+    Assert::main_binary()
+        .with_args(&["gh", "covering", "2", "--dissolve"])
+        .stdin("MULTIPOLYGON(((0.1 0.1,0.2 0.1,0.2 0.2,0.1 0.1)),((12 6,12.1 6,12.1 6.1,12 6)))")
+        .stdout()
+        .is(r#"{"coordinates":[[[[0.0,0.0],[11.25,0.0],[11.25,5.625],[0.0,5.625],[0.0,0.0]]],[[[11.25,5.625],[22.5,5.625],[22.5,11.25],[11.25,11.25],[11.25,5.625]]]],"type":"MultiPolygon"}"#)
+        .unwrap();
+}
+
+#[test]
+fn gh_covering_compact() {
+    let output = "7zz\nebp\nebr\nebx\nebz\necp\necr\necx\necz\nkpb\nkpc\nkpf\nkpg\nkpu\nkpv\nkpy\nkpz\ns0\ns1\n";
+    Assert::main_binary()
+        .with_args(&["gh", "covering", "3", "--compact"])
+        .stdin("POLYGON((0 0,10 0,10 10,0 10,0 0))")
+        .stdout()
+        .is(output)
+        .unwrap();
+}
+
+#[test]
+fn positional_input_arg() {
+    Assert::main_binary()
+        .with_args(&["centroid", "POLYGON((0 0,2 0,2 2,0 2,0 0))"])
+        .stdout()
+        .is(r#"{"coordinates":[1.0,1.0],"type":"Point"}"#)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["measure", "coord-count", "9q5"])
+        .stdout()
+        .is("5")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["bbox", "LINESTRING(0 0,1 3)"])
+        .stdout()
+        .is(r#"{"coordinates":[[[1.0,3.0],[1.0,0.0],[0.0,0.0],[0.0,3.0],[1.0,3.0]]],"type":"Polygon"}"#)
+        .unwrap();
+}
+
+#[test]
+fn measure_distance_units() {
+    Assert::main_binary()
+        .with_args(&["measure", "distance", "34.1,-118", "--unit", "km"])
+        .stdin("34.2,-118")
+        .stdout()
+        .is("11.092509835580081\t34.2,-118")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["--units", "imperial", "measure", "distance", "34.1,-118", "--json"])
+        .stdin("34.2,-118")
+        .stdout()
+        .is(r#"{"unit":"ft","value":36392.74880439659}"#)
+        .unwrap();
+}
+
+#[test]
+fn flip() {
+    let input = r#"LINESTRING(34 -118,35 -117)
+{"type":"Feature","properties":{"a":1},"geometry":{"type":"Point","coordinates":[1,2]}}
+"#;
+    let output = r#"LINESTRING(-118 34,-117 35)
+{"geometry":{"coordinates":[2.0,1.0],"type":"Point"},"properties":{"a":1},"type":"Feature"}
+"#;
+
+    Assert::main_binary()
+        .with_args(&["flip"])
+        .stdin(input)
+        .stdout()
+        .is(output)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["flip", "--detect"])
+        .stdin("34,-118")
+        .stdout()
+        .is("-118,34")
+        .stderr()
+        .contains("out of Lon/Lat range")
+        .unwrap();
+}
+
+#[test]
+fn validate_coords() {
+    Assert::main_binary()
+        .with_args(&["validate", "--coords"])
+        .stdin("POINT(-118 34)\nPOINT(34 -118)\n")
+        .stdout()
+        .is("POINT(-118 34)\nPOINT(34 -118)\n")
+        .stderr()
+        .is("Input 2: coordinates out of Lon/Lat range: POINT(34 -118)")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["validate", "--coords", "--fix"])
+        .stdin("POINT(34 -118)\nPOINT(200 100)\n")
+        .stdout()
+        .is("POINT(-118 34)\nPOINT(200 100)\n")
+        .unwrap();
+}
+
+#[test]
+fn hash() {
+    let input = "POINT(1 2)\n{\"type\":\"Point\",\"coordinates\":[1,2]}\n";
+    let output = "10a39f36ce6fbfdb4ad86e3d96a0fcd00d2fe4ee71e8c5f889ed2b70932e9d7a\tPOINT(1 2)
+10a39f36ce6fbfdb4ad86e3d96a0fcd00d2fe4ee71e8c5f889ed2b70932e9d7a\t{\"type\":\"Point\",\"coordinates\":[1,2]}
+";
+    Assert::main_binary()
+        .with_args(&["hash"])
+        .stdin(input)
+        .stdout()
+        .is(output)
+        .unwrap();
+}
+
+#[test]
+fn diff() {
+    let output = r#"{"geometry":{"coordinates":[2.0,2.5],"type":"Point"},"properties":{"_diff":"modified","id":2,"name":"b"},"type":"Feature"}
+{"geometry":{"coordinates":[4.0,4.0],"type":"Point"},"properties":{"_diff":"added","id":4,"name":"d"},"type":"Feature"}
+{"geometry":{"coordinates":[3.0,3.0],"type":"Point"},"properties":{"_diff":"removed","id":3,"name":"c"},"type":"Feature"}
+"#;
+    Assert::main_binary()
+        .with_args(&[
+            "diff",
+            "./tests/resources/diff_a.geojson",
+            "./tests/resources/diff_b.geojson",
+            "--key",
+            "id",
+        ])
+        .stdout()
+        .is(output)
+        .unwrap();
+}
+
+#[test]
+fn measure_distance_methods() {
+    // Vincenty fails to converge for nearly antipodal points
+    Assert::main_binary()
+        .with_args(&["measure", "distance", "0.5,0", "--method", "karney", "--unit", "km"])
+        .stdin("-0.5,180")
+        .stdout()
+        .contains("20003.93")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["measure", "distance", "0.5,0", "--method", "vincenty"])
+        .stdin("-0.5,180")
+        .fails()
+        .unwrap();
+
+    // Karney is the default
+    Assert::main_binary()
+        .with_args(&["measure", "distance", "POINT(180 0)"])
+        .stdin("POINT(0 0)")
+        .stdout()
+        .is("20003931.458625164\tPOINT(0 0)")
+        .unwrap();
+}
+
+#[test]
+fn measure_distance_ellipsoid() {
+    Assert::main_binary()
+        .with_args(&["--ellipsoid", "sphere", "measure", "distance", "34.1,-118"])
+        .stdin("34.2,-118")
+        .stdout()
+        .is("11119.49266445583\t34.2,-118")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["--radius", "3389500", "measure", "distance", "34.1,-118"])
+        .stdin("34.2,-118")
+        .stdout()
+        .is("5915.793499634758\t34.2,-118")
+        .unwrap();
+}
+
+#[test]
+fn resample() {
+    Assert::main_binary()
+        .with_args(&["resample", "--min-distance", "10m"])
+        .stdin("LINESTRING (0 0, 0.00001 0, 0.0001 0, 0.00011 0, 0.00012 0)")
+        .stdout()
+        .is(r#"{"geometry":{"coordinates":[[0.0,0.0],[0.0001,0.0],[0.00012,0.0]],"type":"LineString"},"properties":{},"type":"Feature"}"#)
+        .unwrap();
+}
+
+#[test]
+fn smooth() {
+    Assert::main_binary()
+        .with_args(&["smooth", "--iterations", "1"])
+        .stdin("LINESTRING (0 0, 4 0, 4 4)")
+        .stdout()
+        .is(r#"{"geometry":{"coordinates":[[0.0,0.0],[1.0,0.0],[3.0,0.0],[4.0,1.0],[4.0,3.0],[4.0,4.0]],"type":"LineString"},"properties":{},"type":"Feature"}"#)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["smooth", "--method", "spline"])
+        .stdin("LINESTRING (0 0, 4 0, 4 4)")
+        .stdout()
+        .is(r#"{"geometry":{"coordinates":[[0.0,0.0],[2.0,-0.25],[4.0,0.0],[4.25,2.0],[4.0,4.0]],"type":"LineString"},"properties":{},"type":"Feature"}"#)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["smooth", "--iterations", "40"])
+        .stdin("LINESTRING(0 0,1 1,2 0)")
+        .fails()
+        .stderr()
+        .contains("Invalid --iterations")
+        .unwrap();
+}
+
+#[test]
+fn extent() {
+    let input = "LINESTRING (-118 34, -117 35)\n34.5,-120\n";
+    Assert::main_binary()
+        .with_args(&["extent"])
+        .stdin(input)
+        .stdout()
+        .is("-118,34,-117,35\n-120,34.5,-120,34.5")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["extent", "--all"])
+        .stdin(input)
+        .stdout()
+        .is("-120,34,-117,35")
+        .unwrap();
+}
+
+#[test]
+fn geometry_collection_round_trip() {
+    let wkt = "GEOMETRYCOLLECTION(POINT(1 2),MULTIPOINT((1 2),(3 4)),LINESTRING(0 0,1 1),MULTILINESTRING((0 0,1 1)),POLYGON((0 0,1 0,1 1,0 0)),MULTIPOLYGON(((0 0,1 0,1 1,0 0))),GEOMETRYCOLLECTION(POINT(3 4)),GEOMETRYCOLLECTION EMPTY)";
+    let gj = r#"{"geometries":[{"coordinates":[1.0,2.0],"type":"Point"},{"coordinates":[[1.0,2.0],[3.0,4.0]],"type":"MultiPoint"},{"coordinates":[[0.0,0.0],[1.0,1.0]],"type":"LineString"},{"coordinates":[[[0.0,0.0],[1.0,1.0]]],"type":"MultiLineString"},{"coordinates":[[[0.0,0.0],[1.0,0.0],[1.0,1.0],[0.0,0.0]]],"type":"Polygon"},{"coordinates":[[[[0.0,0.0],[1.0,0.0],[1.0,1.0],[0.0,0.0]]]],"type":"MultiPolygon"},{"geometries":[{"coordinates":[3.0,4.0],"type":"Point"}],"type":"GeometryCollection"},{"geometries":[],"type":"GeometryCollection"}],"type":"GeometryCollection"}"#;
+
+    Assert::main_binary()
+        .with_args(&["gj", "geom"])
+        .stdin(wkt)
+        .stdout()
+        .is(gj)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["wkt"])
+        .stdin(gj)
+        .stdout()
+        .is(wkt)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["gj", "f"])
+        .stdin("GEOMETRYCOLLECTION(POINT(1 2))")
+        .stdout()
+        .is(r#"{"geometry":{"geometries":[{"coordinates":[1.0,2.0],"type":"Point"}],"type":"GeometryCollection"},"properties":{},"type":"Feature"}"#)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["gj", "fc"])
+        .stdin("GEOMETRYCOLLECTION EMPTY")
+        .stdout()
+        .is(r#"{"type":"FeatureCollection","features":[{"geometry":null,"properties":{},"type":"Feature"}]}"#)
+        .unwrap();
+}
+
+#[test]
+fn empty_geometries() {
+    let input = "POINT EMPTY\nPOLYGON EMPTY\n{\"type\":\"Feature\",\"properties\":{\"a\":1},\"geometry\":null}";
+    Assert::main_binary()
+        .with_args(&["wkt"])
+        .stdin(input)
+        .stdout()
+        .is("POINT EMPTY\nPOLYGON EMPTY\nGEOMETRYCOLLECTION EMPTY")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["gj", "f"])
+        .stdin(input)
+        .stdout()
+        .is(r#"{"geometry":null,"properties":{},"type":"Feature"}
+{"geometry":null,"properties":{},"type":"Feature"}
+{"geometry":null,"properties":{"a":1},"type":"Feature"}"#)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["measure", "coord-count"])
+        .stdin(input)
+        .stdout()
+        .is("0\n0\n0")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["extent", "--all"])
+        .stdin("POINT EMPTY\nGEOMETRYCOLLECTION(POINT EMPTY,POINT(1 2),POINT(3 4))")
+        .stdout()
+        .is("1,2,3,4")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["bbox"])
+        .stdin(input)
+        .stdout()
+        .is("")
+        .unwrap();
+}
+
+#[test]
+fn geojson_crs() {
+    Assert::main_binary()
+        .with_args(&["gj", "fc", "--write-crs", "EPSG:4326"])
+        .stdin("12,34")
+        .stdout()
+        .is(r#"{"type":"FeatureCollection","crs":{"properties":{"name":"urn:ogc:def:crs:EPSG::4326"},"type":"name"},"features":[{"geometry":{"coordinates":[34.0,12.0],"type":"Point"},"properties":{},"type":"Feature"}]}"#)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["wkt"])
+        .stdin(r#"{"type":"FeatureCollection","crs":{"type":"name","properties":{"name":"urn:ogc:def:crs:EPSG::3857"}},"features":[{"type":"Feature","properties":{},"geometry":{"type":"Point","coordinates":[1,2]}}]}"#)
+        .stdout()
+        .is("POINT(1 2)")
+        .stderr()
+        .contains("non-WGS84 'crs' member")
+        .unwrap();
+}
+
+#[test]
+fn bbox_antimeridian() {
+    let input = "LINESTRING (170 -10, 175 0)\nLINESTRING (-178 0, -170 5)";
+    Assert::main_binary()
+        .with_args(&["bbox", "--all", "--antimeridian"])
+        .stdin(input)
+        .stdout()
+        .is(r#"{"coordinates":[[[[180.0,5.0],[180.0,-10.0],[170.0,-10.0],[170.0,5.0],[180.0,5.0]]],[[[-170.0,5.0],[-170.0,-10.0],[-180.0,-10.0],[-180.0,5.0],[-170.0,5.0]]]],"type":"MultiPolygon"}"#)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["extent", "--all", "--antimeridian"])
+        .stdin(input)
+        .stdout()
+        .is("170,-10,-170,5")
+        .unwrap();
+}
+
+#[test]
+fn normalize_lon() {
+    Assert::main_binary()
+        .with_args(&["normalize-lon"])
+        .stdin("LINESTRING(170 0,190 10)")
+        .stdout()
+        .is("LINESTRING(170 0,-170 10)")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["normalize-lon", "--split"])
+        .stdin("LINESTRING(170 0,190 10)")
+        .stdout()
+        .is("MULTILINESTRING((170 0,180 5),(-180 5,-170 10))")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["normalize-lon", "--split"])
+        .stdin("LINESTRING(170 0,180 5,190 10)")
+        .stdout()
+        .is("MULTILINESTRING((170 0,180 5),(-180 5,-170 10))")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["normalize-lon", "--to-0-360"])
+        .stdin("POINT(-170 5)")
+        .stdout()
+        .is("POINT(190 5)")
+        .unwrap();
+}
+
+#[test]
+fn to_multi_and_single() {
+    Assert::main_binary()
+        .with_args(&["to-multi"])
+        .stdin("POLYGON((0 0,1 0,1 1,0 0))\nMULTIPOINT((1 2),(3 4))")
+        .stdout()
+        .is("MULTIPOLYGON(((0 0,1 0,1 1,0 0)))\nMULTIPOINT((1 2),(3 4))")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["to-single"])
+        .stdin("MULTIPOLYGON(((0 0,1 0,1 1,0 0)))\nMULTIPOINT((1 2),(3 4))")
+        .stdout()
+        .is("POLYGON((0 0,1 0,1 1,0 0))\nMULTIPOINT((1 2),(3 4))")
+        .stderr()
+        .contains("MULTIPOINT((1 2),(3 4))")
+        .unwrap();
+}
+
+#[test]
+fn info() {
+    Assert::main_binary()
+        .with_args(&["info"])
+        .stdin("LINESTRING(0 0,1 0)")
+        .stdout()
+        .is("LineString\tvertices: 2\tbbox: 0,0,1,0\tarea: 0 m^2\tlength: 111319.49079327357 m\tproperties: 0")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["info", "--json"])
+        .stdin(r#"{"type":"Feature","id":7,"properties":{"a":1},"geometry":{"type":"Polygon","coordinates":[[[0,0],[1,0],[1,1],[0,1],[0,0]]]}}"#)
+        .stdout()
+        .is(r#"{"area":12308778361.468754,"bbox":[0.0,0.0,1.0,1.0],"id":7,"length":0.0,"properties":1,"type":"Polygon","unit":"m","vertices":5}"#)
+        .unwrap();
+
+    // Clockwise rings give the same (positive) area as counter-clockwise ones
+    Assert::main_binary()
+        .with_args(&["info", "--json"])
+        .stdin("POLYGON((0 0,0 1,1 1,1 0,0 0))")
+        .stdout()
+        .is(r#"{"area":12308778361.468754,"bbox":[0.0,0.0,1.0,1.0],"length":0.0,"properties":0,"type":"Polygon","unit":"m","vertices":5}"#)
+        .unwrap();
+}
+
+#[test]
+fn filter_equals() {
+    Assert::main_binary()
+        .with_args(&["filter", "equals", "POLYGON((0 0,1 0,1 1,0 0))"])
+        .stdin("POLYGON((1 1,1 0,0 0,1 1))\nPOLYGON((0 0,2 0,1 1,0 0))\nMULTIPOLYGON(((0 0,1 0,1 1,0 0)))")
+        .stdout()
+        .is("POLYGON((1 1,1 0,0 0,1 1))\nMULTIPOLYGON(((0 0,1 0,1 1,0 0)))")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["filter", "equals", "POINT(1 2)", "--tolerance", "0.000001", "--negate"])
+        .stdin("POINT(1.0000001 2)\nPOINT(1.1 2)")
+        .stdout()
+        .is("POINT(1.1 2)")
+        .unwrap();
+}
+
+#[test]
+fn filter_covered_by_and_covers() {
+    Assert::main_binary()
+        .with_args(&["filter", "covered-by", "POLYGON((0 0,2 0,2 2,0 2,0 0))"])
+        .stdin("POINT(0 1)\nPOINT(3 1)\nPOLYGON((0 0,1 0,1 1,0 1,0 0))")
+        .stdout()
+        .is("POINT(0 1)\nPOLYGON((0 0,1 0,1 1,0 1,0 0))")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["filter", "contains", "POLYGON((0 0,2 0,2 2,0 2,0 0))"])
+        .stdin("POINT(0 1)")
+        .stdout()
+        .is("")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["filter", "covers", "--negate", "POINT(0 1)"])
+        .stdin("POLYGON((0 0,2 0,2 2,0 2,0 0))\nPOLYGON((5 5,6 5,6 6,5 5))\nPOINT(0 1)")
+        .stdout()
+        .is("POLYGON((5 5,6 5,6 6,5 5))\nPOINT(0 1)")
+        .unwrap();
+}
+
+#[test]
+fn feature_ids_preserved() {
+    Assert::main_binary()
+        .with_args(&["simplify", "0.1"])
+        .stdin(r#"{"type":"Feature","id":"abc","properties":{"a":1},"geometry":{"type":"LineString","coordinates":[[0,0],[1,0.01],[2,0]]}}"#)
+        .stdout()
+        .is(r#"{"geometry":{"coordinates":[[0.0,0.0],[2.0,0.0]],"type":"LineString"},"id":"abc","properties":{"a":1},"type":"Feature"}"#)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["flip"])
+        .stdin(r#"{"type":"Feature","id":12,"properties":{},"geometry":{"type":"Point","coordinates":[1,2]}}"#)
+        .stdout()
+        .is(r#"{"geometry":{"coordinates":[2.0,1.0],"type":"Point"},"id":12,"properties":{},"type":"Feature"}"#)
+        .unwrap();
+}
+
+#[test]
+fn wkt_with_type_and_only() {
+    Assert::main_binary()
+        .with_args(&["wkt", "--with-type", "--only", "point,Polygon"])
+        .stdin("POINT(1 2)\nLINESTRING(0 0,1 1)\nPOLYGON((0 0,1 0,1 1,0 0))")
+        .stdout()
+        .is("POINT\tPOINT(1 2)\nPOLYGON\tPOLYGON((0 0,1 0,1 1,0 0))")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["wkt", "--only", "triangle"])
+        .stdin("POINT(1 2)")
+        .fails()
+        .and()
+        .stderr()
+        .contains("Invalid --only geometry type")
+        .unwrap();
+}
+
+#[test]
+fn measure_coord_count_per_ring() {
+    Assert::main_binary()
+        .with_args(&["measure", "coord-count", "--per-ring"])
+        .stdin("MULTIPOLYGON(((0 0,10 0,10 10,0 10,0 0),(1 1,2 1,2 2,1 1)),((20 20,21 20,21 21,20 20)))\nPOINT(1 2)")
+        .stdout()
+        .is("0\texterior\t5\n0\tinterior\t4\n1\texterior\t4")
+        .unwrap();
+}
+
+#[test]
+fn gh_covering_estimate() {
+    Assert::main_binary()
+        .with_args(&["gh", "covering", "5", "--estimate"])
+        .stdin("9q5\nPOINT(1 2)")
+        .stdout()
+        .is("1156\n1")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["gh", "covering", "5"])
+        .stdin("9q5")
+        .stdout()
+        .satisfies(|out| out.lines().count() == 1156, "Estimate should match the covering")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["gh", "covering", "30", "--estimate"])
+        .stdin("9q5")
+        .fails()
+        .stderr()
+        .contains("Invalid level: 30 - must be between 1 and 12")
+        .unwrap();
+}
+
+#[test]
+fn gj_geom_flatten() {
+    let input = r#"{"type":"FeatureCollection","features":[{"type":"Feature","properties":{},"geometry":{"type":"Point","coordinates":[1,2]}},{"type":"Feature","properties":{},"geometry":null},{"type":"Feature","properties":{},"geometry":{"type":"GeometryCollection","geometries":[{"type":"Point","coordinates":[3,4]},{"type":"GeometryCollection","geometries":[{"type":"LineString","coordinates":[[0,0],[1,1]]}]}]}}]}"#;
+
+    Assert::main_binary()
+        .with_args(&["gj", "geom"])
+        .stdin(input)
+        .stdout()
+        .is(r#"{"coordinates":[1.0,2.0],"type":"Point"}
+{"geometries":[],"type":"GeometryCollection"}
+{"geometries":[{"coordinates":[3.0,4.0],"type":"Point"},{"geometries":[{"coordinates":[[0.0,0.0],[1.0,1.0]],"type":"LineString"}],"type":"GeometryCollection"}],"type":"GeometryCollection"}"#)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["gj", "geom", "--flatten"])
+        .stdin(input)
+        .stdout()
+        .is(r#"{"coordinates":[1.0,2.0],"type":"Point"}
+{"coordinates":[3.0,4.0],"type":"Point"}
+{"coordinates":[[0.0,0.0],[1.0,1.0]],"type":"LineString"}"#)
+        .unwrap();
+}
+
+#[test]
+fn centroid_midpoint() {
+    Assert::main_binary()
+        .with_args(&["centroid", "--midpoint"])
+        .stdin("LINESTRING(0 0,1 0,2 0)\nPOLYGON((0 0,3 0,3 3,2 3,2 1,1 1,1 3,0 3,0 0))")
+        .stdout()
+        .is(r#"{"coordinates":[1.0,0.0],"type":"Point"}
+{"coordinates":[2.5,1.5],"type":"Point"}"#)
+        .unwrap();
+}
+
+#[test]
+fn centroid_raw() {
+    Assert::main_binary()
+        .with_args(&["centroid", "--raw"])
+        .stdin("LINESTRING(0 0,2 0)\n34,-118")
+        .stdout()
+        .is("1,0\n-118,34")
+        .unwrap();
+}
+
+#[test]
+fn map_url() {
+    Assert::main_binary()
+        .with_args(&["map", "--url"])
+        .stdin("1,2")
+        .stdout()
+        .is("http://geojson.io#data=data:application/json,%7B%22features%22:[%7B%22geometry%22:%7B%22coordinates%22:[2.0,1.0],%22type%22:%22Point%22%7D,%22properties%22:%7B%7D,%22type%22:%22Feature%22%7D],%22type%22:%22FeatureCollection%22%7D")
+        .unwrap();
+}
+
+#[test]
+fn map_sample_and_simplify() {
+    Assert::main_binary()
+        .with_args(&["map", "--url", "--sample", "2"])
+        .stdin("1,0\n2,0\n3,0\n4,0\n5,0")
+        .stdout()
+        .satisfies(|url| url.matches("Feature%22%7D").count() == 2, "Should include 2 features")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["map", "--url", "--simplify-for-display", "0.1"])
+        .stdin("LINESTRING(0 0,1 0.001,2 0)")
+        .stdout()
+        .contains("[[0.0,0.0],[2.0,0.0]]")
+        .unwrap();
+}
+
+#[test]
+fn filter_count_only() {
+    Assert::main_binary()
+        .with_args(&["filter", "contains", "--count-only", "POLYGON((0 0,2 0,2 2,0 2,0 0))"])
+        .stdin("POINT(1 1)\nPOINT(3 1)\nPOINT(0.5 0.5)")
+        .stdout()
+        .is("2")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["filter", "--count-only", "--verbose", "--negate", "contains", "POLYGON((0 0,2 0,2 2,0 2,0 0))"])
+        .stdin("POINT(1 1)\nPOINT(3 1)\nPOINT(0.5 0.5)")
+        .stdout()
+        .is("passing: 1\ttotal: 3")
+        .unwrap();
+}
+
+#[test]
+fn sort_by_spatial_key() {
+    Assert::main_binary()
+        .with_args(&["sort"])
+        .stdin("POINT(10 10)\nPOINT(-100 40)\nPOINT EMPTY\nPOINT(-100.1 40)\nPOINT(11 10)")
+        .stdout()
+        .is("POINT(-100.1 40)\nPOINT(-100 40)\nPOINT(11 10)\nPOINT(10 10)\nPOINT EMPTY")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["sort", "--by", "geohash"])
+        .stdin("POINT(10 10)\n9q5\nPOINT(-100.1 40)")
+        .stdout()
+        .is("9q5\nPOINT(-100.1 40)\nPOINT(10 10)")
+        .unwrap();
+}
+
+#[test]
+fn bin_points() {
+    let input = r#"{"type":"Feature","properties":{"v":2},"geometry":{"type":"Point","coordinates":[-118.25,34.05]}}
+{"type":"Feature","properties":{"v":4},"geometry":{"type":"Point","coordinates":[-118.25,34.05]}}
+{"type":"Feature","properties":{"v":"n/a"},"geometry":{"type":"Point","coordinates":[1,2]}}"#;
+    Assert::main_binary()
+        .with_args(&["bin", "--geohash", "4", "--sum", "v", "--mean", "v"])
+        .stdin(input)
+        .stdout()
+        .is(r#"{"geometry":{"coordinates":[[[-118.4765625,33.92578125],[-118.125,33.92578125],[-118.125,34.1015625],[-118.4765625,34.1015625],[-118.4765625,33.92578125]]],"type":"Polygon"},"properties":{"count":2,"geohash":"9q5c","v_mean":3.0,"v_sum":6.0},"type":"Feature"}
+{"geometry":{"coordinates":[[[0.703125,1.93359375],[1.0546875,1.93359375],[1.0546875,2.109375],[0.703125,2.109375],[0.703125,1.93359375]]],"type":"Polygon"},"properties":{"count":1,"geohash":"s02e","v_mean":null,"v_sum":0.0},"type":"Feature"}"#)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["bin", "--grid", "1km"])
+        .stdin("34.05,-118.25\n34.0501,-118.2501\n40,-100")
+        .stdout()
+        .satisfies(|out| out.lines().count() == 2 && out.contains(r#""count":2"#), "Should give 2 cells")
+        .unwrap();
+}
+
+#[test]
+fn bin_invalid_cell_sizes() {
+    for args in &[["--geohash", "0"], ["--geohash", "13"], ["--grid", "0"], ["--grid", "0km"]] {
+        Assert::main_binary()
+            .with_args(&["bin", args[0], args[1]])
+            .stdin("34.05,-118.25")
+            .fails()
+            .stderr()
+            .contains(format!("Invalid {}", args[0]).as_str())
+            .unwrap();
+    }
+}
+
+#[test]
+fn filter_geometry_type() {
+    Assert::main_binary()
+        .with_args(&["filter", "type", "polygon,MultiPolygon"])
+        .stdin("POINT(1 2)\n9q5\nMULTIPOLYGON(((0 0,1 0,1 1,0 0)))\nLINESTRING(0 0,1 1)")
+        .stdout()
+        .is("9q5\nMULTIPOLYGON(((0 0,1 0,1 1,0 0)))")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["filter", "type", "--negate", "point"])
+        .stdin("POINT(1 2)\n12,34\nLINESTRING(0 0,1 1)")
+        .stdout()
+        .is("LINESTRING(0 0,1 1)")
+        .unwrap();
+}
+
+#[test]
+fn gh_covering_bbox() {
+    Assert::main_binary()
+        .with_args(&["gh", "covering", "3", "--bbox", "-118.5,33.8,-118.1,34.2"])
+        .stdout()
+        .is("9q5\n9qh")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["gh", "covering", "3", "--bbox", "1,2,3"])
+        .fails()
+        .and()
+        .stderr()
+        .contains("Invalid --bbox")
+        .unwrap();
+}
+
+#[test]
+fn measure_distance_cumulative() {
+    Assert::main_binary()
+        .with_args(&["measure", "distance", "--cumulative", "LINESTRING(0 0,0 1,0 2)"])
+        .stdout()
+        .is("0\n110574.38855779252\n221149.45337212243")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["measure", "distance", "--cumulative", "--unit", "km"])
+        .stdin("MULTILINESTRING((0 0,1 0),(5 5,5 6))")
+        .stdout()
+        .is("0\n111.31949079327356\n0\n110.58450431644637")
+        .unwrap();
+}
+
+#[test]
+fn polygonize_lines() {
+    Assert::main_binary()
+        .with_args(&["polygonize", "--report-edges"])
+        .stdin("LINESTRING(1 1,0 0,1 0,1 1)\nLINESTRING(1 1,5 5)\nLINESTRING(5 5,6 5,6 6,5 5)\nLINESTRING(1 1,1 2)")
+        .stdout()
+        .is(r#"{"geometry":{"coordinates":[[[1.0,1.0],[0.0,0.0],[1.0,0.0],[1.0,1.0]]],"type":"Polygon"},"properties":{},"type":"Feature"}
+{"geometry":{"coordinates":[[[5.0,5.0],[6.0,5.0],[6.0,6.0],[5.0,5.0]]],"type":"Polygon"},"properties":{},"type":"Feature"}
+{"geometry":{"coordinates":[[1.0,1.0],[1.0,2.0]],"type":"LineString"},"properties":{"edge":"dangle"},"type":"Feature"}
+{"geometry":{"coordinates":[[1.0,1.0],[5.0,5.0]],"type":"LineString"},"properties":{"edge":"cut"},"type":"Feature"}"#)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["polygonize"])
+        .stdin("POINT(1 2)")
+        .fails()
+        .and()
+        .stderr()
+        .contains("Polygonizing requires LineString inputs")
+        .unwrap();
+}
+
+#[test]
+fn node_lines() {
+    Assert::main_binary()
+        .with_args(&["node"])
+        .stdin("LINESTRING(0 0,2 2)\nLINESTRING(0 2,2 0)")
+        .stdout()
+        .is("LINESTRING(0 0,1 1)\nLINESTRING(1 1,2 2)\nLINESTRING(0 2,1 1)\nLINESTRING(1 1,2 0)")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["node"])
+        .stdin("POINT(1 2)")
+        .fails()
+        .and()
+        .stderr()
+        .contains("Noding requires LineString inputs")
+        .unwrap();
+}
+
+#[test]
+fn split_by_blade() {
+    Assert::main_binary()
+        .with_args(&["split-by", "--blade", "LINESTRING(1 -1,1 5)"])
+        .stdin("LINESTRING(0 0,4 0)\nPOLYGON((0 0,0 2,2 2,2 0,0 0))\nPOLYGON((3 3,3 4,4 4,4 3,3 3))")
+        .stdout()
+        .satisfies(
+            |o| {
+                let lines: Vec<&str> = o.lines().collect();
+                lines.len() == 5
+                    && lines[0] == "LINESTRING(0 0,1 0)"
+                    && lines[1] == "LINESTRING(1 0,4 0)"
+                    && lines[2].starts_with("POLYGON")
+                    && lines[3].starts_with("POLYGON")
+                    && lines[4] == "POLYGON((3 3,3 4,4 4,4 3,3 3))"
+            },
+            "Splits the line and first polygon, leaving the second as-is",
+        )
+        .unwrap();
+
+    // Multi- geometries the blade misses are left whole
+    Assert::main_binary()
+        .with_args(&["split-by", "--blade", "LINESTRING(10 -1,10 5)"])
+        .stdin("MULTIPOLYGON(((0 0,0 2,2 2,2 0,0 0)),((3 3,3 4,4 4,4 3,3 3)))\nMULTILINESTRING((0 0,1 0),(0 1,1 1))")
+        .stdout()
+        .is("MULTIPOLYGON(((0 0,0 2,2 2,2 0,0 0)),((3 3,3 4,4 4,4 3,3 3)))\nMULTILINESTRING((0 0,1 0),(0 1,1 1))")
+        .unwrap();
+}
+
+#[test]
+fn clip_to_window() {
+    Assert::main_binary()
+        .with_args(&["clip", "0,0,2,2"])
+        .stdin("LINESTRING(-1 1,3 1)\nPOINT(5 5)\nPOLYGON((1 1,3 1,3 3,1 3,1 1))")
+        .stdout()
+        .is("LINESTRING(0 1,2 1)\nPOLYGON((1 2,1 1,2 1,2 2,1 2))")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["clip", "-1,0"])
+        .stdin("POINT(5 5)")
+        .fails()
+        .and()
+        .stderr()
+        .contains("Invalid bbox")
+        .unwrap();
+}
+
+#[test]
+fn measure_area_signed() {
+    Assert::main_binary()
+        .with_args(&["measure", "area", "--signed"])
+        .stdin("POLYGON((0 0,0 1,1 1,1 0,0 0))\nPOLYGON((0 0,2 0,2 1,0 1,0 0))\nPOINT(1 1)")
+        .stdout()
+        .is("-1\tPOLYGON((0 0,0 1,1 1,1 0,0 0))\n2\tPOLYGON((0 0,2 0,2 1,0 1,0 0))\n0\tPOINT(1 1)")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["measure", "area", "--signed", "--json"])
+        .stdin("POLYGON((0 0,2 0,2 1,0 1,0 0))")
+        .stdout()
+        .is(r#"{"unit":"deg^2","value":2.0}"#)
+        .unwrap();
+}
+
+#[test]
+fn null_geometry_features() {
+    let input = r#"{"type":"Feature","geometry":null,"properties":{"a":1}}"#;
+    Assert::main_binary()
+        .with_args(&["gj", "f"])
+        .stdin(input)
+        .stdout()
+        .is(r#"{"geometry":null,"properties":{"a":1},"type":"Feature"}"#)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["centroid"])
+        .stdin(format!("{}\nPOINT(1 2)", input))
+        .stdout()
+        .is(r#"{"coordinates":[1.0,2.0],"type":"Point"}"#)
+        .stderr()
+        .is("")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["measure", "area", "--verbose"])
+        .stdin(input)
+        .stdout()
+        .is("")
+        .stderr()
+        .contains("Skipping feature with null geometry")
+        .unwrap();
+}
+
+#[test]
+fn repair_winding() {
+    Assert::main_binary()
+        .with_args(&["repair-winding"])
+        .stdin("POLYGON((0 0,0 4,4 4,4 0,0 0),(1 1,2 1,2 2,1 2,1 1))\nPOLYGON((0 0,1 0,1 1,0 0))\nPOINT(1 1)")
+        .stdout()
+        .is("POLYGON((0 0,4 0,4 4,0 4,0 0),(1 1,1 2,2 2,2 1,1 1))\nPOLYGON((0 0,1 0,1 1,0 0))\nPOINT(1 1)")
+        .stderr()
+        .contains("Repaired winding of 1 features")
+        .unwrap();
+}
+
+#[test]
+fn densify_to_count() {
+    Assert::main_binary()
+        .with_args(&["densify", "--to-count", "3"])
+        .stdin("LINESTRING(0 0,0.1 0,1 0)\nPOINT(1 1)")
+        .stdout()
+        .is(r#"{"geometry":{"coordinates":[[0.0,0.0],[0.5000000000000001,0.0],[1.0,0.0]],"type":"LineString"},"properties":{},"type":"Feature"}
+{"geometry":{"coordinates":[1.0,1.0],"type":"Point"},"properties":{},"type":"Feature"}"#)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["densify", "--to-count", "1"])
+        .stdin("LINESTRING(0 0,1 0)")
+        .fails()
+        .and()
+        .stderr()
+        .contains("Invalid --to-count")
+        .unwrap();
+}
+
+#[test]
+fn filter_data_file_with_query_from_stdin() {
+    let data = "./tests/resources/filter_points.wkt";
+
+    Assert::main_binary()
+        .with_args(&["filter", "intersects", "--data-file", data])
+        .stdin("POLYGON((0 0,3 0,3 3,0 3,0 0))")
+        .stdout()
+        .is("POINT(1 1)\nPOINT(2 2)")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["filter", "--data-file", data, "intersects", "--count-only", "POINT(5 5)"])
+        .stdout()
+        .is("1")
+        .unwrap();
+}
+
+#[test]
+fn gh_covering_parents() {
+    Assert::main_binary()
+        .with_args(&["gh", "covering", "3", "--parents", "2"])
+        .stdin("POINT(-122.4 37.75)")
+        .stdout()
+        .is("9q8\t3\n9q\t2")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["gh", "covering", "4", "--parents", "2", "--parents-only"])
+        .stdin("9q5")
+        .stdout()
+        .is("9m\t2\n9q\t2")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["gh", "covering", "3", "--parents", "3"])
+        .stdin("9q5")
+        .fails()
+        .and()
+        .stderr()
+        .contains("Invalid --parents")
+        .unwrap();
+}
+
+#[test]
+fn version_json() {
+    Assert::main_binary()
+        .with_args(&["version"])
+        .stdout()
+        .is(format!("geoq {}", env!("CARGO_PKG_VERSION")).as_str())
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["version", "--json"])
+        .stdout()
+        .satisfies(
+            |o| {
+                let info: serde_json::Value = serde_json::from_str(o).unwrap();
+                info["version"] == env!("CARGO_PKG_VERSION")
+                    && info["target"].is_string()
+                    && info["features"].is_array()
+            },
+            "Gives version, target, and features",
+        )
+        .unwrap();
+}
+
+#[test]
+fn centroid_all() {
+    let input = "POINT(0 0)\nPOINT(4 0)\nLINESTRING(0 1,0 3)";
+    Assert::main_binary()
+        .with_args(&["centroid", "--all", "--weight", "equal"])
+        .stdin(input)
+        .stdout()
+        .is(r#"{"coordinates":[1.3333333333333333,0.6666666666666666],"type":"Point"}"#)
+        .unwrap();
+
+    // Only the line has any length
+    Assert::main_binary()
+        .with_args(&["centroid", "--all", "--weight", "length"])
+        .stdin(input)
+        .stdout()
+        .is(r#"{"coordinates":[0.0,2.0],"type":"Point"}"#)
+        .unwrap();
+}
+
+#[test]
+fn simplify_per_type_epsilon() {
+    Assert::main_binary()
+        .with_args(&["simplify", "0.1", "--epsilon-polygon", "0.0001"])
+        .stdin("LINESTRING(0 0,1 0.01,2 0)\nPOLYGON((0 0,1 0.01,2 0,2 2,0 2,0 0))")
+        .stdout()
+        .is(r#"{"geometry":{"coordinates":[[0.0,0.0],[2.0,0.0]],"type":"LineString"},"properties":{},"type":"Feature"}
+{"geometry":{"coordinates":[[[0.0,0.0],[1.0,0.01],[2.0,0.0],[2.0,2.0],[0.0,2.0],[0.0,0.0]]],"type":"Polygon"},"properties":{},"type":"Feature"}"#)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["simplify", "0.1", "--epsilon-line", "abc"])
+        .stdin("LINESTRING(0 0,1 0.01,2 0)")
+        .fails()
+        .and()
+        .stderr()
+        .contains("Invalid --epsilon-line")
+        .unwrap();
+}
+
+#[test]
+fn simplify_min_points() {
+    Assert::main_binary()
+        .with_args(&["simplify", "0.1", "--min-points", "7"])
+        .stdin("POLYGON((0 0,1 0,2 0.01,3 0,3 3,1.5 3.02,0 3,0 0))")
+        .stdout()
+        .is(r#"{"geometry":{"coordinates":[[[0.0,0.0],[2.0,0.01],[3.0,0.0],[3.0,3.0],[1.5,3.02],[0.0,3.0],[0.0,0.0]]],"type":"Polygon"},"properties":{},"type":"Feature"}"#)
+        .stderr()
+        .contains("Kept 1 rings or lines at --min-points")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["simplify", "0.1", "--min-points", "four"])
+        .stdin("LINESTRING(0 0,1 0.01,2 0)")
+        .fails()
+        .and()
+        .stderr()
+        .contains("Invalid --min-points")
+        .unwrap();
+}
+
+#[test]
+fn filter_repairs_invalid_polygons() {
+    // The hole crosses the exterior, so the part of it outside of the exterior is kept
+    let input = "POLYGON((0 0,4 0,4 4,0 4,0 0),(3 1,5 1,5 3,3 3,3 1))";
+    Assert::main_binary()
+        .with_args(&["filter", "intersects", "--repair", "--verbose", "POINT(4.5 2)"])
+        .stdin(input)
+        .stdout()
+        .is(input)
+        .stderr()
+        .is("Repaired 1 invalid features")
+        .unwrap();
+
+    // Geometries are checked as given by default
+    Assert::main_binary()
+        .with_args(&["filter", "intersects", "--verbose", "POINT(4.5 2)"])
+        .stdin(input)
+        .stdout()
+        .is("")
+        .stderr()
+        .is("")
+        .unwrap();
+
+    // Nothing is reported when no inputs needed repairing
+    Assert::main_binary()
+        .with_args(&["filter", "intersects", "--repair", "--verbose", "POINT(1 1)"])
+        .stdin("POLYGON((0 0,2 0,2 2,0 2,0 0))")
+        .stdout()
+        .is("POLYGON((0 0,2 0,2 2,0 2,0 0))")
+        .stderr()
+        .is("")
+        .unwrap();
+}
+
+#[test]
+fn stats() {
+    let input = "POLYGON((0 0,1 0,1 1,0 1,0 0))\nLINESTRING(0 0,1 0)\n0,2\n";
+    Assert::main_binary()
+        .with_args(&["stats"])
+        .stdin(input)
+        .stdout()
+        .is("total\tcount: 3\tvertices: 8\tarea: 12308778361.468754 m^2\tlength: 111319.49079327357 m\tbbox: 0,0,2,1
+LineString\tcount: 1\tvertices: 2\tarea: 0 m^2\tlength: 111319.49079327357 m
+Point\tcount: 1\tvertices: 1\tarea: 0 m^2\tlength: 0 m
+Polygon\tcount: 1\tvertices: 5\tarea: 12308778361.468754 m^2\tlength: 0 m")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["stats", "--json"])
+        .stdin("LINESTRING(0 0,1 0)\n0,0")
+        .stdout()
+        .is(r#"{"area":0.0,"bbox":[0.0,0.0,1.0,0.0],"count":2,"length":111319.49079327357,"types":{"LineString":{"area":0.0,"count":1,"length":111319.49079327357,"vertices":2},"Point":{"area":0.0,"count":1,"length":0.0,"vertices":1}},"unit":"m","vertices":3}"#)
+        .unwrap();
+}
+
+#[test]
+fn merge_properties() {
+    let input = r#"{"type":"Feature","properties":{"name":"a","pop":1},"geometry":{"type":"Point","coordinates":[1,2]}}
+POINT(3 4)
+{"type":"Feature","properties":{"name":"b","area":3},"geometry":{"type":"Point","coordinates":[1,2]}}
+"#;
+    let merged = |name: &str| {
+        format!(
+            r#"{{"geometry":{{"coordinates":[1.0,2.0],"type":"Point"}},"properties":{{"area":3,"name":{},"pop":1}},"type":"Feature"}}
+{{"geometry":{{"coordinates":[3.0,4.0],"type":"Point"}},"properties":{{}},"type":"Feature"}}"#,
+            name
+        )
+    };
+
+    for (conflict, name) in &[("first", r#""a""#), ("last", r#""b""#), ("array", r#"["a","b"]"#)] {
+        Assert::main_binary()
+            .with_args(&["merge-properties", "--conflict", conflict])
+            .stdin(input)
+            .stdout()
+            .is(merged(name).as_str())
+            .unwrap();
+    }
+}
+
+#[test]
+fn merge_properties_last_repeated_value() {
+    // The last value seen wins, even when it repeats an earlier one
+    let input = "{\"type\":\"Feature\",\"properties\":{\"name\":\"a\"},\"geometry\":{\"type\":\"Point\",\"coordinates\":[1,2]}}
+{\"type\":\"Feature\",\"properties\":{\"name\":\"b\"},\"geometry\":{\"type\":\"Point\",\"coordinates\":[1,2]}}
+{\"type\":\"Feature\",\"properties\":{\"name\":\"a\"},\"geometry\":{\"type\":\"Point\",\"coordinates\":[1,2]}}
+";
+    Assert::main_binary()
+        .with_args(&["merge-properties", "--conflict", "last"])
+        .stdin(input)
+        .stdout()
+        .is(r#"{"geometry":{"coordinates":[1.0,2.0],"type":"Point"},"properties":{"name":"a"},"type":"Feature"}"#)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["merge-properties", "--conflict", "array"])
+        .stdin(input)
+        .stdout()
+        .is(r#"{"geometry":{"coordinates":[1.0,2.0],"type":"Point"},"properties":{"name":["a","b"]},"type":"Feature"}"#)
+        .unwrap();
+}
+
+#[test]
+fn filter_within_bbox_of() {
+    let input = "34.1,-118.2\n9q5c\nLINESTRING(-118.2 34.1,-100 34.1)\n";
+    Assert::main_binary()
+        .with_args(&["filter", "within-bbox-of", "9q5"])
+        .stdin(input)
+        .stdout()
+        .is("34.1,-118.2\n9q5c")
+        .unwrap();
+
+    // The file's queries include 9q5 itself
+    Assert::main_binary()
+        .with_args(&["filter", "within-bbox-of", "--query-file", "./tests/resources/query_file.txt"])
+        .stdin(input)
+        .stdout()
+        .is("34.1,-118.2\n9q5c")
+        .unwrap();
+}
+
+#[test]
+fn filter_and() {
+    let input = "34.1,-118.2\n9q5c\nLINESTRING(-118.2 34.1,-100 34.1)\n";
+    Assert::main_binary()
+        .with_args(&["filter", "intersects", "9q5", "--and", "type", "point,polygon"])
+        .stdin(input)
+        .stdout()
+        .is("34.1,-118.2\n9q5c")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["filter", "--and", "within-bbox-of", "9q5", "--and", "type", "point", "intersects", "9q"])
+        .stdin(input)
+        .stdout()
+        .is("34.1,-118.2")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["filter", "intersects", "9q5", "--and", "within-bbox-of", "9q5", "--negate"])
+        .stdin(input)
+        .stdout()
+        .is("LINESTRING(-118.2 34.1,-100 34.1)")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["filter", "intersects", "9q5", "--and", "nearby", "9q5"])
+        .stdin(input)
+        .fails()
+        .and()
+        .stderr()
+        .contains("Invalid --and predicate")
+        .unwrap();
+}
+
+#[test]
+fn grid() {
+    Assert::main_binary()
+        .with_args(&["grid", "--geohash", "1", "POLYGON((1 1,2 1,2 2,1 2,1 1))"])
+        .stdout()
+        .is(r#"{"geometry":{"coordinates":[[[0.0,0.0],[45.0,0.0],[45.0,45.0],[0.0,45.0],[0.0,0.0]]],"type":"Polygon"},"id":"s","properties":{"geohash":"s"},"type":"Feature"}"#)
+        .unwrap();
+
+    // The 2 points fall in diagonally opposite cells of a 2x2 block
+    Assert::main_binary()
+        .with_args(&["grid", "--size", "100km"])
+        .stdin("1,1\n2,2\n")
+        .stdout()
+        .contains(r#""properties":{"col":1,"row":1}"#)
+        .stdout()
+        .contains(r#""properties":{"col":2,"row":2}"#)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["grid", "9q5"])
+        .fails()
+        .and()
+        .stderr()
+        .contains("Must provide a cell size")
+        .unwrap();
+}
+
+#[test]
+fn geojson_validate() {
+    let input = r#"{"type":"Point","coordinates":[1,2]}
+{"type":"Polygon","coordinates":[[[0,0],[0,1],[1,1],[1,0]]]}
+"#;
+    Assert::main_binary()
+        .with_args(&["gj", "validate"])
+        .stdin(input)
+        .fails()
+        .and()
+        .stdout()
+        .is(input)
+        .stderr()
+        .contains("Input 2: error at coordinates[0]: ring is not closed")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["gj", "validate", "--fix"])
+        .stdin(input)
+        .stdout()
+        .is(r#"{"type":"Point","coordinates":[1,2]}
+{"coordinates":[[[0,0],[1,0],[1,1],[0,1],[0,0]]],"type":"Polygon"}"#)
+        .stderr()
+        .contains("exterior ring is clockwise, but should be counter-clockwise (fixed)")
+        .unwrap();
+}
+
+#[test]
+fn measure_labeled() {
+    Assert::main_binary()
+        .with_args(&["measure", "distance", "--labeled", "--planar", "POINT(0 0)"])
+        .stdin("POINT(3 4)")
+        .stdout()
+        .is("5 m\tPOINT(3 4)")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["measure", "distance", "--labeled", "--planar", "--cumulative", "--unit", "km", "LINESTRING(0 0,3000 4000)"])
+        .stdout()
+        .is("0 km\n5 km")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["measure", "area", "--labeled", "--planar"])
+        .stdin("POLYGON((0 0,0 100,100 100,100 0,0 0))")
+        .stdout()
+        .is("10000 m^2\tPOLYGON((0 0,0 100,100 100,100 0,0 0))")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["measure", "area", "--labeled", "--json", "9q5"])
+        .fails()
+        .unwrap();
+}
+
+#[test]
+fn gh_covering_order() {
+    // A 3x3 block of cells
+    Assert::main_binary()
+        .with_args(&["gh", "covering", "2", "--bbox=1,1,30,14"])
+        .stdout()
+        .is("s0\ns1\ns2\ns3\ns4\ns6\ns8\ns9\nsd\n")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["gh", "covering", "2", "--order", "rowmajor", "--bbox=1,1,30,14"])
+        .stdout()
+        .is("s4\ns6\nsd\ns1\ns3\ns9\ns0\ns2\ns8\n")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["gh", "covering", "2", "--order", "spiral", "--bbox=1,1,30,14"])
+        .fails()
+        .unwrap();
+}
+
+#[test]
+fn filter_contains_with_holes() {
+    // A donut, and a square away from it
+    let query = "MULTIPOLYGON(((0 0,10 0,10 10,0 10,0 0),(2 2,2 8,8 8,8 2,2 2)),((20 20,30 20,30 30,20 30,20 20)))";
+    let input = "POINT(5 5)\nPOINT(2 5)\nPOINT(8 8)\nPOINT(1 1)\nPOINT(25 25)\nLINESTRING(1 1,9 9)\nLINESTRING(1 1,9 1)\n";
+    Assert::main_binary()
+        .with_args(&["filter", "contains", query])
+        .stdin(input)
+        .stdout()
+        .is("POINT(1 1)\nPOINT(25 25)\nLINESTRING(1 1,9 1)\n")
+        .unwrap();
+
+    // Points on the hole's boundary are covered, though not contained
+    Assert::main_binary()
+        .with_args(&["filter", "covered-by", query])
+        .stdin(input)
+        .stdout()
+        .is("POINT(2 5)\nPOINT(8 8)\nPOINT(1 1)\nPOINT(25 25)\nLINESTRING(1 1,9 1)\n")
+        .unwrap();
+}
+
+#[test]
+fn simplify_shared_topology() {
+    // Squares sharing a border with a small bump
+    let input = r#"{"type":"Feature","properties":{"n":"a"},"geometry":{"type":"Polygon","coordinates":[[[0,0],[1,0],[1,0.5],[1.01,0.6],[1,0.7],[1,1],[0,1],[0,0]]]}}
+{"type":"Feature","properties":{"n":"b"},"geometry":{"type":"Polygon","coordinates":[[[1,0],[2,0],[2,1],[1,1],[1,0.7],[1.01,0.6],[1,0.5],[1,0]]]}}
+LINESTRING(0 0,1 0.001,2 0)
+"#;
+    let output = r#"{"geometry":{"coordinates":[[[1.0,0.0],[1.0,1.0],[0.0,1.0],[0.0,0.0],[1.0,0.0]]],"type":"Polygon"},"properties":{"n":"a"},"type":"Feature"}
+{"geometry":{"coordinates":[[[1.0,0.0],[2.0,0.0],[2.0,1.0],[1.0,1.0],[1.0,0.0]]],"type":"Polygon"},"properties":{"n":"b"},"type":"Feature"}
+{"geometry":{"coordinates":[[0.0,0.0],[2.0,0.0]],"type":"LineString"},"properties":{},"type":"Feature"}
+"#;
+    Assert::main_binary()
+        .with_args(&["simplify", "0.01", "--shared-topology"])
+        .stdin(input)
+        .stdout()
+        .is(output)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["simplify", "0.01", "--shared-topology", "--min-points", "5"])
+        .stdin(input)
+        .fails()
+        .unwrap();
+}
+
+#[test]
+fn filter_query_buffer() {
+    // About 440m, 1.1km, and 670m from the query
+    let input = "POINT(-118.25 34.054)\nPOINT(-118.25 34.06)\nLINESTRING(-118.3 34.056,-118.2 34.056)\n";
+    Assert::main_binary()
+        .with_args(&["filter", "intersects", "--query-buffer", "500m", "POINT(-118.25 34.05)"])
+        .stdin(input)
+        .stdout()
+        .is("POINT(-118.25 34.054)\n")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["filter", "--negate", "--query-buffer", "1km", "intersects", "POINT(-118.25 34.05)"])
+        .stdin(input)
+        .stdout()
+        .is("POINT(-118.25 34.06)\n")
+        .unwrap();
+
+    // Just outside of 9q5
+    Assert::main_binary()
+        .with_args(&["filter", "contains", "--query-buffer", "1km", "9q5"])
+        .stdin("POINT(-118.12 34.0)")
+        .stdout()
+        .is("POINT(-118.12 34)")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["filter", "intersects", "--query-buffer", "pizza", "9q5"])
+        .stdin("9q5")
+        .fails()
+        .and()
+        .stderr()
+        .contains("Invalid distance")
+        .unwrap();
+}
+
+#[test]
+fn geojson_foreign_members() {
+    let input = r#"{"type":"FeatureCollection","name":"places","meta":{"v":1},"features":[{"type":"Feature","source":"osm","properties":{"a":1},"geometry":{"type":"Point","coordinates":[1,2]}}]}"#;
+    let feature = r#"{"geometry":{"coordinates":[1.0,2.0],"type":"Point"},"properties":{"a":1},"source":"osm","type":"Feature"}"#;
+    Assert::main_binary()
+        .with_args(&["gj", "fc", "--bbox"])
+        .stdin(input)
+        .stdout()
+        .is(format!(
+            r#"{{"type":"FeatureCollection","features":[{}],"meta":{{"v":1}},"name":"places","bbox":[1.0,2.0,1.0,2.0]}}"#,
+            feature
+        ).as_str())
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["gj", "f"])
+        .stdin(input)
+        .stdout()
+        .is(feature)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["filter", "intersects", "POINT(1 2)"])
+        .stdin(input)
+        .stdout()
+        .is(feature)
+        .unwrap();
+}
+
+#[test]
+fn centroid_multi() {
+    let input = r#"{"type":"Feature","id":3,"properties":{"name":"isles"},"geometry":{"type":"MultiPolygon","coordinates":[[[[0,0],[2,0],[2,2],[0,2],[0,0]]],[[[10,10],[12,10],[12,12],[10,12],[10,10]]]]}}"#;
+    let output = r#"{"geometry":{"coordinates":[1.0,1.0],"type":"Point"},"id":3,"properties":{"component":0,"name":"isles"},"type":"Feature"}
+{"geometry":{"coordinates":[11.0,11.0],"type":"Point"},"id":3,"properties":{"component":1,"name":"isles"},"type":"Feature"}
+"#;
+    Assert::main_binary()
+        .with_args(&["centroid", "--multi"])
+        .stdin(input)
+        .stdout()
+        .is(output)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["centroid", "--multi", "--raw"])
+        .stdin("MULTIPOINT((0 0),(2 2))\nPOINT(5 5)")
+        .stdout()
+        .is("0,0\t0\n2,2\t1\n5,5\t0")
+        .unwrap();
+}
+
+#[test]
+fn measure_area_per_component() {
+    let input = "MULTIPOLYGON(((0 0,100 0,100 100,0 100,0 0)),((200 200,201 200,201 201,200 200)))";
+    Assert::main_binary()
+        .with_args(&["measure", "area", "--planar", "--per-component", input])
+        .stdout()
+        .is(format!("10000\t0\t{}\n0.5\t1\t{}", input, input).as_str())
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["measure", "area", "--planar", "--per-component", "--json"])
+        .stdin(r#"{"type":"Feature","id":"a","properties":{},"geometry":{"type":"Polygon","coordinates":[[[0,0],[10,0],[10,10],[0,0]]]}}"#)
+        .stdout()
+        .is(r#"{"component":0,"id":"a","unit":"m^2","value":50.0}"#)
+        .unwrap();
+}
+
+#[test]
+fn index_geohash() {
+    let input = r#"34.05,-118.25
+{"type":"Feature","id":1,"properties":{"a":1},"geometry":{"type":"Polygon","coordinates":[[[0,0],[2,0],[2,2],[0,2],[0,0]]]}}
+{"type":"Feature","properties":{},"geometry":null}
+"#;
+    let output = r#"{"geometry":{"coordinates":[-118.25,34.05],"type":"Point"},"properties":{"geohash_5":"9q5ct","geohash_7":"9q5ctns"},"type":"Feature"}
+{"geometry":{"coordinates":[[[0.0,0.0],[2.0,0.0],[2.0,2.0],[0.0,2.0],[0.0,0.0]]],"type":"Polygon"},"id":1,"properties":{"a":1,"geohash_5":"s00tw","geohash_7":"s00twy0"},"type":"Feature"}
+{"geometry":null,"properties":{"geohash_5":null,"geohash_7":null},"type":"Feature"}
+"#;
+    Assert::main_binary()
+        .with_args(&["index", "--geohash", "5", "--geohash", "7"])
+        .stdin(input)
+        .stdout()
+        .is(output)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["index", "--geohash", "13", "9q5"])
+        .fails()
+        .and()
+        .stderr()
+        .contains("Invalid --geohash level")
+        .unwrap();
+}
+
+#[test]
+fn filter_nearest() {
+    let input = "POINT(0 3)\nPOINT(0 1)\nPOINT(0 5)\nPOINT(0 2)\nGEOMETRYCOLLECTION EMPTY\nPOINT(10 0)\n";
+    Assert::main_binary()
+        .with_args(&["filter", "nearest", "2", "POINT(0 0)"])
+        .stdin(input)
+        .stdout()
+        .is("POINT(0 1)\nPOINT(0 2)\n")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["filter", "nearest", "2", "--negate", "POINT(0 0)"])
+        .stdin(input)
+        .stdout()
+        .is("POINT(0 3)\nPOINT(0 5)\nGEOMETRYCOLLECTION EMPTY\nPOINT(10 0)\n")
+        .unwrap();
+
+    // Nearest to any of the queries
+    Assert::main_binary()
+        .with_args(&["filter", "nearest", "2", "MULTIPOINT((0 0),(10 1))"])
+        .stdin(input)
+        .stdout()
+        .is("POINT(0 1)\nPOINT(10 0)\n")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["filter", "nearest", "2", "POINT(0 0)", "--and", "type", "point"])
+        .stdin(input)
+        .fails()
+        .unwrap();
+}
+
+#[test]
+fn bbox_all_embed() {
+    Assert::main_binary()
+        .with_args(&["bbox", "--all", "--embed"])
+        .stdin("LINESTRING(0 0,1 3)\nPOINT EMPTY\nPOINT(4 -1)")
+        .stdout()
+        .is(r#"{"bbox":[0.0,-1.0,4.0,3.0],"features":[{"bbox":[0.0,0.0,1.0,3.0],"geometry":{"coordinates":[[0.0,0.0],[1.0,3.0]],"type":"LineString"},"properties":{},"type":"Feature"},{"bbox":[4.0,-1.0,4.0,-1.0],"geometry":{"coordinates":[4.0,-1.0],"type":"Point"},"properties":{},"type":"Feature"}],"type":"FeatureCollection"}"#)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["bbox", "--all", "--embed", "--antimeridian"])
+        .stdin("POINT(170 -10)\nPOINT(-170 5)")
+        .stdout()
+        .contains(r#""type":"Feature"}],"type":"FeatureCollection"}"#)
+        .stdout()
+        .contains(r#"{"bbox":[170.0,-10.0,-170.0,5.0],"features":"#)
+        .unwrap();
+}
+
+#[test]
+fn gh_children_level() {
+    let output = Assert::main_binary()
+        .with_args(&["gh", "children", "--level", "2"])
+        .stdin("9q5\n");
+    output.stdout().contains("9q500\n9q501\n").stdout().contains("9q50z\n9q510\n").stdout().contains("9q5zz").unwrap();
+
+    Assert::main_binary()
+        .with_args(&["gh", "children", "--level", "1"])
+        .stdin("9q5\n")
+        .stdout()
+        .contains("9q50\n9q51\n")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["gh", "children", "--level", "5"])
+        .stdin("9q5\n")
+        .fails()
+        .stderr()
+        .contains("Invalid --level")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["gh", "children", "--level", "2"])
+        .stdin("9q5cbcbcbcb\n")
+        .fails()
+        .stderr()
+        .contains("at most 12 characters")
+        .unwrap();
+}
+
+#[test]
+fn json_munge_string_geometries() {
+    let input = r#"{"geom": "{\"type\":\"Point\",\"coordinates\":[-118.2,34.3]}", "name": "x"}
+{"shape": "LINESTRING(0 0,1 1)", "name": "POINT", "count": "12"}
+"#;
+    let output = r#"{"geometry":{"coordinates":[-118.2,34.3],"type":"Point"},"properties":{"name":"x"},"type":"Feature"}
+{"geometry":{"coordinates":[[0.0,0.0],[1.0,1.0]],"type":"LineString"},"properties":{"count":"12","name":"POINT"},"type":"Feature"}
+"#;
+    Assert::main_binary()
+        .with_args(&["json", "munge"])
+        .stdin(input)
+        .stdout()
+        .is(output)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["json", "munge"])
+        .stdin(r#"{"name": "Horace", "pizza": "pie"}"#)
+        .fails()
+        .stderr()
+        .contains("Couldn't guess GeoJSON Feature")
+        .unwrap();
+}
+
+#[test]
+fn measure_length_3d() {
+    Assert::main_binary()
+        .with_args(&["measure", "length", "LINESTRING(0 0,0 0.001)"])
+        .stdout()
+        .is("110.57427582170098\tLINESTRING(0 0,0 0.001)")
+        .unwrap();
+
+    let track = r#"{"type":"LineString","coordinates":[[0,0,0],[0,0.001,100]]}"#;
+    Assert::main_binary()
+        .with_args(&["measure", "length", "--3d"])
+        .stdin(track)
+        .stdout()
+        .is(format!("149.08611764176302\t{}", track).as_str())
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["measure", "length", "--3d", "--labeled"])
+        .stdin("LINESTRING(0 0,0 0.001)")
+        .stdout()
+        .is("110.57427582170098 m\tLINESTRING(0 0,0 0.001)")
+        .stderr()
+        .contains("No Z coordinates for --3d")
+        .unwrap();
+}
+
+#[test]
+fn centroid_non_finite() {
+    Assert::main_binary()
+        .with_args(&["centroid"])
+        .stdin("POINT(1 2)\nPOINT(1e400 0)")
+        .fails()
+        .stdout()
+        .is(r#"{"coordinates":[1.0,2.0],"type":"Point"}"#)
+        .stderr()
+        .contains(r#"{"error":"non-finite coordinates","input":"POINT(1e400 0)","output":"POINT(inf 0)"}"#)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["centroid"])
+        .stdin("POLYGON((0 0,1e308 0,1e308 1e308,0 0))")
+        .fails()
+        .stderr()
+        .contains(r#""input":"POLYGON((0 0,1e308 0,1e308 1e308,0 0))","output":"POINT(inf 3333"#)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["bbox", "--all"])
+        .stdin("POINT(1 2)\nPOINT(-1e400 0)")
+        .fails()
+        .stdout()
+        .is("")
+        .stderr()
+        .contains(r#""output":"POLYGON((1 2,1 0,-inf 0,-inf 2,1 2))""#)
+        .unwrap();
+}
+
+#[test]
+fn voronoi_collinear_points() {
+    // The points' bbox has no height, so it's padded to give the cells area
+    Assert::main_binary()
+        .with_args(&["voronoi"])
+        .stdin("0,0\n0,2\n")
+        .stdout()
+        .is(r#"{"geometry":{"coordinates":[[[1.0,1.0],[1.0,-1.0],[0.0,-1.0],[0.0,1.0],[1.0,1.0]]],"type":"Polygon"},"properties":{"index":0},"type":"Feature"}
+{"geometry":{"coordinates":[[[1.0,1.0],[2.0,1.0],[2.0,-1.0],[1.0,-1.0],[1.0,1.0]]],"type":"Polygon"},"properties":{"index":1},"type":"Feature"}"#)
+        .unwrap();
+}
+
+#[test]
+fn measure_length_planar() {
+    Assert::main_binary()
+        .with_args(&["measure", "length", "--planar", "LINESTRING(0 0,30 40)"])
+        .stdout()
+        .is("50\tLINESTRING(0 0,30 40)")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["measure", "length", "--planar", "--unit", "km", "MULTILINESTRING((0 0,300 400),(0 0,0 500))"])
+        .stdout()
+        .is("1\tMULTILINESTRING((0 0,300 400),(0 0,0 500))")
+        .unwrap();
+}
+
+#[test]
+fn gj_fc_closes_collection_on_invalid_input() {
+    Assert::main_binary()
+        .with_args(&["gj", "fc"])
+        .stdin("POINT(1 2)\npizza\nPOINT(3 4)\n")
+        .fails()
+        .stdout()
+        .is(r#"{"type":"FeatureCollection","features":[{"geometry":{"coordinates":[1.0,2.0],"type":"Point"},"properties":{},"type":"Feature"}]}"#)
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["gj", "fc", "--pretty"])
+        .stdin("pizza\n")
+        .fails()
+        .stdout()
+        .is("{\n  \"type\": \"FeatureCollection\",\n  \"features\": []\n}")
+        .unwrap();
+}
+
+#[test]
+fn gj_geom_empty_geometries() {
+    Assert::main_binary()
+        .with_args(&["gj", "geom"])
+        .stdin("POINT EMPTY\nLINESTRING EMPTY\nPOINT(1 2)\n")
+        .stdout()
+        .is(r#"{"geometries":[],"type":"GeometryCollection"}
+{"geometries":[],"type":"GeometryCollection"}
+{"coordinates":[1.0,2.0],"type":"Point"}"#)
+        .unwrap();
+}
+
+#[test]
+fn densify_invalid_max_segment() {
+    for arg in &["--max-segment=0", "--max-segment=0km", "--max-segment=-5", "--max-segment=NaN"] {
+        Assert::main_binary()
+            .with_args(&["densify", arg])
+            .stdin("LINESTRING(0 0,1 0)")
+            .fails()
+            .stderr()
+            .contains("Invalid")
+            .unwrap();
+    }
+
+    Assert::main_binary()
+        .with_args(&["densify", "--max-segment", "0"])
+        .stdin("LINESTRING(0 0,1 0)")
+        .fails()
+        .stderr()
+        .contains(r#"Invalid --max-segment: "0" - must be a distance greater than 0"#)
+        .unwrap();
+}
+
+#[test]
+fn grid_invalid_cell_sizes() {
+    for level in &["0", "13"] {
+        Assert::main_binary()
+            .with_args(&["grid", "--geohash", level])
+            .stdin("POLYGON((0 0,1 0,1 1,0 0))")
+            .fails()
+            .stderr()
+            .contains(format!("Invalid --geohash level: \"{}\" - must be an integer between 1 and 12", level).as_str())
+            .unwrap();
+    }
+
+    Assert::main_binary()
+        .with_args(&["grid", "--size", "0m"])
+        .stdin("POLYGON((0 0,1 0,1 1,0 0))")
+        .fails()
+        .stderr()
+        .contains(r#"Invalid --size: "0m" - must be a distance greater than 0"#)
+        .unwrap();
+}
+
+#[test]
+fn pretty_json_output() {
+    Assert::main_binary()
+        .with_args(&["measure", "length", "--json", "--pretty", "LINESTRING(0 0,0 1)"])
+        .stdout()
+        .is("{\n  \"unit\": \"m\",\n  \"value\": 110574.38855779252\n}")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["stats", "--json", "--pretty"])
+        .stdin("POINT(1 2)")
+        .stdout()
+        .satisfies(|o| o.starts_with("{\n  \"") && o.lines().count() > 3, "Should be indented")
+        .unwrap();
+
+    Assert::main_binary()
+        .with_args(&["info", "--json", "--pretty", "POINT(1 2)"])
+        .stdout()
+        .satisfies(|o| o.contains("\n  \"vertices\": 1\n}"), "Should be indented")
+        .unwrap();
+}